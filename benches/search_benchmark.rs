@@ -10,6 +10,7 @@ fn create_test_entries(count: usize) -> Vec<DesktopEntry> {
         .map(|i| DesktopEntry {
             name: format!("Application {}", i),
             generic_name: Some(format!("Test App {}", i)),
+            comment: None,
             exec: format!("app{}", i),
             icon: Some(format!("icon{}", i)),
             categories: vec!["Utility".to_string(), "Development".to_string()],
@@ -17,6 +18,11 @@ fn create_test_entries(count: usize) -> Vec<DesktopEntry> {
             terminal: false,
             path: PathBuf::from(format!("/test/app{}.desktop", i)),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         })
         .collect()
@@ -28,6 +34,7 @@ fn create_realistic_entries() -> Vec<DesktopEntry> {
         DesktopEntry {
             name: "Firefox".to_string(),
             generic_name: Some("Web Browser".to_string()),
+            comment: None,
             exec: "firefox".to_string(),
             icon: Some("firefox".to_string()),
             categories: vec!["Network".to_string(), "WebBrowser".to_string()],
@@ -39,11 +46,17 @@ fn create_realistic_entries() -> Vec<DesktopEntry> {
             terminal: false,
             path: PathBuf::from("/usr/share/applications/firefox.desktop"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         },
         DesktopEntry {
             name: "Visual Studio Code".to_string(),
             generic_name: Some("Text Editor".to_string()),
+            comment: None,
             exec: "code".to_string(),
             icon: Some("vscode".to_string()),
             categories: vec!["Development".to_string(), "IDE".to_string()],
@@ -55,11 +68,17 @@ fn create_realistic_entries() -> Vec<DesktopEntry> {
             terminal: false,
             path: PathBuf::from("/usr/share/applications/code.desktop"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         },
         DesktopEntry {
             name: "Files".to_string(),
             generic_name: Some("File Manager".to_string()),
+            comment: None,
             exec: "nautilus".to_string(),
             icon: Some("system-file-manager".to_string()),
             categories: vec!["System".to_string(), "FileManager".to_string()],
@@ -71,6 +90,11 @@ fn create_realistic_entries() -> Vec<DesktopEntry> {
             terminal: false,
             path: PathBuf::from("/usr/share/applications/org.gnome.Nautilus.desktop"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         },
     ]
@@ -158,6 +182,7 @@ fn bench_realistic_search_scenarios(c: &mut Criterion) {
         entries.push(DesktopEntry {
             name: format!("App {}", i),
             generic_name: Some(format!("Generic App {}", i)),
+            comment: None,
             exec: format!("app{}", i),
             icon: Some(format!("icon{}", i)),
             categories: vec!["Utility".to_string()],
@@ -165,6 +190,11 @@ fn bench_realistic_search_scenarios(c: &mut Criterion) {
             terminal: false,
             path: PathBuf::from(format!("/test/app{}.desktop", i)),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         });
     }
@@ -260,6 +290,7 @@ fn bench_entry_matching(c: &mut Criterion) {
     let entry = DesktopEntry {
         name: "Firefox Web Browser".to_string(),
         generic_name: Some("Web Browser".to_string()),
+        comment: None,
         exec: "firefox".to_string(),
         icon: Some("firefox".to_string()),
         categories: vec!["Network".to_string(), "WebBrowser".to_string()],
@@ -271,6 +302,11 @@ fn bench_entry_matching(c: &mut Criterion) {
         terminal: false,
         path: PathBuf::from("/usr/share/applications/firefox.desktop"),
         no_display: false,
+        hidden: false,
+        dbus_activatable: false,
+        startup_notify: false,
+        startup_wm_class: None,
+        mime_types: vec![],
         actions: vec![],
     };
 
@@ -311,6 +347,7 @@ fn bench_entry_scoring(c: &mut Criterion) {
     let entry = DesktopEntry {
         name: "Firefox Web Browser".to_string(),
         generic_name: Some("Web Browser".to_string()),
+        comment: None,
         exec: "firefox".to_string(),
         icon: Some("firefox".to_string()),
         categories: vec!["Network".to_string()],
@@ -318,6 +355,11 @@ fn bench_entry_scoring(c: &mut Criterion) {
         terminal: false,
         path: PathBuf::from("/usr/share/applications/firefox.desktop"),
         no_display: false,
+        hidden: false,
+        dbus_activatable: false,
+        startup_notify: false,
+        startup_wm_class: None,
+        mime_types: vec![],
         actions: vec![],
     };
 
@@ -59,7 +59,8 @@ fn bench_full_startup(c: &mut Criterion) {
             );
 
             // Load dynamic plugins
-            let (dynamic_plugins, _metrics) = native_launcher::plugins::load_plugins();
+            let (dynamic_plugins, _metrics) =
+                native_launcher::plugins::load_plugins(&config.plugins);
 
             black_box((
                 config,
@@ -100,7 +101,7 @@ fn bench_plugin_initialization(c: &mut Criterion) {
 
     group.bench_function("dynamic_plugin_loading", |b| {
         b.iter(|| {
-            let (plugins, metrics) = native_launcher::plugins::load_plugins();
+            let (plugins, metrics) = native_launcher::plugins::load_plugins(&config.plugins);
             black_box((plugins, metrics));
         });
     });
@@ -8,6 +8,7 @@ fn create_test_entries(count: usize) -> Vec<DesktopEntry> {
         .map(|i| DesktopEntry {
             name: format!("Application {}", i),
             generic_name: Some(format!("Test App {}", i)),
+            comment: None,
             exec: format!("app{}", i),
             icon: Some(format!("icon{}", i)),
             categories: vec!["Utility".to_string(), "Development".to_string()],
@@ -15,6 +16,11 @@ fn create_test_entries(count: usize) -> Vec<DesktopEntry> {
             terminal: false,
             path: PathBuf::from(format!("/test/app{}.desktop", i)),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         })
         .collect()
@@ -28,6 +28,9 @@ use std::slice;
 /// Plugin ABI version - must match launcher's version
 const PLUGIN_ABI_VERSION: u32 = 1;
 
+/// Capability bit: the plugin exports `plugin_handle_keyboard_event`
+const CAPABILITY_KEYBOARD_EVENTS: u32 = 1 << 0;
+
 /// C-compatible string slice
 #[repr(C)]
 pub struct CStringSlice {
@@ -135,6 +138,12 @@ pub extern "C" fn plugin_get_abi_version() -> u32 {
     PLUGIN_ABI_VERSION
 }
 
+/// Optional functions this plugin implements (bit 0: keyboard events)
+#[no_mangle]
+pub extern "C" fn plugin_get_capabilities() -> u32 {
+    CAPABILITY_KEYBOARD_EVENTS
+}
+
 /// Get plugin name
 #[no_mangle]
 pub extern "C" fn plugin_get_name() -> CStringSlice {
@@ -0,0 +1,182 @@
+//! Headless search benchmark (`native-launcher --bench [ITERATIONS]`)
+//!
+//! Runs a fixed query set through `PluginManager::search_incremental` (the same path
+//! the UI uses) and reports per-query timings plus per-plugin averages from the
+//! manager's performance metrics. No GTK initialization is needed.
+
+use crate::plugins::{PluginManager, SearchToken};
+use anyhow::Result;
+use std::fmt;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Fixed query set: single-letter and partial prefixes, full app names, a typo,
+/// a calculator expression and a web-search style query
+pub const DEFAULT_QUERIES: &[&str] = &[
+    "f", "fi", "fire", "firefox", "te", "terminal", "code", "setings", "2+2*3", "weather",
+];
+
+/// Iterations over the query set when none is given on the command line
+pub const DEFAULT_ITERATIONS: usize = 20;
+
+/// Results of a benchmark run
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    /// Passes over the query set
+    pub iterations: usize,
+    /// Total wall time spent searching
+    pub total: Duration,
+    /// Average wall time per query, in query-set order
+    pub queries: Vec<(String, Duration)>,
+    /// Per-plugin (name, average ms, calls), slowest first
+    pub plugins: Vec<(String, f64, u32)>,
+}
+
+impl BenchReport {
+    /// Number of searches performed
+    pub fn searches(&self) -> usize {
+        self.iterations * self.queries.len()
+    }
+
+    /// Average wall time per search in milliseconds
+    pub fn average_ms(&self) -> f64 {
+        if self.searches() == 0 {
+            return 0.0;
+        }
+        self.total.as_secs_f64() * 1000.0 / self.searches() as f64
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Search benchmark: {} queries x {} iterations ({} searches)",
+            self.queries.len(),
+            self.iterations,
+            self.searches()
+        )?;
+        writeln!(
+            f,
+            "Total: {:.2} ms, average {:.3} ms/search",
+            self.total.as_secs_f64() * 1000.0,
+            self.average_ms()
+        )?;
+
+        writeln!(f, "\nPer query (avg ms):")?;
+        for (query, avg) in &self.queries {
+            writeln!(f, "  {:<24} {:>9.3}", query, avg.as_secs_f64() * 1000.0)?;
+        }
+
+        writeln!(f, "\nPer plugin (avg ms, calls):")?;
+        for (name, avg_ms, calls) in &self.plugins {
+            writeln!(f, "  {:<24} {:>9.3} {:>7}", name, avg_ms, calls)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Time `iterations` passes of `queries` through the plugin manager
+pub fn run(
+    manager: &PluginManager,
+    queries: &[&str],
+    iterations: usize,
+    max_results: usize,
+) -> Result<BenchReport> {
+    let generation = Arc::new(AtomicU64::new(0));
+    let mut per_query = vec![Duration::ZERO; queries.len()];
+    let mut total = Duration::ZERO;
+
+    for _ in 0..iterations {
+        for (i, query) in queries.iter().enumerate() {
+            let token = SearchToken::new(generation.clone());
+            let start = Instant::now();
+            manager.search_incremental(query, max_results, &token, |_| {}, |_| {})?;
+            let elapsed = start.elapsed();
+
+            per_query[i] += elapsed;
+            total += elapsed;
+        }
+    }
+
+    let divisor = iterations.max(1) as u32;
+    let queries = queries
+        .iter()
+        .zip(per_query)
+        .map(|(query, time)| (query.to_string(), time / divisor))
+        .collect();
+
+    Ok(BenchReport {
+        iterations,
+        total,
+        queries,
+        plugins: manager.get_performance_metrics(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::desktop::{DesktopEntry, DesktopEntryArena};
+    use std::path::PathBuf;
+
+    fn entry(name: &str) -> DesktopEntry {
+        DesktopEntry {
+            name: name.to_string(),
+            generic_name: None,
+            comment: None,
+            exec: name.to_lowercase(),
+            icon: None,
+            categories: vec![],
+            keywords: vec![],
+            terminal: false,
+            path: PathBuf::from(format!("/test/{}.desktop", name.to_lowercase())),
+            no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
+            actions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_bench_produces_timing_output() {
+        // Only in-memory plugins so the run is fast and deterministic
+        let mut config = Config::default();
+        config.search.usage_ranking = false;
+        config.plugins.files.enabled = false;
+        config.plugins.browser_history.enabled = false;
+        config.plugins.recent_documents = false;
+        config.plugins.git_projects = false;
+        config.plugins.editors = false;
+        config.plugins.ssh = false;
+        config.plugins.clipboard = false;
+        config.plugins.session_switcher = false;
+        config.plugins.window_management = false;
+
+        let arena =
+            DesktopEntryArena::from_vec(vec![entry("Firefox"), entry("Files"), entry("Terminal")]);
+        let manager = PluginManager::new(arena, None, None, &config);
+
+        let report = run(&manager, &["fire", "term", "2+2"], 3, 10).unwrap();
+
+        assert_eq!(report.searches(), 9);
+        assert_eq!(report.queries.len(), 3);
+        let apps = report
+            .plugins
+            .iter()
+            .find(|(name, _, _)| name == "applications")
+            .expect("applications plugin timed");
+        assert_eq!(apps.2, 9);
+
+        let output = report.to_string();
+        assert!(output.contains("3 queries x 3 iterations (9 searches)"));
+        assert!(output.contains("Per plugin"));
+        assert!(output.contains("applications"));
+    }
+}
@@ -1,5 +1,11 @@
+use super::migrate::{self, CURRENT_VERSION};
+use super::schema::PluginsConfig;
 use super::Config;
-use crate::utils::exec::{configure_open_handlers, CommandOpenHandler};
+use crate::utils::atomic_write;
+use crate::utils::exec::{
+    configure_error_notifications, configure_open_command, configure_open_handlers,
+    configure_terminal, CommandOpenHandler,
+};
 use anyhow::Result;
 use std::fs;
 use std::path::PathBuf;
@@ -16,7 +22,7 @@ impl ConfigLoader {
     pub fn new() -> Self {
         let config_path = Self::default_config_path();
         let config = Config::default();
-        apply_open_handler_config(&config);
+        apply_exec_config(&config);
 
         Self {
             config_path,
@@ -26,15 +32,28 @@ impl ConfigLoader {
 
     /// Load configuration from disk, or create default if not exists
     pub fn load() -> Result<Self> {
-        let config_path = Self::default_config_path();
+        Self::load_from(Self::default_config_path())
+    }
 
+    /// Load configuration from `config_path`, upgrading files written by older
+    /// versions (rewritten only when a setting moved)
+    pub fn load_from(config_path: PathBuf) -> Result<Self> {
         let config = if config_path.exists() {
             info!("Loading config from {:?}", config_path);
             let contents = fs::read_to_string(&config_path)?;
 
-            match toml::from_str::<Config>(&contents) {
-                Ok(cfg) => {
+            match parse_config(&contents) {
+                Ok((mut cfg, migrated)) => {
                     info!("Config loaded successfully");
+                    warn_unknown_plugin_keys(&contents);
+                    validate_config(&mut cfg);
+
+                    if migrated {
+                        match Self::save_config(&config_path, &cfg) {
+                            Ok(()) => info!("Config upgraded to version {}", CURRENT_VERSION),
+                            Err(e) => warn!("Failed to save upgraded config: {}", e),
+                        }
+                    }
                     cfg
                 }
                 Err(e) => {
@@ -62,7 +81,7 @@ impl ConfigLoader {
             default
         };
 
-        apply_open_handler_config(&config);
+        apply_exec_config(&config);
 
         Ok(Self {
             config_path,
@@ -86,11 +105,13 @@ impl ConfigLoader {
         }
 
         let contents = fs::read_to_string(&self.config_path)?;
-        let new_config: Config = toml::from_str(&contents)?;
+        let (mut new_config, _) = parse_config(&contents)?;
+        warn_unknown_plugin_keys(&contents);
+        validate_config(&mut new_config);
 
         self.config = new_config;
         info!("Config reloaded successfully");
-        apply_open_handler_config(&self.config);
+        apply_exec_config(&self.config);
 
         Ok(())
     }
@@ -105,7 +126,7 @@ impl ConfigLoader {
     #[allow(dead_code)]
     pub fn update(&mut self, config: Config) -> Result<()> {
         self.config = config;
-        apply_open_handler_config(&self.config);
+        apply_exec_config(&self.config);
         self.save()
     }
 
@@ -124,7 +145,7 @@ impl ConfigLoader {
         }
 
         let toml = toml::to_string_pretty(config)?;
-        fs::write(path, toml)?;
+        atomic_write(path, toml)?;
 
         debug!("Config saved to {:?}", path);
         Ok(())
@@ -136,6 +157,57 @@ impl ConfigLoader {
     }
 }
 
+/// Parse config file contents, running schema migrations first
+///
+/// Returns the config and whether it was upgraded from an older version.
+fn parse_config(contents: &str) -> Result<(Config, bool)> {
+    let mut table: toml::Table = contents.parse()?;
+    let migrated = migrate::migrate(&mut table);
+    let config = toml::Value::Table(table).try_into()?;
+    Ok((config, migrated))
+}
+
+/// Clamp out-of-range settings to sensible values, warning about each change
+fn validate_config(config: &mut Config) {
+    for warning in config.general.validate() {
+        warn!("{}", warning);
+    }
+    for warning in config.search.validate() {
+        warn!("{}", warning);
+    }
+    for warning in config.ui.validate() {
+        warn!("{}", warning);
+    }
+    for warning in config.plugins.browser_history.validate() {
+        warn!("{}", warning);
+    }
+    for warning in config.plugins.files.validate() {
+        warn!("{}", warning);
+    }
+}
+
+/// Warn about `[plugins]` keys that don't match any plugin option (likely typos)
+fn warn_unknown_plugin_keys(contents: &str) {
+    let Ok(table) = contents.parse::<toml::Table>() else {
+        return;
+    };
+
+    if let Some(toml::Value::Table(plugins)) = table.get("plugins") {
+        for key in PluginsConfig::unknown_keys(plugins) {
+            warn!("Unknown plugin option '{}' in [plugins], ignoring", key);
+        }
+    }
+}
+
+/// Push the launch-related settings (opener, open handlers, terminal, shell) to
+/// `utils::exec`
+fn apply_exec_config(config: &Config) {
+    configure_open_command(&config.general.open_command);
+    apply_open_handler_config(config);
+    configure_terminal(&config.terminal.command, config.terminal.hold_on_exit);
+    configure_error_notifications(config.shell.notify_on_error);
+}
+
 fn apply_open_handler_config(config: &Config) {
     let handlers: Vec<CommandOpenHandler> = config
         .handlers
@@ -196,6 +268,120 @@ mod tests {
         assert!(path.to_string_lossy().ends_with("config.toml"));
     }
 
+    fn temp_config_path(name: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("native-launcher-{}-{}", name, nanos))
+            .join("config.toml")
+    }
+
+    #[test]
+    fn test_v1_config_migrated_with_new_defaults() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        let path = temp_config_path("migrate");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // A version 1 file: no version key, old [appearance] layout, no [search]
+        fs::write(&path, "[appearance]\nwidth = 820\ntheme = \"nord\"\n").unwrap();
+
+        let loader = ConfigLoader::load_from(path.clone()).unwrap();
+        let written: toml::Table = fs::read_to_string(&path).unwrap().parse().unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(loader.config().version, CURRENT_VERSION);
+        assert_eq!(loader.config().window.width, 820);
+        assert_eq!(loader.config().ui.theme, "nord");
+        assert!(loader.config().search.web_fallback);
+
+        // The upgraded file is written back with the new section's defaults
+        assert_eq!(
+            written["version"].as_integer(),
+            Some(CURRENT_VERSION.into())
+        );
+        assert_eq!(written["window"]["width"].as_integer(), Some(820));
+        assert_eq!(written["search"]["web_fallback"].as_bool(), Some(true));
+        assert_eq!(written["search"]["max_results"].as_integer(), Some(10));
+        assert!(!written.contains_key("appearance"));
+    }
+
+    #[test]
+    fn test_unversioned_config_without_old_keys_left_untouched() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        let path = temp_config_path("unversioned");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let contents = "# my launcher\n[search]\nmax_results = 15 # plenty\n";
+        fs::write(&path, contents).unwrap();
+
+        let loader = ConfigLoader::load_from(path.clone()).unwrap();
+        let on_disk = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(loader.config().search.max_results, 15);
+        assert_eq!(on_disk, contents);
+    }
+
+    #[test]
+    fn test_unknown_keys_survive_round_trip() {
+        let contents = r#"
+            version = 2
+            experimental = "yes"
+
+            [ui]
+            theme = "dracula"
+            future_option = 3
+
+            [plugins]
+            calculator = false
+            fancy_plugin = true
+
+            [keyboard]
+            close = "Escape"
+        "#;
+
+        let (config, migrated) = parse_config(contents).unwrap();
+        assert!(!migrated);
+        assert!(!config.plugins.calculator.enabled);
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let (reloaded, _) = parse_config(&saved).unwrap();
+        let table: toml::Table = saved.parse().unwrap();
+
+        assert_eq!(table["experimental"].as_str(), Some("yes"));
+        assert_eq!(table["ui"]["future_option"].as_integer(), Some(3));
+        assert_eq!(table["plugins"]["fancy_plugin"].as_bool(), Some(true));
+        assert_eq!(table["keyboard"]["close"].as_str(), Some("Escape"));
+        assert_eq!(reloaded.ui.theme, "dracula");
+        assert!(!reloaded.plugins.calculator.enabled);
+    }
+
+    #[test]
+    fn test_disabled_dynamic_plugin_persisted() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        let path = temp_config_path("disable-plugin");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "version = 2\n\n[plugins]\nslow_warn_ms = 120\n").unwrap();
+
+        let mut loader = ConfigLoader::load_from(path.clone()).unwrap();
+        assert_eq!(loader.config().plugins.slow_warn_ms, 120);
+
+        let mut updated = loader.config().clone();
+        assert!(updated.plugins.disable_dynamic("libslow_plugin"));
+        assert!(!updated.plugins.disable_dynamic("libslow_plugin"));
+        loader.update(updated).unwrap();
+
+        let reloaded = ConfigLoader::load_from(path.clone()).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(reloaded.config().plugins.disabled, vec!["libslow_plugin"]);
+        assert!(reloaded
+            .config()
+            .plugins
+            .is_dynamic_disabled("libslow_plugin"));
+        assert_eq!(reloaded.config().plugins.slow_warn_ms, 120);
+    }
+
     #[test]
     fn apply_open_handler_config_registers_valid_entries() {
         let _guard = open_handler_test_lock().lock().unwrap();
@@ -0,0 +1,148 @@
+//! Config schema migrations
+//!
+//! Config files carry a top-level `version`; files without one are version 1.
+//! Migrations run in order on the raw TOML table before it's deserialized, so
+//! renamed keys keep their values and keys the schema doesn't know are left in
+//! place. A file is only written back (with defaults for missing sections)
+//! when a migration moved a setting; an unversioned file that needs no changes
+//! is left alone, comments included.
+
+use toml::{Table, Value};
+use tracing::{info, warn};
+
+/// Schema version written by this build
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Version assumed for config files without a `version` key
+const UNVERSIONED: u32 = 1;
+
+/// `MIGRATIONS[i]` upgrades a table from version `i + 1` to `i + 2`, returning
+/// whether it changed any setting
+const MIGRATIONS: &[fn(&mut Table) -> bool] = &[migrate_v1_to_v2];
+
+/// Keys moved out of the old `[appearance]` and `[behavior]` sections:
+/// (old section, old key, new section, new key)
+const V2_MOVED_KEYS: &[(&str, &str, &str, &str)] = &[
+    ("appearance", "width", "window", "width"),
+    ("appearance", "height", "window", "height"),
+    ("appearance", "position", "window", "position"),
+    ("appearance", "max_results", "search", "max_results"),
+    ("appearance", "icon_size", "ui", "icon_size"),
+    ("appearance", "theme", "ui", "theme"),
+    ("behavior", "fuzzy_search", "search", "fuzzy_matching"),
+    ("behavior", "remember_usage", "search", "usage_ranking"),
+];
+
+/// Schema version of a raw config table
+pub fn version_of(table: &Table) -> u32 {
+    table
+        .get("version")
+        .and_then(Value::as_integer)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(UNVERSIONED)
+}
+
+/// Upgrade a raw config table to [`CURRENT_VERSION`], returning whether any
+/// setting changed (a missing `version` key alone doesn't count)
+pub fn migrate(table: &mut Table) -> bool {
+    let from = version_of(table);
+    if from > CURRENT_VERSION {
+        warn!(
+            "Config version {} is newer than supported version {}, loading as-is",
+            from, CURRENT_VERSION
+        );
+        return false;
+    }
+    if from == CURRENT_VERSION {
+        return false;
+    }
+
+    let mut changed = false;
+    for version in from..CURRENT_VERSION {
+        info!(
+            "Migrating config from version {} to {}",
+            version,
+            version + 1
+        );
+        changed |= MIGRATIONS[(version - UNVERSIONED) as usize](table);
+    }
+    table.insert(
+        "version".to_string(),
+        Value::Integer(CURRENT_VERSION.into()),
+    );
+    changed
+}
+
+/// v2 split the old `[appearance]`/`[behavior]` sections into
+/// `[window]`, `[search]` and `[ui]`
+fn migrate_v1_to_v2(table: &mut Table) -> bool {
+    let mut changed = false;
+    for (old_section, old_key, new_section, new_key) in V2_MOVED_KEYS {
+        let Some(Value::Table(old)) = table.get_mut(*old_section) else {
+            continue;
+        };
+        let Some(value) = old.remove(*old_key) else {
+            continue;
+        };
+        changed = true;
+
+        let new = table
+            .entry(new_section.to_string())
+            .or_insert_with(|| Value::Table(Table::new()));
+        if let Value::Table(new) = new {
+            // A value already set under the new name wins
+            new.entry(new_key.to_string()).or_insert(value);
+        }
+    }
+
+    // Drop old sections that are now empty; leftovers (unknown keys) are kept
+    for section in ["appearance", "behavior"] {
+        if matches!(table.get(section), Some(Value::Table(t)) if t.is_empty()) {
+            table.remove(section);
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v1_keys_moved() {
+        let mut table: Table = toml::from_str(
+            r#"
+            [appearance]
+            width = 800
+            theme = "nord"
+            show_icons = true
+
+            [behavior]
+            fuzzy_search = false
+
+            [search]
+            max_results = 15
+            "#,
+        )
+        .unwrap();
+
+        assert!(migrate(&mut table));
+        assert_eq!(version_of(&table), CURRENT_VERSION);
+        assert_eq!(table["window"]["width"].as_integer(), Some(800));
+        assert_eq!(table["ui"]["theme"].as_str(), Some("nord"));
+        assert_eq!(table["search"]["fuzzy_matching"].as_bool(), Some(false));
+        assert_eq!(table["search"]["max_results"].as_integer(), Some(15));
+
+        // Unknown keys stay where they were; emptied sections are removed
+        assert_eq!(table["appearance"]["show_icons"].as_bool(), Some(true));
+        assert!(!table.contains_key("behavior"));
+
+        // Already current: nothing to do
+        assert!(!migrate(&mut table));
+
+        // Unversioned but without old keys: only the version is added in memory
+        let mut table: Table = toml::from_str("[search]\nmax_results = 15\n").unwrap();
+        assert!(!migrate(&mut table));
+        assert_eq!(version_of(&table), CURRENT_VERSION);
+    }
+}
@@ -1,5 +1,8 @@
 mod loader;
+mod migrate;
 mod schema;
 
 pub use loader::ConfigLoader;
-pub use schema::Config;
+pub use schema::{
+    BrowserHistoryConfig, Config, PluginsConfig, SearchConfig, UIConfig, WindowConfig,
+};
@@ -1,10 +1,18 @@
+use super::migrate::CURRENT_VERSION;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Main configuration structure
+///
+/// Each table keeps keys it doesn't recognize in `extra`, so they survive a
+/// load/save round-trip (e.g. options from a newer release).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
-#[derive(Default)]
 pub struct Config {
+    /// Schema version, upgraded on load (see `config::migrate`)
+    pub version: u32,
+    pub general: GeneralConfig,
     pub window: WindowConfig,
     pub search: SearchConfig,
     pub ui: UIConfig,
@@ -12,6 +20,73 @@ pub struct Config {
     pub updater: UpdaterConfig,
     pub environment: EnvironmentConfig,
     pub handlers: HandlersConfig,
+    pub terminal: TerminalConfig,
+    pub shell: ShellConfig,
+    /// Extra query prefixes by plugin name (`[prefixes]` with `calculator = "="`),
+    /// accepted alongside the plugin's built-in ones
+    pub prefixes: HashMap<String, String>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            general: GeneralConfig::default(),
+            window: WindowConfig::default(),
+            search: SearchConfig::default(),
+            ui: UIConfig::default(),
+            plugins: PluginsConfig::default(),
+            updater: UpdaterConfig::default(),
+            environment: EnvironmentConfig::default(),
+            handlers: HandlersConfig::default(),
+            terminal: TerminalConfig::default(),
+            shell: ShellConfig::default(),
+            prefixes: HashMap::new(),
+            extra: toml::Table::new(),
+        }
+    }
+}
+
+/// General configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneralConfig {
+    /// Command line opening files and URLs; `{}` is replaced by the shell-quoted
+    /// target, which is appended when there is no `{}`
+    pub open_command: String,
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            open_command: Self::DEFAULT_OPEN_COMMAND.to_string(),
+            extra: toml::Table::new(),
+        }
+    }
+}
+
+impl GeneralConfig {
+    /// Default `open_command`
+    pub const DEFAULT_OPEN_COMMAND: &'static str = "xdg-open {}";
+
+    /// Reset an empty `open_command`, returning a warning if it was reset
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.open_command.trim().is_empty() {
+            warnings.push(format!(
+                "general.open_command is empty, using {:?}",
+                Self::DEFAULT_OPEN_COMMAND
+            ));
+            self.open_command = Self::DEFAULT_OPEN_COMMAND.to_string();
+        }
+
+        warnings
+    }
 }
 
 /// Window configuration
@@ -22,10 +97,29 @@ pub struct WindowConfig {
     pub width: i32,
     /// Window height in pixels
     pub height: i32,
-    /// Window position: "top", "center", or "bottom"
+    /// Window position: "center" (default), "top", or "bottom" (anchor of the
+    /// layer surface)
     pub position: String,
     /// Enable semi-transparent background
     pub transparency: bool,
+    /// Make the window resizable and reopen it at the size it was closed at
+    ///
+    /// The size is kept in a state file under the cache directory, overriding
+    /// `width` / `height`. Only the size is kept: the window is a layer-shell
+    /// surface, so the compositor places it and `position` stays the anchor.
+    pub remember_geometry: bool,
+    /// Show the window as a layer-shell surface on compositors supporting it
+    /// (falls back to a normal window elsewhere)
+    pub layer_shell: bool,
+    /// Layer-shell layer: "overlay" (above fullscreen windows) or "top"
+    pub layer: String,
+    /// Layer-shell keyboard focus: "exclusive" (grab while shown) or "on_demand"
+    pub keyboard_mode: String,
+    /// Close the launcher when it loses focus, e.g. on a click elsewhere
+    /// (hides it in daemon mode)
+    pub hide_on_focus_loss: bool,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for WindowConfig {
@@ -33,8 +127,14 @@ impl Default for WindowConfig {
         Self {
             width: 700,
             height: 550,
-            position: "top".to_string(),
+            position: "center".to_string(),
             transparency: true,
+            remember_geometry: false,
+            layer_shell: true,
+            layer: "overlay".to_string(),
+            keyboard_mode: "exclusive".to_string(),
+            hide_on_focus_loss: false,
+            extra: toml::Table::new(),
         }
     }
 }
@@ -51,8 +151,28 @@ pub struct SearchConfig {
     pub usage_ranking: bool,
     /// Minimum score threshold for results (0-100)
     pub min_score_threshold: i32,
+    /// Fuzzy score an app must beat for queries up to `short_query_len` characters
+    pub min_score_short: i64,
+    /// Fuzzy score an app must beat for longer queries
+    pub min_score_long: i64,
+    /// Longest query (in characters) that uses `min_score_short`
+    pub short_query_len: usize,
     /// Enable pins/favorites feature (Ctrl+P toggle, UI star, scoring boost)
     pub enable_pins: bool,
+    /// Delay after the last keystroke before searching, in milliseconds (0-500)
+    pub debounce_ms: u64,
+    /// Minimum query length before expensive plugins (browser history, system-wide
+    /// file search) run in global search (1-10). Unset keeps each plugin's default.
+    pub min_query_length: Option<usize>,
+    /// Debug: include applications marked `NoDisplay=true` in results
+    pub show_nodisplay: bool,
+    /// Offer a "Search the web" row when nothing else matches
+    pub web_fallback: bool,
+    /// Order of the empty-query list and of equally scored matches:
+    /// "score", "usage", "alpha" or "recent"
+    pub order_strategy: String,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for SearchConfig {
@@ -62,51 +182,198 @@ impl Default for SearchConfig {
             fuzzy_matching: true,
             usage_ranking: true,
             min_score_threshold: 0,
+            min_score_short: 50,
+            min_score_long: 20,
+            short_query_len: 2,
             enable_pins: true,
+            debounce_ms: 30,
+            min_query_length: None,
+            show_nodisplay: false,
+            web_fallback: true,
+            order_strategy: "score".to_string(),
+            extra: toml::Table::new(),
         }
     }
 }
 
+impl SearchConfig {
+    /// Upper bound for `debounce_ms`
+    pub const MAX_DEBOUNCE_MS: u64 = 500;
+    /// Allowed range for `min_query_length`
+    pub const MIN_QUERY_LENGTH_RANGE: std::ops::RangeInclusive<usize> = 1..=10;
+    /// Accepted values of `order_strategy`
+    pub const ORDER_STRATEGIES: &'static [&'static str] = &["score", "usage", "alpha", "recent"];
+
+    /// Clamp out-of-range values, returning a warning for each adjusted field
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.debounce_ms > Self::MAX_DEBOUNCE_MS {
+            warnings.push(format!(
+                "search.debounce_ms = {} is too high, using {}",
+                self.debounce_ms,
+                Self::MAX_DEBOUNCE_MS
+            ));
+            self.debounce_ms = Self::MAX_DEBOUNCE_MS;
+        }
+
+        if let Some(len) = self.min_query_length {
+            let range = Self::MIN_QUERY_LENGTH_RANGE;
+            let clamped = len.clamp(*range.start(), *range.end());
+            if clamped != len {
+                warnings.push(format!(
+                    "search.min_query_length = {} is out of range {}-{}, using {}",
+                    len,
+                    range.start(),
+                    range.end(),
+                    clamped
+                ));
+                self.min_query_length = Some(clamped);
+            }
+        }
+
+        if !Self::ORDER_STRATEGIES.contains(&self.order_strategy.as_str()) {
+            warnings.push(format!(
+                "search.order_strategy = {:?} is not one of {}, using \"score\"",
+                self.order_strategy,
+                Self::ORDER_STRATEGIES.join(", ")
+            ));
+            self.order_strategy = "score".to_string();
+        }
+
+        warnings
+    }
+}
+
 /// UI configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UIConfig {
-    /// Icon size in pixels
+    /// Icon size in pixels (16-256); icons are looked up at this size
     pub icon_size: i32,
+    /// Extra vertical space around each result row in pixels, on top of `density`
+    pub row_spacing: i32,
     /// Show keyboard hints at the bottom
     pub show_keyboard_hints: bool,
     /// Animation duration in milliseconds
     pub animation_duration: u32,
     /// Theme: "dark" or "light" (currently only dark is supported)
     pub theme: String,
+    /// Follow the system light/dark preference using `theme_light` / `theme_dark`
+    pub auto_theme: bool,
+    /// Theme used when the system prefers a light color scheme (requires `auto_theme`)
+    pub theme_light: String,
+    /// Theme used when the system prefers a dark color scheme (requires `auto_theme`)
+    pub theme_dark: String,
     /// Show empty state on launch (Spotlight-style) - hides results until user types
     pub empty_state_on_launch: bool,
     /// UI density: "compact" or "comfortable"
     pub density: String,
     /// Accent color: "coral", "teal", "violet", "blue", "green"
     pub accent: String,
+    /// Up/Down wrap around at the ends of the results list
+    pub wrap_navigation: bool,
+    /// Select the top result after every search update so Enter runs it without
+    /// pressing Down first
+    pub auto_select_first: bool,
+    /// Prefill the last non-empty query (selected) when the launcher is shown again
+    pub restore_last_query: bool,
+    /// Longest result title shown, in characters (0 = no limit)
+    pub max_title_length: usize,
+    /// Longest result subtitle shown, in characters (0 = no limit)
+    pub max_subtitle_length: usize,
+    /// Where over-long titles and subtitles are cut: "auto" (middle for paths),
+    /// "middle" or "end"
+    pub ellipsis: String,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for UIConfig {
     fn default() -> Self {
         Self {
             icon_size: 48,
+            row_spacing: 0,
             show_keyboard_hints: true,
             animation_duration: 150,
             theme: "dark".to_string(),
+            auto_theme: false,
+            theme_light: "light".to_string(),
+            theme_dark: "dark".to_string(),
             empty_state_on_launch: true,
             density: "comfortable".to_string(),
             accent: "coral".to_string(),
+            wrap_navigation: true,
+            auto_select_first: true,
+            restore_last_query: false,
+            max_title_length: 60,
+            max_subtitle_length: 60,
+            ellipsis: "auto".to_string(),
+            extra: toml::Table::new(),
         }
     }
 }
 
+impl UIConfig {
+    /// Allowed range for `icon_size`
+    pub const ICON_SIZE_RANGE: std::ops::RangeInclusive<i32> = 16..=256;
+    /// Allowed range for `row_spacing`
+    pub const ROW_SPACING_RANGE: std::ops::RangeInclusive<i32> = 0..=64;
+    /// Accepted values of `ellipsis`
+    pub const ELLIPSIS_MODES: &'static [&'static str] = &["auto", "middle", "end"];
+
+    /// Clamp out-of-range values, returning a warning for each adjusted field
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for (name, value, range) in [
+            ("icon_size", &mut self.icon_size, Self::ICON_SIZE_RANGE),
+            (
+                "row_spacing",
+                &mut self.row_spacing,
+                Self::ROW_SPACING_RANGE,
+            ),
+        ] {
+            let clamped = (*value).clamp(*range.start(), *range.end());
+            if clamped != *value {
+                warnings.push(format!(
+                    "ui.{} = {} is out of range {}-{}, using {}",
+                    name,
+                    value,
+                    range.start(),
+                    range.end(),
+                    clamped
+                ));
+                *value = clamped;
+            }
+        }
+
+        if !Self::ELLIPSIS_MODES.contains(&self.ellipsis.as_str()) {
+            warnings.push(format!(
+                "ui.ellipsis = {:?} is not one of {}, using \"auto\"",
+                self.ellipsis,
+                Self::ELLIPSIS_MODES.join(", ")
+            ));
+            self.ellipsis = "auto".to_string();
+        }
+
+        warnings
+    }
+}
+
 /// Plugin configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct PluginsConfig {
-    /// Enable calculator plugin
-    pub calculator: bool,
+    /// Calculator plugin: `calculator = false` disables it, a
+    /// `[plugins.calculator]` table tunes it
+    #[serde(deserialize_with = "bool_or_table")]
+    pub calculator: CalculatorConfig,
+    /// Enable advanced calculator plugin (time, units, currency); requires `calculator`
+    pub advanced_calculator: bool,
+    /// Fetch exchange rates from open.er-api.com for currency conversions;
+    /// off uses built-in approximate rates
+    pub live_currency_rates: bool,
     /// Enable shell command plugin
     pub shell: bool,
     /// Enable web search plugin
@@ -115,8 +382,10 @@ pub struct PluginsConfig {
     pub ssh: bool,
     /// Enable editors plugin (workspaces)
     pub editors: bool,
-    /// Enable file browser plugin
-    pub files: bool,
+    /// File browser plugin: `files = false` disables it, a `[plugins.files]`
+    /// table tunes it
+    #[serde(deserialize_with = "bool_or_table")]
+    pub files: FilesConfig,
     /// Enable launcher (self-update) plugin
     pub launcher: bool,
     /// Enable screenshot plugin
@@ -125,8 +394,10 @@ pub struct PluginsConfig {
     pub emoji: bool,
     /// Enable clipboard history plugin
     pub clipboard: bool,
-    /// Enable browser history plugin (recent tabs/websites)
-    pub browser_history: bool,
+    /// Browser history plugin (recent tabs/websites): `browser_history = false`
+    /// disables it, a `[plugins.browser_history]` table tunes it
+    #[serde(deserialize_with = "bool_or_table")]
+    pub browser_history: BrowserHistoryConfig,
     /// Enable recent documents plugin
     pub recent_documents: bool,
     /// Enable window management plugin (Hyprland/Sway)
@@ -135,33 +406,338 @@ pub struct PluginsConfig {
     pub session_switcher: bool,
     /// Enable git projects plugin (repository search)
     pub git_projects: bool,
+    /// Enable theme switcher plugin (@theme)
+    pub theme_switcher: bool,
+    /// Enable QR code plugin (@qr)
+    pub qr_code: bool,
+    /// Enable encoder plugin (@encode / @decode)
+    pub encoder: bool,
+    /// Enable hash plugin (@hash)
+    pub hash: bool,
+    /// Enable generator plugin (@uuid, @random, @password)
+    pub generator: bool,
     /// Shell command prefix (default: ">")
     pub shell_prefix: String,
+    /// Per-plugin caps on results contributed to global search, keyed by plugin name
+    /// (e.g. `[plugins.max_results]` with `files = 5`). Unlisted plugins use built-in defaults.
+    pub max_results: HashMap<String, usize>,
+    /// Priority overrides keyed by plugin name (e.g. `[plugins.priority]` with
+    /// `files = 1100`); unlisted plugins keep their built-in priority
+    pub priority: HashMap<String, i32>,
+    /// Dynamic plugins taking longer than this to load are flagged in the UI (milliseconds)
+    pub slow_warn_ms: u64,
+    /// Dynamic plugins to skip at startup, by library file stem (e.g. `libmy_plugin`)
+    pub disabled: Vec<String>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for PluginsConfig {
     fn default() -> Self {
         Self {
-            calculator: true,
+            calculator: CalculatorConfig::default(),
+            advanced_calculator: true,
+            live_currency_rates: false,
             shell: true,
             web_search: true,
             ssh: true,
             editors: true,
-            files: true,
+            files: FilesConfig::default(),
             launcher: true,
             screenshot: true,
             emoji: true,
             clipboard: true,
-            browser_history: true,
+            browser_history: BrowserHistoryConfig::default(),
             recent_documents: true,
             window_management: true,
             session_switcher: true,
             git_projects: true,
+            theme_switcher: true,
+            qr_code: true,
+            encoder: true,
+            hash: true,
+            generator: true,
             shell_prefix: ">".to_string(),
+            max_results: HashMap::new(),
+            priority: HashMap::new(),
+            slow_warn_ms: 50,
+            disabled: Vec::new(),
+            extra: toml::Table::new(),
+        }
+    }
+}
+
+impl PluginsConfig {
+    /// Result cap for a plugin in global search (`[plugins.max_results]`, else the
+    /// plugin's own setting such as `browser_history.global_max_results`)
+    pub fn result_cap(&self, plugin_name: &str) -> Option<usize> {
+        let plugin_default = match plugin_name {
+            "browser_history" => Some(self.browser_history.global_max_results),
+            _ => None,
+        };
+        self.max_results
+            .get(plugin_name)
+            .copied()
+            .or(plugin_default)
+    }
+
+    /// Priority of a plugin: its `[plugins.priority]` override, else `default`
+    /// (the plugin's own `priority()`)
+    pub fn priority_of(&self, plugin_name: &str, default: i32) -> i32 {
+        self.priority.get(plugin_name).copied().unwrap_or(default)
+    }
+
+    /// Whether the dynamic plugin with library stem `id` is disabled
+    pub fn is_dynamic_disabled(&self, id: &str) -> bool {
+        self.disabled.iter().any(|disabled| disabled == id)
+    }
+
+    /// Add a dynamic plugin to `disabled`, returning false if it already was
+    pub fn disable_dynamic(&mut self, id: &str) -> bool {
+        if self.is_dynamic_disabled(id) {
+            return false;
+        }
+        self.disabled.push(id.to_string());
+        true
+    }
+
+    /// Keys in a raw `[plugins]` table that don't match any known option
+    pub fn unknown_keys(table: &toml::Table) -> Vec<String> {
+        let known = match toml::Table::try_from(PluginsConfig::default()) {
+            Ok(known) => known,
+            Err(_) => return Vec::new(),
+        };
+
+        table
+            .keys()
+            .filter(|key| !known.contains_key(*key))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Browser history plugin configuration (`[plugins.browser_history]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrowserHistoryConfig {
+    /// Enable browser history plugin
+    pub enabled: bool,
+    /// Seconds before the in-memory history is re-read from the browsers
+    pub cache_ttl_secs: u64,
+    /// Most recent entries kept (per browser and after merging)
+    pub max_index_entries: usize,
+    /// Results shown in global (unprefixed) search; `@tabs` is not capped
+    pub global_max_results: usize,
+}
+
+impl Default for BrowserHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cache_ttl_secs: 300,
+            max_index_entries: 100,
+            global_max_results: 2,
+        }
+    }
+}
+
+impl BrowserHistoryConfig {
+    /// Allowed range for `cache_ttl_secs`
+    pub const CACHE_TTL_RANGE: std::ops::RangeInclusive<u64> = 10..=86_400;
+    /// Allowed range for `max_index_entries`
+    pub const MAX_INDEX_ENTRIES_RANGE: std::ops::RangeInclusive<usize> = 10..=10_000;
+    /// Allowed range for `global_max_results`
+    pub const GLOBAL_MAX_RESULTS_RANGE: std::ops::RangeInclusive<usize> = 0..=20;
+
+    /// Clamp out-of-range values, returning a warning for each adjusted field
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let range = Self::CACHE_TTL_RANGE;
+        let clamped = self.cache_ttl_secs.clamp(*range.start(), *range.end());
+        if clamped != self.cache_ttl_secs {
+            warnings.push(format!(
+                "plugins.browser_history.cache_ttl_secs = {} is out of range {}-{}, using {}",
+                self.cache_ttl_secs,
+                range.start(),
+                range.end(),
+                clamped
+            ));
+            self.cache_ttl_secs = clamped;
+        }
+
+        for (name, value, range) in [
+            (
+                "max_index_entries",
+                &mut self.max_index_entries,
+                Self::MAX_INDEX_ENTRIES_RANGE,
+            ),
+            (
+                "global_max_results",
+                &mut self.global_max_results,
+                Self::GLOBAL_MAX_RESULTS_RANGE,
+            ),
+        ] {
+            let clamped = (*value).clamp(*range.start(), *range.end());
+            if clamped != *value {
+                warnings.push(format!(
+                    "plugins.browser_history.{} = {} is out of range {}-{}, using {}",
+                    name,
+                    value,
+                    range.start(),
+                    range.end(),
+                    clamped
+                ));
+                *value = clamped;
+            }
+        }
+
+        warnings
+    }
+}
+
+/// Calculator plugin configuration (`[plugins.calculator]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalculatorConfig {
+    /// Enable calculator plugin
+    pub enabled: bool,
+    /// Show results with the locale's thousands separators ("1,234,567.5");
+    /// Alt+Enter copies that form, Enter the raw number
+    pub group_digits: bool,
+    /// Swap the copies: Enter copies the grouped form, Alt+Enter the raw number
+    pub copy_grouped: bool,
+}
+
+impl Default for CalculatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            group_digits: true,
+            copy_grouped: false,
+        }
+    }
+}
+
+/// File browser plugin configuration (`[plugins.files]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilesConfig {
+    /// Enable file browser plugin
+    pub enabled: bool,
+    /// Only list recent files under these directories (`~/` allowed); empty
+    /// lists all of them
+    pub recent_allow_dirs: Vec<String>,
+    /// Order of recent files: "xbel" (as GTK stores them), "modified" or
+    /// "accessed" (newest first)
+    pub recent_sort: String,
+}
+
+impl Default for FilesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            recent_allow_dirs: Vec::new(),
+            recent_sort: "xbel".to_string(),
+        }
+    }
+}
+
+impl FilesConfig {
+    /// Accepted values of `recent_sort`
+    pub const RECENT_SORTS: &'static [&'static str] = &["xbel", "modified", "accessed"];
+
+    /// Drop `recent_allow_dirs` entries that aren't absolute or `~/` paths and
+    /// reset an unknown `recent_sort`, returning a warning for each
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        self.recent_allow_dirs.retain(|dir| {
+            let valid = dir == "~" || dir.starts_with("~/") || dir.starts_with('/');
+            if !valid {
+                warnings.push(format!(
+                    "plugins.files.recent_allow_dirs entry \"{}\" is not an absolute or ~/ path, ignoring it",
+                    dir
+                ));
+            }
+            valid
+        });
+
+        if !Self::RECENT_SORTS.contains(&self.recent_sort.as_str()) {
+            warnings.push(format!(
+                "plugins.files.recent_sort = {:?} is not one of {}, using \"xbel\"",
+                self.recent_sort,
+                Self::RECENT_SORTS.join(", ")
+            ));
+            self.recent_sort = "xbel".to_string();
+        }
+        warnings
+    }
+
+    /// `recent_allow_dirs` with `~` expanded to the home directory
+    pub fn recent_allow_paths(&self) -> Vec<PathBuf> {
+        let home = dirs::home_dir().unwrap_or_default();
+        self.recent_allow_dirs
+            .iter()
+            .map(|dir| match dir.strip_prefix('~') {
+                Some(rest) => home.join(rest.trim_start_matches('/')),
+                None => PathBuf::from(dir),
+            })
+            .collect()
+    }
+}
+
+/// Plugin options that can also be given as a plain `true`/`false` toggle
+trait Toggle {
+    /// Options for `plugin = true/false`: the defaults with `enabled` set
+    fn toggled(enabled: bool) -> Self;
+}
+
+impl Toggle for BrowserHistoryConfig {
+    fn toggled(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+}
+
+impl Toggle for CalculatorConfig {
+    fn toggled(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+}
+
+impl Toggle for FilesConfig {
+    fn toggled(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
         }
     }
 }
 
+/// Accept the plain `plugin = true/false` toggle as well as the options table
+fn bool_or_table<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + Toggle,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Setting<T> {
+        Toggle(bool),
+        Options(T),
+    }
+
+    Ok(match Setting::deserialize(deserializer)? {
+        Setting::Toggle(enabled) => T::toggled(enabled),
+        Setting::Options(options) => options,
+    })
+}
+
 /// Updater configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -170,6 +746,8 @@ pub struct UpdaterConfig {
     pub check_on_startup: bool,
     /// Automatically download updates (currently not implemented)
     pub auto_download: bool,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for UpdaterConfig {
@@ -177,6 +755,7 @@ impl Default for UpdaterConfig {
         Self {
             check_on_startup: true,
             auto_download: false,
+            extra: toml::Table::new(),
         }
     }
 }
@@ -187,12 +766,57 @@ impl Default for UpdaterConfig {
 pub struct EnvironmentConfig {
     /// Merge login-shell environment variables into spawned processes
     pub merge_login_env: bool,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for EnvironmentConfig {
     fn default() -> Self {
         Self {
             merge_login_env: false,
+            extra: toml::Table::new(),
+        }
+    }
+}
+
+/// Terminal configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TerminalConfig {
+    /// Terminal emulator for terminal apps and elevated commands (empty = auto-detect)
+    pub command: String,
+    /// Keep the terminal open after a terminal command exits, until Enter is pressed
+    pub hold_on_exit: bool,
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl Default for TerminalConfig {
+    fn default() -> Self {
+        Self {
+            command: String::new(),
+            hold_on_exit: false,
+            extra: toml::Table::new(),
+        }
+    }
+}
+
+/// Shell command configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShellConfig {
+    /// Send a desktop notification when a command run from the shell plugin
+    /// exits with a non-zero status
+    pub notify_on_error: bool,
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl Default for ShellConfig {
+    fn default() -> Self {
+        Self {
+            notify_on_error: false,
+            extra: toml::Table::new(),
         }
     }
 }
@@ -203,11 +827,16 @@ impl Default for EnvironmentConfig {
 pub struct HandlersConfig {
     /// Custom open handlers configured by the user
     pub open: Vec<OpenHandlerConfig>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for HandlersConfig {
     fn default() -> Self {
-        Self { open: Vec::new() }
+        Self {
+            open: Vec::new(),
+            extra: toml::Table::new(),
+        }
     }
 }
 
@@ -257,4 +886,163 @@ mod tests {
         assert_eq!(config.window.width, deserialized.window.width);
         assert_eq!(config.search.max_results, deserialized.search.max_results);
     }
+
+    #[test]
+    fn test_search_config_validation() {
+        let mut search = SearchConfig {
+            debounce_ms: 5000,
+            min_query_length: Some(0),
+            order_strategy: "random".to_string(),
+            ..SearchConfig::default()
+        };
+        let warnings = search.validate();
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(search.debounce_ms, SearchConfig::MAX_DEBOUNCE_MS);
+        assert_eq!(search.min_query_length, Some(1));
+        assert_eq!(search.order_strategy, "score");
+
+        let mut search = SearchConfig::default();
+        assert!(search.validate().is_empty());
+        assert_eq!(search.debounce_ms, 30);
+        assert_eq!(search.min_query_length, None);
+    }
+
+    #[test]
+    fn test_general_config_validation() {
+        let mut general = GeneralConfig {
+            open_command: "  ".to_string(),
+            ..GeneralConfig::default()
+        };
+        assert_eq!(general.validate().len(), 1);
+        assert_eq!(general.open_command, "xdg-open {}");
+
+        let config: Config =
+            toml::from_str("[general]\nopen_command = \"handlr open {}\"").unwrap();
+        assert_eq!(config.general.open_command, "handlr open {}");
+        assert!(Config::default().general.clone().validate().is_empty());
+    }
+
+    #[test]
+    fn test_ui_config_validation() {
+        let mut ui = UIConfig {
+            icon_size: 512,
+            row_spacing: -4,
+            ellipsis: "start".to_string(),
+            ..UIConfig::default()
+        };
+        let warnings = ui.validate();
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(ui.icon_size, 256);
+        assert_eq!(ui.row_spacing, 0);
+        assert_eq!(ui.ellipsis, "auto");
+
+        let mut ui = UIConfig {
+            icon_size: 8,
+            ..UIConfig::default()
+        };
+        ui.validate();
+        assert_eq!(ui.icon_size, 16);
+
+        let mut ui = UIConfig {
+            icon_size: 96,
+            row_spacing: 6,
+            ..UIConfig::default()
+        };
+        assert!(ui.validate().is_empty());
+        assert_eq!((ui.icon_size, ui.row_spacing), (96, 6));
+    }
+
+    #[test]
+    fn test_plugin_result_caps() {
+        let config: Config = toml::from_str("[plugins.max_results]\nfiles = 5\n").unwrap();
+        assert_eq!(config.plugins.result_cap("files"), Some(5));
+        assert_eq!(config.plugins.result_cap("browser_history"), Some(2));
+        assert_eq!(config.plugins.result_cap("ssh"), None);
+
+        let config: Config =
+            toml::from_str("[plugins.max_results]\nbrowser_history = 4\n").unwrap();
+        assert_eq!(config.plugins.result_cap("browser_history"), Some(4));
+
+        let config: Config =
+            toml::from_str("[plugins.browser_history]\nglobal_max_results = 5\n").unwrap();
+        assert_eq!(config.plugins.result_cap("browser_history"), Some(5));
+    }
+
+    #[test]
+    fn test_files_options() {
+        let config: Config = toml::from_str("[plugins]\nfiles = false\n").unwrap();
+        assert!(!config.plugins.files.enabled);
+        assert!(config.plugins.files.recent_allow_dirs.is_empty());
+
+        let mut config: Config = toml::from_str(
+            r#"
+            [plugins.files]
+            recent_allow_dirs = ["~/Projects", "/srv/shared", "relative/dir"]
+            recent_sort = "newest"
+            "#,
+        )
+        .unwrap();
+        let files = &mut config.plugins.files;
+        assert!(files.enabled);
+        assert_eq!(files.validate().len(), 2);
+        assert_eq!(files.recent_sort, "xbel");
+        assert_eq!(files.recent_allow_dirs, vec!["~/Projects", "/srv/shared"]);
+        let home = dirs::home_dir().unwrap_or_default();
+        assert_eq!(
+            files.recent_allow_paths(),
+            vec![home.join("Projects"), PathBuf::from("/srv/shared")]
+        );
+    }
+
+    #[test]
+    fn test_browser_history_options() {
+        // The plain toggle still works
+        let config: Config = toml::from_str("[plugins]\nbrowser_history = false\n").unwrap();
+        assert!(!config.plugins.browser_history.enabled);
+        assert_eq!(config.plugins.browser_history.max_index_entries, 100);
+
+        let mut config: Config = toml::from_str(
+            r#"
+            [plugins.browser_history]
+            cache_ttl_secs = 1
+            max_index_entries = 500
+            global_max_results = 99
+            "#,
+        )
+        .unwrap();
+        let browser = &mut config.plugins.browser_history;
+        assert!(browser.enabled);
+        assert_eq!(browser.validate().len(), 2);
+        assert_eq!(browser.cache_ttl_secs, 10);
+        assert_eq!(browser.max_index_entries, 500);
+        assert_eq!(browser.global_max_results, 20);
+
+        // Written back as a table that parses again
+        let saved = toml::to_string(&config).unwrap();
+        let reloaded: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.plugins.browser_history.max_index_entries, 500);
+    }
+
+    #[test]
+    fn test_unknown_plugin_keys() {
+        let table: toml::Table = toml::from_str(
+            r#"
+            calculator = false
+            theme_switcher = false
+            calcualtor = true
+            fancy_plugin = true
+            "#,
+        )
+        .unwrap();
+
+        let mut unknown = PluginsConfig::unknown_keys(&table);
+        unknown.sort();
+        assert_eq!(unknown, vec!["calcualtor", "fancy_plugin"]);
+
+        // Unknown keys are ignored rather than rejected when parsing
+        let config: Config =
+            toml::from_str("[plugins]\ncalculator = false\nfancy_plugin = true\n").unwrap();
+        assert!(!config.plugins.calculator.enabled);
+        assert!(config.plugins.shell);
+    }
 }
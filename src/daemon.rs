@@ -15,8 +15,62 @@ pub fn is_daemon_running() -> bool {
     sock_path.exists() && UnixStream::connect(&sock_path).is_ok()
 }
 
-/// Send signal to running daemon to show the launcher window
-pub fn send_show_signal() -> Result<()> {
+/// Command sent to the daemon over its socket, one per connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaemonCommand {
+    /// Show the launcher window (`show`)
+    Show,
+    /// Show the launcher with the query prefilled and searched (`search:<query>`)
+    Search(String),
+}
+
+impl DaemonCommand {
+    /// Command for the launcher's arguments: `--query <text>` (or `--query=<text>`)
+    /// prefills the search, anything else just shows the window
+    pub fn from_args(args: &[String]) -> Self {
+        let query = args.iter().enumerate().find_map(|(i, arg)| {
+            if arg == "--query" {
+                args.get(i + 1).cloned()
+            } else {
+                arg.strip_prefix("--query=").map(str::to_string)
+            }
+        });
+
+        match query {
+            Some(query) if !query.trim().is_empty() => Self::Search(query),
+            _ => Self::Show,
+        }
+    }
+
+    /// Parse a command read from the socket
+    pub fn parse(message: &str) -> Option<Self> {
+        if message == "show" {
+            return Some(Self::Show);
+        }
+        message
+            .strip_prefix("search:")
+            .map(|query| Self::Search(query.to_string()))
+    }
+
+    /// Wire form of the command, as read by `parse`
+    pub fn to_message(&self) -> String {
+        match self {
+            Self::Show => "show".to_string(),
+            Self::Search(query) => format!("search:{}", query),
+        }
+    }
+
+    /// Query to prefill, if any
+    pub fn query(&self) -> Option<&str> {
+        match self {
+            Self::Show => None,
+            Self::Search(query) => Some(query),
+        }
+    }
+}
+
+/// Send `command` to the running daemon
+pub fn send_command(command: &DaemonCommand) -> Result<()> {
     let sock_path = socket_path();
 
     if !sock_path.exists() {
@@ -26,12 +80,11 @@ pub fn send_show_signal() -> Result<()> {
     debug!("Connecting to daemon socket at {:?}", sock_path);
     let mut stream = UnixStream::connect(&sock_path)?;
 
-    // Send "show" command
     use std::io::Write;
-    stream.write_all(b"show\n")?;
+    stream.write_all(format!("{}\n", command.to_message()).as_bytes())?;
     stream.flush()?;
 
-    info!("Sent show signal to daemon");
+    info!("Sent {:?} to daemon", command);
     Ok(())
 }
 
@@ -165,3 +218,49 @@ pub fn start_browser_indexer(browser_plugin: std::sync::Arc<crate::plugins::Brow
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_query_arg() {
+        assert_eq!(
+            DaemonCommand::from_args(&args(&["native-launcher", "--query", "firefox"])),
+            DaemonCommand::Search("firefox".to_string())
+        );
+        assert_eq!(
+            DaemonCommand::from_args(&args(&["native-launcher", "--query=@emoji smile"])),
+            DaemonCommand::Search("@emoji smile".to_string())
+        );
+        assert_eq!(
+            DaemonCommand::from_args(&args(&["native-launcher"])),
+            DaemonCommand::Show
+        );
+        // Missing or blank text just shows the launcher
+        assert_eq!(
+            DaemonCommand::from_args(&args(&["native-launcher", "--query"])),
+            DaemonCommand::Show
+        );
+        assert_eq!(
+            DaemonCommand::from_args(&args(&["native-launcher", "--query", " "])),
+            DaemonCommand::Show
+        );
+    }
+
+    #[test]
+    fn test_command_round_trip() {
+        for command in [
+            DaemonCommand::Show,
+            DaemonCommand::Search("firefox".to_string()),
+            DaemonCommand::Search("search: with colon".to_string()),
+        ] {
+            assert_eq!(DaemonCommand::parse(&command.to_message()), Some(command));
+        }
+        assert_eq!(DaemonCommand::parse("hide"), None);
+    }
+}
@@ -1,18 +1,19 @@
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs;
+use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 use std::time::UNIX_EPOCH;
 use tracing::{debug, info, warn};
 
-use super::entry::DesktopEntry;
+use super::entry::{current_locale, DesktopEntry};
+use crate::utils::backup::backup_corrupt_file;
 
 /// Cache metadata for a desktop file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
 struct CachedFile {
-    /// Full path to the .desktop file
-    path: PathBuf,
     /// Last modification time (seconds since UNIX epoch)
     mtime: u64,
     /// Parsed desktop entry
@@ -20,13 +21,29 @@ struct CachedFile {
 }
 
 /// Desktop entry cache for fast startup
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Backed by an SQLite database with one row per .desktop file, so saving only
+/// writes the entries that changed since the last save and loading drops just
+/// the rows whose file was modified. Saves run in a transaction, which SQLite's
+/// journal keeps atomic, so unlike the other state files it needs no
+/// [`atomic_write`](crate::utils::atomic_write). Names are localized when
+/// parsed, so the rows are only kept for the messages locale they were
+/// written under.
+#[derive(Debug)]
 pub struct DesktopCache {
     /// Cache format version for compatibility
     #[allow(dead_code)]
     version: u32,
     /// Cached entries keyed by file path
     entries: HashMap<PathBuf, CachedFile>,
+    /// Entries inserted or updated since the last save
+    dirty: HashSet<PathBuf>,
+    /// Entries removed since the last save
+    removed: HashSet<PathBuf>,
+    /// Database the cache was loaded from (default location when `None`)
+    db_path: Option<PathBuf>,
+    /// Messages locale the cached entries were localized for
+    locale: Option<String>,
 }
 
 impl Default for DesktopCache {
@@ -36,17 +53,22 @@ impl Default for DesktopCache {
 }
 
 impl DesktopCache {
-    const VERSION: u32 = 1;
+    /// Stored as the database's `user_version`; bump when `DesktopEntry` changes
+    const VERSION: u32 = 9;
 
     /// Create a new empty cache
     pub fn new() -> Self {
         Self {
             version: Self::VERSION,
             entries: HashMap::new(),
+            dirty: HashSet::new(),
+            removed: HashSet::new(),
+            db_path: None,
+            locale: current_locale(),
         }
     }
 
-    /// Get the cache file path
+    /// Get the cache database path
     fn cache_path() -> Result<PathBuf> {
         let cache_dir = dirs::cache_dir()
             .context("Failed to get cache directory")?
@@ -55,48 +77,190 @@ impl DesktopCache {
         // Create cache directory if it doesn't exist
         fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
 
-        Ok(cache_dir.join("entries.cache"))
+        // Drop the whole-file bincode cache used before the SQLite one
+        let legacy = cache_dir.join("entries.cache");
+        if legacy.exists() {
+            debug!("Removing legacy cache file: {}", legacy.display());
+            let _ = fs::remove_file(&legacy);
+        }
+
+        Ok(cache_dir.join("entries.sqlite"))
+    }
+
+    /// Open the database at `path`, recreating the table on a version mismatch
+    /// and emptying it when it was written under another locale
+    fn open(path: &Path, locale: Option<&str>) -> Result<Connection> {
+        let conn = Connection::open(path).context("Failed to open cache database")?;
+
+        let version: u32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .context("Failed to read cache version")?;
+        if version != Self::VERSION {
+            if version != 0 {
+                warn!(
+                    "Cache version mismatch (expected {}, got {}), rebuilding cache",
+                    Self::VERSION,
+                    version
+                );
+            }
+            conn.execute_batch(&format!(
+                "DROP TABLE IF EXISTS entries;
+                 PRAGMA user_version = {};",
+                Self::VERSION
+            ))
+            .context("Failed to reset cache database")?;
+        }
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS entries (
+                path BLOB PRIMARY KEY,
+                mtime INTEGER NOT NULL,
+                entry BLOB NOT NULL
+            )",
+            [],
+        )
+        .context("Failed to create cache table")?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL)",
+            [],
+        )
+        .context("Failed to create cache metadata table")?;
+        let locale = locale.unwrap_or_default();
+        let cached_locale: Option<String> = conn
+            .query_row("SELECT value FROM meta WHERE key = 'locale'", [], |row| {
+                row.get(0)
+            })
+            .optional()
+            .context("Failed to read cache locale")?;
+        if cached_locale.as_deref() != Some(locale) {
+            if cached_locale.is_some() {
+                info!("Locale changed to {:?}, rebuilding cache", locale);
+            }
+            conn.execute_batch("DELETE FROM entries")
+                .context("Failed to reset cache database")?;
+            conn.execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES ('locale', ?1)",
+                params![locale],
+            )
+            .context("Failed to write cache locale")?;
+        }
+
+        Ok(conn)
     }
 
     /// Load cache from disk
     pub fn load() -> Result<Self> {
-        let path = Self::cache_path()?;
+        Self::load_from(&Self::cache_path()?)
+    }
 
-        if !path.exists() {
-            debug!("Cache file not found, creating new cache");
-            return Ok(Self::new());
+    /// Load the cache stored in the database at `path`
+    ///
+    /// Rows whose file changed (or vanished) since they were written are
+    /// skipped and deleted on the next save; every other row is kept. A
+    /// database that can't be read is moved to `<name>.bak` and rebuilt.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        info!("Loading cache from: {}", path.display());
+        let locale = current_locale();
+        match Self::read_from(path, locale.clone()) {
+            Ok(cache) => Ok(cache),
+            Err(e) if path.exists() => {
+                warn!("Cache at {} is unreadable: {:#}", path.display(), e);
+                backup_corrupt_file(path)?;
+                Self::read_from(path, locale)
+            }
+            Err(e) => Err(e),
         }
+    }
 
-        info!("Loading cache from: {}", path.display());
-        let data = fs::read(&path).context("Failed to read cache file")?;
-
-        let cache: DesktopCache =
-            bincode::deserialize(&data).context("Failed to deserialize cache")?;
-
-        // Check version compatibility
-        if cache.version != Self::VERSION {
-            warn!(
-                "Cache version mismatch (expected {}, got {}), rebuilding cache",
-                Self::VERSION,
-                cache.version
-            );
-            return Ok(Self::new());
+    fn read_from(path: &Path, locale: Option<String>) -> Result<Self> {
+        let conn = Self::open(path, locale.as_deref())?;
+
+        let mut cache = Self::new();
+        cache.db_path = Some(path.to_path_buf());
+        cache.locale = locale;
+
+        let mut stmt = conn.prepare("SELECT path, mtime, entry FROM entries")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, Vec<u8>>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Vec<u8>>(2)?,
+            ))
+        })?;
+
+        let mut stale = 0;
+        for row in rows {
+            let (path, mtime, data) = row?;
+            let path = PathBuf::from(OsStr::from_bytes(&path));
+            let mtime = mtime as u64;
+
+            let current = Self::get_mtime(&path).ok();
+            let entry = match bincode::deserialize::<DesktopEntry>(&data) {
+                Ok(entry) if current == Some(mtime) => entry,
+                _ => {
+                    stale += 1;
+                    cache.removed.insert(path);
+                    continue;
+                }
+            };
+            cache.entries.insert(path, CachedFile { mtime, entry });
         }
 
-        info!("Loaded {} cached entries", cache.entries.len());
+        info!(
+            "Loaded {} cached entries ({} stale)",
+            cache.entries.len(),
+            stale
+        );
         Ok(cache)
     }
 
-    /// Save cache to disk
-    pub fn save(&self) -> Result<()> {
-        let path = Self::cache_path()?;
+    /// Write entries changed since the last save to disk
+    pub fn save(&mut self) -> Result<()> {
+        let path = match &self.db_path {
+            Some(path) => path.clone(),
+            None => Self::cache_path()?,
+        };
+        if self.dirty.is_empty() && self.removed.is_empty() && path.exists() {
+            debug!("Cache unchanged, nothing to save");
+            return Ok(());
+        }
         debug!("Saving cache to: {}", path.display());
 
-        let data = bincode::serialize(&self).context("Failed to serialize cache")?;
-
-        fs::write(&path, data).context("Failed to write cache file")?;
+        let mut conn = Self::open(&path, self.locale.as_deref())?;
+        let tx = conn.transaction()?;
+        {
+            let mut delete = tx.prepare("DELETE FROM entries WHERE path = ?1")?;
+            for removed in &self.removed {
+                delete.execute(params![removed.as_os_str().as_bytes()])?;
+            }
 
-        info!("Saved {} entries to cache", self.entries.len());
+            let mut upsert = tx.prepare(
+                "INSERT OR REPLACE INTO entries (path, mtime, entry) VALUES (?1, ?2, ?3)",
+            )?;
+            for dirty in &self.dirty {
+                let Some(cached) = self.entries.get(dirty) else {
+                    continue;
+                };
+                let data =
+                    bincode::serialize(&cached.entry).context("Failed to serialize entry")?;
+                upsert.execute(params![
+                    dirty.as_os_str().as_bytes(),
+                    cached.mtime as i64,
+                    data
+                ])?;
+            }
+        }
+        tx.commit().context("Failed to write cache database")?;
+
+        info!(
+            "Saved cache: {} updated, {} removed, {} total",
+            self.dirty.len(),
+            self.removed.len(),
+            self.entries.len()
+        );
+        self.dirty.clear();
+        self.removed.clear();
         Ok(())
     }
 
@@ -135,8 +299,9 @@ impl DesktopCache {
     pub fn insert(&mut self, path: PathBuf, entry: DesktopEntry) -> Result<()> {
         let mtime = Self::get_mtime(&path)?;
 
-        self.entries
-            .insert(path.clone(), CachedFile { path, mtime, entry });
+        self.removed.remove(&path);
+        self.dirty.insert(path.clone());
+        self.entries.insert(path, CachedFile { mtime, entry });
 
         Ok(())
     }
@@ -145,7 +310,10 @@ impl DesktopCache {
     #[allow(dead_code)]
 
     pub fn remove(&mut self, path: &Path) {
-        self.entries.remove(path);
+        if self.entries.remove(path).is_some() {
+            self.dirty.remove(path);
+            self.removed.insert(path.to_path_buf());
+        }
     }
 
     /// Remove entries that no longer exist on disk
@@ -160,7 +328,7 @@ impl DesktopCache {
 
         for path in to_remove {
             debug!("Removing deleted file from cache: {}", path.display());
-            self.entries.remove(&path);
+            self.remove(&path);
         }
     }
 
@@ -193,3 +361,141 @@ pub struct CacheStats {
     pub total_entries: usize,
     pub version: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_changed_mtime_invalidates_only_that_row() {
+        let dir =
+            std::env::temp_dir().join(format!("native-launcher-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let db = dir.join("entries.sqlite");
+
+        let mut cache = DesktopCache::load_from(&db).unwrap();
+        let mut paths = Vec::new();
+        for name in ["firefox", "kitty"] {
+            let path = dir.join(format!("{}.desktop", name));
+            fs::write(
+                &path,
+                format!(
+                    "[Desktop Entry]\nType=Application\nName={}\nExec={}\n",
+                    name, name
+                ),
+            )
+            .unwrap();
+            cache
+                .insert(path.clone(), DesktopEntry::from_file(path.clone()).unwrap())
+                .unwrap();
+            paths.push(path);
+        }
+        cache.save().unwrap();
+
+        let reloaded = DesktopCache::load_from(&db).unwrap();
+        assert_eq!(reloaded.get_all().len(), 2);
+        assert!(reloaded.dirty.is_empty() && reloaded.removed.is_empty());
+
+        // Touch one file: only its row is dropped on the next load
+        let later = SystemTime::now() + Duration::from_secs(120);
+        File::options()
+            .write(true)
+            .open(&paths[1])
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        let mut reloaded = DesktopCache::load_from(&db).unwrap();
+        assert!(reloaded.get(&paths[0]).is_some());
+        assert!(reloaded.get(&paths[1]).is_none());
+        assert_eq!(reloaded.get_all().len(), 1);
+
+        // Saving deletes the stale row without rewriting the valid one
+        assert!(reloaded.dirty.is_empty());
+        reloaded.save().unwrap();
+        let conn = Connection::open(&db).unwrap();
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM entries", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_locale_change_drops_cached_entries() {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-cache-locale-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let db = dir.join("entries.sqlite");
+
+        let desktop = dir.join("firefox.desktop");
+        fs::write(
+            &desktop,
+            "[Desktop Entry]\nType=Application\nName=Firefox\nName[de]=Feuerfuchs\nExec=firefox\n",
+        )
+        .unwrap();
+        let mut cache = DesktopCache::read_from(&db, Some("de".to_string())).unwrap();
+        let entry = DesktopEntry::from_file_with_locale(desktop.clone(), Some("de")).unwrap();
+        cache.insert(desktop.clone(), entry).unwrap();
+        cache.save().unwrap();
+
+        let same = DesktopCache::read_from(&db, Some("de".to_string())).unwrap();
+        assert_eq!(same.get(&desktop).unwrap().name, "Feuerfuchs");
+
+        // Another locale must not see the German name
+        let other = DesktopCache::read_from(&db, Some("fr".to_string())).unwrap();
+        assert!(other.get(&desktop).is_none());
+        assert!(DesktopCache::read_from(&db, None)
+            .unwrap()
+            .get_all()
+            .is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_corrupt_database_backed_up_and_rebuilt() {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-cache-corrupt-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let db = dir.join("entries.sqlite");
+
+        let desktop = dir.join("firefox.desktop");
+        fs::write(
+            &desktop,
+            "[Desktop Entry]\nType=Application\nName=Firefox\nExec=firefox\n",
+        )
+        .unwrap();
+        let mut cache = DesktopCache::load_from(&db).unwrap();
+        cache
+            .insert(desktop.clone(), DesktopEntry::from_file(desktop).unwrap())
+            .unwrap();
+        cache.save().unwrap();
+        drop(cache);
+
+        // Cut the file off mid-header, as a crash during a write could
+        let data = fs::read(&db).unwrap();
+        fs::write(&db, &data[..50]).unwrap();
+
+        let mut cache = DesktopCache::load_from(&db).unwrap();
+        assert!(cache.get_all().is_empty());
+        assert_eq!(
+            fs::read(dir.join("entries.sqlite.bak")).unwrap(),
+            &data[..50]
+        );
+        cache.save().unwrap();
+        assert!(DesktopCache::load_from(&db).is_ok());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
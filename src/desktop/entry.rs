@@ -18,10 +18,12 @@ pub struct DesktopAction {
 /// Represents a parsed desktop application entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DesktopEntry {
-    /// Application name
+    /// Application name (`Name`), localized for the messages locale when translated
     pub name: String,
-    /// Generic name (optional)
+    /// Generic name (`GenericName`, optional), localized like `name`
     pub generic_name: Option<String>,
+    /// Short description (`Comment`), localized for `$LANG` when translated
+    pub comment: Option<String>,
     /// Executable command
     pub exec: String,
     /// Icon name or path
@@ -34,26 +36,55 @@ pub struct DesktopEntry {
     pub terminal: bool,
     /// Original .desktop file path
     pub path: PathBuf,
-    /// Whether the entry should be shown
+    /// Entry is valid but shouldn't be listed (`NoDisplay=true`)
     pub no_display: bool,
+    /// Entry was deleted and must never be shown (`Hidden=true`)
+    pub hidden: bool,
+    /// App is started via `org.freedesktop.Application` D-Bus activation
+    /// (`DBusActivatable=true`)
+    pub dbus_activatable: bool,
+    /// App supports startup notification (`StartupNotify=true`)
+    pub startup_notify: bool,
+    /// WM class of the app's windows (`StartupWMClass`), for matching open windows
+    pub startup_wm_class: Option<String>,
+    /// MIME types the app can open (`MimeType`), e.g. `image/png`
+    pub mime_types: Vec<String>,
     /// Available desktop actions (context actions)
     pub actions: Vec<DesktopAction>,
 }
 
 impl DesktopEntry {
-    /// Parse a desktop entry from a .desktop file
+    /// Parse a desktop entry from a .desktop file, localized for the messages locale
     pub fn from_file(path: PathBuf) -> Result<Self> {
+        Self::from_file_with_locale(path, current_locale().as_deref())
+    }
+
+    /// Parse a desktop entry, preferring `Name[<locale>]`, `GenericName[<locale>]`
+    /// and `Comment[<locale>]` (or their `[<lang>]` variants) and merging
+    /// `Keywords[<locale>]` with the default keywords
+    pub fn from_file_with_locale(path: PathBuf, locale: Option<&str>) -> Result<Self> {
         use freedesktop_desktop_entry::DesktopEntry as FdEntry;
 
-        let entry = FdEntry::from_path(path.clone(), &[] as &[&str])?;
+        // Localized keys not listed here are dropped by the parser; the generic
+        // language (`fr` for `fr_FR`) is added automatically
+        let locales: Vec<&str> = locale.into_iter().collect();
+        let entry = FdEntry::from_path(path.clone(), &locales)?;
 
-        // Get the Desktop Entry section
+        // `Name[<locale>]` (or `Name[<lang>]`), else the untranslated name
         let name = entry
-            .name(&[] as &[&str])
+            .name(&locales)
             .ok_or_else(|| anyhow::anyhow!("Desktop entry missing Name field"))?
             .to_string();
 
-        let generic_name = entry.generic_name(&[] as &[&str]).map(|s| s.to_string());
+        let generic_name = entry.generic_name(&locales).map(|s| s.to_string());
+
+        // `Comment[<locale>]` (or `Comment[<lang>]`), else the untranslated comment
+        let comment = (!locales.is_empty())
+            .then(|| entry.desktop_entry_localized("Comment", &locales))
+            .flatten()
+            .map(|s| s.to_string())
+            .or_else(|| entry.desktop_entry("Comment").map(|s| s.to_string()))
+            .filter(|s| !s.trim().is_empty());
 
         let exec = entry
             .exec()
@@ -63,23 +94,43 @@ impl DesktopEntry {
         let icon = entry.icon().map(|s| s.to_string());
 
         let categories = entry
-            .categories()
-            .map(|cats| {
-                cats.iter()
-                    .flat_map(|s| s.split(';'))
-                    .filter(|s| !s.is_empty())
-                    .map(String::from)
-                    .collect()
-            })
+            .desktop_entry("Categories")
+            .map(split_list)
             .unwrap_or_default();
 
-        let keywords = entry
-            .keywords(&[] as &[&str])
-            .map(|kws| kws.iter().map(|s| s.to_string()).collect())
-            .unwrap_or_default();
+        // Localized keywords first, then the untranslated ones
+        let mut keywords = Vec::new();
+        if !locales.is_empty() {
+            if let Some(localized) = entry.desktop_entry_localized("Keywords", &locales) {
+                keywords = split_list(&localized);
+            }
+        }
+        // The untranslated name stays searchable when the name is translated
+        let untranslated_name = entry
+            .desktop_entry("Name")
+            .filter(|untranslated| *untranslated != name)
+            .map(|untranslated| untranslated.to_string());
+        for keyword in untranslated_name.into_iter().chain(
+            entry
+                .desktop_entry("Keywords")
+                .map(split_list)
+                .unwrap_or_default(),
+        ) {
+            if !keywords.contains(&keyword) {
+                keywords.push(keyword);
+            }
+        }
 
         let terminal = entry.terminal();
         let no_display = entry.no_display();
+        let hidden = entry.desktop_entry("Hidden") == Some("true");
+        let dbus_activatable = entry.desktop_entry("DBusActivatable") == Some("true");
+        let startup_notify = entry.desktop_entry("StartupNotify") == Some("true");
+        let startup_wm_class = entry.desktop_entry("StartupWMClass").map(|s| s.to_string());
+        let mime_types = entry
+            .desktop_entry("MimeType")
+            .map(split_list)
+            .unwrap_or_default();
 
         // Parse desktop actions
         let actions = Self::parse_actions(&entry, &path)?;
@@ -87,6 +138,7 @@ impl DesktopEntry {
         Ok(DesktopEntry {
             name,
             generic_name,
+            comment,
             exec,
             icon,
             categories,
@@ -94,6 +146,11 @@ impl DesktopEntry {
             terminal,
             path,
             no_display,
+            hidden,
+            dbus_activatable,
+            startup_notify,
+            startup_wm_class,
+            mime_types,
             actions,
         })
     }
@@ -162,6 +219,35 @@ impl DesktopEntry {
         None
     }
 
+    /// Whether the app declares it can open `mime`; `image/*` style entries
+    /// cover a whole media type
+    pub fn handles_mime(&self, mime: &str) -> bool {
+        let media_type = mime.split('/').next().unwrap_or(mime);
+        self.mime_types.iter().any(|declared| {
+            declared.eq_ignore_ascii_case(mime)
+                || declared
+                    .strip_suffix("/*")
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(media_type))
+        })
+    }
+
+    /// Whether the app is installed through Flatpak: exported by a Flatpak
+    /// installation or launched with `flatpak run`
+    pub fn is_flatpak(&self) -> bool {
+        if self
+            .path
+            .to_string_lossy()
+            .contains("/flatpak/exports/share/applications/")
+        {
+            return true;
+        }
+        let mut words = self.exec.split_whitespace();
+        words
+            .next()
+            .is_some_and(|program| program.rsplit('/').next() == Some("flatpak"))
+            && words.next() == Some("run")
+    }
+
     /// Check if this entry matches a search query
     #[allow(dead_code)]
 
@@ -253,3 +339,155 @@ impl DesktopEntry {
         0
     }
 }
+
+/// Current messages locale, without encoding or modifier (`fr_FR.UTF-8` -> `fr_FR`)
+pub fn current_locale() -> Option<String> {
+    locale_from("LC_MESSAGES", |var| std::env::var(var).ok())
+}
+
+/// Current numeric locale (number formatting), like [`current_locale`]
+pub fn current_numeric_locale() -> Option<String> {
+    locale_from("LC_NUMERIC", |var| std::env::var(var).ok())
+}
+
+/// Locale for `category` (e.g. `LC_MESSAGES`) from the first set variable of
+/// `$LC_ALL`, `$<category>` and `$LANG`, looked up through `var`
+fn locale_from(category: &str, var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    ["LC_ALL", category, "LANG"]
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !value.is_empty())
+        .and_then(|lang| normalize_locale(&lang))
+}
+
+/// Strip encoding and modifier from a locale name; `C`/`POSIX` mean no locale
+fn normalize_locale(lang: &str) -> Option<String> {
+    let locale = lang.split(['.', '@']).next().unwrap_or_default().trim();
+    match locale {
+        "" | "C" | "POSIX" => None,
+        _ => Some(locale.to_string()),
+    }
+}
+
+/// Split a desktop entry list value on `;`, honouring `\;` escapes and
+/// dropping empty items (including the one after a trailing `;`)
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(';') => current.push(';'),
+                Some('\\') => current.push('\\'),
+                Some(other) => {
+                    current.push('\\');
+                    current.push(other);
+                }
+                None => current.push('\\'),
+            },
+            ';' => items.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    items.push(current);
+
+    items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_list_escapes_and_trailing_separator() {
+        assert_eq!(split_list("web;browser;"), vec!["web", "browser"]);
+        assert_eq!(split_list(r"a\;b;c;;"), vec!["a;b", "c"]);
+        assert_eq!(split_list(r"back\\slash"), vec![r"back\slash"]);
+        assert!(split_list(";").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(normalize_locale("fr_FR.UTF-8").as_deref(), Some("fr_FR"));
+        assert_eq!(normalize_locale("de_DE@euro").as_deref(), Some("de_DE"));
+        assert_eq!(normalize_locale("C.UTF-8"), None);
+        assert_eq!(normalize_locale("POSIX"), None);
+    }
+
+    fn write_translated_entry(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-entry-{}-{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.desktop", name));
+        std::fs::write(
+            &path,
+            "[Desktop Entry]\nType=Application\nName=Files\nName[de]=Dateien\n\
+             Name[fr]=Fichiers\nGenericName=File Manager\nGenericName[de]=Dateiverwaltung\n\
+             Exec=nautilus\n",
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_locale_from_env_order() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |var: &str| {
+                vars.iter()
+                    .find(|(name, _)| *name == var)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            locale_from("LC_MESSAGES", env(&[("LANG", "de_DE.UTF-8")])).as_deref(),
+            Some("de_DE")
+        );
+        assert_eq!(
+            locale_from(
+                "LC_MESSAGES",
+                env(&[("LANG", "de_DE.UTF-8"), ("LC_MESSAGES", "fr_CA.UTF-8")])
+            )
+            .as_deref(),
+            Some("fr_CA")
+        );
+        assert_eq!(
+            locale_from("LC_MESSAGES", env(&[("LC_ALL", ""), ("LANG", "C.UTF-8")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_localized_name_for_language() {
+        let path = write_translated_entry("de");
+        let locale = locale_from("LC_MESSAGES", |var| {
+            (var == "LANG").then(|| "de_DE.UTF-8".to_string())
+        });
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), locale.as_deref()).unwrap();
+        assert_eq!(entry.name, "Dateien");
+        assert_eq!(entry.generic_name.as_deref(), Some("Dateiverwaltung"));
+        assert!(entry.keywords.contains(&"Files".to_string()));
+
+        // Language-only fallback, with untranslated fields left as they are
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), Some("fr_CA")).unwrap();
+        assert_eq!(entry.name, "Fichiers");
+        assert_eq!(entry.generic_name.as_deref(), Some("File Manager"));
+
+        // No locale (or no translation): the C-locale default
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        assert_eq!(entry.name, "Files");
+        assert!(entry.keywords.is_empty());
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), Some("ja_JP")).unwrap();
+        assert_eq!(entry.name, "Files");
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
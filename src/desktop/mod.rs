@@ -6,4 +6,4 @@ pub mod watcher;
 
 pub use entry::{DesktopAction, DesktopEntry};
 pub use scanner::DesktopScanner;
-pub use store::{DesktopEntryArena, SharedDesktopEntry};
+pub use store::{DesktopEntryArena, SearchKeys, SharedArena, SharedDesktopEntry};
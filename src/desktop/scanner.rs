@@ -9,35 +9,67 @@ use super::entry::DesktopEntry;
 /// Scans system directories for .desktop files
 pub struct DesktopScanner {
     search_paths: Vec<PathBuf>,
+    /// Keep `NoDisplay=true` entries (debug mode)
+    show_nodisplay: bool,
 }
 
 impl DesktopScanner {
     /// Create a new scanner with default search paths
+    ///
+    /// Paths are ordered by XDG precedence (highest first): entries in earlier
+    /// directories shadow same-ID entries in later ones.
     pub fn new() -> Self {
-        let mut search_paths = Vec::new();
+        let mut candidates = Vec::new();
 
-        // System-wide applications
-        search_paths.push(PathBuf::from("/usr/share/applications"));
-        search_paths.push(PathBuf::from("/usr/local/share/applications"));
-
-        // User-specific applications
-        if let Some(home) = dirs::home_dir() {
-            search_paths.push(home.join(".local/share/applications"));
+        // User-specific applications ($XDG_DATA_HOME/applications)
+        if let Some(data_dir) = dirs::data_local_dir() {
+            candidates.push(data_dir.join("applications"));
         }
 
         // XDG data dirs
         if let Ok(xdg_data_dirs) = std::env::var("XDG_DATA_DIRS") {
             for dir in xdg_data_dirs.split(':') {
                 if !dir.is_empty() {
-                    search_paths.push(PathBuf::from(dir).join("applications"));
+                    candidates.push(PathBuf::from(dir).join("applications"));
                 }
             }
         }
 
-        Self { search_paths }
+        // System-wide applications
+        candidates.push(PathBuf::from("/usr/local/share/applications"));
+        candidates.push(PathBuf::from("/usr/share/applications"));
+
+        Self::with_paths(candidates)
     }
 
-    /// Add a custom search path
+    /// Create a scanner for explicit search paths, highest precedence first.
+    /// Paths that resolve to the same directory (e.g. via symlinks) are only kept once.
+    pub fn with_paths(paths: Vec<PathBuf>) -> Self {
+        let mut search_paths: Vec<PathBuf> = Vec::new();
+        let mut resolved: Vec<PathBuf> = Vec::new();
+
+        for path in paths {
+            let real = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if !resolved.contains(&real) {
+                resolved.push(real);
+                search_paths.push(path);
+            }
+        }
+
+        Self {
+            search_paths,
+            show_nodisplay: false,
+        }
+    }
+
+    /// Include `NoDisplay=true` entries in scan results (debug mode).
+    /// `Hidden=true` entries are always dropped.
+    pub fn with_show_nodisplay(mut self, show_nodisplay: bool) -> Self {
+        self.show_nodisplay = show_nodisplay;
+        self
+    }
+
+    /// Add a custom search path (lowest precedence)
     #[allow(dead_code)]
 
     pub fn add_path(&mut self, path: PathBuf) {
@@ -76,8 +108,8 @@ impl DesktopScanner {
             }
         }
 
-        // Remove duplicates (prefer user entries over system entries)
-        entries = self.deduplicate_entries(entries);
+        // Remove shadowed entries (prefer user entries over system entries)
+        let entries = self.visible_entries(Self::deduplicate_entries(entries));
 
         info!("Scan complete: {} total entries", entries.len());
         Ok(entries)
@@ -121,21 +153,22 @@ impl DesktopScanner {
                     continue;
                 }
 
+                let id = match Self::desktop_file_id(path, file_path) {
+                    Some(id) => id,
+                    None => continue,
+                };
+
                 // Try cache first
                 if let Some(cached_entry) = cache.get(file_path) {
                     cache_hits += 1;
-                    if !cached_entry.no_display {
-                        entries.push(cached_entry.clone());
-                    }
+                    entries.push((id, cached_entry.clone()));
                 } else {
                     // Cache miss - parse file
                     cache_misses += 1;
                     match DesktopEntry::from_file(file_path.to_path_buf()) {
                         Ok(desktop_entry) => {
-                            if !desktop_entry.no_display {
-                                debug!("Parsed: {}", desktop_entry.name);
-                                entries.push(desktop_entry.clone());
-                            }
+                            debug!("Parsed: {}", desktop_entry.name);
+                            entries.push((id, desktop_entry.clone()));
                             // Update cache
                             if let Err(e) = cache.insert(file_path.to_path_buf(), desktop_entry) {
                                 warn!("Failed to cache {}: {}", file_path.display(), e);
@@ -156,8 +189,8 @@ impl DesktopScanner {
             warn!("Failed to save cache: {}", e);
         }
 
-        // Remove duplicates
-        entries = self.deduplicate_entries(entries);
+        // Remove shadowed entries
+        let entries = self.visible_entries(Self::deduplicate_entries(entries));
 
         info!("Scan complete: {} total entries", entries.len());
         Ok(entries)
@@ -166,10 +199,10 @@ impl DesktopScanner {
     /// Scan a single directory for .desktop files
     #[allow(dead_code)]
 
-    fn scan_directory(&self, path: &Path) -> Result<Vec<DesktopEntry>> {
+    fn scan_directory(&self, root: &Path) -> Result<Vec<(String, DesktopEntry)>> {
         let mut entries = Vec::new();
 
-        for entry in WalkDir::new(path)
+        for entry in WalkDir::new(root)
             .follow_links(true)
             .max_depth(3)
             .into_iter()
@@ -182,15 +215,15 @@ impl DesktopScanner {
                 continue;
             }
 
+            let id = match Self::desktop_file_id(root, path) {
+                Some(id) => id,
+                None => continue,
+            };
+
             match DesktopEntry::from_file(path.to_path_buf()) {
                 Ok(desktop_entry) => {
-                    // Skip entries marked as NoDisplay
-                    if !desktop_entry.no_display {
-                        debug!("Parsed: {}", desktop_entry.name);
-                        entries.push(desktop_entry);
-                    } else {
-                        debug!("Skipping NoDisplay entry: {}", desktop_entry.name);
-                    }
+                    debug!("Parsed: {}", desktop_entry.name);
+                    entries.push((id, desktop_entry));
                 }
                 Err(e) => {
                     debug!("Failed to parse {}: {}", path.display(), e);
@@ -201,25 +234,58 @@ impl DesktopScanner {
         Ok(entries)
     }
 
-    /// Remove duplicate entries, preferring entries from later paths
-    fn deduplicate_entries(&self, entries: Vec<DesktopEntry>) -> Vec<DesktopEntry> {
-        use std::collections::HashMap;
+    /// Desktop-file ID per the XDG spec: path relative to the applications
+    /// directory with `/` replaced by `-` (e.g. `kde/konsole.desktop` -> `kde-konsole.desktop`)
+    fn desktop_file_id(root: &Path, file: &Path) -> Option<String> {
+        let relative = file.strip_prefix(root).ok()?;
+        Some(relative.to_string_lossy().replace('/', "-"))
+    }
+
+    /// Remove shadowed entries, keeping the first (highest-precedence) entry for each
+    /// desktop-file ID. Symlinks resolving to an already kept file are dropped too.
+    fn deduplicate_entries(entries: Vec<(String, DesktopEntry)>) -> Vec<DesktopEntry> {
+        use std::collections::HashSet;
 
-        let mut seen = HashMap::new();
+        let mut seen_ids = HashSet::new();
+        let mut seen_files = HashSet::new();
         let mut result = Vec::new();
 
-        // Process in reverse order so user entries (which come later) override system entries
-        for entry in entries.into_iter().rev() {
-            let key = entry.name.clone();
-            if let std::collections::hash_map::Entry::Vacant(e) = seen.entry(key) {
-                e.insert(());
-                result.push(entry);
+        for (id, entry) in entries {
+            if !seen_ids.insert(id) {
+                debug!("Shadowed desktop entry: {}", entry.path.display());
+                continue;
+            }
+
+            let real = std::fs::canonicalize(&entry.path).unwrap_or_else(|_| entry.path.clone());
+            if !seen_files.insert(real) {
+                debug!("Skipping symlinked duplicate: {}", entry.path.display());
+                continue;
             }
+
+            result.push(entry);
         }
 
-        result.reverse();
         result
     }
+
+    /// Drop entries that shouldn't be listed. Runs after deduplication so a user
+    /// override with `Hidden=true` or `NoDisplay=true` still hides the system entry it shadows.
+    fn visible_entries(&self, entries: Vec<DesktopEntry>) -> Vec<DesktopEntry> {
+        entries
+            .into_iter()
+            .filter(|entry| {
+                if entry.hidden {
+                    debug!("Skipping Hidden entry: {}", entry.name);
+                    false
+                } else if entry.no_display && !self.show_nodisplay {
+                    debug!("Skipping NoDisplay entry: {}", entry.name);
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
 }
 
 impl Default for DesktopScanner {
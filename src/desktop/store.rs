@@ -1,45 +1,121 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use super::entry::DesktopEntry;
 
 /// Shared pointer type for desktop entries allocated in the arena
 pub type SharedDesktopEntry = Arc<DesktopEntry>;
 
+/// Lowercased copies of the searchable fields of a desktop entry.
+///
+/// Built once per entry when the arena is created so scorers don't lowercase every
+/// field of every entry on each keystroke. Display code keeps using the original
+/// `DesktopEntry` strings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchKeys {
+    pub name: String,
+    pub generic_name: Option<String>,
+    pub exec: String,
+    pub keywords: Vec<String>,
+    pub categories: Vec<String>,
+    /// Desktop file stem, e.g. `org.kde.konsole` for `org.kde.konsole.desktop`
+    pub file_id: String,
+    /// Every ASCII character appearing in any of the fields above
+    pub char_mask: u128,
+}
+
+impl SearchKeys {
+    /// Precompute the lowercased search fields for an entry.
+    pub fn from_entry(entry: &DesktopEntry) -> Self {
+        let mut keys = Self {
+            name: entry.name.to_lowercase(),
+            generic_name: entry.generic_name.as_ref().map(|g| g.to_lowercase()),
+            exec: entry.exec.to_lowercase(),
+            keywords: entry.keywords.iter().map(|k| k.to_lowercase()).collect(),
+            categories: entry.categories.iter().map(|c| c.to_lowercase()).collect(),
+            file_id: entry
+                .path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_lowercase())
+                .unwrap_or_default(),
+            char_mask: 0,
+        };
+
+        keys.char_mask = std::iter::once(&keys.name)
+            .chain(&keys.generic_name)
+            .chain(std::iter::once(&keys.exec))
+            .chain(&keys.keywords)
+            .chain(&keys.categories)
+            .chain(std::iter::once(&keys.file_id))
+            .fold(0, |mask, field| mask | Self::char_mask(field));
+        keys
+    }
+
+    /// Bitmask of the ASCII characters in `text`, ignoring whitespace.
+    ///
+    /// Non-ASCII characters are left out, so masks only ever under-approximate
+    /// the characters present.
+    pub fn char_mask(text: &str) -> u128 {
+        text.chars()
+            .filter(|c| c.is_ascii() && !c.is_ascii_whitespace())
+            .fold(0, |mask, c| mask | (1u128 << c as u32))
+    }
+
+    /// Whether the (lowercased) query matches the desktop file id at the start of a
+    /// `.`/`-` separated segment, e.g. "org.kde" or "konsole" for `org.kde.konsole`.
+    ///
+    /// Queries shorter than 3 characters never match, to keep false positives down.
+    pub fn matches_file_id(&self, query_lower: &str) -> bool {
+        if query_lower.len() < 3 {
+            return false;
+        }
+
+        self.file_id
+            .match_indices(query_lower)
+            .any(|(start, _)| start == 0 || self.file_id[..start].ends_with(['.', '-']))
+    }
+
+    /// Cheap prefilter: whether every ASCII character of the (lowercased) query
+    /// occurs somewhere in this entry's searchable text.
+    ///
+    /// Every scoring rule needs all query characters within a single field, so
+    /// entries failing this check can never score above zero.
+    #[inline]
+    pub fn may_match(&self, query_mask: u128) -> bool {
+        query_mask & !self.char_mask == 0
+    }
+}
+
 /// Compact arena that stores desktop entries once and shares them across components.
 ///
 /// Internally this holds an `Arc<[SharedDesktopEntry]>`, so cloning the arena or
 /// individual entries is cheap and avoids repeatedly allocating `DesktopEntry` data.
+/// A parallel slice of [`SearchKeys`] is kept alongside for scoring.
 #[derive(Clone, Debug, Default)]
 pub struct DesktopEntryArena {
     entries: Arc<[SharedDesktopEntry]>,
+    keys: Arc<[SearchKeys]>,
 }
 
 impl DesktopEntryArena {
     /// Create a new arena from owned desktop entries.
     pub fn from_vec(entries: Vec<DesktopEntry>) -> Self {
-        if entries.is_empty() {
-            return Self {
-                entries: Arc::from([]),
-            };
-        }
-
         let shared: Vec<SharedDesktopEntry> = entries.into_iter().map(Arc::new).collect();
-        Self {
-            entries: Arc::from(shared.into_boxed_slice()),
-        }
+        Self::from_shared(shared)
     }
 
     /// Create an arena directly from already shared entries.
-    #[allow(dead_code)]
     pub fn from_shared(entries: Vec<SharedDesktopEntry>) -> Self {
         if entries.is_empty() {
-            return Self {
-                entries: Arc::from([]),
-            };
+            return Self::default();
         }
 
+        let keys: Vec<SearchKeys> = entries
+            .iter()
+            .map(|entry| SearchKeys::from_entry(entry))
+            .collect();
         Self {
             entries: Arc::from(entries.into_boxed_slice()),
+            keys: Arc::from(keys.into_boxed_slice()),
         }
     }
 
@@ -59,9 +135,50 @@ impl DesktopEntryArena {
         self.entries.iter()
     }
 
+    /// Iterate over all shared entries together with their precomputed search keys.
+    pub fn iter_with_keys(&self) -> impl Iterator<Item = (&SharedDesktopEntry, &SearchKeys)> {
+        self.entries.iter().zip(self.keys.iter())
+    }
+
     /// Return all entries as a `Vec` of shared pointers.
     #[allow(dead_code)]
     pub fn to_vec(&self) -> Vec<SharedDesktopEntry> {
         self.entries.iter().cloned().collect()
     }
 }
+
+/// Swappable handle to the current arena.
+///
+/// Shared between the plugin manager, the applications plugin and the daemon's
+/// rescan thread: readers take a cheap snapshot with [`SharedArena::load`] and a
+/// rescan replaces the whole arena at once with [`SharedArena::store`], so a search
+/// never sees a half-updated entry list.
+#[derive(Clone, Debug, Default)]
+pub struct SharedArena {
+    inner: Arc<RwLock<DesktopEntryArena>>,
+}
+
+impl SharedArena {
+    /// Wrap an arena in a shareable handle.
+    pub fn new(arena: DesktopEntryArena) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(arena)),
+        }
+    }
+
+    /// Snapshot of the current arena (cloning only bumps reference counts).
+    pub fn load(&self) -> DesktopEntryArena {
+        match self.inner.read() {
+            Ok(arena) => arena.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Replace the arena seen by all holders of this handle.
+    pub fn store(&self, arena: DesktopEntryArena) {
+        match self.inner.write() {
+            Ok(mut current) => *current = arena,
+            Err(poisoned) => *poisoned.into_inner() = arena,
+        }
+    }
+}
@@ -1,11 +1,12 @@
-// Future feature: hot-reload desktop files when they change
+// Hot-reload desktop files when they change (daemon mode uses `spawn_rescan_watcher`)
 #![allow(dead_code)]
 
 use anyhow::{Context, Result};
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::path::PathBuf;
-use std::sync::mpsc::{channel, Receiver};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 use super::cache::DesktopCache;
@@ -76,6 +77,37 @@ impl DesktopWatcher {
         Ok(cache_updated)
     }
 
+    /// Block until a .desktop file is created, modified or removed, then until
+    /// events have been quiet for `debounce` (package installs touch many files).
+    /// Returns false once the watcher has shut down.
+    pub fn wait_for_changes(&self, debounce: Duration) -> bool {
+        loop {
+            match self.rx.recv() {
+                Ok(Ok(event)) if self.is_entry_change(&event) => break,
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => warn!("File watch error: {}", e),
+                Err(_) => return false,
+            }
+        }
+
+        loop {
+            match self.rx.recv_timeout(debounce) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                    return true
+                }
+            }
+        }
+    }
+
+    /// Whether an event adds, changes or removes a .desktop file
+    fn is_entry_change(&self, event: &Event) -> bool {
+        matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) && self.should_process_event(event)
+    }
+
     /// Check if an event should be processed
     fn should_process_event(&self, event: &Event) -> bool {
         // Only process .desktop files
@@ -126,6 +158,27 @@ impl DesktopWatcher {
     }
 }
 
+/// Watch `paths` on a background thread and call `rescan` after each debounced
+/// burst of .desktop file changes
+pub fn spawn_rescan_watcher<F>(paths: Vec<PathBuf>, debounce: Duration, mut rescan: F) -> Result<()>
+where
+    F: FnMut() + Send + 'static,
+{
+    let mut watcher = DesktopWatcher::new(paths)?;
+    watcher.start_watching()?;
+
+    std::thread::Builder::new()
+        .name("desktop-watcher".to_string())
+        .spawn(move || {
+            while watcher.wait_for_changes(debounce) {
+                rescan();
+            }
+        })
+        .context("Failed to spawn desktop watcher thread")?;
+
+    Ok(())
+}
+
 /// Background watcher thread manager
 pub struct WatcherThread {
     cache: Arc<Mutex<DesktopCache>>,
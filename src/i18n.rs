@@ -0,0 +1,184 @@
+//! Translations of the launcher's own UI strings
+//!
+//! Messages are looked up by their English text through [`tr!`](crate::tr), so
+//! the English string is both the key and the default. The catalog is picked
+//! once from the messages locale (`$LC_ALL`, `$LC_MESSAGES`, `$LANG`), by full
+//! locale (`pt_BR`) first and language (`pt`) second; anything without a
+//! translation stays English.
+
+use crate::desktop::entry::current_locale;
+use std::sync::OnceLock;
+
+type Messages = &'static [(&'static str, &'static str)];
+
+/// Built-in catalogs: (locale or language, English -> translation)
+const CATALOGS: &[(&str, Messages)] = &[("de", GERMAN), ("es", SPANISH), ("fr", FRENCH)];
+
+const GERMAN: Messages = &[
+    ("Navigate", "Navigieren"),
+    ("Launch", "Starten"),
+    ("Folder", "Ordner"),
+    ("Copy Path", "Pfad kopieren"),
+    ("Pin", "Anheften"),
+    ("Copy Command", "Befehl kopieren"),
+    ("Close", "Schließen"),
+    ("Run Action", "Aktion ausführen"),
+    ("First", "Erste"),
+    ("Web", "Web"),
+    ("Back", "Zurück"),
+    (
+        "No screenshot utility detected",
+        "Kein Bildschirmfoto-Programm gefunden",
+    ),
+    (
+        "Install grimshot, hyprshot, gnome-screenshot, spectacle, maim, or scrot",
+        "Installieren Sie grimshot, hyprshot, gnome-screenshot, spectacle, maim oder scrot",
+    ),
+    (
+        "No screenshot options available",
+        "Keine Bildschirmfoto-Optionen verfügbar",
+    ),
+    (
+        "No matching screenshot option",
+        "Keine passende Bildschirmfoto-Option",
+    ),
+];
+
+const SPANISH: Messages = &[
+    ("Navigate", "Navegar"),
+    ("Launch", "Iniciar"),
+    ("Folder", "Carpeta"),
+    ("Copy Path", "Copiar ruta"),
+    ("Pin", "Fijar"),
+    ("Copy Command", "Copiar comando"),
+    ("Close", "Cerrar"),
+    ("Run Action", "Ejecutar acción"),
+    ("First", "Primero"),
+    ("Web", "Web"),
+    ("Back", "Atrás"),
+    (
+        "No screenshot utility detected",
+        "No se detectó ninguna herramienta de captura de pantalla",
+    ),
+    (
+        "Install grimshot, hyprshot, gnome-screenshot, spectacle, maim, or scrot",
+        "Instala grimshot, hyprshot, gnome-screenshot, spectacle, maim o scrot",
+    ),
+    (
+        "No screenshot options available",
+        "No hay opciones de captura de pantalla disponibles",
+    ),
+    (
+        "No matching screenshot option",
+        "Ninguna opción de captura de pantalla coincide",
+    ),
+];
+
+const FRENCH: Messages = &[
+    ("Navigate", "Naviguer"),
+    ("Launch", "Lancer"),
+    ("Folder", "Dossier"),
+    ("Copy Path", "Copier le chemin"),
+    ("Pin", "Épingler"),
+    ("Copy Command", "Copier la commande"),
+    ("Close", "Fermer"),
+    ("Run Action", "Exécuter l'action"),
+    ("First", "Premier"),
+    ("Web", "Web"),
+    ("Back", "Retour"),
+    (
+        "No screenshot utility detected",
+        "Aucun outil de capture d'écran détecté",
+    ),
+    (
+        "Install grimshot, hyprshot, gnome-screenshot, spectacle, maim, or scrot",
+        "Installez grimshot, hyprshot, gnome-screenshot, spectacle, maim ou scrot",
+    ),
+    (
+        "No screenshot options available",
+        "Aucune option de capture d'écran disponible",
+    ),
+    (
+        "No matching screenshot option",
+        "Aucune option de capture d'écran ne correspond",
+    ),
+];
+
+/// Translations for one locale; empty for English and unknown locales
+#[derive(Debug, Clone, Copy)]
+pub struct Catalog {
+    messages: Messages,
+}
+
+impl Catalog {
+    /// Catalog for a normalized locale (`de_DE`); `None` means English
+    pub fn for_locale(locale: Option<&str>) -> Self {
+        let messages = locale
+            .and_then(|locale| {
+                let language = locale.split('_').next().unwrap_or(locale);
+                Self::find(locale).or_else(|| Self::find(language))
+            })
+            .unwrap_or(&[]);
+        Self { messages }
+    }
+
+    fn find(name: &str) -> Option<Messages> {
+        CATALOGS
+            .iter()
+            .find(|(catalog, _)| *catalog == name)
+            .map(|(_, messages)| *messages)
+    }
+
+    /// Translation of `msgid`, or `msgid` itself when there is none
+    pub fn get(&self, msgid: &'static str) -> &'static str {
+        self.messages
+            .iter()
+            .find(|(english, _)| *english == msgid)
+            .map_or(msgid, |(_, translated)| translated)
+    }
+}
+
+/// Catalog for the current messages locale
+pub fn catalog() -> &'static Catalog {
+    static CATALOG: OnceLock<Catalog> = OnceLock::new();
+    CATALOG.get_or_init(|| Catalog::for_locale(current_locale().as_deref()))
+}
+
+/// Translate an English UI string for the current locale
+///
+/// `tr!("Close")` is `"Schließen"` under a German locale and `"Close"` where
+/// there is no translation.
+#[macro_export]
+macro_rules! tr {
+    ($msgid:literal) => {
+        $crate::i18n::catalog().get($msgid)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translation_for_loaded_locale_else_english() {
+        let german = Catalog::for_locale(Some("de_DE"));
+        assert_eq!(german.get("Close"), "Schließen");
+        assert_eq!(german.get("Copy Command"), "Befehl kopieren");
+        // Language-only catalogs cover regional locales
+        assert_eq!(Catalog::for_locale(Some("fr_CA")).get("Pin"), "Épingler");
+
+        // No catalog, or no translation in it: the English default
+        assert_eq!(Catalog::for_locale(None).get("Close"), "Close");
+        assert_eq!(Catalog::for_locale(Some("ja_JP")).get("Close"), "Close");
+        assert_eq!(german.get("Not a known message"), "Not a known message");
+    }
+
+    #[test]
+    fn test_catalogs_translate_the_same_messages() {
+        let english: Vec<_> = GERMAN.iter().map(|(english, _)| *english).collect();
+        for (name, messages) in CATALOGS {
+            let keys: Vec<_> = messages.iter().map(|(english, _)| *english).collect();
+            assert_eq!(keys, english, "catalog {} is out of sync", name);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Last search query, remembered across launcher invocations (`ui.restore_last_query`)
+//!
+//! The query is written when the window closes and read back when it is shown
+//! again, so it survives both the daemon hiding its window and a fresh launch.
+
+use crate::utils::atomic_write;
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Persisted last non-empty query
+#[derive(Debug, Clone)]
+pub struct LastQueryStore {
+    path: PathBuf,
+}
+
+impl LastQueryStore {
+    /// Store in the default cache location
+    pub fn new() -> Self {
+        Self::with_path(Self::default_path())
+    }
+
+    /// Store at a specific file
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Remember `query`; blank queries keep the previously saved one
+    pub fn save(&self, query: &str) -> Result<()> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        atomic_write(&self.path, query)?;
+        debug!("Saved last query to {:?}", self.path);
+        Ok(())
+    }
+
+    /// Last saved query, if any
+    pub fn load(&self) -> Option<String> {
+        let query = fs::read_to_string(&self.path).ok()?;
+        let query = query.trim();
+        (!query.is_empty()).then(|| query.to_string())
+    }
+
+    fn default_path() -> PathBuf {
+        let cache_dir = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+        cache_dir.join("native-launcher").join("last_query")
+    }
+}
+
+impl Default for LastQueryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_query_save_and_restore() {
+        let dir =
+            std::env::temp_dir().join(format!("native-launcher-last-query-{}", std::process::id()));
+        let store = LastQueryStore::with_path(dir.join("last_query"));
+
+        // Nothing saved yet
+        assert_eq!(store.load(), None);
+
+        store.save("fire").unwrap();
+        assert_eq!(store.load().as_deref(), Some("fire"));
+
+        // Clearing the search box doesn't forget the last real query
+        store.save("   ").unwrap();
+        assert_eq!(store.load().as_deref(), Some("fire"));
+
+        store.save(" @ssh prod ").unwrap();
+        let restored = LastQueryStore::with_path(dir.join("last_query")).load();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(restored.as_deref(), Some("@ssh prod"));
+    }
+}
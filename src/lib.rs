@@ -1,10 +1,15 @@
 // Library exports for testing and potential future use
 
+pub mod bench;
 pub mod config;
+pub mod daemon;
 pub mod desktop;
+pub mod i18n;
+pub mod last_query;
 pub mod pins;
 pub mod plugins;
 pub mod search;
 pub mod ui;
 pub mod usage;
 pub mod utils;
+pub mod window_size;
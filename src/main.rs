@@ -1,6 +1,9 @@
+mod bench;
 mod config;
 mod daemon;
 mod desktop;
+mod i18n;
+mod last_query;
 mod pins;
 mod plugins;
 mod search;
@@ -8,26 +11,48 @@ mod ui;
 mod updater;
 mod usage;
 mod utils;
+mod window_size;
 
-use crate::pins::PinsStore;
+use crate::pins::{PinnedItem, PinsStore};
 use anyhow::Result;
 use config::ConfigLoader;
-use desktop::DesktopScanner;
+use desktop::{DesktopEntry, DesktopScanner};
 use gtk4::gdk::Key;
 use gtk4::prelude::*;
 use gtk4::{Application, Box as GtkBox, Orientation};
-use plugins::{KeyboardAction, KeyboardEvent, PluginManager};
-use std::cell::RefCell;
+use plugins::{
+    KeyboardAction, KeyboardEvent, PluginManager, PluginResult, ResultAction, ResultActivation,
+    SearchToken,
+};
+use std::cell::{OnceCell, RefCell};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 use tracing_subscriber::EnvFilter;
-use ui::{load_theme_with_name, KeyboardHints, LauncherWindow, ResultsList, SearchWidget};
+use ui::{
+    load_theme_with_name, DebugOverlay, KeyboardHints, LauncherWindow, Navigation, ResultsList,
+    SearchWidget, StatusBar,
+};
 use usage::UsageTracker;
-use utils::{build_open_command, execute_command};
+use utils::packages::PackageManager;
+use utils::{build_open_command, execute_command, expand_exec};
 
 const APP_ID: &str = "com.github.native-launcher";
 
+/// How long a first Ctrl+Shift+Delete waits for the confirming second press
+const UNINSTALL_CONFIRM_WINDOW: Duration = Duration::from_secs(5);
+
+/// Uninstall armed by a first Ctrl+Shift+Delete, run on the confirming press
+struct PendingUninstall {
+    /// Desktop file of the app the confirmation is for
+    path: String,
+    package: String,
+    command: String,
+    armed_at: Instant,
+}
+
 fn main() -> Result<()> {
     // Initialize logging
     tracing_subscriber::fmt()
@@ -36,9 +61,21 @@ fn main() -> Result<()> {
         )
         .init();
 
-    // Check for daemon mode flag
     let args: Vec<String> = std::env::args().collect();
+
+    // Headless search benchmark: runs before any GTK or daemon setup and exits
+    if let Some(pos) = args.iter().position(|arg| arg == "--bench") {
+        let iterations = args
+            .get(pos + 1)
+            .and_then(|arg| arg.parse().ok())
+            .unwrap_or(bench::DEFAULT_ITERATIONS);
+        return run_bench_mode(iterations);
+    }
+
+    // Check for daemon mode flag
     let daemon_mode = args.contains(&"--daemon".to_string());
+    // `--query <text>` opens the launcher with the search prefilled
+    let command = daemon::DaemonCommand::from_args(&args);
 
     if daemon_mode {
         info!("Starting in daemon mode");
@@ -48,16 +85,65 @@ fn main() -> Result<()> {
     // Check if daemon is already running
     if daemon::is_daemon_running() {
         info!("Daemon is already running, sending show signal");
-        daemon::send_show_signal()?;
+        daemon::send_command(&command)?;
         return Ok(());
     }
 
     // Run in normal mode (single-shot)
     info!("Starting in normal mode");
-    run_normal_mode()
+    run_normal_mode(command.query())
+}
+
+fn run_bench_mode(iterations: usize) -> Result<()> {
+    info!("Running search benchmark ({} iterations)", iterations);
+
+    let config = ConfigLoader::load()
+        .unwrap_or_else(|e| {
+            error!("Failed to load config: {}, using defaults", e);
+            ConfigLoader::new()
+        })
+        .config()
+        .clone();
+
+    let scan_start = std::time::Instant::now();
+    let scanner = DesktopScanner::new().with_show_nodisplay(config.search.show_nodisplay);
+    let entries = scanner.scan_cached()?;
+    let entry_count = entries.len();
+    let scan_time = scan_start.elapsed();
+
+    // No usage data or pins so runs are comparable across machines and sessions
+    let init_start = std::time::Instant::now();
+    let plugin_manager = PluginManager::new(
+        desktop::DesktopEntryArena::from_vec(entries),
+        None,
+        None,
+        &config,
+    );
+    let init_time = init_start.elapsed();
+
+    let report = bench::run(
+        &plugin_manager,
+        bench::DEFAULT_QUERIES,
+        iterations,
+        config.search.max_results,
+    )?;
+
+    println!(
+        "Scanned {} desktop entries in {:.2} ms",
+        entry_count,
+        scan_time.as_secs_f64() * 1000.0
+    );
+    println!(
+        "Initialized {} plugins in {:.2} ms\n",
+        plugin_manager.enabled_plugins().len(),
+        init_time.as_secs_f64() * 1000.0
+    );
+    print!("{}", report);
+
+    Ok(())
 }
 
-fn run_normal_mode() -> Result<()> {
+fn run_normal_mode(initial_query: Option<&str>) -> Result<()> {
     info!("Starting Native Launcher");
 
     // Load configuration
@@ -97,7 +183,7 @@ fn run_normal_mode() -> Result<()> {
 
     // Scan for desktop applications
     info!("Scanning for desktop applications...");
-    let scanner = DesktopScanner::new();
+    let scanner = DesktopScanner::new().with_show_nodisplay(config.search.show_nodisplay);
     let raw_entries = scanner.scan_cached()?;
     info!("Found {} applications", raw_entries.len());
 
@@ -141,15 +227,15 @@ fn run_normal_mode() -> Result<()> {
 
     // Load dynamic plugins
     info!("Loading dynamic plugins...");
-    let (dynamic_plugins, plugin_metrics) = plugins::load_plugins();
+    let (dynamic_plugins, plugin_metrics) = plugins::load_plugins(&config.plugins);
     for plugin in dynamic_plugins {
         plugin_manager.register_plugin(plugin);
     }
 
     // Populate browser index if enabled and stale (normal mode - dev only)
     // In production, users should run in daemon mode for background indexing
-    if cfg!(debug_assertions) && config.plugins.browser_history {
-        let browser_plugin = plugins::BrowserHistoryPlugin::new();
+    if cfg!(debug_assertions) && config.plugins.browser_history.enabled {
+        let browser_plugin = plugins::BrowserHistoryPlugin::new(&config.plugins.browser_history);
         if let Some(index) = browser_plugin.get_index() {
             if index.needs_rebuild() {
                 info!("Browser index needs refresh, populating in background (dev mode)...");
@@ -194,9 +280,10 @@ fn run_normal_mode() -> Result<()> {
     let usage_tracker_clone = usage_tracker_rc.clone();
     let config_clone = config.clone();
     let metrics_clone = plugin_metrics_rc.clone();
+    let initial_query = initial_query.map(str::to_string);
 
     app.connect_activate(move |app| {
-        if let Err(e) = build_ui(
+        match build_ui(
             app,
             plugin_manager_clone.clone(),
             usage_tracker_clone.clone(),
@@ -205,18 +292,61 @@ fn run_normal_mode() -> Result<()> {
             metrics_clone.clone(),
             Some(pins_store.clone()), // Ensure new pins_store parameter is passed
         ) {
-            error!("Failed to build UI: {}", e);
-            app.quit();
+            Ok(entry) => {
+                if let Some(query) = &initial_query {
+                    prefill_query(&entry, query);
+                }
+            }
+            Err(e) => {
+                error!("Failed to build UI: {}", e);
+                app.quit();
+            }
         }
     });
 
-    // Run the application
-    let exit_code = app.run();
+    // Run the application; our own flags (`--query`) are not GTK options
+    let program: Vec<String> = std::env::args().take(1).collect();
+    let exit_code = app.run_with_args(&program);
     info!("Application exited with code: {:?}", exit_code);
 
     Ok(())
 }
 
+/// Find the package providing the app at `path` and the command removing it
+fn prepare_uninstall(
+    package_manager: Option<PackageManager>,
+    path: &str,
+) -> Result<PendingUninstall> {
+    let manager =
+        package_manager.ok_or_else(|| anyhow::anyhow!("No supported package manager found"))?;
+    let entry = DesktopEntry::from_file(path.into())?;
+    let (package, command) = utils::packages::uninstall_command(manager, &entry.exec)?;
+    Ok(PendingUninstall {
+        path: path.to_string(),
+        package,
+        command,
+        armed_at: Instant::now(),
+    })
+}
+
+/// Put `query` into the search entry, cursor at the end; the entry's change
+/// handler runs the search as if it had been typed
+fn prefill_query(entry: &gtk4::Entry, query: &str) {
+    entry.set_text(query);
+    entry.set_position(-1);
+}
+
+/// Add a dynamic plugin to `plugins.disabled` and save the config
+fn disable_dynamic_plugin(plugin_id: &str) -> Result<()> {
+    let mut loader = ConfigLoader::load()?;
+    let mut updated_config = loader.config().clone();
+    if updated_config.plugins.disable_dynamic(plugin_id) {
+        loader.update(updated_config)?;
+        info!("Plugin '{}' disabled in config", plugin_id);
+    }
+    Ok(())
+}
+
 fn build_ui(
     app: &Application,
     plugin_manager: Rc<RefCell<PluginManager>>,
@@ -225,34 +355,55 @@ fn build_ui(
     config: &config::Config,
     plugin_metrics: Rc<Vec<plugins::PluginMetrics>>,
     pins_store: Option<Arc<PinsStore>>,
-) -> Result<()> {
+) -> Result<gtk4::Entry> {
     info!("Building UI");
 
     // Load CSS theme from config
-    info!("Loading theme: {}", config.ui.theme);
-    load_theme_with_name(&config.ui.theme);
+    let theme_name = ui::color_scheme::current_theme(&config.ui);
+    info!("Loading theme: {}", theme_name);
+    if let Err(e) = load_theme_with_name(theme_name) {
+        warn!("{:#}, falling back to dark theme", e);
+        if let Err(e) = load_theme_with_name("dark") {
+            error!("Failed to load fallback theme: {:#}", e);
+        }
+    }
 
     let merge_login_env = config.environment.merge_login_env;
 
     // Create main window with config
-    let launcher_window = LauncherWindow::new(app);
+    let launcher_window = LauncherWindow::new(app, &config.window);
 
-    // Apply window config - use FIXED size to prevent expansion
-    launcher_window
+    // Apply window config - use FIXED size to prevent expansion; a remembered
+    // size from the last session wins over the configured one
+    let (width, height) = config
         .window
-        .set_default_width(config.window.width);
+        .remember_geometry
+        .then(|| window_size::WindowSizeStore::new().load())
+        .flatten()
+        .unwrap_or((config.window.width, config.window.height));
+    launcher_window.window.set_default_width(width);
+    launcher_window.window.set_default_height(height);
+
+    // CRITICAL: Prevent window from resizing beyond default size, unless the
+    // user opted into remembering the size they resize it to
     launcher_window
         .window
-        .set_default_height(config.window.height);
-
-    // CRITICAL: Prevent window from resizing beyond default size
-    launcher_window.window.set_resizable(false);
+        .set_resizable(config.window.remember_geometry);
 
     // Create search widget
     let search_widget = SearchWidget::new();
 
     // Create results list
     let results_list = ResultsList::new();
+    results_list.set_wrap_navigation(config.ui.wrap_navigation);
+    results_list.set_auto_select_first(config.ui.auto_select_first);
+    results_list.set_icon_size(config.ui.icon_size);
+    results_list.set_row_spacing(config.ui.row_spacing);
+    results_list.set_text_limits(
+        config.ui.max_title_length,
+        config.ui.max_subtitle_length,
+        ui::truncate::EllipsisMode::parse(&config.ui.ellipsis),
+    );
     if let Some(pins) = &pins_store {
         results_list.set_pins_store(pins.clone());
     }
@@ -262,10 +413,20 @@ fn build_ui(
     // Create keyboard hints
     let keyboard_hints = KeyboardHints::new();
 
+    // Per-plugin search timings (F12 or NL_DEBUG=1)
+    let debug_overlay = DebugOverlay::new();
+
+    // Transient feedback for copy/pin actions, shown above the hints
+    let status_bar = StatusBar::new();
+    let show_status: Rc<dyn Fn(&str)> = {
+        let status_bar = status_bar.clone();
+        Rc::new(move |message| status_bar.show_status(message))
+    };
+
     // Check for slow plugins and show warning if needed
     let slow_plugins: Vec<_> = plugin_metrics
         .iter()
-        .filter(|m| m.success && m.is_very_slow())
+        .filter(|m| m.success && m.is_very_slow(config.plugins.slow_warn_ms))
         .collect();
 
     let plugin_warning = if !slow_plugins.is_empty() {
@@ -280,9 +441,10 @@ fn build_ui(
 
         let warning_text = gtk4::Label::builder()
             .label(format!(
-                "⚠️ {} slow plugin{} detected (>50ms load time)",
+                "⚠️ {} slow plugin{} detected (>{}ms load time)",
                 slow_plugins.len(),
-                if slow_plugins.len() > 1 { "s" } else { "" }
+                if slow_plugins.len() > 1 { "s" } else { "" },
+                config.plugins.slow_warn_ms
             ))
             .css_classes(vec!["plugin-warning-text"])
             .halign(gtk4::Align::Start)
@@ -291,6 +453,30 @@ fn build_ui(
         warning_box.append(&warning_icon);
         warning_box.append(&warning_text);
 
+        // One button per slow plugin that turns it off for future launches
+        for metric in &slow_plugins {
+            let plugin_id = metric.plugin_id();
+            let disable_button = gtk4::Button::builder()
+                .label(format!("Disable {}", plugin_id))
+                .css_classes(vec!["flat", "plugin-warning-action"])
+                .build();
+
+            let show_status = show_status.clone();
+            disable_button.connect_clicked(move |button| {
+                match disable_dynamic_plugin(&plugin_id) {
+                    Ok(()) => {
+                        button.set_sensitive(false);
+                        show_status(&format!("{} disabled, restart to apply", plugin_id));
+                    }
+                    Err(e) => {
+                        warn!("Failed to disable plugin {}: {:#}", plugin_id, e);
+                        show_status("Failed to disable plugin");
+                    }
+                }
+            });
+            warning_box.append(&disable_button);
+        }
+
         // Log plugin details
         for metric in &slow_plugins {
             info!(
@@ -341,6 +527,8 @@ fn build_ui(
     main_box.append(&search_widget.container);
     main_box.append(&results_list.container);
     // Footer removed from layout per design
+    main_box.append(&debug_overlay.label);
+    main_box.append(&status_bar.label);
     main_box.append(&keyboard_hints.container);
 
     launcher_window.window.set_child(Some(&main_box));
@@ -364,10 +552,14 @@ fn build_ui(
         // Footer removed; no footer updates
         let plugin_manager = plugin_manager.clone();
         let max_results = config.search.max_results;
+        let debounce = std::time::Duration::from_millis(config.search.debounce_ms);
+        let show_status = show_status.clone();
+        let debug_overlay = debug_overlay.clone();
 
         // Debounce timeout holder and cancellation flag
-        // We use a counter instead of removing sources to avoid GTK panics
-        let debounce_counter: Rc<RefCell<u64>> = Rc::new(RefCell::new(0));
+        // We use a counter instead of removing sources to avoid GTK panics;
+        // the same generation counter cancels in-flight incremental searches
+        let debounce_counter = Arc::new(AtomicU64::new(0));
 
         search_widget.entry.connect_changed(move |entry| {
             let query = entry.text().to_string();
@@ -376,29 +568,30 @@ fn build_ui(
 
             // Increment counter to cancel any pending searches
             // Previous timeout will check counter and skip search if stale
-            let current_count = {
-                let mut counter = debounce_counter.borrow_mut();
-                *counter += 1;
-                *counter
-            };
+            let current_count = debounce_counter.fetch_add(1, Ordering::SeqCst) + 1;
 
             // Clone refs for closure
             let plugin_manager_clone = plugin_manager.clone();
             let plugin_manager_for_metrics = plugin_manager.clone();
+            let debug_overlay = debug_overlay.clone();
             let results_list_clone = results_list.clone();
             // Footer removed: no loading indicator
             let debounce_counter_clone = debounce_counter.clone();
             let query_clone = query.clone();
+            let show_status = show_status.clone();
 
-            // DEBOUNCED: Wait 30ms after last keystroke before searching (optimized for fast typing)
+            // DEBOUNCED: Wait `search.debounce_ms` (default 30ms) after last keystroke before searching
             // Shorter delay provides better responsiveness without excessive searches
-            gtk4::glib::timeout_add_local_once(std::time::Duration::from_millis(30), move || {
+            gtk4::glib::timeout_add_local_once(debounce, move || {
                 // Check if this timeout is still valid (not superseded by newer typing)
-                if *debounce_counter_clone.borrow() != current_count {
+                if debounce_counter_clone.load(Ordering::SeqCst) != current_count {
                     debug!("Skipping stale search (user still typing)");
                     return;
                 }
 
+                // Any newer keystroke bumps the counter and cancels this search
+                let search_token = SearchToken::new(debounce_counter_clone.clone());
+
                 // Use incremental search for better perceived performance
                 let manager = plugin_manager_clone.borrow();
                 let results_list_for_fast = results_list_clone.clone();
@@ -410,6 +603,7 @@ fn build_ui(
                 let result = manager.search_incremental(
                     &query_clone,
                     max_results,
+                    &search_token,
                     // Fast results callback - apps, calculator (instant)
                     move |fast_results| {
                         debug!("Displaying {} fast results", fast_results.len());
@@ -424,6 +618,7 @@ fn build_ui(
 
                         // Log performance metrics (every 10th search)
                         let manager_ref = plugin_manager_for_metrics.borrow();
+                        debug_overlay.update(&manager_ref.timing_snapshot());
                         let metrics = manager_ref.get_performance_metrics();
                         if !metrics.is_empty() {
                             let total_calls: u32 = metrics.iter().map(|(_, _, count)| count).sum();
@@ -440,6 +635,13 @@ fn build_ui(
                 if let Err(e) = result {
                     error!("Incremental search failed: {}", e);
                 }
+
+                // Say why results may be missing when a plugin failed
+                let errors = manager.last_errors();
+                if !errors.is_empty() && !search_token.is_cancelled() {
+                    let message: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                    show_status(&message.join("; "));
+                }
             });
         });
     }
@@ -451,64 +653,39 @@ fn build_ui(
         let usage_tracker_clone = usage_tracker.clone();
         let search_entry_clone = search_widget.entry.clone();
         let plugin_manager_clone = plugin_manager.clone();
+        let show_status = show_status.clone();
 
         search_widget.entry.connect_activate(move |entry| {
-            // Get current modifiers
-            let display = entry.display();
-            let seat = display.default_seat();
-            let modifiers = seat
-                .and_then(|s| s.keyboard())
-                .map(|k| k.modifier_state())
-                .unwrap_or(gtk4::gdk::ModifierType::empty());
-
-            // Shift+Enter on clipboard results: copy without closing window
-            if modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK) {
-                if let Some(plugin_name) = results_list.get_selected_plugin_name() {
-                    if plugin_name == "clipboard" {
-                        if let Some((command, terminal)) = results_list.get_selected_command() {
-                            if let Err(e) = execute_command(&command, terminal, merge_login_env) {
-                                error!("Failed to execute copy command: {}", e);
-                            }
-                            // Do not close window; stop further handling
-                            return;
-                        }
-                    }
-                }
-            }
+            let modifiers = current_modifiers(entry);
 
             // Create keyboard event and dispatch to plugins
             let query = search_entry_clone.text().to_string();
-            let has_selection = results_list.get_selected_command().is_some();
+            let selected_command = results_list.get_selected_command().map(|(cmd, _)| cmd);
 
-            let keyboard_event = KeyboardEvent::new(Key::Return, modifiers, query, has_selection);
+            let keyboard_event =
+                KeyboardEvent::new(Key::Return, modifiers, query, selected_command.is_some())
+                    .with_selected_command(selected_command);
 
-            // Dispatch to plugins
-            let action = plugin_manager_clone
-                .borrow()
-                .dispatch_keyboard_event(&keyboard_event);
+            // Dispatch to plugins, unless an actions menu entry is selected
+            let action = activate_result_action(&results_list).unwrap_or_else(|| {
+                plugin_manager_clone
+                    .borrow()
+                    .dispatch_keyboard_event(&keyboard_event)
+            });
 
             match action {
                 KeyboardAction::None => {
                     // No plugin handled it, launch selected item
-                    if let Some((exec, terminal)) = results_list.get_selected_command() {
-                        info!("Launching: {}", exec);
-
-                        // Track usage when enabled
-                        if usage_enabled {
-                            if let Some(path) = results_list.get_selected_path() {
-                                usage_tracker_clone.borrow_mut().record_launch(&path);
-                                info!("Recorded launch for {}", path);
-                            }
-                        }
-
-                        // IMPORTANT: Hide window BEFORE launching app
-                        // This ensures the new app gets focus and appears in foreground
-                        window_clone.close();
-
-                        if let Err(e) = execute_command(&exec, terminal, merge_login_env) {
-                            error!("Failed to launch {}: {}", exec, e);
-                        }
-                    }
+                    handle_selected_result(
+                        &results_list,
+                        &window_clone,
+                        entry,
+                        &usage_tracker_clone,
+                        usage_enabled,
+                        merge_login_env,
+                        modifiers,
+                        &show_status,
+                    );
                 }
                 KeyboardAction::OpenUrl(url) => {
                     info!("Opening URL from plugin: {}", url);
@@ -537,55 +714,21 @@ fn build_ui(
                     debug!("Keyboard event handled by plugin");
                 }
                 KeyboardAction::OpenFolder(path) => {
-                    info!("Opening folder: {}", path);
-                    let folder = if std::path::Path::new(&path).is_dir() {
-                        path
-                    } else {
-                        std::path::Path::new(&path)
-                            .parent()
-                            .map(|p| p.to_string_lossy().to_string())
-                            .unwrap_or_else(|| ".".to_string())
-                    };
-
-                    window_clone.close();
-
-                    let open_command = build_open_command(&folder);
-                    if let Err(e) = execute_command(&open_command, false, merge_login_env) {
-                        error!("Failed to open folder: {}", e);
-                    }
+                    open_folder(&path, &window_clone, merge_login_env);
                 }
                 KeyboardAction::CopyPath(path) => {
                     info!("Copying path to clipboard: {}", path);
-                    let copy_cmd = if std::process::Command::new("which")
-                        .arg("wl-copy")
-                        .output()
-                        .map(|o| o.status.success())
-                        .unwrap_or(false)
-                    {
-                        format!("echo -n '{}' | wl-copy", path.replace('\'', r"'\''"))
-                    } else if std::process::Command::new("which")
-                        .arg("xclip")
-                        .output()
-                        .map(|o| o.status.success())
-                        .unwrap_or(false)
-                    {
-                        format!(
-                            "echo -n '{}' | xclip -selection clipboard",
-                            path.replace('\'', r"'\''")
-                        )
-                    } else {
-                        error!("No clipboard tool found (need wl-copy or xclip)");
-                        return;
-                    };
-
-                    if let Err(e) = std::process::Command::new("sh")
-                        .arg("-c")
-                        .arg(&copy_cmd)
-                        .spawn()
-                    {
-                        error!("Failed to copy path: {}", e);
+                    match utils::clipboard::copy_text(&path) {
+                        Ok(()) => show_status("Path copied"),
+                        Err(e) => {
+                            error!("Failed to copy path: {}", e);
+                            show_status(&e.to_string());
+                        }
                     }
                 }
+                KeyboardAction::CopyToClipboard(text) => {
+                    copy_and_maybe_close(&text, &window_clone, modifiers, &*show_status);
+                }
             }
         });
     }
@@ -595,14 +738,24 @@ fn build_ui(
         let results_list_clone = results_list.clone();
         let window_clone = launcher_window.window.clone();
         let usage_tracker_clone = usage_tracker.clone();
+        let search_entry_clone = search_widget.entry.clone();
+        let show_status = show_status.clone();
 
-        results_list.list.connect_row_activated(move |_, _| {
+        results_list.list.connect_row_activated(move |list, _| {
+            // Actions menu entries go through the same path as Enter
+            if results_list_clone.get_selected_result_action().is_some() {
+                search_entry_clone.emit_activate();
+                return;
+            }
             handle_selected_result(
                 &results_list_clone,
                 &window_clone,
+                &search_entry_clone,
                 &usage_tracker_clone,
                 usage_enabled,
                 merge_login_env,
+                current_modifiers(list),
+                &show_status,
             );
         });
     }
@@ -615,6 +768,12 @@ fn build_ui(
         let search_entry_clone = search_widget.entry.clone();
         // Footer removed
         let plugin_manager_clone = plugin_manager.clone();
+        let max_results = config.search.max_results;
+        let show_status = show_status.clone();
+        // Detected on the first uninstall, not while the window is being built
+        let package_manager: OnceCell<Option<PackageManager>> = OnceCell::new();
+        let pending_uninstall: RefCell<Option<PendingUninstall>> = RefCell::new(None);
+        let debug_overlay = debug_overlay.clone();
 
         let key_controller = gtk4::EventControllerKey::new();
         key_controller.connect_key_pressed(move |_, key, _, modifiers| {
@@ -624,72 +783,109 @@ fn build_ui(
                     window_clone.close();
                     gtk4::glib::Propagation::Stop
                 }
-                Key::Down => {
-                    // Move selection down
-                    results_list_clone.select_next();
-
-                    // Preview theme if a theme item is selected
-                    if let Some((command, _)) = results_list_clone.get_selected_command() {
-                        if let Some(theme_name) = command.strip_prefix("@theme:") {
-                            info!("Previewing theme: {}", theme_name);
-                            load_theme_with_name(theme_name);
+                Key::F12 => {
+                    // F12: Show or hide the per-plugin timing overlay
+                    debug_overlay.toggle();
+                    debug_overlay.update(&plugin_manager_clone.borrow().timing_snapshot());
+                    gtk4::glib::Propagation::Stop
+                }
+                Key::Menu => {
+                    // Menu key: Show the selected result's actions menu
+                    if !results_list_clone.show_result_actions() {
+                        debug!("Selected result has no actions");
+                    }
+                    gtk4::glib::Propagation::Stop
+                }
+                Key::Up | Key::Down if modifiers.contains(gtk4::gdk::ModifierType::ALT_MASK) => {
+                    // Alt+Up / Alt+Down: Reorder the selected pinned app
+                    if let (Some(pins), Some(pin_key)) =
+                        (&pins_store, results_list_clone.get_selected_pin_key())
+                    {
+                        let moved = if key == Key::Up {
+                            pins.move_up(&pin_key)
+                        } else {
+                            pins.move_down(&pin_key)
+                        };
+
+                        match moved {
+                            Ok(true) => {
+                                // Re-run the current query so the new pin order is rendered
+                                let query = search_entry_clone.text().to_string();
+                                let limit = if query.is_empty() { 20 } else { max_results };
+                                match plugin_manager_clone.borrow().search(&query, limit) {
+                                    Ok(results) => {
+                                        results_list_clone.update_plugin_results(results);
+                                        results_list_clone.select_pin_key(&pin_key);
+                                    }
+                                    Err(e) => error!("Failed to refresh results: {}", e),
+                                }
+                            }
+                            Ok(false) => debug!("Pin not moved (not pinned or at boundary)"),
+                            Err(e) => warn!("Failed to reorder pin: {}", e),
                         }
                     }
-
+                    gtk4::glib::Propagation::Stop
+                }
+                Key::Down => {
+                    // Move selection down
+                    navigate_results(&results_list_clone, Navigation::Next);
                     gtk4::glib::Propagation::Stop
                 }
                 Key::Up => {
                     // Move selection up
-                    results_list_clone.select_previous();
-
-                    // Preview theme if a theme item is selected
-                    if let Some((command, _)) = results_list_clone.get_selected_command() {
-                        if let Some(theme_name) = command.strip_prefix("@theme:") {
-                            info!("Previewing theme: {}", theme_name);
-                            load_theme_with_name(theme_name);
-                        }
-                    }
-
+                    navigate_results(&results_list_clone, Navigation::Previous);
                     gtk4::glib::Propagation::Stop
                 }
-                Key::Return => {
-                    // Shift+Enter on clipboard results: copy without closing window
-                    if modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK) {
-                        if let Some(plugin_name) = results_list_clone.get_selected_plugin_name() {
-                            if plugin_name == "clipboard" {
-                                if let Some((command, terminal)) =
-                                    results_list_clone.get_selected_command()
-                                {
-                                    if let Err(e) =
-                                        execute_command(&command, terminal, merge_login_env)
-                                    {
-                                        error!("Failed to execute copy command: {}", e);
-                                    }
-                                    // Keep window open
-                                    return gtk4::glib::Propagation::Stop;
-                                }
+                Key::Return
+                    if modifiers.contains(
+                        gtk4::gdk::ModifierType::CONTROL_MASK | gtk4::gdk::ModifierType::ALT_MASK,
+                    ) =>
+                {
+                    // Ctrl+Alt+Enter: Run in a terminal that stays open after the command exits
+                    match results_list_clone.get_selected_executable_command() {
+                        // Destructive results run only on a second press
+                        Some(_)
+                            if !results_list_clone.confirm_activation_with("Ctrl+Alt+Enter") => {}
+                        Some((exec, _)) => {
+                            window_clone.close();
+                            if let Err(e) = utils::execute_held_open(&exec, merge_login_env) {
+                                error!("Failed to launch {} in a terminal: {}", exec, e);
                             }
                         }
+                        None => debug!("Selected result can't be run in a terminal"),
                     }
+                    gtk4::glib::Propagation::Stop
+                }
+                Key::Return => {
                     // Create keyboard event and dispatch to plugins
                     let query = search_entry_clone.text().to_string();
-                    let has_selection = results_list_clone.get_selected_command().is_some();
+                    let selected_command = results_list_clone
+                        .get_selected_command()
+                        .map(|(cmd, _)| cmd);
 
-                    let keyboard_event = KeyboardEvent::new(key, modifiers, query, has_selection);
+                    let keyboard_event =
+                        KeyboardEvent::new(key, modifiers, query, selected_command.is_some())
+                            .with_selected_command(selected_command);
 
                     // Dispatch to plugins - they handle Ctrl+Enter for web search, etc.
-                    let action = plugin_manager_clone
-                        .borrow()
-                        .dispatch_keyboard_event(&keyboard_event);
+                    // An actions menu entry does its own thing instead
+                    let action = activate_result_action(&results_list_clone).unwrap_or_else(|| {
+                        plugin_manager_clone
+                            .borrow()
+                            .dispatch_keyboard_event(&keyboard_event)
+                    });
 
                     match action {
                         KeyboardAction::None => {
                             handle_selected_result(
                                 &results_list_clone,
                                 &window_clone,
+                                &search_entry_clone,
                                 &usage_tracker_clone,
                                 usage_enabled,
                                 merge_login_env,
+                                modifiers,
+                                &show_status,
                             );
                         }
                         KeyboardAction::OpenUrl(url) => {
@@ -718,78 +914,219 @@ fn build_ui(
                             debug!("Keyboard event handled by plugin");
                         }
                         KeyboardAction::OpenFolder(path) => {
-                            info!("Opening folder: {}", path);
-                            // Open containing folder (extract parent directory from path)
-                            let folder = if std::path::Path::new(&path).is_dir() {
-                                path
-                            } else {
-                                std::path::Path::new(&path)
-                                    .parent()
-                                    .map(|p| p.to_string_lossy().to_string())
-                                    .unwrap_or_else(|| ".".to_string())
-                            };
-
-                            window_clone.close();
-
-                            let open_command = build_open_command(&folder);
-                            if let Err(e) = execute_command(&open_command, false, merge_login_env) {
-                                error!("Failed to open folder: {}", e);
-                            }
+                            open_folder(&path, &window_clone, merge_login_env);
                         }
                         KeyboardAction::CopyPath(path) => {
                             info!("Copying path to clipboard: {}", path);
-                            // Copy to clipboard using wl-copy or xclip
-                            let copy_cmd = if std::process::Command::new("which")
-                                .arg("wl-copy")
-                                .output()
-                                .map(|o| o.status.success())
-                                .unwrap_or(false)
-                            {
-                                format!("echo -n '{}' | wl-copy", path.replace('\'', r"'\''"))
-                            } else if std::process::Command::new("which")
-                                .arg("xclip")
-                                .output()
-                                .map(|o| o.status.success())
-                                .unwrap_or(false)
-                            {
-                                format!(
-                                    "echo -n '{}' | xclip -selection clipboard",
-                                    path.replace('\'', r"'\''")
-                                )
-                            } else {
-                                error!("No clipboard tool found (need wl-copy or xclip)");
-                                return gtk4::glib::Propagation::Stop;
-                            };
-
-                            if let Err(e) = std::process::Command::new("sh")
-                                .arg("-c")
-                                .arg(&copy_cmd)
-                                .spawn()
-                            {
-                                error!("Failed to copy path: {}", e);
+                            match utils::clipboard::copy_text(&path) {
+                                Ok(()) => show_status("Path copied"),
+                                Err(e) => {
+                                    error!("Failed to copy path: {}", e);
+                                    show_status(&e.to_string());
+                                }
                             }
 
                             // Don't close window - user might want to copy multiple paths
                         }
+                        KeyboardAction::CopyToClipboard(text) => {
+                            copy_and_maybe_close(&text, &window_clone, modifiers, &*show_status);
+                        }
                     }
 
                     gtk4::glib::Propagation::Stop
                 }
+                Key::Delete | Key::KP_Delete
+                    if modifiers.contains(
+                        gtk4::gdk::ModifierType::CONTROL_MASK | gtk4::gdk::ModifierType::SHIFT_MASK,
+                    ) =>
+                {
+                    // Ctrl+Shift+Delete: Uninstall the selected app; destructive, so the
+                    // first press only asks and a second press on the same app confirms
+                    let Some(path) = results_list_clone.get_selected_path() else {
+                        debug!("Selected result is not an app");
+                        return gtk4::glib::Propagation::Stop;
+                    };
+
+                    let armed = pending_uninstall.borrow_mut().take();
+                    match armed {
+                        Some(pending)
+                            if pending.path == path
+                                && pending.armed_at.elapsed() < UNINSTALL_CONFIRM_WINDOW =>
+                        {
+                            info!("Uninstalling {}: {}", pending.package, pending.command);
+                            window_clone.close();
+                            if let Err(e) = execute_command(&pending.command, true, merge_login_env)
+                            {
+                                error!("Failed to uninstall {}: {}", pending.package, e);
+                            }
+                        }
+                        _ => match prepare_uninstall(
+                            *package_manager.get_or_init(PackageManager::detect),
+                            &path,
+                        ) {
+                            Ok(pending) => {
+                                show_status(&format!(
+                                    "Uninstall {}? Press Ctrl+Shift+Delete again to confirm",
+                                    pending.package
+                                ));
+                                pending_uninstall.replace(Some(pending));
+                            }
+                            Err(e) => {
+                                warn!("Can't uninstall {}: {:#}", path, e);
+                                show_status(&format!("{:#}", e));
+                            }
+                        },
+                    }
+                    gtk4::glib::Propagation::Stop
+                }
                 _ => {
-                    // Ctrl+P: Toggle pin on selected app (if supported)
+                    // Ctrl+P: Toggle pin on selected app or command
                     if modifiers.contains(gtk4::gdk::ModifierType::CONTROL_MASK) {
                         let maybe_char = key.to_unicode();
                         if maybe_char == Some('p') || maybe_char == Some('P') {
-                            if let Some(path) = results_list_clone.get_selected_path() {
-                                if let Some(pins) = &pins_store {
-                                    match pins.toggle(&path) {
-                                        Ok(_pinned) => {
-                                            // Refresh only visuals (stars)
-                                            results_list_clone.rerender();
+                            if let Some(pins) = &pins_store {
+                                let toggled = if let Some(path) =
+                                    results_list_clone.get_selected_path()
+                                {
+                                    Some(pins.toggle(&path))
+                                } else {
+                                    results_list_clone
+                                        .get_selected_result()
+                                        .filter(|result| !result.command.is_empty())
+                                        .map(|result| {
+                                            let mut item =
+                                                PinnedItem::command(result.title, result.command)
+                                                    .with_terminal(result.terminal);
+                                            if let Some(icon) = result.icon {
+                                                item = item.with_icon(icon);
+                                            }
+                                            pins.toggle_item(item)
+                                        })
+                                };
+
+                                match toggled {
+                                    Some(Ok(pinned)) => {
+                                        // Refresh only visuals (stars)
+                                        results_list_clone.rerender();
+                                        show_status(if pinned { "Pinned" } else { "Unpinned" });
+                                    }
+                                    Some(Err(e)) => {
+                                        warn!("Failed to toggle pin: {}", e);
+                                        show_status("Failed to update pins");
+                                    }
+                                    None => {}
+                                }
+                            }
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        // Ctrl+O: Open the folder containing the selected file/workspace
+                        else if maybe_char == Some('o') || maybe_char == Some('O') {
+                            let action = results_list_clone
+                                .get_selected_result()
+                                .and_then(|result| result.target_path)
+                                .map(KeyboardAction::OpenFolder);
+                            match action {
+                                Some(KeyboardAction::OpenFolder(path)) => {
+                                    open_folder(&path, &window_clone, merge_login_env);
+                                }
+                                _ => debug!("Selected result has no target path"),
+                            }
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        // Ctrl+Shift+C: Copy the selected result's command line
+                        else if (maybe_char == Some('c') || maybe_char == Some('C'))
+                            && modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK)
+                        {
+                            let command = results_list_clone
+                                .get_selected_command()
+                                .and_then(|(exec, _)| utils::copyable_command(&exec));
+                            match command {
+                                Some(command) => {
+                                    info!("Copying command to clipboard: {}", command);
+                                    match utils::clipboard::copy_text(&command) {
+                                        Ok(()) => show_status("Command copied"),
+                                        Err(e) => {
+                                            error!("Failed to copy command: {}", e);
+                                            show_status(&e.to_string());
+                                        }
+                                    }
+                                }
+                                None => debug!("Selected result has no copyable command"),
+                            }
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        // Ctrl+L: Copy the selected web page as a markdown link
+                        else if maybe_char == Some('l') || maybe_char == Some('L') {
+                            let link = results_list_clone
+                                .get_selected_result()
+                                .and_then(|result| result.markdown_link());
+                            match link {
+                                Some(link) => {
+                                    info!("Copying link to clipboard: {}", link);
+                                    match utils::clipboard::copy_text(&link) {
+                                        Ok(()) => show_status("Link copied"),
+                                        Err(e) => {
+                                            error!("Failed to copy link: {}", e);
+                                            show_status(&e.to_string());
                                         }
-                                        Err(e) => warn!("Failed to toggle pin: {}", e),
                                     }
                                 }
+                                None => debug!("Selected result has no URL"),
+                            }
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        // Ctrl+Shift+R: Run the selected app or shell command as root
+                        else if (maybe_char == Some('r') || maybe_char == Some('R'))
+                            && modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK)
+                        {
+                            match results_list_clone.get_selected_executable_command() {
+                                // Destructive results run only on a second press
+                                Some(_)
+                                    if !results_list_clone
+                                        .confirm_activation_with("Ctrl+Shift+R") => {}
+                                Some((exec, terminal)) => {
+                                    window_clone.close();
+                                    if let Err(e) =
+                                        utils::execute_elevated(&exec, terminal, merge_login_env)
+                                    {
+                                        error!("Failed to launch {} elevated: {}", exec, e);
+                                    }
+                                }
+                                None => debug!("Selected result can't be run elevated"),
+                            }
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        // Ctrl+I: Show the selected app's details in place of the
+                        // results; pressed on the details, go back to the results
+                        else if maybe_char == Some('i') || maybe_char == Some('I') {
+                            let showing_info = results_list_clone
+                                .get_selected_result()
+                                .is_some_and(|result| {
+                                    result.plugin_name == ui::app_info::APP_INFO_SOURCE
+                                });
+                            if showing_info {
+                                let query = search_entry_clone.text().to_string();
+                                let limit = if query.is_empty() { 20 } else { max_results };
+                                match plugin_manager_clone.borrow().search(&query, limit) {
+                                    Ok(results) => {
+                                        results_list_clone.update_plugin_results(results)
+                                    }
+                                    Err(e) => error!("Failed to refresh results: {}", e),
+                                }
+                                return gtk4::glib::Propagation::Stop;
+                            }
+
+                            let entry = results_list_clone
+                                .get_selected_path()
+                                .map(|path| DesktopEntry::from_file(path.into()));
+                            match entry {
+                                Some(Ok(entry)) => results_list_clone
+                                    .update_plugin_results(ui::app_info::app_info_results(&entry)),
+                                Some(Err(e)) => {
+                                    warn!("Can't read app details: {:#}", e);
+                                    show_status("Can't read app details");
+                                }
+                                None => debug!("Selected result is not an app"),
                             }
                             return gtk4::glib::Propagation::Stop;
                         }
@@ -806,9 +1143,12 @@ fn build_ui(
                             handle_selected_result(
                                 &results_list_clone,
                                 &window_clone,
+                                &search_entry_clone,
                                 &usage_tracker_clone,
                                 usage_enabled,
                                 merge_login_env,
+                                modifiers,
+                                &show_status,
                             );
 
                             return gtk4::glib::Propagation::Stop;
@@ -841,29 +1181,235 @@ fn build_ui(
         search_widget.entry.add_controller(entry_key_controller);
     }
 
+    // Home/End and Page Up/Down move through the results. Runs in the capture phase
+    // because the entry would otherwise use them to move its cursor; Shift keeps
+    // them for text selection.
+    {
+        let results_list_clone = results_list.clone();
+
+        let page_controller = gtk4::EventControllerKey::new();
+        page_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+        page_controller.connect_key_pressed(move |_, key, _, modifiers| {
+            if modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK) {
+                return gtk4::glib::Propagation::Proceed;
+            }
+
+            let navigation = match key {
+                Key::Home | Key::KP_Home => Navigation::First,
+                Key::End | Key::KP_End => Navigation::Last,
+                Key::Page_Up | Key::KP_Page_Up => Navigation::PageUp,
+                Key::Page_Down | Key::KP_Page_Down => Navigation::PageDown,
+                _ => return gtk4::glib::Propagation::Proceed,
+            };
+            navigate_results(&results_list_clone, navigation);
+            gtk4::glib::Propagation::Stop
+        });
+
+        search_widget.entry.add_controller(page_controller);
+    }
+
+    // Directory drill-down for path queries: Tab/Right expands the selected directory,
+    // Left/Backspace at a trailing '/' goes up a level; Tab also applies shell
+    // completions. Runs in the capture phase so the entry's own cursor/delete
+    // handling doesn't consume the keys first.
+    {
+        let results_list_clone = results_list.clone();
+        let search_entry_clone = search_widget.entry.clone();
+
+        let nav_controller = gtk4::EventControllerKey::new();
+        nav_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+        nav_controller.connect_key_pressed(move |_, key, _, modifiers| {
+            use gtk4::gdk::ModifierType;
+
+            if modifiers.intersects(
+                ModifierType::CONTROL_MASK | ModifierType::ALT_MASK | ModifierType::SHIFT_MASK,
+            ) {
+                return gtk4::glib::Propagation::Proceed;
+            }
+
+            // Only act when the cursor sits at the end of the query without a selection
+            let query = search_entry_clone.text().to_string();
+            let cursor_at_end = search_entry_clone.position() == query.chars().count() as i32
+                && search_entry_clone.selection_bounds().is_none();
+            if !cursor_at_end {
+                return gtk4::glib::Propagation::Proceed;
+            }
+
+            // Tab on a shell completion replaces the query with it
+            if key == Key::Tab {
+                if let Some(completion) = results_list_clone
+                    .get_selected_result()
+                    .and_then(|result| result.completion)
+                {
+                    debug!("Shell completion: {} -> {}", query, completion);
+                    search_entry_clone.set_text(&completion);
+                    search_entry_clone.set_position(-1);
+                    return gtk4::glib::Propagation::Stop;
+                }
+            }
+
+            // Tab/→ on a result with actions (files, "Open With") opens its actions
+            // menu; directories are drilled into instead
+            if matches!(key, Key::Tab | Key::Right)
+                && results_list_clone
+                    .get_selected_result()
+                    .is_some_and(|result| !result.is_directory && result.has_actions())
+                && results_list_clone.show_result_actions()
+            {
+                return gtk4::glib::Propagation::Stop;
+            }
+
+            let new_query = match key {
+                Key::Tab | Key::Right => results_list_clone
+                    .get_selected_result()
+                    .filter(|result| result.is_directory)
+                    .and_then(|result| plugins::files::descend_path_query(&query, &result.title)),
+                Key::Left | Key::BackSpace => plugins::files::ascend_path_query(&query),
+                _ => None,
+            };
+
+            match new_query {
+                Some(new_query) => {
+                    debug!("Directory navigation: {} -> {}", query, new_query);
+                    search_entry_clone.set_text(&new_query);
+                    search_entry_clone.set_position(-1);
+                    gtk4::glib::Propagation::Stop
+                }
+                None => gtk4::glib::Propagation::Proceed,
+            }
+        });
+
+        search_widget.entry.add_controller(nav_controller);
+    }
+
+    // Continue from the last query: prefill it selected, so typing replaces it
+    if config.ui.restore_last_query {
+        let last_query = last_query::LastQueryStore::new();
+
+        let entry = search_widget.entry.clone();
+        let store = last_query.clone();
+        launcher_window.window.connect_map(move |_| {
+            if entry.text().is_empty() {
+                if let Some(query) = store.load() {
+                    entry.set_text(&query);
+                }
+            }
+            entry.select_region(0, -1);
+        });
+
+        let entry = search_widget.entry.clone();
+        launcher_window.window.connect_close_request(move |_| {
+            if let Err(e) = last_query.save(&entry.text()) {
+                warn!("Failed to save last query: {}", e);
+            }
+            gtk4::glib::Propagation::Proceed
+        });
+    }
+
+    // Dismiss the launcher when focus moves elsewhere, but not to a dialog of its own
+    if config.window.hide_on_focus_loss {
+        launcher_window.window.connect_is_active_notify(|window| {
+            let change = ui::focus::FocusChange {
+                is_active: window.is_active(),
+                is_visible: window.is_visible(),
+                has_child_window: has_visible_child_window(window),
+            };
+            if change.should_close() {
+                debug!("Launcher lost focus, closing");
+                window.close();
+            }
+        });
+    }
+
+    // Keep the size the window was resized to for the next launch (layer-shell
+    // surfaces are placed by the compositor, so there's no position to keep)
+    if config.window.remember_geometry {
+        launcher_window.window.connect_close_request(|window| {
+            let (width, height) = (window.width(), window.height());
+            if width > 0 && height > 0 {
+                if let Err(e) = window_size::WindowSizeStore::new().save(width, height) {
+                    warn!("Failed to save window size: {:#}", e);
+                }
+            }
+            gtk4::glib::Propagation::Proceed
+        });
+    }
+
     // Show window
     launcher_window.show();
     search_widget.grab_focus();
 
     info!("UI built successfully");
-    Ok(())
+    Ok(search_widget.entry.clone())
 }
 
 // Footer hints removed – bottom bar now handles all shortcut hints
 
+#[allow(clippy::too_many_arguments)]
 fn handle_selected_result(
     results_list: &ResultsList,
     window: &gtk4::ApplicationWindow,
+    search_entry: &gtk4::Entry,
     usage_tracker: &Rc<RefCell<UsageTracker>>,
     usage_enabled: bool,
     merge_login_env: bool,
+    modifiers: gtk4::gdk::ModifierType,
+    show_status: &Rc<dyn Fn(&str)>,
 ) -> bool {
     if let Some((exec, terminal)) = results_list.get_selected_command() {
+        // App details (Ctrl+I) are read-only
+        if exec.is_empty()
+            && results_list
+                .get_selected_result()
+                .is_some_and(|result| result.plugin_name == ui::app_info::APP_INFO_SOURCE)
+        {
+            return true;
+        }
+
+        // Generated values (passwords, digests) are copied rather than run
+        if let Some(result) = results_list
+            .get_selected_result()
+            .filter(|result| result.activation.is_some())
+        {
+            if run_activation(result, window, modifiers, show_status) {
+                return true;
+            }
+        }
+
+        // Informational rows (e.g. calculator reference) insert text instead of launching
+        if let Some(text) = exec.strip_prefix("@insert:") {
+            search_entry.set_text(text);
+            search_entry.set_position(-1);
+            return true;
+        }
+
+        // Broken themes are listed for visibility but can't be applied
+        if let Some(theme_name) = exec.strip_prefix("@theme-invalid:") {
+            warn!(
+                "Theme '{}' has invalid CSS and can't be selected",
+                theme_name
+            );
+            return true;
+        }
+
         if let Some(theme_name) = exec.strip_prefix("@theme:") {
             info!("Switching to theme: {}", theme_name);
-            load_theme_with_name(theme_name);
 
-            match ConfigLoader::load() {
+            let loader = ConfigLoader::load();
+            if let Err(e) = load_theme_with_name(theme_name) {
+                // Revert any preview to the last persisted theme
+                warn!("{:#}", e);
+                if let Ok(loader) = &loader {
+                    let previous = ui::color_scheme::current_theme(&loader.config().ui);
+                    if let Err(e) = load_theme_with_name(previous) {
+                        error!("Failed to restore previous theme: {:#}", e);
+                    }
+                }
+                return true;
+            }
+
+            match loader {
                 Ok(mut loader) => {
                     let mut updated_config = loader.config().clone();
                     updated_config.ui.theme = theme_name.to_string();
@@ -880,18 +1426,48 @@ fn handle_selected_result(
             return true;
         }
 
+        // Destructive results run only on a second activation
+        if !results_list.confirm_activation() {
+            return true;
+        }
+
         info!("Launching: {}", exec);
 
         if usage_enabled {
             if let Some(path) = results_list.get_selected_path() {
                 usage_tracker.borrow_mut().record_launch(&path);
                 info!("Recorded launch for {}", path);
+            } else if let Some(result) = results_list
+                .get_selected_result()
+                .filter(|result| result.plugin_name == "ssh")
+            {
+                plugins::ssh::record_host_usage(&result.title);
             }
         }
 
+        // Apps (not their actions) launch through their desktop entry, which handles
+        // D-Bus activation and startup notification
+        let app_entry = results_list
+            .get_selected_path()
+            .and_then(|path| DesktopEntry::from_file(path.into()).ok())
+            .filter(|entry| expand_exec(&entry.exec, &[]) == exec);
+
+        // Request the token while the launcher still has focus
+        let startup_id = app_entry
+            .as_ref()
+            .and_then(|entry| utils::startup_id_for(entry, || request_startup_id(window, entry)));
+
         window.close();
 
-        if let Err(e) = execute_command(&exec, terminal, merge_login_env) {
+        let from_shell = results_list
+            .get_selected_result()
+            .is_some_and(|result| result.plugin_name == "shell");
+        let launched = match app_entry {
+            Some(entry) => utils::launch_entry(&entry, merge_login_env, startup_id),
+            None if from_shell => utils::execute_shell_command(&exec, terminal, merge_login_env),
+            None => execute_command(&exec, terminal, merge_login_env),
+        };
+        if let Err(e) = launched {
             error!("Failed to launch {}: {}", exec, e);
         }
 
@@ -901,6 +1477,166 @@ fn handle_selected_result(
     false
 }
 
+/// Ask the display for a startup notification id (X11) or xdg-activation token
+/// (Wayland) for launching `entry`; `None` when the compositor doesn't support it
+fn request_startup_id(window: &gtk4::ApplicationWindow, entry: &DesktopEntry) -> Option<String> {
+    let context = WidgetExt::display(window).app_launch_context();
+    let app_info = gtk4::gio::DesktopAppInfo::from_filename(&entry.path);
+    context
+        .startup_notify_id(app_info.as_ref(), &[])
+        .map(|id| id.to_string())
+}
+
+/// What Enter does on the selected actions menu entry; `None` if the selection
+/// isn't one, or is "Open" and should be handled like Enter on the result itself
+fn activate_result_action(results_list: &ResultsList) -> Option<KeyboardAction> {
+    let (action, source) = results_list.get_selected_result_action()?;
+    match action.keyboard_action(&source) {
+        KeyboardAction::None => None,
+        // Trashing asks first, like destructive results
+        _ if !results_list.confirm_activation() => Some(KeyboardAction::Handled),
+        keyboard_action => {
+            info!("{} on {}", action.label(), source.title);
+            if action == ResultAction::OpenWith {
+                results_list.update_plugin_results(source.sub_results);
+            }
+            Some(keyboard_action)
+        }
+    }
+}
+
+/// Move the results selection, previewing the theme if a theme item gets selected
+fn navigate_results(results_list: &ResultsList, navigation: Navigation) {
+    results_list.navigate(navigation);
+
+    if let Some((command, _)) = results_list.get_selected_command() {
+        if let Some(theme_name) = command.strip_prefix("@theme:") {
+            info!("Previewing theme: {}", theme_name);
+            if let Err(e) = load_theme_with_name(theme_name) {
+                warn!("Cannot preview theme: {:#}", e);
+            }
+        }
+    }
+}
+
+/// Carry out `KeyboardAction::OpenFolder`: open the folder (or the folder
+/// containing the file) at `path` and close the launcher
+fn open_folder(path: &str, window: &gtk4::ApplicationWindow, merge_login_env: bool) {
+    let folder = utils::containing_folder(path);
+    info!("Opening folder: {}", folder);
+
+    window.close();
+
+    let open_command = build_open_command(&folder);
+    if let Err(e) = execute_command(&open_command, false, merge_login_env) {
+        error!("Failed to open folder: {}", e);
+    }
+}
+
+/// Copy text for `KeyboardAction::CopyToClipboard`, closing the window unless
+/// Shift is held (Shift+Enter keeps the launcher open for further copies)
+fn copy_and_maybe_close(
+    text: &str,
+    window: &gtk4::ApplicationWindow,
+    modifiers: gtk4::gdk::ModifierType,
+    show_status: &dyn Fn(&str),
+) {
+    // The text may be a generated password; keep it out of the log
+    debug!("Copying {} characters to clipboard", text.chars().count());
+    if let Err(e) = utils::clipboard::copy_text(text) {
+        error!("Failed to copy to clipboard: {}", e);
+        show_status(&e.to_string());
+        return;
+    }
+
+    if modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK) {
+        show_status("Copied!");
+    } else {
+        window.close();
+    }
+}
+
+/// Carry out the selected result's `ResultActivation`; false when its command should
+/// still run afterwards
+fn run_activation(
+    result: PluginResult,
+    window: &gtk4::ApplicationWindow,
+    modifiers: gtk4::gdk::ModifierType,
+    show_status: &Rc<dyn Fn(&str)>,
+) -> bool {
+    match result.activation {
+        None => false,
+        Some(ResultActivation::Copy(text)) => {
+            copy_and_maybe_close(&text, window, modifiers, &**show_status);
+            true
+        }
+        Some(ResultActivation::CopyComputed(compute)) => {
+            show_status("Working…");
+            let window = window.clone();
+            let show_status = show_status.clone();
+            gtk4::glib::spawn_future_local(async move {
+                match gtk4::gio::spawn_blocking(move || compute()).await {
+                    Ok(Ok(text)) => copy_and_maybe_close(&text, &window, modifiers, &*show_status),
+                    Ok(Err(e)) => {
+                        error!("{:#}", e);
+                        show_status(&e.to_string());
+                    }
+                    Err(_) => error!("Computing the text to copy panicked"),
+                }
+            });
+            true
+        }
+        Some(ResultActivation::SaveIcon(path)) => {
+            match save_icon(&path, result.icon_data.as_deref()) {
+                Ok(()) => false,
+                Err(e) => {
+                    error!("Failed to save {}: {:#}", path.display(), e);
+                    show_status(&e.to_string());
+                    true
+                }
+            }
+        }
+    }
+}
+
+/// Write a result's inline icon to `path` for `ResultActivation::SaveIcon`; an
+/// existing file is kept, since the name identifies the contents
+fn save_icon(path: &std::path::Path, data: Option<&[u8]>) -> Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    let data = data.ok_or_else(|| anyhow::anyhow!("Result has no image to save"))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    utils::atomic_write(path, data)?;
+    debug!("Saved {}", path.display());
+    Ok(())
+}
+
+/// Modifier keys held right now, for activations that don't come with a key event
+fn current_modifiers(widget: &impl IsA<gtk4::Widget>) -> gtk4::gdk::ModifierType {
+    widget
+        .display()
+        .default_seat()
+        .and_then(|seat| seat.keyboard())
+        .map(|keyboard| keyboard.modifier_state())
+        .unwrap_or(gtk4::gdk::ModifierType::empty())
+}
+
+/// Whether a window transient for `window` (a dialog it opened) is showing
+fn has_visible_child_window(window: &gtk4::ApplicationWindow) -> bool {
+    gtk4::Window::list_toplevels()
+        .into_iter()
+        .filter_map(|widget| widget.downcast::<gtk4::Window>().ok())
+        .any(|toplevel| {
+            toplevel.is_visible()
+                && toplevel
+                    .transient_for()
+                    .is_some_and(|parent| &parent == window.upcast_ref::<gtk4::Window>())
+        })
+}
+
 fn run_daemon_mode() -> Result<()> {
     info!("Initializing daemon mode");
 
@@ -935,7 +1671,7 @@ fn run_daemon_mode() -> Result<()> {
 
     // Scan for desktop applications
     info!("Scanning for desktop applications...");
-    let scanner = DesktopScanner::new();
+    let scanner = DesktopScanner::new().with_show_nodisplay(config.search.show_nodisplay);
     let raw_entries = scanner.scan_cached()?;
     info!("Found {} applications", raw_entries.len());
 
@@ -967,8 +1703,10 @@ fn run_daemon_mode() -> Result<()> {
     });
 
     // Create browser history plugin separately so we can start indexer
-    let browser_plugin = if config.plugins.browser_history {
-        Some(Arc::new(plugins::BrowserHistoryPlugin::new()))
+    let browser_plugin = if config.plugins.browser_history.enabled {
+        Some(Arc::new(plugins::BrowserHistoryPlugin::new(
+            &config.plugins.browser_history,
+        )))
     } else {
         None
     };
@@ -986,11 +1724,29 @@ fn run_daemon_mode() -> Result<()> {
 
     // Load dynamic plugins
     info!("Loading dynamic plugins...");
-    let (dynamic_plugins, plugin_metrics) = plugins::load_plugins();
+    let (dynamic_plugins, plugin_metrics) = plugins::load_plugins(&config.plugins);
     for plugin in dynamic_plugins {
         plugin_manager.register_plugin(plugin);
     }
 
+    // Rescan when .desktop files change so newly installed apps show up without a
+    // restart; the new arena is swapped in behind the applications plugin
+    let shared_entries = plugin_manager.shared_entries();
+    let watch_paths = scanner.paths().to_vec();
+    if let Err(e) = desktop::watcher::spawn_rescan_watcher(
+        watch_paths,
+        std::time::Duration::from_secs(1),
+        move || match scanner.scan_cached() {
+            Ok(entries) => {
+                info!("Applications changed, rescanned {} entries", entries.len());
+                shared_entries.store(desktop::DesktopEntryArena::from_vec(entries));
+            }
+            Err(e) => warn!("Failed to rescan applications: {}", e),
+        },
+    ) {
+        warn!("Not watching application directories: {}", e);
+    }
+
     let plugin_manager = Rc::new(RefCell::new(plugin_manager));
     info!(
         "Enabled plugins: {:?}",
@@ -1032,7 +1788,13 @@ fn run_daemon_mode() -> Result<()> {
 
     // Track window state
     let window_ref: Rc<RefCell<Option<gtk4::ApplicationWindow>>> = Rc::new(RefCell::new(None));
+    // Keeps the color-scheme portal subscription alive while the daemon runs
+    let scheme_watcher: Rc<RefCell<Option<gtk4::gio::DBusProxy>>> = Rc::new(RefCell::new(None));
     let window_ref_for_socket = window_ref.clone();
+    // Search entry of the daemon window, for `search:<query>` commands
+    let entry_ref: Rc<RefCell<Option<gtk4::Entry>>> = Rc::new(RefCell::new(None));
+    let entry_ref_for_socket = entry_ref.clone();
+    let plugin_manager_for_socket = plugin_manager.clone();
 
     // Handle socket messages in GTK main loop
     gtk4::glib::spawn_future_local(async move {
@@ -1041,13 +1803,20 @@ fn run_daemon_mode() -> Result<()> {
             if let Ok(command) = socket_receiver.try_recv() {
                 info!("Daemon received command: {}", command);
 
-                if command == "show" {
+                if let Some(command) = daemon::DaemonCommand::parse(&command) {
                     let window_opt = window_ref_for_socket.borrow_mut();
 
                     if let Some(window) = window_opt.as_ref() {
                         // Window exists, just show it
                         info!("Showing existing window");
                         window.present();
+                        if let (Some(query), Some(entry)) =
+                            (command.query(), entry_ref_for_socket.borrow().as_ref())
+                        {
+                            prefill_query(entry, query);
+                        }
+                        // Refresh slow plugin caches while the user starts typing
+                        plugin_manager_for_socket.borrow().warm_up();
                     } else {
                         // Window doesn't exist, create it
                         info!("Window not found, this shouldn't happen in daemon mode");
@@ -1084,14 +1853,31 @@ fn run_daemon_mode() -> Result<()> {
             metrics_clone.clone(),
             Some(pins_store.clone()),
         ) {
-            Ok(()) => {
+            Ok(entry) => {
+                *entry_ref.borrow_mut() = Some(entry);
+
                 // Store window reference
                 if let Some(window) = app.active_window() {
                     if let Ok(app_window) = window.downcast::<gtk4::ApplicationWindow>() {
+                        // Closing (Escape, focus loss) only hides the window, so
+                        // the next show signal can present it again
+                        app_window.set_hide_on_close(true);
                         *window_opt = Some(app_window);
                         info!("Window created and stored for daemon mode");
                     }
                 }
+
+                // Re-apply the theme live when the system light/dark preference changes
+                if config_clone.ui.auto_theme {
+                    let ui_config = config_clone.ui.clone();
+                    *scheme_watcher.borrow_mut() =
+                        ui::color_scheme::watch_color_scheme(move |scheme| {
+                            let theme_name = ui::color_scheme::theme_for_scheme(&ui_config, scheme);
+                            if let Err(e) = load_theme_with_name(theme_name) {
+                                warn!("Failed to apply {} theme: {:#}", theme_name, e);
+                            }
+                        });
+                }
             }
             Err(e) => {
                 error!("Failed to build UI: {}", e);
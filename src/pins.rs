@@ -1,48 +1,160 @@
+use crate::utils::atomic_write;
+use crate::utils::backup::backup_corrupt_file;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 
-/// Persistent store for pinned (favorite) applications
+/// A pinned launcher item: either a desktop application or an arbitrary command
+///
+/// Desktop apps are identified by their `.desktop` path; everything else by its command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedItem {
+    /// Display title
+    pub title: String,
+    /// Command executed when the pin is activated
+    pub command: String,
+    /// Optional icon name or path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Run the command in a terminal
+    #[serde(default)]
+    pub terminal: bool,
+    /// Desktop file path when the pin refers to an installed application
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desktop_path: Option<String>,
+}
+
+impl PinnedItem {
+    /// Create a pin for an arbitrary command (file, folder, SSH host, ...)
+    pub fn command(title: String, command: String) -> Self {
+        Self {
+            title,
+            command,
+            icon: None,
+            terminal: false,
+            desktop_path: None,
+        }
+    }
+
+    /// Create a pin for a desktop application
+    pub fn desktop(desktop_path: &str) -> Self {
+        let title = Path::new(desktop_path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| desktop_path.to_string());
+
+        Self {
+            title,
+            command: String::new(),
+            icon: None,
+            terminal: false,
+            desktop_path: Some(desktop_path.to_string()),
+        }
+    }
+
+    /// Set icon
+    pub fn with_icon(mut self, icon: String) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Set terminal flag
+    pub fn with_terminal(mut self, terminal: bool) -> Self {
+        self.terminal = terminal;
+        self
+    }
+
+    /// Identity used for lookups: desktop path for apps, command otherwise
+    pub fn key(&self) -> &str {
+        self.desktop_path.as_deref().unwrap_or(&self.command)
+    }
+}
+
+/// Persistent store for pinned (favorite) applications and commands
+///
+/// Pins keep a user-defined order: newly pinned entries are appended and can be
+/// reordered with [`PinsStore::move_up`] / [`PinsStore::move_down`].
 #[derive(Debug)]
 pub struct PinsStore {
-    pins: RwLock<HashSet<String>>, // desktop file paths
+    pins: RwLock<Vec<PinnedItem>>, // in display order
     path: PathBuf,                 // JSON file path
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PinsFile {
-    pins: HashSet<String>,
+    pins: Vec<StoredPin>,
+}
+
+/// On-disk pin entry. Older versions stored bare desktop file paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredPin {
+    Path(String),
+    Item(PinnedItem),
+}
+
+impl From<StoredPin> for PinnedItem {
+    fn from(pin: StoredPin) -> Self {
+        match pin {
+            StoredPin::Path(path) => PinnedItem::desktop(&path),
+            StoredPin::Item(item) => item,
+        }
+    }
 }
 
 impl PinsStore {
     /// Create an empty store with default path
     pub fn new() -> Self {
+        Self::with_path(Self::default_path())
+    }
+
+    /// Create an empty store backed by a custom file path
+    fn with_path(path: PathBuf) -> Self {
         Self {
-            pins: RwLock::new(HashSet::new()),
-            path: Self::default_path(),
+            pins: RwLock::new(Vec::new()),
+            path,
         }
     }
 
-    /// Load pins from disk (JSON). If file doesn't exist, returns empty store.
+    /// Load pins from disk (JSON). If file doesn't exist, returns empty store;
+    /// a corrupt file is moved to `pins.json.bak` and the store starts empty.
+    ///
+    /// Path-only files written by older versions are migrated to [`PinnedItem`]s.
     pub fn load() -> Result<Self> {
-        let path = Self::default_path();
+        Self::load_from(Self::default_path())
+    }
+
+    fn load_from(path: PathBuf) -> Result<Self> {
         if !path.exists() {
             debug!("Pins file not found at {:?}, starting empty", path);
-            return Ok(Self {
-                pins: RwLock::new(HashSet::new()),
-                path,
-            });
+            return Ok(Self::with_path(path));
         }
 
         let data = fs::read(&path)?;
-        let parsed: PinsFile = serde_json::from_slice(&data)?;
-        info!("Loaded {} pinned apps", parsed.pins.len());
+        let parsed: PinsFile = match serde_json::from_slice(&data) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                error!("Pins file {:?} is corrupt: {}", path, e);
+                backup_corrupt_file(&path)?;
+                return Ok(Self::with_path(path));
+            }
+        };
+
+        // Drop duplicates while keeping the first occurrence's position
+        let mut pins: Vec<PinnedItem> = Vec::with_capacity(parsed.pins.len());
+        for pin in parsed.pins {
+            let item = PinnedItem::from(pin);
+            if !pins.iter().any(|p| p.key() == item.key()) {
+                pins.push(item);
+            }
+        }
+
+        info!("Loaded {} pinned items", pins.len());
         Ok(Self {
-            pins: RwLock::new(parsed.pins),
+            pins: RwLock::new(pins),
             path,
         })
     }
@@ -53,44 +165,108 @@ impl PinsStore {
             fs::create_dir_all(parent)?;
         }
 
-        let pins = self.pins.read().unwrap().clone();
+        let pins = self
+            .pins
+            .read()
+            .unwrap()
+            .iter()
+            .cloned()
+            .map(StoredPin::Item)
+            .collect();
         let payload = PinsFile { pins };
         let json = serde_json::to_vec_pretty(&payload)?;
-        fs::write(&self.path, json)?;
+        atomic_write(&self.path, json)?;
         debug!("Pins saved to {:?}", self.path);
         Ok(())
     }
 
-    /// Check if a desktop entry path is pinned
-    pub fn is_pinned(&self, desktop_path: &str) -> bool {
+    /// Check if a desktop entry path or pinned command is pinned
+    pub fn is_pinned(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Position of a pin in the user-defined order (0 = top)
+    pub fn position(&self, key: &str) -> Option<usize> {
         self.pins
             .read()
             .unwrap()
-            .contains(&desktop_path.to_string())
+            .iter()
+            .position(|p| p.key() == key)
     }
 
     /// Toggle pinned state for a desktop entry path. Returns new state (true if pinned).
     pub fn toggle(&self, desktop_path: &str) -> Result<bool> {
+        self.toggle_item(PinnedItem::desktop(desktop_path))
+    }
+
+    /// Toggle pinned state for an item. Returns new state (true if pinned).
+    ///
+    /// Newly pinned items are placed at the bottom of the pinned list.
+    pub fn toggle_item(&self, item: PinnedItem) -> Result<bool> {
         let mut guard = self.pins.write().unwrap();
-        if guard.contains(desktop_path) {
-            guard.remove(desktop_path);
+        if let Some(index) = guard.iter().position(|p| p.key() == item.key()) {
+            guard.remove(index);
             drop(guard);
             self.save()?;
-            info!("Unpinned {}", desktop_path);
+            info!("Unpinned {}", item.key());
             Ok(false)
         } else {
-            guard.insert(desktop_path.to_string());
+            info!("Pinned {}", item.key());
+            guard.push(item);
             drop(guard);
             self.save()?;
-            info!("Pinned {}", desktop_path);
             Ok(true)
         }
     }
 
-    /// List all pinned desktop paths
+    /// Move a pin one slot towards the top.
+    ///
+    /// Returns `false` (without touching disk) if the key is not pinned or already first.
+    pub fn move_up(&self, key: &str) -> Result<bool> {
+        let mut guard = self.pins.write().unwrap();
+        match guard.iter().position(|p| p.key() == key) {
+            Some(index) if index > 0 => {
+                guard.swap(index, index - 1);
+                drop(guard);
+                self.save()?;
+                debug!("Moved pin {} up to position {}", key, index - 1);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Move a pin one slot towards the bottom.
+    ///
+    /// Returns `false` (without touching disk) if the key is not pinned or already last.
+    pub fn move_down(&self, key: &str) -> Result<bool> {
+        let mut guard = self.pins.write().unwrap();
+        match guard.iter().position(|p| p.key() == key) {
+            Some(index) if index + 1 < guard.len() => {
+                guard.swap(index, index + 1);
+                drop(guard);
+                self.save()?;
+                debug!("Moved pin {} down to position {}", key, index + 1);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// List all pinned items in display order
+    pub fn items(&self) -> Vec<PinnedItem> {
+        self.pins.read().unwrap().clone()
+    }
+
+    /// List all pin keys (desktop paths or commands) in display order
     #[allow(dead_code)]
     pub fn list(&self) -> Vec<String> {
-        self.pins.read().unwrap().iter().cloned().collect()
+        self.pins
+            .read()
+            .unwrap()
+            .iter()
+            .map(|p| p.key().to_string())
+            .collect()
     }
 
     fn default_path() -> PathBuf {
@@ -104,3 +280,145 @@ impl Default for PinsStore {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_pins_path(prefix: &str) -> PathBuf {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("native-launcher-pins-{}-{}", prefix, ts))
+            .join("pins.json")
+    }
+
+    fn store_with(path: &Path, pins: &[&str]) -> PinsStore {
+        let store = PinsStore::with_path(path.to_path_buf());
+        for pin in pins {
+            store.toggle(pin).unwrap();
+        }
+        store
+    }
+
+    fn cleanup(path: &Path) {
+        if let Some(parent) = path.parent() {
+            let _ = fs::remove_dir_all(parent);
+        }
+    }
+
+    #[test]
+    fn test_move_up_and_down() {
+        let path = unique_pins_path("move");
+        let store = store_with(&path, &["a", "b", "c"]);
+
+        assert!(store.move_up("c").unwrap());
+        assert_eq!(store.list(), vec!["a", "c", "b"]);
+
+        assert!(store.move_down("a").unwrap());
+        assert_eq!(store.list(), vec!["c", "a", "b"]);
+        assert_eq!(store.position("b"), Some(2));
+        cleanup(&path);
+    }
+
+    #[test]
+    fn test_move_at_boundaries_is_noop() {
+        let path = unique_pins_path("bounds");
+        let store = store_with(&path, &["a", "b"]);
+
+        assert!(!store.move_up("a").unwrap());
+        assert!(!store.move_down("b").unwrap());
+        assert!(!store.move_up("missing").unwrap());
+        assert!(!store.move_down("missing").unwrap());
+        assert_eq!(store.list(), vec!["a", "b"]);
+        cleanup(&path);
+    }
+
+    #[test]
+    fn test_order_persists_across_reload() {
+        let path = unique_pins_path("reload");
+        let store = store_with(&path, &["a", "b", "c"]);
+        store.move_up("c").unwrap();
+        store.move_up("c").unwrap();
+
+        let reloaded = PinsStore::load_from(path.clone()).unwrap();
+        assert_eq!(reloaded.list(), vec!["c", "a", "b"]);
+        cleanup(&path);
+    }
+
+    #[test]
+    fn test_toggle_appends_and_removes() {
+        let path = unique_pins_path("toggle");
+        let store = store_with(&path, &["a", "b"]);
+
+        assert!(!store.toggle("a").unwrap());
+        assert!(store.toggle("a").unwrap());
+        assert_eq!(store.list(), vec!["b", "a"]);
+        cleanup(&path);
+    }
+
+    #[test]
+    fn test_pin_command_item() {
+        let path = unique_pins_path("command");
+        let store = store_with(&path, &["/usr/share/applications/firefox.desktop"]);
+
+        let item = PinnedItem::command("SSH: prod".to_string(), "ssh prod".to_string())
+            .with_icon("network-server".to_string())
+            .with_terminal(true);
+        assert!(store.toggle_item(item.clone()).unwrap());
+        assert!(store.is_pinned("ssh prod"));
+        assert_eq!(store.position("ssh prod"), Some(1));
+
+        let reloaded = PinsStore::load_from(path.clone()).unwrap();
+        assert_eq!(reloaded.items()[1], item);
+
+        assert!(!store.toggle_item(item).unwrap());
+        assert!(!store.is_pinned("ssh prod"));
+        cleanup(&path);
+    }
+
+    #[test]
+    fn test_load_migrates_path_only_file() {
+        let path = unique_pins_path("legacy");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{"pins": ["/usr/share/applications/firefox.desktop", "/usr/share/applications/code.desktop"]}"#,
+        )
+        .unwrap();
+
+        let store = PinsStore::load_from(path.clone()).unwrap();
+        let items = store.items();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "firefox");
+        assert_eq!(
+            items[0].desktop_path.as_deref(),
+            Some("/usr/share/applications/firefox.desktop")
+        );
+        assert!(store.is_pinned("/usr/share/applications/code.desktop"));
+
+        // Saving rewrites the file in the new format, which loads back unchanged
+        store.save().unwrap();
+        let reloaded = PinsStore::load_from(path.clone()).unwrap();
+        assert_eq!(reloaded.items(), items);
+        cleanup(&path);
+    }
+
+    #[test]
+    fn test_corrupt_file_backed_up_and_store_starts_empty() {
+        let path = unique_pins_path("corrupt");
+        store_with(&path, &["a", "b"]).save().unwrap();
+        let data = fs::read(&path).unwrap();
+        fs::write(&path, &data[..data.len() / 2]).unwrap();
+
+        let store = PinsStore::load_from(path.clone()).unwrap();
+        assert!(store.list().is_empty());
+        assert!(!path.exists());
+        let backup = path.with_file_name("pins.json.bak");
+        assert_eq!(fs::read(&backup).unwrap(), &data[..data.len() / 2]);
+        cleanup(&path);
+    }
+}
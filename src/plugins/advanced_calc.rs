@@ -1,38 +1,259 @@
-use super::traits::{Plugin, PluginContext, PluginResult};
+use super::currency_rates::{CurrencyRates, RateTable};
+use super::traits::{
+    KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult, ResultActivation,
+};
 use anyhow::Result;
-use chrono::{Duration, Local, Utc};
-use std::collections::HashMap;
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, Utc, Weekday};
+use gtk4::gdk::Key;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Calculations remembered for "@calc" recall
+const HISTORY_CAPACITY: usize = 20;
+
+/// Approximate rates (units per USD, 2024) used until live rates were fetched once
+const FALLBACK_RATES: &[(&str, f64)] = &[
+    ("USD", 1.0),
+    ("EUR", 0.92),
+    ("GBP", 0.79),
+    ("JPY", 149.50),
+    ("CNY", 7.24),
+    ("INR", 83.12),
+    ("CAD", 1.36),
+    ("AUD", 1.53),
+    ("CHF", 0.88),
+    ("KRW", 1329.0),
+];
+
+/// Recent calculations (expression, result), most recent first
+///
+/// Kept in memory only. The calculator records every expression it evaluates,
+/// which includes each prefix typed on the way to the final one, so an expression
+/// extending or trimming the most recent entry replaces it instead of adding a row.
+#[derive(Debug, Default)]
+pub struct CalculationHistory {
+    entries: Mutex<VecDeque<(String, String)>>,
+}
+
+impl CalculationHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember that `expression` evaluated to `result`
+    pub fn record(&self, expression: &str, result: &str) {
+        let expression = expression.trim();
+        // A bare number isn't a calculation worth recalling
+        if expression.is_empty() || expression.parse::<f64>().is_ok() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((latest, _)) = entries.front() {
+            if latest.starts_with(expression) || expression.starts_with(latest.as_str()) {
+                entries.pop_front();
+            }
+        }
+        entries.retain(|(existing, _)| existing != expression);
+        entries.push_front((expression.to_string(), result.to_string()));
+        entries.truncate(HISTORY_CAPACITY);
+    }
+
+    /// Remembered calculations, most recent first
+    pub fn recent(&self) -> Vec<(String, String)> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+
+    /// Result remembered for `expression`
+    fn result_for(&self, expression: &str) -> Option<String> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .find(|(existing, _)| existing == expression)
+            .map(|(_, result)| result.clone())
+    }
+}
+
+/// Result of a date expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateAnswer {
+    /// A calendar date ("today + 30 days", "next friday")
+    Date(NaiveDate),
+    /// Days from the first date to the second ("days until 2025-12-25")
+    Days { from: NaiveDate, to: NaiveDate },
+}
+
+/// Evaluate date arithmetic relative to `today`
+///
+/// Dates are ISO (`2025-12-25`), `today`, `tomorrow`, `yesterday` or
+/// `next <weekday>`. Supported forms: `<date> + 3 weeks` (or `-`, with days,
+/// weeks, months or years), `days until <date>`, `days since <date>`,
+/// `<date> to <date>` and a bare date.
+pub fn evaluate_date_expression(query: &str, today: NaiveDate) -> Option<DateAnswer> {
+    let query = query.trim().to_lowercase();
+
+    if let Some(rest) = query.strip_prefix("days ") {
+        let (direction, date) = rest.trim_start().split_once(' ')?;
+        let date = parse_date_term(date, today)?;
+        return match direction {
+            "until" | "till" | "to" => Some(DateAnswer::Days {
+                from: today,
+                to: date,
+            }),
+            "since" | "from" => Some(DateAnswer::Days {
+                from: date,
+                to: today,
+            }),
+            _ => None,
+        };
+    }
+
+    let offset = regex::Regex::new(r"^(.+?)\s*([+-])\s*(\d+)\s*(day|week|month|year)s?$").ok()?;
+    if let Some(caps) = offset.captures(&query) {
+        let date = parse_date_term(caps.get(1)?.as_str(), today)?;
+        let amount: u32 = caps.get(3)?.as_str().parse().ok()?;
+        let forward = caps.get(2)?.as_str() == "+";
+        let unit = caps.get(4)?.as_str();
+        let shifted = match unit {
+            "day" | "week" => {
+                let per_unit = if unit == "week" { 7 } else { 1 };
+                let days = chrono::Days::new(u64::from(amount) * per_unit);
+                if forward {
+                    date.checked_add_days(days)
+                } else {
+                    date.checked_sub_days(days)
+                }
+            }
+            _ => {
+                let per_unit = if unit == "year" { 12 } else { 1 };
+                let months = Months::new(amount.checked_mul(per_unit)?);
+                if forward {
+                    date.checked_add_months(months)
+                } else {
+                    date.checked_sub_months(months)
+                }
+            }
+        }?;
+        return Some(DateAnswer::Date(shifted));
+    }
+
+    if let Some((from, to)) = query.split_once(" to ") {
+        return Some(DateAnswer::Days {
+            from: parse_date_term(from, today)?,
+            to: parse_date_term(to, today)?,
+        });
+    }
+
+    parse_date_term(&query, today).map(DateAnswer::Date)
+}
+
+/// A single date: ISO, today/tomorrow/yesterday or "next <weekday>"
+fn parse_date_term(term: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match term.trim() {
+        "today" => Some(today),
+        "tomorrow" => today.succ_opt(),
+        "yesterday" => today.pred_opt(),
+        term => {
+            if let Some(day) = term.strip_prefix("next ") {
+                let weekday: Weekday = day.trim().parse().ok()?;
+                let ahead = (weekday.num_days_from_monday() + 7
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                // "next friday" on a Friday is a week away, not today
+                let ahead = if ahead == 0 { 7 } else { ahead };
+                return today.checked_add_days(chrono::Days::new(u64::from(ahead)));
+            }
+            NaiveDate::parse_from_str(term, "%Y-%m-%d").ok()
+        }
+    }
+}
+
+/// Whether `query` starts like a date expression (cheap check for `should_handle`)
+fn looks_like_date_expression(query_lower: &str) -> bool {
+    let starts_with_iso_date = query_lower.len() >= 10
+        && query_lower
+            .bytes()
+            .take(10)
+            .enumerate()
+            .all(|(i, b)| match i {
+                4 | 7 => b == b'-',
+                _ => b.is_ascii_digit(),
+            });
+
+    starts_with_iso_date
+        || [
+            "today",
+            "tomorrow",
+            "yesterday",
+            "days until ",
+            "days since ",
+            "next ",
+        ]
+        .iter()
+        .any(|prefix| query_lower.starts_with(prefix))
+}
+
+/// "in 3 days", "2 days ago" or "today"
+fn relative_days(days: i64) -> String {
+    match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        d if d > 0 => format!("in {} days", d),
+        d => format!("{} days ago", -d),
+    }
+}
 
 /// Advanced calculator plugin with time, unit, and currency conversions
 #[derive(Debug)]
 pub struct AdvancedCalculatorPlugin {
     enabled: bool,
-    currency_rates: HashMap<String, f64>, // Base: USD
+    /// Exchange rates, fetched in the background and cached to disk when enabled
+    currency_rates: CurrencyRates,
+    /// Recent calculations listed by a bare "@calc"
+    history: Arc<CalculationHistory>,
 }
 
 impl AdvancedCalculatorPlugin {
     pub fn new() -> Self {
-        // Initialize with some basic currency rates (should be fetched from API in production)
-        let mut currency_rates = HashMap::new();
-
-        // Base rates (as of 2024 - static fallback)
-        currency_rates.insert("USD".to_string(), 1.0);
-        currency_rates.insert("EUR".to_string(), 0.92);
-        currency_rates.insert("GBP".to_string(), 0.79);
-        currency_rates.insert("JPY".to_string(), 149.50);
-        currency_rates.insert("CNY".to_string(), 7.24);
-        currency_rates.insert("INR".to_string(), 83.12);
-        currency_rates.insert("CAD".to_string(), 1.36);
-        currency_rates.insert("AUD".to_string(), 1.53);
-        currency_rates.insert("CHF".to_string(), 0.88);
-        currency_rates.insert("KRW".to_string(), 1329.0);
-
         Self {
             enabled: true,
-            currency_rates,
+            currency_rates: CurrencyRates::new(),
+            history: Arc::new(CalculationHistory::new()),
         }
     }
 
+    /// Use `rates` for currency conversions instead of the cached live rates
+    pub fn with_currency_rates(mut self, rates: CurrencyRates) -> Self {
+        self.currency_rates = rates;
+        self
+    }
+
+    /// Calculation history to share with the calculator plugin, which records into it
+    pub fn history(&self) -> Arc<CalculationHistory> {
+        self.history.clone()
+    }
+
+    /// Bare "@calc": recent calculations, Enter re-inserts one and Ctrl+Enter copies its result
+    fn history_results(&self) -> Vec<PluginResult> {
+        self.history
+            .recent()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (expression, result))| {
+                PluginResult::new(
+                    format!("{} = {}", expression, result),
+                    format!("@insert:@calc {}", expression),
+                    self.name().to_string(),
+                )
+                .with_subtitle("Recent calculation • Enter to edit, Ctrl+Enter to copy".to_string())
+                .with_icon("accessories-calculator".to_string())
+                .with_score(9500 - i as i64) // Above the calculator reference
+            })
+            .collect()
+    }
+
     /// Parse time-based queries like "1 hour ago", "350 days ago", "in 5 hours"
     fn parse_time_query(&self, query: &str) -> Option<Vec<PluginResult>> {
         let query_lower = query.to_lowercase();
@@ -156,6 +377,48 @@ impl AdvancedCalculatorPlugin {
         None
     }
 
+    /// Parse date arithmetic like "today + 30 days", "days until 2025-12-25"
+    fn parse_date_arithmetic(&self, query: &str) -> Option<Vec<PluginResult>> {
+        let today = Local::now().date_naive();
+        let (value, subtitle) = match evaluate_date_expression(query, today)? {
+            DateAnswer::Date(date) => (
+                date.format("%Y-%m-%d").to_string(),
+                format!(
+                    "{} • {}",
+                    date.format("%A, %-d %B %Y"),
+                    relative_days((date - today).num_days())
+                ),
+            ),
+            DateAnswer::Days { from, to } => {
+                let days = (to - from).num_days();
+                let weeks = if days.abs() >= 14 {
+                    format!(" ({} weeks, {} days)", days / 7, days % 7)
+                } else {
+                    String::new()
+                };
+                (
+                    format!("{} days", days),
+                    format!(
+                        "{} → {}{}",
+                        from.format("%Y-%m-%d"),
+                        to.format("%Y-%m-%d"),
+                        weeks
+                    ),
+                )
+            }
+        };
+
+        Some(vec![PluginResult::new(
+            value.clone(),
+            String::new(),
+            self.name().to_string(),
+        )
+        .with_subtitle(format!("{} • Press Enter to copy", subtitle))
+        .with_icon("x-office-calendar".to_string())
+        .with_score(9500)
+        .with_activation(ResultActivation::Copy(value))])
+    }
+
     /// Parse unit conversions like "150 days to years", "5 km to miles"
     fn parse_unit_conversion(&self, query: &str) -> Option<Vec<PluginResult>> {
         let query_lower = query.to_lowercase();
@@ -339,6 +602,9 @@ impl AdvancedCalculatorPlugin {
     }
 
     /// Parse currency conversions like "100 USD to EUR"
+    ///
+    /// Uses the live rates (possibly stale while offline); before any were
+    /// fetched, built-in approximate rates with an "unavailable" note instead.
     fn parse_currency_conversion(&self, query: &str) -> Option<Vec<PluginResult>> {
         let query_upper = query.to_uppercase();
 
@@ -350,11 +616,25 @@ impl AdvancedCalculatorPlugin {
             let from_currency = caps.get(2)?.as_str();
             let to_currency = caps.get(3)?.as_str();
 
-            let from_rate = self.currency_rates.get(from_currency)?;
-            let to_rate = self.currency_rates.get(to_currency)?;
-
-            // Convert: amount * (to_rate / from_rate)
-            let result = amount * (to_rate / from_rate);
+            let (result, source) = match self.currency_rates.current() {
+                Some(table) => {
+                    let fetched = DateTime::from_timestamp(table.fetched_at as i64, 0)
+                        .map(|at| {
+                            at.with_timezone(&Local)
+                                .format("%Y-%m-%d %H:%M")
+                                .to_string()
+                        })
+                        .unwrap_or_default();
+                    (
+                        table.convert(amount, from_currency, to_currency)?,
+                        format!("rates from {}", fetched),
+                    )
+                }
+                None => (
+                    fallback_rates().convert(amount, from_currency, to_currency)?,
+                    "rates unavailable (offline), approximate".to_string(),
+                ),
+            };
 
             return Some(vec![PluginResult::new(
                 format!("{:.2} {}", result, to_currency),
@@ -362,8 +642,8 @@ impl AdvancedCalculatorPlugin {
                 self.name().to_string(),
             )
             .with_subtitle(format!(
-                "{} {} ≈ {:.2} {}",
-                amount, from_currency, result, to_currency
+                "{} {} ≈ {:.2} {} • {}",
+                amount, from_currency, result, to_currency, source
             ))
             .with_icon("emblem-money".to_string())
             .with_score(9500)]);
@@ -415,6 +695,17 @@ impl AdvancedCalculatorPlugin {
     }
 }
 
+/// Built-in rates for conversions before live rates were ever fetched
+fn fallback_rates() -> RateTable {
+    RateTable {
+        fetched_at: 0,
+        rates: FALLBACK_RATES
+            .iter()
+            .map(|(code, rate)| (code.to_string(), *rate))
+            .collect(),
+    }
+}
+
 impl Default for AdvancedCalculatorPlugin {
     fn default() -> Self {
         Self::new()
@@ -427,7 +718,7 @@ impl Plugin for AdvancedCalculatorPlugin {
     }
 
     fn description(&self) -> &str {
-        "Advanced calculations: time (1 hour ago), unit conversions (150 days to years), currency (100 USD to EUR), timezone conversions"
+        "Advanced calculations: time (1 hour ago), dates (today + 30 days), unit conversions (150 days to years), currency (100 USD to EUR), timezone conversions"
     }
 
     fn command_prefixes(&self) -> Vec<&str> {
@@ -462,6 +753,11 @@ impl Plugin for AdvancedCalculatorPlugin {
             return true;
         }
 
+        // Check for date arithmetic
+        if looks_like_date_expression(&query_lower) {
+            return true;
+        }
+
         // Check for timezone queries
         if query_lower.contains("utc") || query_lower.contains("timezone") {
             return true;
@@ -479,11 +775,20 @@ impl Plugin for AdvancedCalculatorPlugin {
             return Ok(vec![]);
         }
 
+        if query.trim() == "@calc" {
+            return Ok(self.history_results());
+        }
+
         // Try time-based queries first
         if let Some(results) = self.parse_time_query(query) {
             return Ok(results);
         }
 
+        // Try date arithmetic
+        if let Some(results) = self.parse_date_arithmetic(query) {
+            return Ok(results);
+        }
+
         // Try unit conversions
         if let Some(results) = self.parse_unit_conversion(query) {
             return Ok(results);
@@ -501,4 +806,19 @@ impl Plugin for AdvancedCalculatorPlugin {
 
         Ok(vec![])
     }
+
+    fn handle_keyboard_event(&self, event: &KeyboardEvent) -> KeyboardAction {
+        // Ctrl+Enter on a recalled calculation copies its result
+        if event.key != Key::Return || !event.has_ctrl() || event.query.trim() != "@calc" {
+            return KeyboardAction::None;
+        }
+
+        event
+            .selected_command
+            .as_deref()
+            .and_then(|command| command.strip_prefix("@insert:@calc "))
+            .and_then(|expression| self.history.result_for(expression))
+            .map(KeyboardAction::CopyToClipboard)
+            .unwrap_or(KeyboardAction::None)
+    }
 }
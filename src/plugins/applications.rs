@@ -1,7 +1,13 @@
-use super::traits::{Plugin, PluginContext, PluginResult};
-use crate::desktop::{DesktopEntry, DesktopEntryArena, SharedDesktopEntry};
+use super::traits::{Plugin, PluginContext, PluginResult, ResultBadge};
+use super::windows::WindowList;
+use crate::desktop::SharedArena;
+use crate::desktop::{DesktopEntry, DesktopEntryArena, SearchKeys, SharedDesktopEntry};
 use crate::pins::PinsStore;
+use crate::search::{
+    did_you_mean, exact_match_score, exact_match_term, usage_boost, OrderStrategy, ScoreThresholds,
+};
 use crate::usage::UsageTracker;
+use crate::utils::expand_exec;
 use crate::utils::icons::resolve_icon_with_category_fallback;
 use anyhow::Result;
 use fuzzy_matcher::skim::SkimMatcherV2;
@@ -10,16 +16,19 @@ use std::sync::Arc;
 
 /// Plugin for searching desktop applications
 pub struct ApplicationsPlugin {
-    entries: DesktopEntryArena,
+    entries: SharedArena,
     matcher: SkimMatcherV2,
     usage_tracker: Option<UsageTracker>,
     pins: Option<Arc<PinsStore>>,
+    windows: Option<Arc<WindowList>>,
+    thresholds: ScoreThresholds,
+    order: OrderStrategy,
 }
 
 impl std::fmt::Debug for ApplicationsPlugin {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ApplicationsPlugin")
-            .field("entries", &self.entries.len())
+            .field("entries", &self.entries.load().len())
             .field("usage_tracker", &self.usage_tracker.is_some())
             .finish()
     }
@@ -29,20 +38,26 @@ impl ApplicationsPlugin {
     /// Create a new applications plugin
     pub fn new(entries: DesktopEntryArena) -> Self {
         Self {
-            entries,
+            entries: SharedArena::new(entries),
             matcher: SkimMatcherV2::default(),
             usage_tracker: None,
             pins: None,
+            windows: None,
+            thresholds: ScoreThresholds::default(),
+            order: OrderStrategy::default(),
         }
     }
 
     /// Create with usage tracking
     pub fn with_usage_tracking(entries: DesktopEntryArena, usage_tracker: UsageTracker) -> Self {
         Self {
-            entries,
+            entries: SharedArena::new(entries),
             matcher: SkimMatcherV2::default(),
             usage_tracker: Some(usage_tracker),
             pins: None,
+            windows: None,
+            thresholds: ScoreThresholds::default(),
+            order: OrderStrategy::default(),
         }
     }
 
@@ -53,19 +68,46 @@ impl ApplicationsPlugin {
         pins: Option<Arc<PinsStore>>,
     ) -> Self {
         Self {
-            entries,
+            entries: SharedArena::new(entries),
             matcher: SkimMatcherV2::default(),
             usage_tracker,
             pins,
+            windows: None,
+            thresholds: ScoreThresholds::default(),
+            order: OrderStrategy::default(),
         }
     }
 
+    /// Offer to focus apps that already have an open window
+    pub fn with_window_list(mut self, windows: Arc<WindowList>) -> Self {
+        self.windows = Some(windows);
+        self
+    }
+
+    /// Use custom minimum fuzzy scores (see [`ScoreThresholds::from_config`])
+    pub fn with_score_thresholds(mut self, thresholds: ScoreThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Use a different ordering for the empty query and ties (see [`OrderStrategy::from_config`])
+    pub fn with_order_strategy(mut self, order: OrderStrategy) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Handle to the entries this plugin searches; storing a new arena through it
+    /// takes effect on the next search
+    pub fn shared_entries(&self) -> SharedArena {
+        self.entries.clone()
+    }
+
     /// Calculate fuzzy match score for an entry
-    fn calculate_fuzzy_score(&self, entry: &DesktopEntry, query: &str) -> i64 {
+    fn calculate_fuzzy_score(&self, entry: &DesktopEntry, keys: &SearchKeys, query: &str) -> i64 {
         let mut best_score = 0i64;
 
         // 1. Try exact match first (highest priority)
-        let name_lower = entry.name.to_lowercase();
+        let name_lower = keys.name.as_str();
         if name_lower.contains(query) {
             // Exact substring match gets huge bonus
             best_score = best_score.max(10000 + (1000 / (name_lower.len() as i64 + 1)));
@@ -87,8 +129,7 @@ impl ApplicationsPlugin {
         }
 
         // 3. Fuzzy match on generic name (secondary field)
-        if let Some(ref generic) = entry.generic_name {
-            let generic_lower = generic.to_lowercase();
+        if let (Some(generic), Some(generic_lower)) = (&entry.generic_name, &keys.generic_name) {
             if generic_lower.contains(query) {
                 best_score = best_score.max(5000);
             }
@@ -114,8 +155,371 @@ impl ApplicationsPlugin {
             }
         }
 
+        // 6. Desktop file id segments (e.g. "org.kde" for org.kde.konsole.desktop),
+        // kept below keyword and exec matches to avoid noisy results
+        if keys.matches_file_id(query) {
+            best_score = best_score.max(1000);
+        }
+
         best_score
     }
+
+    /// Rank entries by pin order, then the order strategy (usage by default)
+    ///
+    /// The ordering is encoded into the score so the manager's global sort preserves it.
+    fn rank_by_usage(
+        &self,
+        mut entries: Vec<SharedDesktopEntry>,
+        max_results: usize,
+    ) -> Vec<PluginResult> {
+        let tracker_opt = &self.usage_tracker;
+        let pins_opt = &self.pins;
+        let pin_position = |path: &str| pins_opt.as_ref().and_then(|p| p.position(path));
+
+        let order = self.order.for_empty_query();
+
+        // Sort by user-defined pin order first, then by the order strategy (usage
+        // score by default), then name (stable across runs)
+        entries.sort_by(|a, b| {
+            let a_pin = pin_position(&a.path.to_string_lossy()).unwrap_or(usize::MAX);
+            let b_pin = pin_position(&b.path.to_string_lossy()).unwrap_or(usize::MAX);
+
+            a_pin
+                .cmp(&b_pin)
+                .then_with(|| order.compare(tracker_opt.as_ref(), a, b))
+        });
+
+        entries
+            .into_iter()
+            .take(max_results)
+            .enumerate()
+            .map(|(index, entry)| {
+                let entry = entry.as_ref();
+                let path = entry.path.to_string_lossy().to_string();
+                let score = match pin_position(&path) {
+                    // Large boost for pinned to ensure they appear first globally,
+                    // stepping down by pin position to keep the user-defined order
+                    Some(position) => PINNED_BASE_SCORE - position as i64,
+                    None if order == OrderStrategy::Usage => {
+                        let usage = tracker_opt
+                            .as_ref()
+                            .map(|t| t.get_score(&path))
+                            .unwrap_or(0.0);
+                        // Scale usage to i64; usage is typically small (<10)
+                        (usage * 1000.0).round() as i64
+                    }
+                    // Other strategies: step down by position to keep their order
+                    None => (max_results - index) as i64,
+                };
+
+                self.entry_result(entry, score)
+            })
+            .collect()
+    }
+
+    /// Split off the `RECENT_LAUNCH_ROWS` most recently launched entries (most
+    /// recent first) from the rest; pinned apps stay with the rest to keep their
+    /// pinned place
+    fn split_recent(
+        &self,
+        mut entries: Vec<SharedDesktopEntry>,
+    ) -> (Vec<SharedDesktopEntry>, Vec<SharedDesktopEntry>) {
+        let Some(tracker) = &self.usage_tracker else {
+            return (Vec::new(), entries);
+        };
+        let is_pinned = |path: &str| self.pins.as_ref().is_some_and(|p| p.is_pinned(path));
+
+        let mut recent = Vec::with_capacity(RECENT_LAUNCH_ROWS);
+        for path in tracker.recent(tracker.app_count()) {
+            if recent.len() == RECENT_LAUNCH_ROWS {
+                break;
+            }
+            if is_pinned(&path) {
+                continue;
+            }
+            if let Some(pos) = entries
+                .iter()
+                .position(|entry| entry.path.to_string_lossy() == path)
+            {
+                recent.push(entries.swap_remove(pos));
+            }
+        }
+        (recent, entries)
+    }
+
+    /// Result row for an application entry
+    fn entry_result(&self, entry: &DesktopEntry, score: i64) -> PluginResult {
+        // Resolve icon with category fallback
+        let icon_path =
+            resolve_icon_with_category_fallback(entry.icon.as_deref(), &entry.categories);
+
+        let mut result = PluginResult::new(
+            entry.name.clone(),
+            expand_exec(&entry.exec, &[]),
+            self.name().to_string(),
+        )
+        // The comment describes the app best ("Browse the World Wide Web")
+        .with_subtitle(
+            entry
+                .comment
+                .clone()
+                .or_else(|| entry.generic_name.clone())
+                .unwrap_or_default(),
+        )
+        .with_icon(icon_path.to_string_lossy().to_string())
+        .with_terminal(entry.terminal)
+        .with_desktop_path(entry.path.to_string_lossy().to_string())
+        .with_score(score);
+
+        // Add terminal badge for terminal apps
+        if entry.terminal {
+            result = result.with_badge_icon("utilities-terminal-symbolic".to_string());
+        }
+        if entry.is_flatpak() {
+            result = result.with_badge(ResultBadge::Flatpak);
+        }
+
+        result
+    }
+
+    /// Follow each running app's result with a "Switch to" row that focuses its window
+    ///
+    /// The window list is fetched once per search; apps without a matching window
+    /// are left alone.
+    fn add_focus_results(
+        &self,
+        results: Vec<PluginResult>,
+        entries: &[SharedDesktopEntry],
+    ) -> Vec<PluginResult> {
+        let Some(window_list) = &self.windows else {
+            return results;
+        };
+        let windows = window_list.windows();
+        if windows.is_empty() {
+            return results;
+        }
+
+        let mut with_focus = Vec::with_capacity(results.len());
+        for (mut result, entry) in results.into_iter().zip(entries) {
+            let focus = window_list
+                .find_for_entry(&windows, entry)
+                .map(|(window, command)| {
+                    let mut focus = PluginResult::new(
+                        format!("Switch to {}", entry.name),
+                        command,
+                        self.name().to_string(),
+                    )
+                    .with_subtitle(window.title)
+                    .with_badge_icon("view-restore-symbolic".to_string())
+                    .with_score(result.score - 1);
+                    if let Some(icon) = &result.icon {
+                        focus = focus.with_icon(icon.clone());
+                    }
+                    focus
+                });
+            if focus.is_some() {
+                result = result.with_badge(ResultBadge::Running);
+            }
+
+            with_focus.push(result);
+            with_focus.extend(focus);
+        }
+        with_focus
+    }
+
+    /// Render pinned commands (non-app pins) as results, scored by pin position
+    fn pinned_command_results(&self) -> Vec<PluginResult> {
+        let Some(pins) = &self.pins else {
+            return Vec::new();
+        };
+
+        pins.items()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, item)| item.desktop_path.is_none())
+            .map(|(position, item)| {
+                let mut result = PluginResult::new(
+                    item.title.clone(),
+                    item.command.clone(),
+                    self.name().to_string(),
+                )
+                .with_terminal(item.terminal)
+                .with_score(PINNED_BASE_SCORE - position as i64);
+
+                if item.title != item.command {
+                    result = result.with_subtitle(item.command);
+                }
+                if let Some(icon) = item.icon {
+                    result = result.with_icon(icon);
+                }
+                result
+            })
+            .collect()
+    }
+
+    /// List every entry tagged with the requested freedesktop category
+    fn search_category(&self, category: &str, max_results: usize) -> Vec<PluginResult> {
+        let Some(category) = resolve_category(category) else {
+            return Vec::new();
+        };
+        let category = category.to_lowercase();
+
+        let entries: Vec<_> = self
+            .entries
+            .load()
+            .iter_with_keys()
+            .filter(|(_, keys)| keys.categories.contains(&category))
+            .map(|(entry, _)| entry.clone())
+            .collect();
+
+        // Lift the whole list above fallback rows (e.g. web search) from other plugins
+        self.rank_by_usage(entries, max_results)
+            .into_iter()
+            .map(|mut result| {
+                result.score += CATEGORY_BASE_SCORE;
+                result
+            })
+            .collect()
+    }
+
+    /// Command mode (`$nvim`): fuzzy match the program each entry runs, ignoring
+    /// names and keywords, and list the matches by their command
+    fn search_commands(&self, command: &str, max_results: usize) -> Vec<PluginResult> {
+        let command = command.trim().to_lowercase();
+        if command.is_empty() {
+            return Vec::new();
+        }
+
+        let entries = self.entries.load();
+        let mut matches: Vec<(&DesktopEntry, i64)> = entries
+            .iter()
+            .filter_map(|entry| {
+                let program = exec_program(&entry.exec)?.to_lowercase();
+                let mut score = self.matcher.fuzzy_match(&program, &command)?;
+                if program == command {
+                    score += EXACT_COMMAND_BONUS;
+                }
+                Some((entry.as_ref(), score))
+            })
+            .collect();
+
+        matches.sort_by(|(entry_a, score_a), (entry_b, score_b)| {
+            score_b
+                .cmp(score_a)
+                .then_with(|| entry_a.name.cmp(&entry_b.name))
+        });
+        matches.truncate(max_results);
+
+        matches
+            .into_iter()
+            .map(|(entry, score)| {
+                let mut result = self.entry_result(entry, score);
+                result.title = result.command.clone();
+                result.with_subtitle(entry.name.clone())
+            })
+            .collect()
+    }
+}
+
+/// Name of the program an `Exec` line starts, skipping `env` and its assignments
+fn exec_program(exec: &str) -> Option<&str> {
+    let program = exec
+        .split_whitespace()
+        .map(|token| token.trim_matches('"'))
+        .find(|token| *token != "env" && !token.contains('='))?;
+    program.rsplit('/').next()
+}
+
+/// Bonus for command-mode matches that name the program exactly
+const EXACT_COMMAND_BONUS: i64 = 1000;
+
+/// Base score for pinned items; each pin steps down by its position to keep the user order
+const PINNED_BASE_SCORE: i64 = 2_000_000;
+
+/// Base score for the recently launched rows of the empty query, between pins
+/// and the most used apps; each row steps down to keep the launch order
+const RECENT_BASE_SCORE: i64 = 1_000_000;
+
+/// Number of recently launched apps listed first on the empty query
+const RECENT_LAUNCH_ROWS: usize = 3;
+
+/// Number of most used apps whose icons are resolved when the window is shown
+const WARM_UP_ICONS: usize = 12;
+
+/// Score for "did you mean" suggestions: above fallbacks, below any real match
+const SUGGESTION_SCORE: i64 = 100;
+
+/// Base score for category listings so they outrank generic fallback results
+const CATEGORY_BASE_SCORE: i64 = 10_000;
+
+/// Main categories from the freedesktop menu specification
+const MAIN_CATEGORIES: [&str; 13] = [
+    "AudioVideo",
+    "Audio",
+    "Video",
+    "Development",
+    "Education",
+    "Game",
+    "Graphics",
+    "Network",
+    "Office",
+    "Science",
+    "Settings",
+    "System",
+    "Utility",
+];
+
+/// Extract the category name from `category:<name>` or `@cat <name>` queries
+fn parse_category_filter(query: &str) -> Option<&str> {
+    let rest = if let Some(rest) = query.strip_prefix("@cat") {
+        rest
+    } else if query
+        .get(..9)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("category:"))
+    {
+        &query[9..]
+    } else {
+        return None;
+    };
+
+    Some(rest.trim())
+}
+
+/// Map a user-typed category (case-insensitive, with aliases and prefixes) to a category name
+fn resolve_category(input: &str) -> Option<String> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+
+    let alias = match input.as_str() {
+        "audio" | "video" | "media" | "multimedia" | "music" => Some("AudioVideo"),
+        "dev" | "code" | "programming" => Some("Development"),
+        "games" | "gaming" => Some("Game"),
+        "internet" | "web" | "net" => Some("Network"),
+        "utilities" | "utils" | "accessories" => Some("Utility"),
+        "preferences" | "config" => Some("Settings"),
+        _ => None,
+    };
+    if let Some(alias) = alias {
+        return Some(alias.to_string());
+    }
+
+    // Exact or prefix match against the main categories ("devel" -> Development)
+    if let Some(main) = MAIN_CATEGORIES
+        .iter()
+        .find(|c| c.to_lowercase() == input)
+        .or_else(|| {
+            MAIN_CATEGORIES
+                .iter()
+                .find(|c| c.to_lowercase().starts_with(&input))
+        })
+    {
+        return Some(main.to_string());
+    }
+
+    // Additional categories (e.g. "WebBrowser") are matched verbatim
+    Some(input)
 }
 
 impl Plugin for ApplicationsPlugin {
@@ -128,13 +532,15 @@ impl Plugin for ApplicationsPlugin {
     }
 
     fn command_prefixes(&self) -> Vec<&str> {
-        vec!["@app"]
+        vec!["@app", "@cat", "@bin"]
     }
 
     fn should_handle(&self, query: &str) -> bool {
-        // Don't interfere with other @ commands (unless it's @app)
+        // Don't interfere with other @ commands (unless it's @app, @cat or @bin)
         if query.starts_with('@') {
-            return query.starts_with("@app");
+            return query.starts_with("@app")
+                || query.starts_with("@cat")
+                || query.starts_with("@bin");
         }
 
         // Applications plugin handles all non-@ queries (fallback)
@@ -142,105 +548,62 @@ impl Plugin for ApplicationsPlugin {
     }
 
     fn search(&self, query: &str, context: &PluginContext) -> Result<Vec<PluginResult>> {
-        let query_lower = query.to_lowercase();
+        // Category filter: "category:network" or "@cat audio"
+        if let Some(category) = parse_category_filter(query) {
+            return Ok(self.search_category(category, context.max_results));
+        }
 
-        // If empty query, return most used apps
-        if query.is_empty() {
-            let mut results: Vec<_> = self.entries.iter().cloned().collect();
-
-            let tracker_opt = &self.usage_tracker;
-            let pins_opt = &self.pins;
-
-            // Sort by pinned first, then usage score, then name (stable across runs)
-            results.sort_by(|a, b| {
-                let a_path = a.path.to_string_lossy().to_string();
-                let b_path = b.path.to_string_lossy().to_string();
-                let a_pinned = pins_opt
-                    .as_ref()
-                    .map(|p| p.is_pinned(&a_path))
-                    .unwrap_or(false);
-                let b_pinned = pins_opt
-                    .as_ref()
-                    .map(|p| p.is_pinned(&b_path))
-                    .unwrap_or(false);
-
-                b_pinned
-                    .cmp(&a_pinned)
-                    .then_with(|| {
-                        if let Some(tracker) = tracker_opt {
-                            let score_a = tracker.get_score(&a_path);
-                            let score_b = tracker.get_score(&b_path);
-                            score_b
-                                .partial_cmp(&score_a)
-                                .unwrap_or(std::cmp::Ordering::Equal)
-                        } else {
-                            std::cmp::Ordering::Equal
-                        }
-                    })
-                    .then_with(|| a.name.cmp(&b.name))
-            });
+        // Command mode: "@bin nvim" matches the program an app runs, not its name
+        if let Some(command) = query
+            .strip_prefix("@bin")
+            .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        {
+            return Ok(self.search_commands(command.trim(), context.max_results));
+        }
 
-            // Encode sort into score so global manager sort preserves ordering
-            let mapped: Vec<PluginResult> = results
-                .into_iter()
-                .take(context.max_results)
-                .map(|entry| {
-                    let entry = entry.as_ref();
-                    let path = entry.path.to_string_lossy().to_string();
-                    let pinned = pins_opt
-                        .as_ref()
-                        .map(|p| p.is_pinned(&path))
-                        .unwrap_or(false);
-                    let usage = tracker_opt
-                        .as_ref()
-                        .map(|t| t.get_score(&path))
-                        .unwrap_or(0.0);
-                    // Large boost for pinned to ensure they appear first globally
-                    let pin_boost: i64 = if pinned { 1_000_000 } else { 0 };
-                    // Scale usage to i64; usage is typically small (<10)
-                    let usage_points: i64 = (usage * 1000.0).round() as i64;
-                    let score = pin_boost + usage_points;
-
-                    // Resolve icon with category fallback
-                    let icon_path = resolve_icon_with_category_fallback(
-                        entry.icon.as_deref(),
-                        &entry.categories,
-                    );
-
-                    let mut result = PluginResult::new(
-                        entry.name.clone(),
-                        entry.exec.clone(),
-                        self.name().to_string(),
-                    )
-                    .with_subtitle(entry.generic_name.clone().unwrap_or_default())
-                    .with_icon(icon_path.to_string_lossy().to_string())
-                    .with_terminal(entry.terminal)
-                    .with_desktop_path(path)
-                    .with_score(score);
-
-                    // Add terminal badge for terminal apps
-                    if entry.terminal {
-                        result = result.with_badge_icon("utilities-terminal-symbolic".to_string());
-                    }
+        // 'term: literal substring matches on the name only, no fuzzy scoring
+        let exact_term = exact_match_term(query);
+        let query_lower = exact_term.clone().unwrap_or_else(|| query.to_lowercase());
 
-                    result
-                })
+        // If empty query, return pins, then the last few launched apps, then the
+        // most used ones
+        if query.is_empty() {
+            let entries: Vec<_> = self.entries.load().iter().cloned().collect();
+            let (recent, others) = self.split_recent(entries);
+            let mut results: Vec<_> = recent
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| self.entry_result(entry, RECENT_BASE_SCORE - i as i64))
                 .collect();
-
-            return Ok(mapped);
+            results.extend(self.rank_by_usage(others, context.max_results));
+            results.extend(self.pinned_command_results());
+            results.sort_by_key(|r| std::cmp::Reverse(r.score));
+            results.truncate(context.max_results);
+            return Ok(results);
         }
 
-        // Score entries using fuzzy matching + usage boost
-        let mut results: Vec<(SharedDesktopEntry, f64)> = self
-            .entries
-            .iter()
-            .filter_map(|entry| {
-                let fuzzy_score = self.calculate_fuzzy_score(entry.as_ref(), &query_lower);
+        // Score entries using fuzzy matching + usage boost, skipping entries that
+        // lack some query character entirely
+        let query_mask = SearchKeys::char_mask(&query_lower);
+        let min_score = if exact_term.is_some() {
+            0
+        } else {
+            self.thresholds.for_query(&query_lower)
+        };
+        let entries = self.entries.load();
+        let mut results: Vec<(SharedDesktopEntry, f64)> = entries
+            .iter_with_keys()
+            .filter(|(_, keys)| keys.may_match(query_mask))
+            .filter_map(|(entry, keys)| {
+                let fuzzy_score = match &exact_term {
+                    Some(term) => exact_match_score(&keys.name, term).unwrap_or(0),
+                    None => self.calculate_fuzzy_score(entry, keys, &query_lower),
+                };
 
-                if fuzzy_score > 0 {
+                if fuzzy_score > min_score {
                     let mut final_score = if let Some(tracker) = &self.usage_tracker {
                         let usage_score = tracker.get_score(&entry.path.to_string_lossy());
-                        fuzzy_score as f64 * (1.0 + usage_score * 0.1)
+                        fuzzy_score as f64 * usage_boost(usage_score)
                     } else {
                         fuzzy_score as f64
                     };
@@ -260,47 +623,459 @@ impl Plugin for ApplicationsPlugin {
             })
             .collect();
 
+        // Nothing matched: suggest the closest name for a misspelled query
+        if results.is_empty() && !query.starts_with('@') && exact_term.is_none() {
+            return Ok(did_you_mean(entries.iter_with_keys(), &query_lower)
+                .map(|entry| {
+                    let name = entry.name.clone();
+                    self.entry_result(&entry, SUGGESTION_SCORE)
+                        .with_subtitle(format!("Did you mean \"{}\"?", name))
+                })
+                .into_iter()
+                .collect());
+        }
+
         // Sort by score
         results.sort_by(|(entry_a, score_a), (entry_b, score_b)| {
             score_b
                 .partial_cmp(score_a)
                 .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| entry_a.name.cmp(&entry_b.name))
+                .then_with(|| {
+                    self.order
+                        .compare(self.usage_tracker.as_ref(), entry_a, entry_b)
+                })
         });
 
+        results.truncate(context.max_results);
+        let entries: Vec<SharedDesktopEntry> =
+            results.iter().map(|(entry, _)| entry.clone()).collect();
+
         // Convert to PluginResult
-        Ok(results
+        let results = results
             .into_iter()
-            .take(context.max_results)
-            .map(|(entry, score)| {
-                let entry = entry.as_ref();
-
-                // Resolve icon with category fallback
-                let icon_path =
-                    resolve_icon_with_category_fallback(entry.icon.as_deref(), &entry.categories);
-
-                let mut result = PluginResult::new(
-                    entry.name.clone(),
-                    entry.exec.clone(),
-                    self.name().to_string(),
-                )
-                .with_subtitle(entry.generic_name.clone().unwrap_or_default())
-                .with_icon(icon_path.to_string_lossy().to_string())
-                .with_terminal(entry.terminal)
-                .with_desktop_path(entry.path.to_string_lossy().to_string())
-                .with_score(score as i64);
-
-                // Add terminal badge for terminal apps
-                if entry.terminal {
-                    result = result.with_badge_icon("utilities-terminal-symbolic".to_string());
-                }
+            .map(|(entry, score)| self.entry_result(&entry, score as i64))
+            .collect();
 
-                result
-            })
-            .collect())
+        Ok(self.add_focus_results(results, &entries))
     }
 
     fn priority(&self) -> i32 {
         1000 // Highest priority - main functionality
     }
+
+    /// Resolve the icons of the most used apps so the empty query renders at once
+    fn warm_up(&self) {
+        let Some(tracker) = &self.usage_tracker else {
+            return;
+        };
+        let top = tracker.top(WARM_UP_ICONS);
+        let entries: Vec<SharedDesktopEntry> = self
+            .entries
+            .load()
+            .iter()
+            .filter(|entry| top.contains(&entry.path.to_string_lossy().into_owned()))
+            .cloned()
+            .collect();
+        if entries.is_empty() {
+            return;
+        }
+
+        std::thread::spawn(move || {
+            for entry in entries {
+                resolve_icon_with_category_fallback(entry.icon.as_deref(), &entry.categories);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::path::PathBuf;
+
+    fn create_test_entry(name: &str, categories: &[&str]) -> DesktopEntry {
+        DesktopEntry {
+            name: name.to_string(),
+            generic_name: None,
+            comment: None,
+            exec: name.to_lowercase(),
+            icon: None,
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+            keywords: vec![],
+            terminal: false,
+            path: PathBuf::from(format!("/{}.desktop", name)),
+            no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
+            actions: vec![],
+        }
+    }
+
+    fn create_test_plugin() -> ApplicationsPlugin {
+        ApplicationsPlugin::new(DesktopEntryArena::from_vec(vec![
+            create_test_entry("Code", &["Development", "IDE"]),
+            create_test_entry("Firefox", &["Network", "WebBrowser"]),
+            create_test_entry("Rhythmbox", &["AudioVideo", "Audio"]),
+            create_test_entry("Builder", &["Development"]),
+        ]))
+    }
+
+    #[test]
+    fn test_category_filter_returns_only_matching_entries() {
+        let plugin = create_test_plugin();
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = plugin.search("category:development", &ctx).unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["Builder", "Code"]);
+    }
+
+    #[test]
+    fn test_category_aliases_and_prefix() {
+        let plugin = create_test_plugin();
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = plugin.search("@cat audio", &ctx).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Rhythmbox");
+
+        let results = plugin.search("Category:NET", &ctx).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Firefox");
+
+        assert!(plugin.should_handle("@cat dev"));
+        assert!(plugin.search("@cat", &ctx).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_running_app_gets_focus_result() {
+        use crate::plugins::windows::{Compositor, OpenWindow};
+
+        let window = OpenWindow {
+            class: "firefox".to_string(),
+            title: "Mozilla Firefox".to_string(),
+            id: "0x42".to_string(),
+        };
+        let plugin = create_test_plugin().with_window_list(Arc::new(WindowList::with_windows(
+            Compositor::Hyprland,
+            vec![window],
+        )));
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = plugin.search("fire", &ctx).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "Firefox");
+        assert_eq!(results[1].title, "Switch to Firefox");
+        assert_eq!(results[1].subtitle.as_deref(), Some("Mozilla Firefox"));
+        assert_eq!(
+            results[1].command,
+            "hyprctl dispatch focuswindow address:0x42"
+        );
+        assert_eq!(results[1].score, results[0].score - 1);
+        assert_eq!(results[0].badges, vec![ResultBadge::Running]);
+
+        // Apps without an open window are unchanged
+        let results = plugin.search("code", &ctx).unwrap();
+        assert!(results.iter().all(|r| !r.title.starts_with("Switch to")));
+    }
+
+    #[test]
+    fn test_flatpak_apps_get_badge() {
+        let mut exported = create_test_entry("Builder", &[]);
+        exported.path =
+            PathBuf::from("/var/lib/flatpak/exports/share/applications/org.gnome.Builder.desktop");
+        let mut run = create_test_entry("Fractal", &[]);
+        run.exec = "/usr/bin/flatpak run --branch=stable org.gnome.Fractal".to_string();
+        let mut wrapped = create_test_entry("Code", &[]);
+        wrapped.exec = "env flatpak-spawn --host code".to_string();
+        let plugin =
+            ApplicationsPlugin::new(DesktopEntryArena::from_vec(vec![exported, run, wrapped]));
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let badges = |query: &str| plugin.search(query, &ctx).unwrap().remove(0).badges;
+        assert_eq!(badges("builder"), vec![ResultBadge::Flatpak]);
+        assert_eq!(badges("fractal"), vec![ResultBadge::Flatpak]);
+        assert!(badges("code").is_empty());
+    }
+
+    #[test]
+    fn test_misspelled_query_suggests_closest_app() {
+        let plugin = create_test_plugin();
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = plugin.search("fierfix", &ctx).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Firefox");
+        assert_eq!(
+            results[0].subtitle.as_deref(),
+            Some("Did you mean \"Firefox\"?")
+        );
+        assert_eq!(results[0].score, SUGGESTION_SCORE);
+
+        assert!(plugin.search("qwzxvb", &ctx).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_comment_preferred_as_subtitle() {
+        let mut firefox = create_test_entry("Firefox", &["Network"]);
+        firefox.generic_name = Some("Web Browser".to_string());
+        firefox.comment = Some("Browse the World Wide Web".to_string());
+        let mut files = create_test_entry("Files", &[]);
+        files.generic_name = Some("File Manager".to_string());
+        let plugin = ApplicationsPlugin::new(DesktopEntryArena::from_vec(vec![
+            firefox,
+            files,
+            create_test_entry("Code", &[]),
+        ]));
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let subtitle = |query: &str| plugin.search(query, &ctx).unwrap()[0].subtitle.clone();
+        assert_eq!(
+            subtitle("firefox").as_deref(),
+            Some("Browse the World Wide Web")
+        );
+        // No comment: the generic name, then nothing
+        assert_eq!(subtitle("files").as_deref(), Some("File Manager"));
+        assert_eq!(subtitle("code").as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_command_mode_matches_exec_name() {
+        let mut neovim = create_test_entry("Neovim", &["Development"]);
+        neovim.exec = "nvim %F".to_string();
+        neovim.terminal = true;
+        let mut flatpak = create_test_entry("Builder", &[]);
+        flatpak.exec = "env GTK_THEME=Adwaita /usr/bin/gnome-builder".to_string();
+        let plugin = ApplicationsPlugin::new(DesktopEntryArena::from_vec(vec![
+            neovim,
+            flatpak,
+            create_test_entry("Firefox", &[]),
+        ]));
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        assert!(plugin.should_handle("@bin nvim"));
+        let results = plugin.search("@bin nvim", &ctx).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "nvim");
+        assert_eq!(results[0].subtitle.as_deref(), Some("Neovim"));
+        assert!(results[0].terminal);
+
+        // Only the program counts: names and env assignments don't match
+        assert!(plugin.search("@bin neovim", &ctx).unwrap().is_empty());
+        assert!(plugin.search("@bin adwaita", &ctx).unwrap().is_empty());
+        let results = plugin.search("@bin gbuild", &ctx).unwrap();
+        assert_eq!(results[0].subtitle.as_deref(), Some("Builder"));
+        assert!(plugin.search("@bin", &ctx).unwrap().is_empty());
+        // "$" belongs to the shell plugin
+        assert!(plugin.search("$nvim", &ctx).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_empty_query_lists_recent_launches_first() {
+        use crate::usage::AppUsage;
+        use std::collections::HashMap;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let usage = |launch_count, age| AppUsage {
+            launch_count,
+            last_used: now - age,
+            first_used: now - 60 * 86400,
+            launch_history: Vec::new(),
+        };
+        let tracker = UsageTracker::from_usage(HashMap::from([
+            ("/Builder.desktop".to_string(), usage(1, 10)),
+            ("/Code.desktop".to_string(), usage(2, 60)),
+            ("/Rhythmbox.desktop".to_string(), usage(1, 120)),
+            ("/Firefox.desktop".to_string(), usage(50, 3600)),
+            ("/Terminal.desktop".to_string(), usage(1, 30 * 86400)),
+        ]));
+        let plugin = ApplicationsPlugin::with_usage_tracking(
+            DesktopEntryArena::from_vec(
+                ["Code", "Firefox", "Rhythmbox", "Builder", "Terminal"]
+                    .iter()
+                    .map(|name| create_test_entry(name, &[]))
+                    .collect(),
+            ),
+            tracker,
+        );
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        // Last launched first, then by frecency
+        let results = plugin.search("", &ctx).unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Builder", "Code", "Rhythmbox", "Firefox", "Terminal"]
+        );
+    }
+
+    #[test]
+    fn test_order_strategy_applies_to_empty_query_and_ties() {
+        use crate::usage::AppUsage;
+        use std::collections::HashMap;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let usage = |launch_count, age| AppUsage {
+            launch_count,
+            last_used: now - age,
+            first_used: now - 60 * 86400,
+            launch_history: Vec::new(),
+        };
+        // The three latest launches fill the recent rows; of the rest Zeal is used
+        // most and Mate was launched but has no usage score left
+        let tracker = UsageTracker::from_usage(HashMap::from([
+            ("/Builder.desktop".to_string(), usage(1, 10)),
+            ("/Code.desktop".to_string(), usage(1, 20)),
+            ("/Rhythmbox.desktop".to_string(), usage(1, 30)),
+            ("/Zeal.desktop".to_string(), usage(20, 20 * 86400)),
+            ("/Mate.desktop".to_string(), usage(0, 20 * 86400)),
+        ]));
+        let arena = DesktopEntryArena::from_vec(
+            [
+                "Builder",
+                "Code",
+                "Kate",
+                "Mate",
+                "Mousepad",
+                "Rhythmbox",
+                "Zeal",
+            ]
+            .iter()
+            .map(|name| create_test_entry(name, &[]))
+            .collect(),
+        );
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+        let titles = |order: OrderStrategy, query: &str| -> Vec<String> {
+            ApplicationsPlugin::with_usage_tracking(arena.clone(), tracker.clone())
+                .with_order_strategy(order)
+                .search(query, &ctx)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.title)
+                .collect()
+        };
+
+        assert_eq!(
+            titles(OrderStrategy::Score, "")[3..],
+            ["Zeal", "Kate", "Mate", "Mousepad"]
+        );
+        assert_eq!(
+            titles(OrderStrategy::Alpha, "")[3..],
+            ["Kate", "Mate", "Mousepad", "Zeal"]
+        );
+        assert_eq!(
+            titles(OrderStrategy::Recent, "")[3..],
+            ["Mate", "Zeal", "Kate", "Mousepad"]
+        );
+        // "'ate" scores Kate and Mate the same, so only the tie order differs
+        assert_eq!(titles(OrderStrategy::Score, "'ate"), ["Kate", "Mate"]);
+        assert_eq!(titles(OrderStrategy::Recent, "'ate"), ["Mate", "Kate"]);
+    }
+
+    #[test]
+    fn test_resolve_category() {
+        assert_eq!(resolve_category("devel").as_deref(), Some("Development"));
+        assert_eq!(resolve_category("GAMES").as_deref(), Some("Game"));
+        assert_eq!(
+            resolve_category("webbrowser").as_deref(),
+            Some("webbrowser")
+        );
+        assert_eq!(resolve_category("  "), None);
+    }
+
+    #[test]
+    fn test_category_filter_with_multibyte_query() {
+        assert_eq!(parse_category_filter("Category: games"), Some("games"));
+        // Byte 9 falls inside "í"; this must not panic
+        assert_eq!(parse_category_filter("categoría"), None);
+        assert_eq!(parse_category_filter("日本語のアプリ"), None);
+
+        let plugin = ApplicationsPlugin::new(DesktopEntryArena::from_vec(vec![]));
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+        assert!(plugin.search("categorí", &ctx).is_ok());
+    }
+
+    #[test]
+    fn test_score_thresholds_drop_weak_matches() {
+        let mut terminal = create_test_entry("GNOME Terminal", &[]);
+        terminal.keywords = vec!["shell".to_string()];
+        let arena = DesktopEntryArena::from_vec(vec![terminal]);
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        // "shell" only fuzzy matches the keyword, scoring 111
+        let results = ApplicationsPlugin::new(arena.clone())
+            .search("shell", &ctx)
+            .unwrap();
+        assert_eq!(results[0].title, "GNOME Terminal");
+        assert!(results[0].score > SUGGESTION_SCORE);
+
+        let strict = ApplicationsPlugin::new(arena).with_score_thresholds(ScoreThresholds {
+            short: 500,
+            long: 200,
+            short_query_len: 2,
+        });
+        assert!(strict
+            .search("shell", &ctx)
+            .unwrap()
+            .iter()
+            .all(|r| r.score == SUGGESTION_SCORE));
+        // Exact-term queries aren't fuzzy scored, so no threshold applies
+        assert_eq!(strict.search("'term", &ctx).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_precomputed_keys_keep_scores() {
+        // Scores recorded before entry fields were lowercased up front
+        let mut code = create_test_entry("Visual Studio Code", &["Development", "IDE"]);
+        code.generic_name = Some("Text Editor".to_string());
+        code.keywords = vec!["VSCode".to_string(), "editor".to_string()];
+        let mut firefox = create_test_entry("Firefox", &[]);
+        firefox.generic_name = Some("Web Browser".to_string());
+        firefox.keywords = vec!["Internet".to_string(), "WWW".to_string()];
+        let mut terminal = create_test_entry("GNOME Terminal", &[]);
+        terminal.generic_name = Some("Terminal".to_string());
+        terminal.keywords = vec!["shell".to_string(), "CLI".to_string()];
+
+        let expected: [(&str, [i64; 3]); 8] = [
+            ("code", [10052, 0, 0]),
+            ("vsc", [165, 0, 0]),
+            ("firefox", [0, 25125, 0]),
+            ("www", [0, 65, 0]),
+            ("term", [0, 0, 10066]),
+            ("shell", [0, 0, 111]),
+            ("develop", [74, 0, 0]),
+            ("editor", [5000, 0, 0]),
+        ];
+
+        let arena = DesktopEntryArena::from_vec(vec![code, firefox, terminal]);
+        let plugin = ApplicationsPlugin::new(arena.clone());
+        for (query, scores) in expected {
+            let actual: Vec<i64> = arena
+                .iter_with_keys()
+                .map(|(entry, keys)| plugin.calculate_fuzzy_score(entry, keys, query))
+                .collect();
+            assert_eq!(actual, scores, "scores changed for {:?}", query);
+        }
+    }
 }
@@ -1,8 +1,13 @@
 use super::browser_index::BrowserIndex;
-use super::traits::{KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult};
+use super::traits::{
+    KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult, ResultBadge,
+};
+use crate::config::BrowserHistoryConfig;
+use crate::utils::open_command_line;
 use anyhow::Result;
 use rusqlite::{Connection, OpenFlags};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
@@ -14,8 +19,17 @@ pub struct BrowserHistoryPlugin {
     enabled: bool,
     cache: Arc<std::sync::Mutex<CachedHistory>>,
     index: Option<Arc<BrowserIndex>>,
+    /// Minimum query length for global (unprefixed) search
+    min_query_length: usize,
+    /// Most recent entries read from each browser and kept after merging
+    max_index_entries: usize,
+    /// Set while a `warm_up` refresh is in flight
+    warming: Arc<AtomicBool>,
 }
 
+/// Default minimum query length for global search (keeps keystroke lag low)
+const DEFAULT_MIN_QUERY_LENGTH: usize = 4;
+
 #[derive(Debug)]
 struct CachedHistory {
     entries: Vec<HistoryEntry>,
@@ -35,7 +49,7 @@ pub struct HistoryEntry {
 }
 
 impl BrowserHistoryPlugin {
-    pub fn new() -> Self {
+    pub fn new(config: &BrowserHistoryConfig) -> Self {
         let index = match BrowserIndex::new() {
             Ok(idx) => {
                 debug!("Browser index initialized");
@@ -52,12 +66,21 @@ impl BrowserHistoryPlugin {
             cache: Arc::new(std::sync::Mutex::new(CachedHistory {
                 entries: Vec::new(),
                 last_refresh: UNIX_EPOCH,
-                ttl: Duration::from_secs(300), // 5 minutes
+                ttl: Duration::from_secs(config.cache_ttl_secs),
             })),
             index,
+            min_query_length: DEFAULT_MIN_QUERY_LENGTH,
+            max_index_entries: config.max_index_entries,
+            warming: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Override the minimum query length for global search
+    pub fn with_min_query_length(mut self, min_query_length: usize) -> Self {
+        self.min_query_length = min_query_length;
+        self
+    }
+
     /// Get reference to browser index for background updates
     pub fn get_index(&self) -> Option<Arc<BrowserIndex>> {
         self.index.clone()
@@ -137,7 +160,7 @@ impl BrowserHistoryPlugin {
 
         let mut deduplicated: Vec<_> = seen.into_values().collect();
         deduplicated.sort_by(|a, b| b.last_visit.cmp(&a.last_visit));
-        deduplicated.truncate(100); // Keep top 100 most recent
+        deduplicated.truncate(self.max_index_entries); // Keep the most recent
 
         debug!(
             "Fetched {} unique browser history entries",
@@ -147,33 +170,28 @@ impl BrowserHistoryPlugin {
     }
 
     fn fetch_chrome_history(&self) -> Option<Vec<HistoryEntry>> {
-        let home = dirs::home_dir()?;
-        let history_path = home.join(".config/google-chrome/Default/History");
+        let history_path = dirs::config_dir()?.join("google-chrome/Default/History");
         self.read_chromium_history(&history_path, "Chrome")
     }
 
     fn fetch_brave_history(&self) -> Option<Vec<HistoryEntry>> {
-        let home = dirs::home_dir()?;
-        let history_path = home.join(".config/BraveSoftware/Brave-Browser/Default/History");
+        let history_path = dirs::config_dir()?.join("BraveSoftware/Brave-Browser/Default/History");
         self.read_chromium_history(&history_path, "Brave")
     }
 
     fn fetch_edge_history(&self) -> Option<Vec<HistoryEntry>> {
-        let home = dirs::home_dir()?;
-        let history_path = home.join(".config/microsoft-edge/Default/History");
+        let history_path = dirs::config_dir()?.join("microsoft-edge/Default/History");
         self.read_chromium_history(&history_path, "Edge")
     }
 
     fn fetch_vivaldi_history(&self) -> Option<Vec<HistoryEntry>> {
-        let home = dirs::home_dir()?;
-        let history_path = home.join(".config/vivaldi/Default/History");
+        let history_path = dirs::config_dir()?.join("vivaldi/Default/History");
         self.read_chromium_history(&history_path, "Vivaldi")
     }
 
     fn fetch_opera_history(&self) -> Option<Vec<HistoryEntry>> {
-        let home = dirs::home_dir()?;
         // Opera uses different path structure
-        let history_path = home.join(".config/opera/History");
+        let history_path = dirs::config_dir()?.join("opera/History");
         self.read_chromium_history(&history_path, "Opera")
     }
 
@@ -220,12 +238,12 @@ impl BrowserHistoryPlugin {
                 "SELECT url, title, visit_count, last_visit_time 
              FROM urls 
              ORDER BY last_visit_time DESC 
-             LIMIT 100",
+             LIMIT ?1",
             )
             .ok()?;
 
         let entries = stmt
-            .query_map([], |row| {
+            .query_map([self.max_index_entries as i64], |row| {
                 let url: String = row.get(0)?;
                 let title: String = row.get(1).unwrap_or_else(|_| url.clone());
                 let visit_count: i64 = row.get(2).unwrap_or(0);
@@ -262,13 +280,10 @@ impl BrowserHistoryPlugin {
     }
 
     fn fetch_firefox_history(&self) -> Option<Vec<HistoryEntry>> {
-        let home = dirs::home_dir()?;
-        let firefox_dir = home.join(".mozilla/firefox");
-
-        if !firefox_dir.exists() {
+        let Some(firefox_dir) = firefox_dir() else {
             debug!("Firefox directory not found");
             return None;
-        }
+        };
 
         // Find default profile
         let profile = std::fs::read_dir(&firefox_dir)
@@ -311,12 +326,12 @@ impl BrowserHistoryPlugin {
              FROM moz_places 
              WHERE last_visit_date IS NOT NULL 
              ORDER BY last_visit_date DESC 
-             LIMIT 100",
+             LIMIT ?1",
             )
             .ok()?;
 
         let entries = stmt
-            .query_map([], |row| {
+            .query_map([self.max_index_entries as i64], |row| {
                 let url: String = row.get(0)?;
                 let title: Option<String> = row.get(1).ok();
                 let visit_count: i64 = row.get(2).unwrap_or(0);
@@ -372,9 +387,51 @@ impl BrowserHistoryPlugin {
             .collect()
     }
 
+    /// Result row for a history entry or bookmark, scored by recency and visits
+    fn entry_result(&self, entry: &HistoryEntry, now: i64) -> PluginResult {
+        // Boost bookmarks slightly
+        let age_hours = ((now - entry.last_visit) / 3600).max(1);
+        let recency_score = 1000 / age_hours; // More recent = higher score
+        let popularity_score = entry.visit_count.min(100);
+        let bookmark_boost = if entry.is_bookmark { 50 } else { 0 };
+        let score = recency_score + popularity_score + bookmark_boost;
+
+        // Bookmarks are marked by their badge
+        let subtitle = if entry.is_bookmark {
+            entry.domain.clone()
+        } else if entry.domain != entry.url {
+            format!("{} • {} visits", entry.domain, entry.visit_count)
+        } else {
+            format!("{} visits", entry.visit_count)
+        };
+
+        // Use favicon if available, otherwise default icon
+        let icon = if let Some(ref favicon_path) = entry.favicon_path {
+            favicon_path.to_string_lossy().to_string()
+        } else {
+            "web-browser".to_string()
+        };
+
+        let result = PluginResult::new(
+            entry.title.clone(),
+            self.build_url_open_command(&entry.url),
+            self.name().to_string(),
+        )
+        .with_subtitle(subtitle)
+        .with_icon(icon)
+        .with_score(score)
+        .with_url(entry.url.clone());
+
+        if entry.is_bookmark {
+            result.with_badge(ResultBadge::Bookmark)
+        } else {
+            result
+        }
+    }
+
     fn build_url_open_command(&self, url: &str) -> String {
-        // Use xdg-open or equivalent to open URL in default browser
-        format!("xdg-open {}", shell_escape(url))
+        // `[general] open_command`, xdg-open by default
+        open_command_line(url)
     }
 
     fn fetch_all_bookmarks(&self) -> Vec<HistoryEntry> {
@@ -474,12 +531,7 @@ impl BrowserHistoryPlugin {
     }
 
     fn fetch_firefox_bookmarks(&self) -> Option<Vec<HistoryEntry>> {
-        let home = dirs::home_dir()?;
-        let firefox_dir = home.join(".mozilla/firefox");
-
-        if !firefox_dir.exists() {
-            return None;
-        }
+        let firefox_dir = firefox_dir()?;
 
         let profile = std::fs::read_dir(&firefox_dir)
             .ok()?
@@ -616,10 +668,9 @@ impl Plugin for BrowserHistoryPlugin {
             return true; // Always handle prefixed queries
         }
 
-        // For global search: require minimum 4 characters to reduce keystroke lag
-        // Increased from 3 to 4 for better performance when typing fast
+        // For global search: require a minimum length (default 4) to reduce keystroke lag
         let trimmed = query.trim();
-        !trimmed.starts_with("@") && trimmed.len() >= 4
+        !trimmed.starts_with("@") && trimmed.chars().count() >= self.min_query_length
     }
 
     fn search(&self, query: &str, context: &PluginContext) -> Result<Vec<PluginResult>> {
@@ -635,7 +686,7 @@ impl Plugin for BrowserHistoryPlugin {
         };
 
         // Quick exit for short queries in global search (performance)
-        if !has_prefix && filter.len() < 4 {
+        if !has_prefix && filter.chars().count() < self.min_query_length {
             return Ok(Vec::new());
         }
 
@@ -644,9 +695,13 @@ impl Plugin for BrowserHistoryPlugin {
             filter, has_prefix
         );
 
+        // Global search shows only a few entries: the manager lowers the limit to
+        // `[plugins.max_results]`, else `global_max_results` (default 2)
+        let limit = context.max_results;
+
         // Try fast path with persistent index first
         let entries = if let Some(ref index) = self.index {
-            match index.search(filter, context.max_results) {
+            match index.search(filter, limit) {
                 Ok(indexed) => {
                     debug!("Retrieved {} results from browser index", indexed.len());
                     indexed.into_iter().map(|e| e.into()).collect()
@@ -656,67 +711,25 @@ impl Plugin for BrowserHistoryPlugin {
                         "Failed to search browser index: {}, falling back to live fetch",
                         e
                     );
-                    self.search_entries(filter, context.max_results)
+                    self.search_entries(filter, limit)
                 }
             }
         } else {
             warn!("No browser index available, using in-memory cache");
-            self.search_entries(filter, context.max_results)
+            self.search_entries(filter, limit)
         };
 
-        // For global search (no prefix), limit results to avoid overwhelming
-        let max = if has_prefix {
-            context.max_results
-        } else {
-            2 // Only show top 2 browser results in global search (reduced from 3 for performance)
-        };
-
-        let entries: Vec<_> = entries.into_iter().take(max).collect();
+        let entries: Vec<_> = entries.into_iter().take(limit).collect();
 
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
 
-        let mut results = Vec::with_capacity(entries.len());
-        for entry in entries {
-            // Score based on recency and visit count
-            // Boost bookmarks slightly
-            let age_hours = ((now - entry.last_visit) / 3600).max(1);
-            let recency_score = 1000 / age_hours; // More recent = higher score
-            let popularity_score = entry.visit_count.min(100);
-            let bookmark_boost = if entry.is_bookmark { 50 } else { 0 };
-            let score = recency_score + popularity_score + bookmark_boost;
-
-            // Build subtitle with bookmark indicator
-            let subtitle = if entry.is_bookmark {
-                format!("★ {} • Bookmarked", entry.domain)
-            } else if entry.domain != entry.url {
-                format!("{} • {} visits", entry.domain, entry.visit_count)
-            } else {
-                format!("{} visits", entry.visit_count)
-            };
-
-            // Use favicon if available, otherwise default icon
-            let icon = if let Some(ref favicon_path) = entry.favicon_path {
-                favicon_path.to_string_lossy().to_string()
-            } else {
-                "web-browser".to_string()
-            };
-
-            let result = PluginResult::new(
-                entry.title.clone(),
-                self.build_url_open_command(&entry.url),
-                self.name().to_string(),
-            )
-            .with_subtitle(subtitle)
-            .with_icon(icon)
-            .with_score(score);
-
-            results.push(result);
-        }
-
-        Ok(results)
+        Ok(entries
+            .iter()
+            .map(|entry| self.entry_result(entry, now))
+            .collect())
     }
 
     fn priority(&self) -> i32 {
@@ -730,6 +743,39 @@ impl Plugin for BrowserHistoryPlugin {
     fn handle_keyboard_event(&self, _event: &KeyboardEvent) -> KeyboardAction {
         KeyboardAction::None
     }
+
+    /// Rebuild a stale index, or refresh the in-memory cache when there is no
+    /// index, so the first history search after showing doesn't read the browsers
+    fn warm_up(&self) {
+        if !self.enabled || self.warming.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let plugin = self.clone();
+        std::thread::spawn(move || {
+            match &plugin.index {
+                Some(index) if index.needs_rebuild() => {
+                    debug!("Browser index is stale, rebuilding on show");
+                    if let Err(e) = index.rebuild_index(plugin.fetch_history()) {
+                        warn!("Failed to refresh browser index: {}", e);
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    plugin.get_cached_or_refresh();
+                }
+            }
+            plugin.warming.store(false, Ordering::Release);
+        });
+    }
+}
+
+/// Firefox profile directory: the XDG location (`$XDG_CONFIG_HOME/mozilla/firefox`)
+/// used by recent releases, else the legacy `~/.mozilla/firefox`
+fn firefox_dir() -> Option<PathBuf> {
+    let xdg = dirs::config_dir().map(|dir| dir.join("mozilla/firefox"));
+    let legacy = dirs::home_dir().map(|home| home.join(".mozilla/firefox"));
+    [xdg, legacy].into_iter().flatten().find(|dir| dir.exists())
 }
 
 fn extract_domain(url: &str) -> String {
@@ -745,22 +791,6 @@ fn extract_domain(url: &str) -> String {
     }
 }
 
-fn shell_escape(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_string();
-    }
-    let mut escaped = String::from("'");
-    for ch in value.chars() {
-        if ch == '\'' {
-            escaped.push_str("'\\''");
-        } else {
-            escaped.push(ch);
-        }
-    }
-    escaped.push('\'');
-    escaped
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -772,9 +802,99 @@ mod tests {
         assert_eq!(extract_domain("example.com"), "example.com");
     }
 
+    #[test]
+    fn test_configured_min_query_length() {
+        let plugin =
+            BrowserHistoryPlugin::new(&BrowserHistoryConfig::default()).with_min_query_length(2);
+        assert!(!plugin.should_handle("g"));
+        assert!(plugin.should_handle("gi"));
+
+        let plugin =
+            BrowserHistoryPlugin::new(&BrowserHistoryConfig::default()).with_min_query_length(6);
+        assert!(!plugin.should_handle("githu"));
+        assert!(plugin.should_handle("github"));
+        assert!(plugin.should_handle("@tabs"));
+
+        // Queries below the minimum return nothing even when searched directly
+        let config = crate::config::Config::default();
+        let ctx = PluginContext::new(10, &config);
+        assert!(plugin.search("githu", &ctx).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_global_max_results_limits_unprefixed_search() {
+        let mut plugin = BrowserHistoryPlugin::new(&BrowserHistoryConfig::default());
+        // Search the in-memory cache, filled as if just fetched
+        plugin.index = None;
+        {
+            let mut cache = plugin.cache.lock().unwrap();
+            cache.last_refresh = SystemTime::now();
+            cache.entries = (0..10)
+                .map(|i| HistoryEntry {
+                    title: format!("GitHub page {}", i),
+                    url: format!("https://github.com/page/{}", i),
+                    domain: "github.com".to_string(),
+                    visit_count: 1,
+                    last_visit: 1_700_000_000 - i,
+                    favicon_path: None,
+                    is_bookmark: false,
+                })
+                .collect();
+        }
+
+        // Global search runs with the context the manager lowers to the plugin's cap
+        let global_search = |config: &crate::config::Config| {
+            let ctx = PluginContext::new(10, config).for_plugin("browser_history");
+            plugin.search("github", &ctx).unwrap().len()
+        };
+        let mut app_config = crate::config::Config::default();
+        app_config.plugins.browser_history.global_max_results = 3;
+        assert_eq!(global_search(&app_config), 3);
+        // A `[plugins.max_results]` override can raise the cap above the default
+        app_config
+            .plugins
+            .max_results
+            .insert("browser_history".to_string(), 4);
+        assert_eq!(global_search(&app_config), 4);
+        app_config.plugins.max_results.clear();
+        app_config.plugins.browser_history.global_max_results = 0;
+        assert_eq!(global_search(&app_config), 0);
+
+        // Explicit @tabs searches are not capped
+        let ctx = PluginContext::new(10, &app_config);
+        assert_eq!(plugin.search("@tabs github", &ctx).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_markdown_link_from_result() {
+        let plugin = BrowserHistoryPlugin::new(&BrowserHistoryConfig::default());
+        let entry = HistoryEntry {
+            title: "Rust [book] (2nd edition)".to_string(),
+            url: "https://en.wikipedia.org/wiki/Rust_(programming_language)".to_string(),
+            domain: "en.wikipedia.org".to_string(),
+            visit_count: 3,
+            last_visit: 1_700_000_000,
+            favicon_path: None,
+            is_bookmark: true,
+        };
+
+        let result = plugin.entry_result(&entry, 1_700_000_000);
+        assert_eq!(result.url.as_deref(), Some(entry.url.as_str()));
+        assert_eq!(
+            result.markdown_link().as_deref(),
+            Some(
+                "[Rust \\[book\\] (2nd edition)](https://en.wikipedia.org/wiki/Rust_%28programming_language%29)"
+            )
+        );
+
+        // Results without a URL have no link
+        let app = PluginResult::new("Firefox".into(), "firefox".into(), "applications".into());
+        assert_eq!(app.markdown_link(), None);
+    }
+
     #[test]
     fn test_should_handle_prefix() {
-        let plugin = BrowserHistoryPlugin::new();
+        let plugin = BrowserHistoryPlugin::new(&BrowserHistoryConfig::default());
         // Handles prefixed queries
         assert!(plugin.should_handle("@tabs foo"));
         assert!(plugin.should_handle("@history bar"));
@@ -793,7 +913,7 @@ mod tests {
 
     #[test]
     fn test_strip_prefix() {
-        let plugin = BrowserHistoryPlugin::new();
+        let plugin = BrowserHistoryPlugin::new(&BrowserHistoryConfig::default());
         assert_eq!(plugin.strip_prefix("@tabs github"), " github");
         assert_eq!(plugin.strip_prefix("@history rust"), " rust");
         assert_eq!(plugin.strip_prefix("plain"), "plain");
@@ -801,7 +921,7 @@ mod tests {
 
     #[test]
     fn test_build_url_command() {
-        let plugin = BrowserHistoryPlugin::new();
+        let plugin = BrowserHistoryPlugin::new(&BrowserHistoryConfig::default());
         let cmd = plugin.build_url_open_command("https://example.com/test");
         assert!(cmd.contains("xdg-open"));
         assert!(cmd.contains("example.com"));
@@ -812,7 +932,7 @@ mod tests {
         use crate::config::ConfigLoader;
         use crate::plugins::traits::{Plugin, PluginContext};
 
-        let plugin = BrowserHistoryPlugin::new();
+        let plugin = BrowserHistoryPlugin::new(&BrowserHistoryConfig::default());
         let config_loader = ConfigLoader::new();
         let context = PluginContext::new(10, config_loader.config());
 
@@ -1,15 +1,244 @@
-use super::traits::{Plugin, PluginContext, PluginResult};
+use super::advanced_calc::CalculationHistory;
+use super::traits::{KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult};
+use crate::desktop::entry::current_numeric_locale;
 use anyhow::Result;
+use evalexpr::{ContextWithMutableFunctions, ContextWithMutableVariables, HashMapContext, Value};
+use gtk4::gdk::Key;
+use std::sync::{Arc, OnceLock};
+
+type MathFn = fn(f64) -> f64;
+
+/// Score of a calculation found inside another query ("firefox 2+2"), low
+/// enough to stay below the results the rest of the query finds
+pub const AMBIENT_SCORE: i64 = 100;
+
+/// Functions available in expressions: (name, signature, description, implementation)
+const FUNCTIONS: &[(&str, &str, &str, MathFn)] = &[
+    ("sqrt", "sqrt(x)", "Square root", f64::sqrt),
+    ("cbrt", "cbrt(x)", "Cube root", f64::cbrt),
+    ("abs", "abs(x)", "Absolute value", f64::abs),
+    ("sin", "sin(x)", "Sine (radians)", f64::sin),
+    ("cos", "cos(x)", "Cosine (radians)", f64::cos),
+    ("tan", "tan(x)", "Tangent (radians)", f64::tan),
+    ("asin", "asin(x)", "Arc sine", f64::asin),
+    ("acos", "acos(x)", "Arc cosine", f64::acos),
+    ("atan", "atan(x)", "Arc tangent", f64::atan),
+    ("ln", "ln(x)", "Natural logarithm", f64::ln),
+    ("log", "log(x)", "Base-10 logarithm", f64::log10),
+    ("log2", "log2(x)", "Base-2 logarithm", f64::log2),
+    ("exp", "exp(x)", "e raised to x", f64::exp),
+    ("floor", "floor(x)", "Round down", f64::floor),
+    ("ceil", "ceil(x)", "Round up", f64::ceil),
+    ("round", "round(x)", "Round to nearest integer", f64::round),
+];
+
+type AggregateFn = fn(&[f64]) -> f64;
+
+/// Functions over a comma-separated list of values, e.g. "avg(1, 2, 3)":
+/// (name, signature, description, implementation); never called with no values
+const AGGREGATES: &[(&str, &str, &str, AggregateFn)] = &[
+    ("sum", "sum(a, b, …)", "Sum of the values", |values| {
+        values.iter().sum()
+    }),
+    ("avg", "avg(a, b, …)", "Average of the values", |values| {
+        values.iter().sum::<f64>() / values.len() as f64
+    }),
+    ("min", "min(a, b, …)", "Smallest of the values", |values| {
+        values.iter().copied().fold(f64::INFINITY, f64::min)
+    }),
+    ("max", "max(a, b, …)", "Largest of the values", |values| {
+        values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+    }),
+];
+
+/// Named constants available in expressions: (name, description, value)
+const CONSTANTS: &[(&str, &str, f64)] = &[
+    (
+        "pi",
+        "π, ratio of circumference to diameter",
+        std::f64::consts::PI,
+    ),
+    ("e", "Euler's number", std::f64::consts::E),
+    ("tau", "τ = 2π", std::f64::consts::TAU),
+];
+
+/// Evaluation context with the functions and constants above registered
+fn math_context() -> &'static HashMapContext {
+    static CONTEXT: OnceLock<HashMapContext> = OnceLock::new();
+    CONTEXT.get_or_init(|| {
+        let mut context = HashMapContext::new();
+        for (name, _, _, func) in FUNCTIONS {
+            let func = *func;
+            context
+                .set_function(
+                    name.to_string(),
+                    evalexpr::Function::new(move |arg| Ok(Value::Float(func(arg.as_number()?)))),
+                )
+                .expect("HashMapContext accepts functions");
+        }
+        for (name, _, _, func) in AGGREGATES {
+            let (name, func) = (*name, *func);
+            context
+                .set_function(
+                    name.to_string(),
+                    evalexpr::Function::new(move |arg| {
+                        let values = aggregate_values(arg)?;
+                        if values.is_empty() {
+                            return Err(evalexpr::EvalexprError::CustomMessage(format!(
+                                "{}() needs at least one value",
+                                name
+                            )));
+                        }
+                        Ok(Value::Float(func(&values)))
+                    }),
+                )
+                .expect("HashMapContext accepts functions");
+        }
+        for (name, _, value) in CONSTANTS {
+            context
+                .set_value(name.to_string(), Value::Float(*value))
+                .expect("HashMapContext accepts variables");
+        }
+        context
+    })
+}
+
+/// Numbers passed to an aggregate function: a tuple for "f(1, 2)", a single
+/// value for "f(1)" and nothing for "f()"
+fn aggregate_values(arg: &Value) -> evalexpr::EvalexprResult<Vec<f64>> {
+    match arg {
+        Value::Tuple(values) => values.iter().map(Value::as_number).collect(),
+        Value::Empty => Ok(Vec::new()),
+        value => Ok(vec![value.as_number()?]),
+    }
+}
+
+/// Thousands separator and decimal mark of a locale, for grouped results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Put between groups of three digits
+    pub group: char,
+    /// Decimal mark
+    pub decimal: char,
+}
+
+impl NumberFormat {
+    /// `1,234,567.5`, for English and unknown locales
+    pub const DEFAULT: Self = Self {
+        group: ',',
+        decimal: '.',
+    };
+
+    /// Format of the numeric locale (`$LC_NUMERIC`)
+    pub fn current() -> Self {
+        Self::for_locale(current_numeric_locale().as_deref())
+    }
+
+    /// Format for a locale such as `de_DE`
+    pub fn for_locale(locale: Option<&str>) -> Self {
+        let Some(locale) = locale else {
+            return Self::DEFAULT;
+        };
+        let language = locale.split('_').next().unwrap_or_default();
+        let (group, decimal) = match (language, locale) {
+            (_, "de_CH" | "it_CH") => ('\u{2019}', '.'),
+            (
+                "da" | "de" | "el" | "es" | "hr" | "id" | "it" | "nl" | "pt" | "ro" | "sl" | "sr"
+                | "tr" | "vi",
+                _,
+            ) => ('.', ','),
+            (
+                "bg" | "cs" | "et" | "fi" | "fr" | "hu" | "lt" | "lv" | "nb" | "nn" | "pl" | "ru"
+                | "sk" | "sv" | "uk",
+                _,
+            ) => ('\u{00A0}', ','),
+            _ => return Self::DEFAULT,
+        };
+        Self { group, decimal }
+    }
+
+    /// Group the digits of a raw result ("1234567.5" -> "1,234,567.5"); text
+    /// that isn't a plain number ("inf") is returned as is
+    pub fn group(&self, raw: &str) -> String {
+        let (sign, digits) = match raw.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", raw),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+        if integer.is_empty() || !integer.bytes().all(|b| b.is_ascii_digit()) {
+            return raw.to_string();
+        }
+
+        let mut grouped = String::with_capacity(raw.len() * 2);
+        grouped.push_str(sign);
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push(self.group);
+            }
+            grouped.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            grouped.push(self.decimal);
+            grouped.push_str(fraction);
+        }
+        grouped
+    }
+}
 
 /// Plugin for evaluating mathematical expressions
 #[derive(Debug)]
 pub struct CalculatorPlugin {
     enabled: bool,
+    /// Where evaluated expressions are recorded for "@calc" recall
+    history: Option<Arc<CalculationHistory>>,
+    /// Format results are shown in; raw numbers when `None`
+    grouping: Option<NumberFormat>,
+    /// Enter copies the grouped form and Alt+Enter the raw number
+    copy_grouped: bool,
 }
 
 impl CalculatorPlugin {
     pub fn new() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            history: None,
+            grouping: None,
+            copy_grouped: false,
+        }
+    }
+
+    /// Record evaluated expressions into `history`
+    pub fn with_history(mut self, history: Arc<CalculationHistory>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Show results with `format`'s thousands separators; Enter copies the raw
+    /// number and Alt+Enter the grouped one, swapped with `copy_grouped`
+    pub fn with_digit_grouping(mut self, format: NumberFormat, copy_grouped: bool) -> Self {
+        self.grouping = Some(format);
+        self.copy_grouped = copy_grouped;
+        self
+    }
+
+    /// `raw` as shown in the result title
+    fn display(&self, raw: &str) -> String {
+        match &self.grouping {
+            Some(format) => format.group(raw),
+            None => raw.to_string(),
+        }
+    }
+
+    /// Text copied for `result`: raw or grouped depending on Alt and `copy_grouped`
+    fn copy_text(&self, result: f64, alternate: bool) -> String {
+        let raw = Self::format_result(result);
+        match &self.grouping {
+            Some(format) if alternate != self.copy_grouped => format.group(&raw),
+            _ => raw,
+        }
     }
 
     /// Check if query looks like a math expression
@@ -20,7 +249,186 @@ impl CalculatorPlugin {
             .chars()
             .any(|c| matches!(c, '+' | '-' | '*' | '/' | '(' | ')' | '^' | '%'));
 
-        has_numbers && (has_operators || query.parse::<f64>().is_ok())
+        (has_numbers && (has_operators || query.parse::<f64>().is_ok()))
+            || Self::has_function_call(query)
+    }
+
+    /// Check if query calls one of the known functions, e.g. "sin(pi/2)"
+    fn has_function_call(query: &str) -> bool {
+        let query = query.trim_start();
+        FUNCTIONS
+            .iter()
+            .map(|(name, ..)| name)
+            .chain(AGGREGATES.iter().map(|(name, ..)| name))
+            .any(|name| query.starts_with(&format!("{}(", name)))
+    }
+
+    /// Arithmetic inside a query that isn't a calculation as a whole, e.g.
+    /// "2+2" in "firefox 2+2"
+    ///
+    /// Only whole words of digits and operators count, so names with digits
+    /// ("python3", "mp3-player", "7-zip") never match, and an operator is needed
+    /// between two numbers. Hyphen chains such as dates ("2024-01-15") are
+    /// skipped. The last such run of words wins.
+    fn embedded_expression(query: &str) -> Option<String> {
+        let is_math_word = |word: &str| {
+            word.chars().all(|c| {
+                c.is_ascii_digit()
+                    || matches!(c, '.' | '+' | '-' | '*' | '/' | '^' | '%' | '(' | ')')
+            })
+        };
+
+        let mut runs: Vec<Vec<&str>> = vec![Vec::new()];
+        for word in query.split_whitespace() {
+            if is_math_word(word) {
+                runs.last_mut().expect("runs starts non-empty").push(word);
+            } else if !runs.last().expect("runs starts non-empty").is_empty() {
+                runs.push(Vec::new());
+            }
+        }
+
+        runs.into_iter()
+            .rev()
+            .map(|run| run.join(" "))
+            .find(|expr| {
+                let compact: String = expr.chars().filter(|c| *c != ' ').collect();
+                let chars: Vec<char> = compact.chars().collect();
+                let has_binary_operator = chars.windows(3).any(|w| {
+                    (w[0].is_ascii_digit() || w[0] == ')')
+                        && matches!(w[1], '+' | '-' | '*' | '/' | '^' | '%')
+                        && (w[2].is_ascii_digit() || w[2] == '(' || w[2] == '.')
+                });
+                let hyphen_chain = compact.matches('-').count() >= 2
+                    && compact.chars().all(|c| c.is_ascii_digit() || c == '-');
+                has_binary_operator && !hyphen_chain
+            })
+    }
+
+    /// Expression to evaluate for a query, if the query is a calculation
+    fn expression(query: &str) -> Option<&str> {
+        match Self::strip_prefix(query) {
+            Some("" | "help" | "?") => None,
+            Some(expr) => Some(expr),
+            None if Self::is_math_expression(query) => Some(query),
+            None => None,
+        }
+    }
+
+    /// Best-effort completion of an expression still being typed: drops trailing
+    /// operators and commas and closes open parentheses ("(2+3)*(4-" -> "(2+3)*(4)")
+    fn complete_partial(expr: &str) -> Option<String> {
+        let trimmed = expr.trim_end_matches(|c: char| {
+            c.is_whitespace() || matches!(c, '+' | '-' | '*' | '/' | '^' | '%' | '(' | ',')
+        });
+        let open = trimmed.matches('(').count();
+        let closed = trimmed.matches(')').count();
+        if trimmed.is_empty() || closed > open {
+            return None;
+        }
+
+        let completed = format!("{}{}", trimmed, ")".repeat(open - closed));
+        (completed != expr.trim()).then_some(completed)
+    }
+
+    /// Evaluate `expr`, completing it first if it is still being typed
+    ///
+    /// Returns the value and, for a partial expression, the completed one.
+    fn evaluate_as_typed(&self, expr: &str) -> Result<(f64, Option<String>)> {
+        match self.evaluate(expr) {
+            Ok(value) => Ok((value, None)),
+            Err(e) => {
+                let completed = Self::complete_partial(expr).ok_or(e)?;
+                let value = self.evaluate(&completed)?;
+                Ok((value, Some(completed)))
+            }
+        }
+    }
+
+    /// Format a result without trailing zeros ("4", "0.333333")
+    fn format_result(result: f64) -> String {
+        if result.fract() == 0.0 {
+            format!("{:.0}", result)
+        } else {
+            format!("{:.6}", result).trim_end_matches('0').to_string()
+        }
+    }
+
+    /// Strip the "@calc" / "@cal" command prefix, if present as a whole word
+    /// ("@calendar" is not a calculation)
+    fn strip_prefix(query: &str) -> Option<&str> {
+        ["@calc", "@cal"]
+            .iter()
+            .filter_map(|prefix| query.strip_prefix(prefix))
+            .find(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+            .map(str::trim)
+    }
+
+    /// Command of a calculation's result row; Enter copies instead of running it
+    fn result_command(result: f64) -> String {
+        format!("echo '{}'", Self::format_result(result))
+    }
+
+    /// Arithmetic typed inside another query, with its value
+    fn ambient_value(&self, query: &str) -> Option<(String, f64)> {
+        let expr = Self::embedded_expression(query)?;
+        let value = self.evaluate(&expr).ok()?;
+        Some((expr, value))
+    }
+
+    /// Low-scored result for arithmetic typed inside another query
+    fn ambient_result(&self, query: &str) -> Option<PluginResult> {
+        let (expr, value) = self.ambient_value(query)?;
+        Some(
+            PluginResult::new(
+                self.display(&Self::format_result(value)),
+                Self::result_command(value),
+                self.name().to_string(),
+            )
+            .with_subtitle(format!("= {}", expr))
+            .with_icon("accessories-calculator".to_string())
+            .with_score(AMBIENT_SCORE),
+        )
+    }
+
+    /// Reference listing of functions and constants; selecting a row inserts its name
+    fn help_results(&self) -> Vec<PluginResult> {
+        let functions = FUNCTIONS
+            .iter()
+            .map(|(name, signature, description, _)| (name, signature, description))
+            .chain(
+                AGGREGATES
+                    .iter()
+                    .map(|(name, signature, description, _)| (name, signature, description)),
+            )
+            .map(|(name, signature, description)| {
+                (
+                    signature.to_string(),
+                    description.to_string(),
+                    format!("{}(", name),
+                )
+            });
+        let constants = CONSTANTS.iter().map(|(name, description, value)| {
+            (
+                name.to_string(),
+                format!("{} = {:.6}", description, value),
+                name.to_string(),
+            )
+        });
+
+        functions
+            .chain(constants)
+            .enumerate()
+            .map(|(i, (title, description, insert))| {
+                PluginResult::new(
+                    title,
+                    format!("@insert:@calc {}", insert),
+                    self.name().to_string(),
+                )
+                .with_subtitle(format!("{} • Press Enter to insert", description))
+                .with_icon("accessories-calculator".to_string())
+                .with_score(9000 - i as i64) // Keep reference order
+            })
+            .collect()
     }
 
     /// Evaluate a math expression
@@ -40,7 +448,7 @@ impl CalculatorPlugin {
         }
 
         // Use evalexpr crate for safe expression evaluation
-        match evalexpr::eval(&expr) {
+        match evalexpr::eval_with_context(&expr, math_context()) {
             Ok(value) => {
                 if let evalexpr::Value::Float(f) = value {
                     Ok(f)
@@ -120,31 +528,55 @@ impl Plugin for CalculatorPlugin {
     }
 
     fn should_handle(&self, query: &str) -> bool {
-        self.enabled && Self::is_math_expression(query)
+        self.enabled
+            && (Self::strip_prefix(query).is_some()
+                || Self::is_math_expression(query)
+                || Self::embedded_expression(query).is_some())
     }
 
     fn search(&self, query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
-        if !self.enabled || !Self::is_math_expression(query) {
+        if !self.enabled {
             return Ok(vec![]);
         }
 
-        match self.evaluate(query) {
-            Ok(result) => {
-                let formatted = if result.fract() == 0.0 {
-                    format!("{:.0}", result)
-                } else {
-                    format!("{:.6}", result).trim_end_matches('0').to_string()
+        // Explicit "@calc" queries: show the reference on empty/help, otherwise evaluate as-is
+        let (query, prefixed) = match Self::strip_prefix(query) {
+            Some("" | "help" | "?") => return Ok(self.help_results()),
+            Some(expr) => (expr, true),
+            None => (query, false),
+        };
+        let evaluated = if prefixed || Self::is_math_expression(query) {
+            self.evaluate_as_typed(query)
+        } else {
+            Err(anyhow::anyhow!("Not a calculation"))
+        };
+
+        match evaluated {
+            Ok((result, completed)) => {
+                let formatted = Self::format_result(result);
+                // Previews of half-typed expressions don't go into the history
+                let subtitle = match completed {
+                    Some(completed) => format!("= {} (incomplete)", completed),
+                    None => {
+                        if let Some(history) = &self.history {
+                            history.record(query, &formatted);
+                        }
+                        format!("= {}", query)
+                    }
                 };
 
+                // Enter copies the value instead (see handle_keyboard_event)
                 Ok(vec![PluginResult::new(
-                    formatted.clone(),
-                    format!("echo '{}'", formatted), // Copy to clipboard would be better
+                    self.display(&formatted),
+                    Self::result_command(result),
                     self.name().to_string(),
                 )
-                .with_subtitle(format!("= {}", query))
+                .with_subtitle(subtitle)
                 .with_icon("accessories-calculator".to_string())
                 .with_score(10000)]) // High score to show above app results
             }
+            // Arithmetic typed after something else ("firefox 2+2") gets a low-scored row
+            Err(_) if !prefixed => Ok(self.ambient_result(query).into_iter().collect()),
             Err(_) => Ok(vec![]), // Invalid expression, no results
         }
     }
@@ -156,6 +588,33 @@ impl Plugin for CalculatorPlugin {
     fn enabled(&self) -> bool {
         self.enabled
     }
+
+    fn handle_keyboard_event(&self, event: &KeyboardEvent) -> KeyboardAction {
+        // Enter (or Shift+Enter to keep the window open) on a calculation copies the
+        // result, Alt+Enter in the other format when digits are grouped;
+        // Ctrl+Enter is left to other plugins (e.g. web search)
+        if event.key != Key::Return || event.has_ctrl() {
+            return KeyboardAction::None;
+        }
+
+        let value = match Self::expression(&event.query).map(|expr| self.evaluate(expr)) {
+            Some(Ok(result)) => Some(result),
+            // Calculations inside other queries
+            _ => self.ambient_value(&event.query).map(|(_, result)| result),
+        };
+
+        // Only the calculation's own row copies; another selected row (an app
+        // called "2048") launches as usual
+        match value {
+            Some(result)
+                if event.selected_command.as_deref()
+                    == Some(Self::result_command(result).as_str()) =>
+            {
+                KeyboardAction::CopyToClipboard(self.copy_text(result, event.has_alt()))
+            }
+            _ => KeyboardAction::None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -179,6 +638,202 @@ mod tests {
         assert_eq!(calc.evaluate("100/4").unwrap(), 25.0);
     }
 
+    #[test]
+    fn test_partial_expression_preview() {
+        assert_eq!(
+            CalculatorPlugin::complete_partial("(2+3)*(4-"),
+            Some("(2+3)*(4)".to_string())
+        );
+        assert_eq!(
+            CalculatorPlugin::complete_partial("12*3 + "),
+            Some("12*3".to_string())
+        );
+        assert_eq!(CalculatorPlugin::complete_partial("2+2"), None);
+        assert_eq!(CalculatorPlugin::complete_partial("2)+"), None);
+
+        let calc = CalculatorPlugin::new();
+        let config = crate::config::Config::default();
+        let results = calc
+            .search("(2+3)*(4-", &PluginContext::new(10, &config))
+            .unwrap();
+        assert_eq!(results[0].title, "20");
+        assert_eq!(
+            results[0].subtitle.as_deref(),
+            Some("= (2+3)*(4) (incomplete)")
+        );
+    }
+
+    #[test]
+    fn test_functions_and_constants() {
+        let calc = CalculatorPlugin::new();
+        assert_eq!(calc.evaluate("sqrt(16)").unwrap(), 4.0);
+        assert_eq!(calc.evaluate("abs(-3)").unwrap(), 3.0);
+        assert!((calc.evaluate("sin(pi / 2)").unwrap() - 1.0).abs() < 1e-9);
+        assert!((calc.evaluate("ln(e)").unwrap() - 1.0).abs() < 1e-9);
+        assert!((calc.evaluate("tau - 2 * pi").unwrap()).abs() < 1e-9);
+        assert!(CalculatorPlugin::is_math_expression("cos(pi)"));
+    }
+
+    #[test]
+    fn test_help_listing_covers_functions_and_constants() {
+        use crate::config::Config;
+
+        let calc = CalculatorPlugin::new();
+        let config = Config::default();
+        let ctx = PluginContext::new(50, &config);
+
+        for query in ["@calc", "@calc help"] {
+            let results = calc.search(query, &ctx).unwrap();
+            let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+            for expected in ["sin(x)", "cos(x)", "sqrt(x)", "log(x)", "ln(x)", "abs(x)"] {
+                assert!(
+                    titles.contains(&expected),
+                    "missing {} in {:?}",
+                    expected,
+                    titles
+                );
+            }
+            for expected in ["pi", "e", "tau"] {
+                assert!(
+                    titles.contains(&expected),
+                    "missing {} in {:?}",
+                    expected,
+                    titles
+                );
+            }
+            assert!(results
+                .iter()
+                .all(|r| r.command.starts_with("@insert:@calc ")));
+        }
+
+        // Every listed function and constant must evaluate
+        for (name, ..) in FUNCTIONS {
+            let value = calc.evaluate(&format!("{}(0.5)", name));
+            assert!(value.is_ok(), "{} failed to evaluate: {:?}", name, value);
+        }
+        for (name, _, value) in CONSTANTS {
+            assert_eq!(calc.evaluate(name).unwrap(), *value);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_functions() {
+        use crate::config::Config;
+
+        let calc = CalculatorPlugin::new();
+        assert_eq!(calc.evaluate("avg(1, 2, 3, 4)").unwrap(), 2.5);
+        assert_eq!(calc.evaluate("sum(1,2,3,4)").unwrap(), 10.0);
+        assert_eq!(calc.evaluate("min(4, -2.5, 7)").unwrap(), -2.5);
+        assert_eq!(calc.evaluate("max(4, -2.5, 7)").unwrap(), 7.0);
+        assert_eq!(calc.evaluate("sum(0.5, 2) * 2").unwrap(), 5.0);
+
+        // A single value is its own aggregate
+        for name in ["avg", "sum", "min", "max"] {
+            assert_eq!(calc.evaluate(&format!("{}(42)", name)).unwrap(), 42.0);
+        }
+
+        // Empty lists and non-numeric values are errors, not panics or zeros
+        assert!(calc.evaluate("avg()").is_err());
+        assert!(calc.evaluate("sum(1, two, 3)").is_err());
+        assert!(calc.evaluate("max(1, \"a\")").is_err());
+
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+        let results = calc.search("@calc avg(1,2,3,4)", &ctx).unwrap();
+        assert_eq!(results[0].title, "2.5");
+        assert!(calc.should_handle("sum(1,2)"));
+        assert!(calc.search("@calc avg()", &ctx).unwrap().is_empty());
+        // Still being typed: the trailing comma is dropped for the preview
+        let results = calc.search("@calc sum(1, 2,", &ctx).unwrap();
+        assert_eq!(results[0].title, "3");
+    }
+
+    #[test]
+    fn test_search_with_prefix() {
+        use crate::config::Config;
+
+        let calc = CalculatorPlugin::new();
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = calc.search("@calc sqrt(9) + 1", &ctx).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "4");
+        assert_eq!(calc.search("@cal 6*7", &ctx).unwrap()[0].title, "42");
+
+        // Other @-prefixes starting with "cal" are not calculations
+        assert_eq!(CalculatorPlugin::strip_prefix("@calendar"), None);
+        assert_eq!(CalculatorPlugin::strip_prefix("@calculus 1+1"), None);
+        assert!(!calc.should_handle("@calendar"));
+    }
+
+    #[test]
+    fn test_math_inside_other_queries() {
+        use crate::config::Config;
+
+        let calc = CalculatorPlugin::new();
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        // A calculation on its own is a regular, top-scored result
+        assert!(calc.should_handle("2+2"));
+        let results = calc.search("2+2", &ctx).unwrap();
+        assert_eq!(results[0].score, 10000);
+
+        let results = calc.search("firefox 2+2", &ctx).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "4");
+        assert_eq!(results[0].subtitle.as_deref(), Some("= 2+2"));
+        assert_eq!(results[0].score, AMBIENT_SCORE);
+        assert_eq!(
+            CalculatorPlugin::embedded_expression("notes 12 * (3 + 1) todo").as_deref(),
+            Some("12 * (3 + 1)")
+        );
+
+        // Digits in names, versions and dates aren't calculations
+        for query in [
+            "python3",
+            "mp3-player",
+            "7-zip",
+            "gtk 4.0",
+            "report 2024-01-15",
+            "firefox",
+        ] {
+            assert_eq!(
+                CalculatorPlugin::embedded_expression(query),
+                None,
+                "{} should not be a calculation",
+                query
+            );
+            assert!(calc.search(query, &ctx).unwrap().is_empty(), "{}", query);
+        }
+        assert!(!calc.should_handle("python3"));
+    }
+
+    #[test]
+    fn test_enter_copies_ambient_result_from_its_own_row() {
+        use gtk4::gdk::ModifierType;
+
+        let calc = CalculatorPlugin::new();
+        let enter = |selected: &str| {
+            calc.handle_keyboard_event(
+                &KeyboardEvent::new(
+                    Key::Return,
+                    ModifierType::empty(),
+                    "firefox 2+2".to_string(),
+                    true,
+                )
+                .with_selected_command(Some(selected.to_string())),
+            )
+        };
+
+        assert!(matches!(
+            enter("echo '4'"),
+            KeyboardAction::CopyToClipboard(text) if text == "4"
+        ));
+        assert!(matches!(enter("firefox %u"), KeyboardAction::None));
+    }
+
     #[test]
     fn test_search() {
         use crate::config::Config;
@@ -191,4 +846,116 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "4");
     }
+
+    #[test]
+    fn test_group_digits() {
+        let english = NumberFormat::DEFAULT;
+        assert_eq!(english.group("1234567"), "1,234,567");
+        assert_eq!(english.group("123"), "123");
+        assert_eq!(english.group("-1000"), "-1,000");
+        assert_eq!(english.group("1234567.125"), "1,234,567.125");
+        assert_eq!(english.group("0.333333"), "0.333333");
+        assert_eq!(english.group("inf"), "inf");
+
+        let german = NumberFormat::for_locale(Some("de_DE"));
+        assert_eq!(german.group("1234567.5"), "1.234.567,5");
+        let french = NumberFormat::for_locale(Some("fr_FR"));
+        assert_eq!(french.group("-9876543.25"), "-9\u{a0}876\u{a0}543,25");
+        assert_eq!(
+            NumberFormat::for_locale(Some("de_CH")).group("1234.5"),
+            "1\u{2019}234.5"
+        );
+        assert_eq!(NumberFormat::for_locale(Some("en_US")), english);
+        assert_eq!(NumberFormat::for_locale(None), english);
+    }
+
+    #[test]
+    fn test_copy_raw_or_grouped() {
+        use gtk4::gdk::ModifierType;
+
+        let copy = |calc: &CalculatorPlugin, modifiers| match calc.handle_keyboard_event(
+            &KeyboardEvent::new(Key::Return, modifiers, "1234*1000.5".to_string(), true)
+                .with_selected_command(Some("echo '1234617'".to_string())),
+        ) {
+            KeyboardAction::CopyToClipboard(text) => text,
+            other => panic!("Expected CopyToClipboard action, got {:?}", other),
+        };
+        let config = crate::config::Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let calc = CalculatorPlugin::new().with_digit_grouping(NumberFormat::DEFAULT, false);
+        assert_eq!(
+            calc.search("1234*1000.5", &ctx).unwrap()[0].title,
+            "1,234,617"
+        );
+        assert_eq!(copy(&calc, ModifierType::empty()), "1234617");
+        assert_eq!(copy(&calc, ModifierType::ALT_MASK), "1,234,617");
+        // Shift only keeps the window open, in either format
+        assert_eq!(copy(&calc, ModifierType::SHIFT_MASK), "1234617");
+        assert_eq!(
+            copy(&calc, ModifierType::ALT_MASK | ModifierType::SHIFT_MASK),
+            "1,234,617"
+        );
+
+        let calc = CalculatorPlugin::new().with_digit_grouping(NumberFormat::DEFAULT, true);
+        assert_eq!(copy(&calc, ModifierType::empty()), "1,234,617");
+        assert_eq!(copy(&calc, ModifierType::ALT_MASK), "1234617");
+
+        // Without grouping both copy the raw number
+        let calc = CalculatorPlugin::new();
+        assert_eq!(
+            calc.search("1234*1000.5", &ctx).unwrap()[0].title,
+            "1234617"
+        );
+        assert_eq!(copy(&calc, ModifierType::ALT_MASK), "1234617");
+    }
+
+    #[test]
+    fn test_enter_copies_result() {
+        use gtk4::gdk::ModifierType;
+
+        let calc = CalculatorPlugin::new();
+        let config = crate::config::Config::default();
+        let ctx = PluginContext::new(10, &config);
+        // Enter with the calculation's own row selected
+        let enter = |query: &str, modifiers| {
+            let row = calc.search(query, &ctx).unwrap().into_iter().next();
+            calc.handle_keyboard_event(
+                &KeyboardEvent::new(Key::Return, modifiers, query.to_string(), true)
+                    .with_selected_command(row.map(|row| row.command)),
+            )
+        };
+
+        match enter("1234*5678", ModifierType::empty()) {
+            KeyboardAction::CopyToClipboard(text) => assert_eq!(text, "7006652"),
+            other => panic!("Expected CopyToClipboard action, got {:?}", other),
+        }
+        match enter("@calc 2.5*3", ModifierType::empty()) {
+            KeyboardAction::CopyToClipboard(text) => assert_eq!(text, "7.5"),
+            other => panic!("Expected CopyToClipboard action, got {:?}", other),
+        }
+
+        // Non-calculations, help listings and Ctrl+Enter are left to other handlers
+        for (query, modifiers) in [
+            ("firefox", ModifierType::empty()),
+            ("@calc", ModifierType::empty()),
+            ("2+", ModifierType::empty()),
+            ("2+2", ModifierType::CONTROL_MASK),
+        ] {
+            assert!(
+                matches!(enter(query, modifiers), KeyboardAction::None),
+                "{:?} should not be handled",
+                query
+            );
+        }
+
+        // Another row selected: an app called "2048" launches instead
+        let app_selected =
+            KeyboardEvent::new(Key::Return, ModifierType::empty(), "2048".to_string(), true)
+                .with_selected_command(Some("2048-game".to_string()));
+        assert!(matches!(
+            calc.handle_keyboard_event(&app_selected),
+            KeyboardAction::None
+        ));
+    }
 }
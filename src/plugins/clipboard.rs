@@ -1,11 +1,17 @@
 use super::traits::{KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult};
+use crate::utils::shell_escape;
 use anyhow::Result;
+use gtk4::gdk::Key;
+use std::collections::HashMap;
 use std::process::Command;
+use std::sync::Mutex;
 
 #[derive(Debug)]
 pub struct ClipboardPlugin {
     enabled: bool,
     provider: Option<Provider>,
+    /// Entry ids of the last listed results, keyed by result command
+    listed: Mutex<HashMap<String, String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +32,7 @@ impl ClipboardPlugin {
         Self {
             enabled: provider.is_some(),
             provider,
+            listed: Mutex::new(HashMap::new()),
         }
     }
 
@@ -62,6 +69,19 @@ impl ClipboardPlugin {
             None => "true".to_string(),
         }
     }
+
+    /// Decode an entry's content, if it is text
+    fn decode_text(&self, id: &str) -> Option<String> {
+        let Some(Provider::Cliphist { path }) = &self.provider else {
+            return None;
+        };
+
+        let output = Command::new(path).arg("decode").arg(id).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8(output.stdout).ok()
+    }
 }
 
 impl Plugin for ClipboardPlugin {
@@ -98,16 +118,21 @@ impl Plugin for ClipboardPlugin {
         let entries = self.search_entries(filter, context.max_results);
 
         let mut results = Vec::with_capacity(entries.len());
+        let mut listed = HashMap::with_capacity(entries.len());
         for (idx, e) in entries.into_iter().enumerate() {
             let title = elide(&e.preview, 120);
-            let mut pr =
-                PluginResult::new(title, self.build_copy_command(&e), self.name().to_string())
-                    .with_icon("edit-paste".to_string())
-                    .with_score(10_000 - idx as i64);
+            let command = self.build_copy_command(&e);
+            let mut pr = PluginResult::new(title, command.clone(), self.name().to_string())
+                .with_icon("edit-paste".to_string())
+                .with_score(10_000 - idx as i64);
             if let Some(mime) = &e.mime {
                 pr = pr.with_subtitle(mime.clone());
             }
             results.push(pr);
+            listed.insert(command, e.id);
+        }
+        if let Ok(mut guard) = self.listed.lock() {
+            *guard = listed;
         }
 
         Ok(results)
@@ -121,9 +146,26 @@ impl Plugin for ClipboardPlugin {
         self.enabled
     }
 
-    fn handle_keyboard_event(&self, _event: &KeyboardEvent) -> KeyboardAction {
-        // Enter executes default copy command; Shift+Enter may be added later if needed
-        KeyboardAction::None
+    fn handle_keyboard_event(&self, event: &KeyboardEvent) -> KeyboardAction {
+        // Enter copies text entries (Shift+Enter keeps the window open). Binary entries
+        // such as images fall through to running the decode pipeline.
+        if event.key != Key::Return
+            || event.has_ctrl()
+            || event.has_alt()
+            || !self.should_handle(&event.query)
+        {
+            return KeyboardAction::None;
+        }
+
+        let id = event
+            .selected_command
+            .as_ref()
+            .and_then(|command| self.listed.lock().ok()?.get(command).cloned());
+
+        match id.and_then(|id| self.decode_text(&id)) {
+            Some(text) => KeyboardAction::CopyToClipboard(text),
+            None => KeyboardAction::None,
+        }
     }
 }
 
@@ -243,22 +285,6 @@ fn elide(s: &str, max_chars: usize) -> String {
     out
 }
 
-fn shell_escape(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_string();
-    }
-    let mut escaped = String::from("'");
-    for ch in value.chars() {
-        if ch == '\'' {
-            escaped.push_str("'\\''");
-        } else {
-            escaped.push(ch);
-        }
-    }
-    escaped.push('\'');
-    escaped
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,6 +302,7 @@ mod tests {
         let plugin = ClipboardPlugin {
             enabled: true,
             provider: None,
+            listed: Mutex::new(HashMap::new()),
         };
         assert!(plugin.should_handle("@clip foo"));
         assert!(!plugin.should_handle("clip foo"));
@@ -288,6 +315,7 @@ mod tests {
             provider: Some(Provider::Cliphist {
                 path: "cliphist".to_string(),
             }),
+            listed: Mutex::new(HashMap::new()),
         };
         let cmd = plugin.build_copy_command(&Entry {
             id: "42".to_string(),
@@ -304,6 +332,7 @@ mod tests {
         let plugin = ClipboardPlugin {
             enabled: false,
             provider: None,
+            listed: Mutex::new(HashMap::new()),
         };
         let cfg = Config::default();
         let ctx = PluginContext::new(10, &cfg);
@@ -0,0 +1,262 @@
+//! Exchange rates for the advanced calculator's currency conversions
+//!
+//! With `plugins.live_currency_rates` on, rates (base USD) are fetched from a
+//! free API at most every [`RATES_TTL`] and cached to disk, so conversions keep
+//! working offline with the last rates seen. Fetching happens on a background
+//! thread; searches only ever read the rates already in memory.
+
+use crate::utils::atomic_write;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+/// Rates older than this are refreshed (still used until the refresh succeeds)
+pub const RATES_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Wait between failed fetches, so typing offline doesn't retry every keystroke
+const RETRY_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// Free, keyless endpoint with the latest rates against USD
+const RATES_URL: &str = "https://open.er-api.com/v6/latest/USD";
+
+/// Exchange rates against USD and when they were fetched
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateTable {
+    /// Seconds since the UNIX epoch
+    pub fetched_at: u64,
+    /// Units of each currency (upper-case ISO 4217 code) per US dollar
+    pub rates: HashMap<String, f64>,
+}
+
+impl RateTable {
+    /// Convert `amount` of `from` into `to`; `None` for unknown currencies
+    pub fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        let from_rate = self.rates.get(from).filter(|rate| **rate > 0.0)?;
+        let to_rate = self.rates.get(to)?;
+        Some(amount * to_rate / from_rate)
+    }
+
+    /// Whether the rates are at least `ttl` old at `now` (seconds since the epoch)
+    pub fn is_expired(&self, now: u64, ttl: Duration) -> bool {
+        now.saturating_sub(self.fetched_at) >= ttl.as_secs()
+    }
+}
+
+/// Response of [`RATES_URL`]
+#[derive(Debug, Deserialize)]
+struct RatesResponse {
+    result: String,
+    rates: HashMap<String, f64>,
+}
+
+/// Cached exchange rates, refreshed in the background when stale
+#[derive(Debug)]
+pub struct CurrencyRates {
+    /// Where rates are cached between runs (`None` keeps them in memory only)
+    cache_path: Option<PathBuf>,
+    /// Whether stale rates are fetched again
+    auto_refresh: bool,
+    table: Arc<Mutex<Option<RateTable>>>,
+    refreshing: Arc<AtomicBool>,
+    last_attempt: Mutex<Option<Instant>>,
+}
+
+impl CurrencyRates {
+    /// Rates cached under the user's cache directory, never fetched unless
+    /// enabled with [`Self::with_auto_refresh`]
+    pub fn new() -> Self {
+        let cache_path =
+            dirs::cache_dir().map(|dir| dir.join("native-launcher").join("currency_rates.json"));
+        let table = cache_path.as_ref().and_then(|path| match load_table(path) {
+            Ok(table) => Some(table),
+            Err(e) => {
+                debug!("No cached currency rates: {:#}", e);
+                None
+            }
+        });
+
+        Self {
+            cache_path,
+            auto_refresh: false,
+            table: Arc::new(Mutex::new(table)),
+            refreshing: Arc::new(AtomicBool::new(false)),
+            last_attempt: Mutex::new(None),
+        }
+    }
+
+    /// Fetch stale rates again in the background
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.auto_refresh = auto_refresh;
+        self
+    }
+
+    /// Fixed rates that are never refreshed or cached
+    #[allow(dead_code)]
+    pub fn from_table(table: RateTable) -> Self {
+        Self {
+            cache_path: None,
+            auto_refresh: false,
+            table: Arc::new(Mutex::new(Some(table))),
+            refreshing: Arc::new(AtomicBool::new(false)),
+            last_attempt: Mutex::new(None),
+        }
+    }
+
+    /// Rates to convert with, even if stale; `None` until rates were ever fetched
+    ///
+    /// Starts a background refresh when the rates are missing or expired, so a
+    /// later search sees the new ones.
+    pub fn current(&self) -> Option<RateTable> {
+        let table = self.table.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+        let stale = table
+            .as_ref()
+            .is_none_or(|table| table.is_expired(now_secs(), RATES_TTL));
+        if stale {
+            self.refresh_in_background();
+        }
+        table
+    }
+
+    fn refresh_in_background(&self) {
+        if !self.auto_refresh {
+            return;
+        }
+        {
+            let mut last_attempt = self.last_attempt.lock().unwrap_or_else(|e| e.into_inner());
+            if last_attempt.is_some_and(|at| at.elapsed() < RETRY_AFTER) {
+                return;
+            }
+            if self.refreshing.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            *last_attempt = Some(Instant::now());
+        }
+
+        let table = Arc::clone(&self.table);
+        let refreshing = Arc::clone(&self.refreshing);
+        let cache_path = self.cache_path.clone();
+        std::thread::spawn(move || {
+            match fetch_table() {
+                Ok(fetched) => {
+                    info!("Fetched {} currency rates", fetched.rates.len());
+                    if let Some(path) = &cache_path {
+                        if let Err(e) = save_table(path, &fetched) {
+                            warn!("Failed to cache currency rates: {:#}", e);
+                        }
+                    }
+                    *table.lock().unwrap_or_else(|e| e.into_inner()) = Some(fetched);
+                }
+                Err(e) => debug!("Currency rates unavailable: {:#}", e),
+            }
+            refreshing.store(false, Ordering::SeqCst);
+        });
+    }
+}
+
+impl Default for CurrencyRates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn fetch_table() -> Result<RateTable> {
+    let response: RatesResponse = ureq::get(RATES_URL)
+        .timeout(Duration::from_secs(5))
+        .call()?
+        .into_json()
+        .context("Invalid currency rates response")?;
+    if response.result != "success" || response.rates.is_empty() {
+        return Err(anyhow!(
+            "Currency rates request failed: {}",
+            response.result
+        ));
+    }
+
+    Ok(RateTable {
+        // When we fetched them, not when the API last updated: the TTL is about
+        // how often we ask
+        fetched_at: now_secs(),
+        rates: response.rates,
+    })
+}
+
+fn load_table(path: &Path) -> Result<RateTable> {
+    let data =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&data).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn save_table(path: &Path, table: &RateTable) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    atomic_write(path, serde_json::to_string(table)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(fetched_at: u64) -> RateTable {
+        RateTable {
+            fetched_at,
+            rates: [("USD", 1.0), ("EUR", 0.5), ("JPY", 150.0)]
+                .into_iter()
+                .map(|(code, rate)| (code.to_string(), rate))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_conversion_with_fixed_rates() {
+        let table = table(0);
+        assert_eq!(table.convert(100.0, "USD", "EUR"), Some(50.0));
+        assert_eq!(table.convert(100.0, "EUR", "USD"), Some(200.0));
+        assert_eq!(table.convert(10.0, "EUR", "JPY"), Some(3000.0));
+        assert_eq!(table.convert(42.0, "JPY", "JPY"), Some(42.0));
+        assert_eq!(table.convert(1.0, "USD", "XYZ"), None);
+        assert_eq!(table.convert(1.0, "XYZ", "USD"), None);
+
+        let rates = CurrencyRates::from_table(table.clone());
+        assert_eq!(rates.current(), Some(table));
+        assert!(!rates.refreshing.load(Ordering::SeqCst));
+
+        // Live rates are opt-in: without them nothing is fetched
+        let rates = CurrencyRates::new();
+        rates.current();
+        assert!(!rates.refreshing.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let fetched_at = 1_700_000_000;
+        let table = table(fetched_at);
+        assert!(!table.is_expired(fetched_at, RATES_TTL));
+        assert!(!table.is_expired(fetched_at + RATES_TTL.as_secs() - 1, RATES_TTL));
+        assert!(table.is_expired(fetched_at + RATES_TTL.as_secs(), RATES_TTL));
+        // A clock set back doesn't make the rates look expired
+        assert!(!table.is_expired(fetched_at - 3600, RATES_TTL));
+
+        // Cached rates survive a round trip through the cache file
+        let path =
+            std::env::temp_dir().join(format!("native-launcher-rates-{}.json", std::process::id()));
+        save_table(&path, &table).unwrap();
+        assert_eq!(load_table(&path).unwrap(), table);
+        let _ = fs::remove_file(&path);
+    }
+}
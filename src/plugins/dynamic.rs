@@ -3,6 +3,7 @@
 //! Enables loading external plugins compiled as shared libraries (.so files on Linux).
 //! Plugins must implement the C FFI interface defined in `PluginFFI`.
 
+use crate::config::PluginsConfig;
 use crate::plugins::traits::{KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult};
 use anyhow::{anyhow, Context, Result};
 use libloading::{Library, Symbol};
@@ -16,6 +17,35 @@ use tracing::{debug, error, info, warn};
 /// Increment when breaking changes are made to the FFI interface
 const PLUGIN_ABI_VERSION: u32 = 1;
 
+/// Optional entry points a plugin implements, returned by the optional
+/// `plugin_get_capabilities() -> u32` export as bitflags
+///
+/// Plugins without the export predate it and get [`PluginCapabilities::BASIC`],
+/// everything ABI version 1 required. Unknown bits are ignored so newer plugins
+/// keep loading in older launchers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginCapabilities(u32);
+
+impl PluginCapabilities {
+    /// Exports `plugin_handle_keyboard_event`
+    pub const KEYBOARD_EVENTS: Self = Self(1 << 0);
+    /// `plugin_search` returns [`CPluginResultWithIcon`] elements, which may carry
+    /// an inline PNG icon; `plugin_free_results` frees the icon buffers too
+    pub const INLINE_ICONS: Self = Self(1 << 1);
+    /// What plugins without `plugin_get_capabilities` are assumed to implement
+    pub const BASIC: Self = Self::KEYBOARD_EVENTS;
+
+    /// Capabilities from the bits a plugin returned
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Whether all capabilities in `other` are set
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
 /// Plugin loading metrics
 #[derive(Debug, Clone)]
 pub struct PluginMetrics {
@@ -39,9 +69,15 @@ impl PluginMetrics {
         self.load_time.as_millis() > 10
     }
 
-    /// Check if this plugin is very slow (>50ms load time)
-    pub fn is_very_slow(&self) -> bool {
-        self.load_time.as_millis() > 50
+    /// Check if this plugin is very slow (load time above `threshold_ms`,
+    /// `plugins.slow_warn_ms` in config)
+    pub fn is_very_slow(&self, threshold_ms: u64) -> bool {
+        self.load_time.as_millis() > u128::from(threshold_ms)
+    }
+
+    /// Identifier used in `plugins.disabled`: the library file stem
+    pub fn plugin_id(&self) -> String {
+        plugin_id(&self.path)
     }
 
     /// Check if this plugin uses a lot of memory (>5MB)
@@ -88,6 +124,23 @@ impl CStringSlice {
     }
 }
 
+/// C-compatible byte buffer
+#[repr(C)]
+pub struct CByteSlice {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+impl CByteSlice {
+    /// Copy the bytes out; `None` for an empty buffer
+    unsafe fn to_vec(&self) -> Option<Vec<u8>> {
+        if self.ptr.is_null() || self.len == 0 {
+            return None;
+        }
+        Some(std::slice::from_raw_parts(self.ptr, self.len).to_vec())
+    }
+}
+
 /// C-compatible result array
 #[repr(C)]
 pub struct CResultArray {
@@ -107,6 +160,16 @@ pub struct CPluginResult {
     pub score: i64,
 }
 
+/// C-compatible plugin result with an optional inline PNG icon, used by plugins
+/// advertising [`PluginCapabilities::INLINE_ICONS`]
+///
+/// An empty `icon_data` falls back to the `icon` name or path of `result`.
+#[repr(C)]
+pub struct CPluginResultWithIcon {
+    pub result: CPluginResult,
+    pub icon_data: CByteSlice,
+}
+
 /// C-compatible plugin context
 #[repr(C)]
 pub struct CPluginContext {
@@ -162,8 +225,9 @@ pub struct PluginFFI {
     pub should_handle: unsafe extern "C" fn(query: CStringSlice) -> bool,
     /// Search for results
     pub search: unsafe extern "C" fn(query: CStringSlice, context: CPluginContext) -> CResultArray,
-    /// Handle keyboard event
-    pub handle_keyboard_event: unsafe extern "C" fn(event: CKeyboardEvent) -> CKeyboardActionData,
+    /// Handle keyboard event; only resolved with [`PluginCapabilities::KEYBOARD_EVENTS`]
+    pub handle_keyboard_event:
+        Option<unsafe extern "C" fn(event: CKeyboardEvent) -> CKeyboardActionData>,
     /// Free result array (plugin must provide this to free its own memory)
     pub free_results: unsafe extern "C" fn(results: CResultArray),
     /// Free string data (plugin must provide this)
@@ -178,6 +242,8 @@ pub struct DynamicPlugin {
     #[allow(dead_code)]
     library: Library,
     ffi: PluginFFI,
+    /// Optional entry points the plugin implements
+    capabilities: PluginCapabilities,
     /// Metrics collected during plugin loading
     pub metrics: PluginMetrics,
 }
@@ -233,12 +299,24 @@ impl DynamicPlugin {
                 .context("Missing plugin_search")?
         };
 
-        let handle_keyboard_event: Symbol<
-            unsafe extern "C" fn(CKeyboardEvent) -> CKeyboardActionData,
-        > = unsafe {
+        // Optional: plugins built before capabilities existed implement the basics
+        let capabilities = unsafe {
             library
-                .get(b"plugin_handle_keyboard_event")
-                .context("Missing plugin_handle_keyboard_event")?
+                .get::<unsafe extern "C" fn() -> u32>(b"plugin_get_capabilities")
+                .map(|get_capabilities| PluginCapabilities::from_bits(get_capabilities()))
+                .unwrap_or(PluginCapabilities::BASIC)
+        };
+        debug!("Plugin capabilities: {:?}", capabilities);
+
+        let handle_keyboard_event = if capabilities.contains(PluginCapabilities::KEYBOARD_EVENTS) {
+            let symbol: Symbol<unsafe extern "C" fn(CKeyboardEvent) -> CKeyboardActionData> = unsafe {
+                library
+                    .get(b"plugin_handle_keyboard_event")
+                    .context("Missing plugin_handle_keyboard_event")?
+            };
+            Some(*symbol)
+        } else {
+            None
         };
 
         let free_results: Symbol<unsafe extern "C" fn(CResultArray)> = unsafe {
@@ -288,7 +366,7 @@ impl DynamicPlugin {
             get_priority: *get_priority,
             should_handle: *should_handle,
             search: *search,
-            handle_keyboard_event: *handle_keyboard_event,
+            handle_keyboard_event,
             free_results: *free_results,
             free_string: *free_string,
         };
@@ -310,14 +388,6 @@ impl DynamicPlugin {
             path.display()
         );
 
-        // Warn if plugin is slow
-        if load_time.as_millis() > 50 {
-            warn!(
-                "Plugin '{}' took {:?} to load (>50ms threshold)",
-                name, load_time
-            );
-        }
-
         let metrics = PluginMetrics {
             load_time,
             memory_bytes,
@@ -332,17 +402,46 @@ impl DynamicPlugin {
             priority,
             library,
             ffi,
+            capabilities,
             metrics,
         })
     }
 }
 
+impl DynamicPlugin {
+    /// Build a `PluginResult` from a plugin's C result
+    unsafe fn convert_result(
+        &self,
+        c_result: &CPluginResult,
+        icon_data: Option<Vec<u8>>,
+    ) -> Result<PluginResult> {
+        let title = c_result.title.to_string()?;
+        let subtitle = c_result.subtitle.to_string().ok();
+        let icon = c_result.icon.to_string().ok();
+        let command = c_result.command.to_string()?;
+
+        let mut result =
+            PluginResult::new(title, command, self.name.clone()).with_score(c_result.score);
+        if let Some(sub) = subtitle.filter(|s| !s.is_empty()) {
+            result = result.with_subtitle(sub);
+        }
+        if let Some(ico) = icon.filter(|s| !s.is_empty()) {
+            result = result.with_icon(ico);
+        }
+        if let Some(data) = icon_data {
+            result = result.with_icon_data(data);
+        }
+        Ok(result.with_terminal(c_result.terminal))
+    }
+}
+
 impl std::fmt::Debug for DynamicPlugin {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DynamicPlugin")
             .field("name", &self.name)
             .field("description", &self.description)
             .field("priority", &self.priority)
+            .field("capabilities", &self.capabilities)
             .finish()
     }
 }
@@ -380,41 +479,47 @@ impl Plugin for DynamicPlugin {
 
         let c_results = unsafe { (self.ffi.search)(query_slice, c_context) };
 
-        // Convert C results to Rust
-        let mut results = Vec::new();
-        if !c_results.ptr.is_null() {
+        // Convert C results to Rust, copying everything before the plugin frees it
+        let converted = if c_results.ptr.is_null() {
+            Ok(Vec::new())
+        } else if self.capabilities.contains(PluginCapabilities::INLINE_ICONS) {
+            unsafe {
+                let slice = std::slice::from_raw_parts(
+                    c_results.ptr as *const CPluginResultWithIcon,
+                    c_results.len,
+                );
+                slice
+                    .iter()
+                    .map(|c| self.convert_result(&c.result, c.icon_data.to_vec()))
+                    .collect()
+            }
+        } else {
             unsafe {
                 let slice = std::slice::from_raw_parts(c_results.ptr, c_results.len);
-                for c_result in slice {
-                    let title = c_result.title.to_string()?;
-                    let subtitle = c_result.subtitle.to_string().ok();
-                    let icon = c_result.icon.to_string().ok();
-                    let command = c_result.command.to_string()?;
-
-                    let mut result = PluginResult::new(title, command, self.name.clone())
-                        .with_score(c_result.score);
-                    if let Some(sub) = subtitle.filter(|s| !s.is_empty()) {
-                        result = result.with_subtitle(sub);
-                    }
-                    if let Some(ico) = icon.filter(|s| !s.is_empty()) {
-                        result = result.with_icon(ico);
-                    }
-                    result = result.with_terminal(c_result.terminal);
-
-                    results.push(result);
-                }
+                slice
+                    .iter()
+                    .map(|c_result| self.convert_result(c_result, None))
+                    .collect()
             }
-        }
+        };
 
         // Free C memory
         unsafe {
             (self.ffi.free_results)(c_results);
         }
 
-        Ok(results)
+        converted
+    }
+
+    fn handles_keyboard_events(&self) -> bool {
+        self.ffi.handle_keyboard_event.is_some()
     }
 
     fn handle_keyboard_event(&self, event: &KeyboardEvent) -> KeyboardAction {
+        let Some(handle_keyboard_event) = self.ffi.handle_keyboard_event else {
+            return KeyboardAction::None;
+        };
+
         let query_cstr = match CString::new(event.query.clone()) {
             Ok(s) => s,
             Err(_) => return KeyboardAction::None,
@@ -428,7 +533,7 @@ impl Plugin for DynamicPlugin {
             has_selection: event.has_selection,
         };
 
-        let c_action = unsafe { (self.ffi.handle_keyboard_event)(c_event) };
+        let c_action = unsafe { handle_keyboard_event(c_event) };
 
         let action = match c_action.action {
             CKeyboardAction::None => KeyboardAction::None,
@@ -455,9 +560,10 @@ impl Plugin for DynamicPlugin {
     }
 }
 
-/// Discover and load all plugins from standard directories
+/// Discover and load all plugins from standard directories, skipping those in
+/// `plugins.disabled` and warning about any slower than `plugins.slow_warn_ms`
 /// Returns tuple of (plugins, all_metrics)
-pub fn load_plugins() -> (Vec<Box<dyn Plugin>>, Vec<PluginMetrics>) {
+pub fn load_plugins(config: &PluginsConfig) -> (Vec<Box<dyn Plugin>>, Vec<PluginMetrics>) {
     let mut plugins: Vec<Box<dyn Plugin>> = Vec::new();
     let mut all_metrics: Vec<PluginMetrics> = Vec::new();
 
@@ -482,6 +588,11 @@ pub fn load_plugins() -> (Vec<Box<dyn Plugin>>, Vec<PluginMetrics>) {
                         continue;
                     }
 
+                    if config.is_dynamic_disabled(&plugin_id(&path)) {
+                        info!("Skipping disabled plugin: {}", path.display());
+                        continue;
+                    }
+
                     match DynamicPlugin::load(&path) {
                         Ok(plugin) => {
                             info!("Successfully loaded plugin: {}", plugin.name());
@@ -537,13 +648,14 @@ pub fn load_plugins() -> (Vec<Box<dyn Plugin>>, Vec<PluginMetrics>) {
         // Warn about slow plugins
         let slow_plugins: Vec<_> = all_metrics
             .iter()
-            .filter(|m| m.success && m.is_very_slow())
+            .filter(|m| m.success && m.is_very_slow(config.slow_warn_ms))
             .collect();
 
         if !slow_plugins.is_empty() {
             warn!(
-                "Detected {} slow plugins (>50ms load time):",
-                slow_plugins.len()
+                "Detected {} slow plugins (>{}ms load time):",
+                slow_plugins.len(),
+                config.slow_warn_ms
             );
             for metric in slow_plugins {
                 warn!(
@@ -562,13 +674,31 @@ pub fn load_plugins() -> (Vec<Box<dyn Plugin>>, Vec<PluginMetrics>) {
     (plugins, all_metrics)
 }
 
+/// Library file stem of a plugin path (`libmy_plugin.so` -> `libmy_plugin`)
+fn plugin_id(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// User plugin directory under `config_dir` (`$XDG_CONFIG_HOME/native-launcher/plugins`)
+pub(crate) fn user_plugins_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join("native-launcher").join("plugins")
+}
+
 /// Get plugin search paths in order of priority
 fn get_plugin_search_paths() -> Vec<PathBuf> {
+    plugin_search_paths(dirs::config_dir().as_deref())
+}
+
+/// Plugin search paths in order of priority, with user plugins under `config_dir`
+fn plugin_search_paths(config_dir: Option<&Path>) -> Vec<PathBuf> {
     let mut paths = Vec::new();
 
     // User plugins (highest priority)
-    if let Some(config_dir) = dirs::config_dir() {
-        paths.push(config_dir.join("native-launcher/plugins"));
+    if let Some(config_dir) = config_dir {
+        paths.push(user_plugins_dir(config_dir));
     }
 
     // System plugins
@@ -589,6 +719,230 @@ mod tests {
         assert!(paths[0].to_string_lossy().contains("config"));
     }
 
+    #[test]
+    fn test_plugin_dir_follows_config_dir() {
+        let custom = std::env::temp_dir().join("native-launcher-xdg-config");
+
+        let paths = plugin_search_paths(Some(&custom));
+        let script_dirs =
+            crate::plugins::script_plugin::ScriptPluginManager::plugin_directories(Some(&custom));
+
+        let expected = custom.join("native-launcher").join("plugins");
+        assert_eq!(paths[0], expected);
+        assert_eq!(script_dirs[0], expected);
+    }
+
+    #[test]
+    fn test_slow_threshold_is_configurable() {
+        let metrics = PluginMetrics {
+            load_time: Duration::from_millis(30),
+            memory_bytes: 0,
+            path: PathBuf::from("/plugins/libslow_plugin.so"),
+            success: true,
+            error: None,
+        };
+
+        assert!(!metrics.is_very_slow(PluginsConfig::default().slow_warn_ms));
+        assert!(metrics.is_very_slow(20));
+        assert!(!metrics.is_very_slow(30));
+        assert_eq!(metrics.plugin_id(), "libslow_plugin");
+    }
+
+    static KEYBOARD_CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    fn empty_slice() -> CStringSlice {
+        CStringSlice {
+            ptr: std::ptr::null(),
+            len: 0,
+        }
+    }
+
+    unsafe extern "C" fn abi_version() -> u32 {
+        PLUGIN_ABI_VERSION
+    }
+    unsafe extern "C" fn text() -> CStringSlice {
+        empty_slice()
+    }
+    unsafe extern "C" fn priority() -> c_int {
+        100
+    }
+    unsafe extern "C" fn should_handle(_: CStringSlice) -> bool {
+        false
+    }
+    unsafe extern "C" fn search(_: CStringSlice, _: CPluginContext) -> CResultArray {
+        CResultArray {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+            capacity: 0,
+        }
+    }
+    unsafe extern "C" fn free_results(_: CResultArray) {}
+    unsafe extern "C" fn free_string(_: CStringSlice) {}
+    unsafe extern "C" fn counting_keyboard_handler(_: CKeyboardEvent) -> CKeyboardActionData {
+        KEYBOARD_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        CKeyboardActionData {
+            action: CKeyboardAction::Handled,
+            data: empty_slice(),
+            terminal: false,
+        }
+    }
+
+    /// A plugin backed by functions in this test binary, with the keyboard
+    /// handler resolved the way `DynamicPlugin::load` would for `capabilities`
+    fn in_process_plugin(capabilities: PluginCapabilities) -> DynamicPlugin {
+        let handle_keyboard_event = capabilities
+            .contains(PluginCapabilities::KEYBOARD_EVENTS)
+            .then_some(counting_keyboard_handler as _);
+
+        DynamicPlugin {
+            name: "in-process".to_string(),
+            description: String::new(),
+            // Ahead of every built-in plugin, so dispatch reaches it first
+            priority: 10_000,
+            library: libloading::os::unix::Library::this().into(),
+            ffi: PluginFFI {
+                get_abi_version: abi_version,
+                get_name: text,
+                get_description: text,
+                get_priority: priority,
+                should_handle,
+                search,
+                handle_keyboard_event,
+                free_results,
+                free_string,
+            },
+            capabilities,
+            metrics: PluginMetrics {
+                load_time: Duration::ZERO,
+                memory_bytes: 0,
+                path: PathBuf::from("/plugins/libin_process.so"),
+                success: true,
+                error: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_plugin_without_keyboard_capability_gets_no_events() {
+        use crate::desktop::DesktopEntryArena;
+        use crate::plugins::PluginManager;
+        use gtk4::gdk::{Key, ModifierType};
+
+        assert!(PluginCapabilities::BASIC.contains(PluginCapabilities::KEYBOARD_EVENTS));
+        assert!(!PluginCapabilities::from_bits(0b10).contains(PluginCapabilities::KEYBOARD_EVENTS));
+
+        let event = KeyboardEvent::new(Key::Return, ModifierType::CONTROL_MASK, "q".into(), true);
+        let config = crate::config::Config::default();
+
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        let plugin = in_process_plugin(PluginCapabilities::from_bits(0));
+        assert!(!plugin.handles_keyboard_events());
+        assert!(matches!(
+            plugin.handle_keyboard_event(&event),
+            KeyboardAction::None
+        ));
+        manager.register_plugin(Box::new(plugin));
+        manager.dispatch_keyboard_event(&event);
+        assert_eq!(KEYBOARD_CALLS.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        // Advertising the capability routes events to the plugin again
+        let plugin = in_process_plugin(PluginCapabilities::KEYBOARD_EVENTS);
+        assert!(matches!(
+            plugin.handle_keyboard_event(&event),
+            KeyboardAction::Handled
+        ));
+        assert_eq!(KEYBOARD_CALLS.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    static ICON_PNG: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+    static FREED_ICON: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    fn str_slice(s: &'static str) -> CStringSlice {
+        CStringSlice {
+            ptr: s.as_ptr() as *const c_char,
+            len: s.len(),
+        }
+    }
+
+    unsafe extern "C" fn search_with_icon(_: CStringSlice, _: CPluginContext) -> CResultArray {
+        let png = ICON_PNG.get().expect("icon generated by the test");
+        // Hand out a copy the plugin owns, like a real plugin would
+        let icon = Box::leak(png.clone().into_boxed_slice());
+        let results = vec![CPluginResultWithIcon {
+            result: CPluginResult {
+                title: str_slice("Weather"),
+                subtitle: str_slice("Sunny, 21°C"),
+                icon: empty_slice(),
+                command: str_slice("xdg-open https://wttr.in"),
+                terminal: false,
+                score: 900,
+            },
+            icon_data: CByteSlice {
+                ptr: icon.as_ptr(),
+                len: icon.len(),
+            },
+        }];
+        let mut results = std::mem::ManuallyDrop::new(results);
+        CResultArray {
+            ptr: results.as_mut_ptr() as *mut CPluginResult,
+            len: results.len(),
+            capacity: results.capacity(),
+        }
+    }
+
+    unsafe extern "C" fn free_results_with_icon(array: CResultArray) {
+        let results = Vec::from_raw_parts(
+            array.ptr as *mut CPluginResultWithIcon,
+            array.len,
+            array.capacity,
+        );
+        for result in results {
+            let icon = &result.icon_data;
+            FREED_ICON.store(icon.ptr as usize, std::sync::atomic::Ordering::SeqCst);
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                icon.ptr as *mut u8,
+                icon.len,
+            )));
+        }
+    }
+
+    #[test]
+    fn test_inline_icon_bytes_decode_and_are_freed() {
+        use crate::utils::icons::pixbuf_from_png;
+        use gtk4::gdk_pixbuf::{Colorspace, Pixbuf};
+
+        let source = Pixbuf::new(Colorspace::Rgb, true, 8, 64, 32).unwrap();
+        source.fill(0x3478f6ff);
+        let png = source.save_to_bufferv("png", &[]).unwrap();
+        ICON_PNG.set(png.clone()).unwrap();
+
+        let mut plugin = in_process_plugin(PluginCapabilities::INLINE_ICONS);
+        plugin.ffi.search = search_with_icon;
+        plugin.ffi.free_results = free_results_with_icon;
+
+        let config = crate::config::Config::default();
+        let results = plugin
+            .search("weather", &PluginContext::new(10, &config))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Weather");
+        assert_eq!(results[0].icon, None);
+
+        // The launcher kept its own copy of the bytes...
+        let icon_data = results[0].icon_data.as_deref().unwrap();
+        assert_eq!(icon_data, png.as_slice());
+        // ...and the plugin's buffer went back through plugin_free_results
+        let freed = FREED_ICON.load(std::sync::atomic::Ordering::SeqCst);
+        assert_ne!(freed, 0);
+        assert_ne!(freed, icon_data.as_ptr() as usize);
+
+        let pixbuf = pixbuf_from_png(icon_data, 48).unwrap();
+        assert_eq!((pixbuf.width(), pixbuf.height()), (48, 24));
+
+        assert!(pixbuf_from_png(b"not a png", 48).is_err());
+    }
+
     #[test]
     fn test_abi_version() {
         assert_eq!(PLUGIN_ABI_VERSION, 1);
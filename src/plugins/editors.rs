@@ -711,6 +711,15 @@ impl Plugin for EditorsPlugin {
                 parent_app: Some(workspace.editor.clone()),
                 desktop_path: None,
                 badge_icon: None, // No badge for editor workspaces
+                is_directory: false,
+                target_path: Some(workspace.path.to_string_lossy().to_string()),
+                confirm: None,
+                icon_data: None,
+                actions: Vec::new(),
+                url: None,
+                badges: Vec::new(),
+                completion: None,
+                activation: None,
             });
 
             if results.len() >= context.max_results {
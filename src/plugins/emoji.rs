@@ -1,7 +1,8 @@
 use super::traits::{KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult};
+use crate::utils::clipboard::{self, ClipboardTool};
+use crate::utils::shell_escape;
 use anyhow::Result;
 use serde::Deserialize;
-use std::process::Command;
 use std::sync::OnceLock;
 
 #[derive(Debug)]
@@ -12,7 +13,7 @@ pub struct EmojiPlugin {
 
 impl EmojiPlugin {
     pub fn new() -> Self {
-        let clipboard = detect_clipboard_tool();
+        let clipboard = clipboard::detect_tool();
         Self {
             enabled: true,
             clipboard,
@@ -70,17 +71,8 @@ impl EmojiPlugin {
 
     fn build_copy_command(&self, ch: &str) -> String {
         let content = shell_escape(ch);
-        if let Some(tool) = &self.clipboard {
-            let pipe = match tool {
-                ClipboardTool::WlCopy { command } => format!("printf {} | {}", content, command),
-                ClipboardTool::Xclip { command } => {
-                    format!("printf {} | {} -selection clipboard", content, command)
-                }
-                ClipboardTool::Xsel { command } => {
-                    format!("printf {} | {} --clipboard --input", content, command)
-                }
-            };
-            return format!("sh -c {}", shell_escape(&pipe));
+        if let Some(tool) = self.clipboard {
+            return format!("sh -c {}", shell_escape(&tool.copy_command(ch)));
         }
         // Fallback: try wl-copy then xclip then xsel
         let pipe = format!(
@@ -161,58 +153,6 @@ fn load_emoji_db() -> Vec<EmojiRecord> {
     }
 }
 
-#[derive(Debug, Clone)]
-enum ClipboardTool {
-    WlCopy { command: String },
-    Xclip { command: String },
-    Xsel { command: String },
-}
-
-fn detect_clipboard_tool() -> Option<ClipboardTool> {
-    if let Some(cmd) = command_path("wl-copy") {
-        return Some(ClipboardTool::WlCopy { command: cmd });
-    }
-    if let Some(cmd) = command_path("xclip") {
-        return Some(ClipboardTool::Xclip { command: cmd });
-    }
-    if let Some(cmd) = command_path("xsel") {
-        return Some(ClipboardTool::Xsel { command: cmd });
-    }
-    None
-}
-
-fn command_path(command: &str) -> Option<String> {
-    Command::new("which")
-        .arg(command)
-        .output()
-        .ok()
-        .filter(|o| o.status.success())
-        .and_then(|o| {
-            let path = String::from_utf8_lossy(&o.stdout).trim().to_string();
-            if path.is_empty() {
-                None
-            } else {
-                Some(path)
-            }
-        })
-}
-
-fn shell_escape(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_string();
-    }
-    let mut escaped = String::from("'");
-    for ch in value.chars() {
-        if ch == '\'' {
-            escaped.push_str("'\\''");
-        } else {
-            escaped.push(ch);
-        }
-    }
-    escaped.push('\'');
-    escaped
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,9 +175,7 @@ mod tests {
     fn builds_copy_command() {
         let plugin = EmojiPlugin {
             enabled: true,
-            clipboard: Some(ClipboardTool::WlCopy {
-                command: "wl-copy".to_string(),
-            }),
+            clipboard: Some(ClipboardTool::WlCopy),
         };
         let cmd = plugin.build_copy_command("😀");
         assert!(cmd.starts_with("sh -c "));
@@ -0,0 +1,332 @@
+//! Encode/decode plugin (`@encode` / `@decode`)
+//!
+//! Transforms the typed text with base64, URL (percent) encoding or hex, e.g.
+//! `@encode base64 hello` or `@decode url Hello%20World`. Without a codec name
+//! every codec is tried. Activating a result copies its output to the clipboard.
+
+use super::traits::{Plugin, PluginContext, PluginResult, ResultActivation};
+use anyhow::{anyhow, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+/// Supported encodings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Base64,
+    Url,
+    Hex,
+}
+
+impl Codec {
+    const ALL: [Codec; 3] = [Codec::Base64, Codec::Url, Codec::Hex];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "base64" | "b64" => Some(Codec::Base64),
+            "url" | "uri" | "percent" => Some(Codec::Url),
+            "hex" => Some(Codec::Hex),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Codec::Base64 => "base64",
+            Codec::Url => "url",
+            Codec::Hex => "hex",
+        }
+    }
+
+    /// Encode `input`
+    pub fn encode(self, input: &str) -> String {
+        match self {
+            Codec::Base64 => BASE64.encode(input),
+            Codec::Url => urlencoding::encode(input).into_owned(),
+            Codec::Hex => input.bytes().map(|b| format!("{:02x}", b)).collect(),
+        }
+    }
+
+    /// Decode `input`; the decoded bytes must be UTF-8 text
+    pub fn decode(self, input: &str) -> Result<String> {
+        let bytes = match self {
+            Codec::Base64 => BASE64
+                .decode(input)
+                .map_err(|e| anyhow!("Not valid base64: {}", e))?,
+            Codec::Url => {
+                return urlencoding::decode(input)
+                    .map(|text| text.into_owned())
+                    .context("Decoded URL is not UTF-8 text");
+            }
+            Codec::Hex => decode_hex(input)?,
+        };
+        String::from_utf8(bytes).context("Decoded bytes are not UTF-8 text")
+    }
+
+    /// Whether `input` is plausibly encoded with this codec, used to offer
+    /// decoding from `@encode` too
+    fn looks_encoded(self, input: &str) -> bool {
+        match self {
+            Codec::Base64 => {
+                input.len() >= 4
+                    && input.len().is_multiple_of(4)
+                    && input
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='))
+                    && self.decode(input).is_ok()
+            }
+            Codec::Url => input.contains('%') && self.decode(input).is_ok(),
+            Codec::Hex => false,
+        }
+    }
+}
+
+/// Which way to transform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Encode,
+    Decode,
+}
+
+impl Direction {
+    fn label(self) -> &'static str {
+        match self {
+            Direction::Encode => "encode",
+            Direction::Decode => "decode",
+        }
+    }
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>> {
+    let digits: Vec<u8> = input.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(anyhow!("Not valid hex: odd number of digits"));
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).unwrap_or_default();
+            u8::from_str_radix(pair, 16).map_err(|_| anyhow!("Not valid hex: '{}'", pair))
+        })
+        .collect()
+}
+
+/// One transform offered for a query
+#[derive(Debug)]
+struct Transform {
+    codec: Codec,
+    direction: Direction,
+    output: Result<String>,
+}
+
+#[derive(Debug)]
+pub struct EncoderPlugin {
+    enabled: bool,
+}
+
+impl EncoderPlugin {
+    pub fn new() -> Self {
+        Self { enabled: true }
+    }
+
+    /// Direction and arguments of `query`, if it starts with one of the prefixes
+    fn split_prefix(query: &str) -> Option<(Direction, &str)> {
+        let (direction, rest) = if let Some(rest) = query.strip_prefix("@encode") {
+            (Direction::Encode, rest)
+        } else {
+            (Direction::Decode, query.strip_prefix("@decode")?)
+        };
+        // "@encoder" is not "@encode r"
+        (rest.is_empty() || rest.starts_with(char::is_whitespace)).then_some((direction, rest))
+    }
+
+    /// Transforms for `query`, in display order
+    fn transforms(&self, query: &str) -> Vec<Transform> {
+        let Some((direction, rest)) = Self::split_prefix(query) else {
+            return Vec::new();
+        };
+
+        // "@encode base64 hello" names the codec; "@encode hello" tries them all
+        let rest = rest.trim();
+        let (name, named_input) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let (codecs, input) = match Codec::from_name(name) {
+            Some(codec) => (vec![codec], named_input.trim()),
+            None => (Codec::ALL.to_vec(), rest),
+        };
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let named = codecs.len() == 1;
+        let mut transforms = Vec::new();
+        for codec in codecs {
+            let output = match direction {
+                Direction::Encode => Ok(codec.encode(input)),
+                Direction::Decode => codec.decode(input),
+            };
+            // Trying every codec: only show decoders that understood the input
+            if direction == Direction::Decode && !named && output.is_err() {
+                continue;
+            }
+            transforms.push(Transform {
+                codec,
+                direction,
+                output,
+            });
+
+            // Encoding something that already looks encoded: offer decoding too
+            if direction == Direction::Encode && codec.looks_encoded(input) {
+                transforms.push(Transform {
+                    codec,
+                    direction: Direction::Decode,
+                    output: codec.decode(input),
+                });
+            }
+        }
+
+        if transforms.is_empty() {
+            transforms.push(Transform {
+                codec: Codec::Base64,
+                direction,
+                output: Err(anyhow!("Not valid base64, url or hex")),
+            });
+        }
+        transforms
+    }
+}
+
+impl Default for EncoderPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for EncoderPlugin {
+    fn name(&self) -> &str {
+        "encoder"
+    }
+
+    fn description(&self) -> &str {
+        "Base64, URL and hex encoding via @encode / @decode"
+    }
+
+    fn command_prefixes(&self) -> Vec<&str> {
+        vec!["@encode", "@decode"]
+    }
+
+    fn should_handle(&self, query: &str) -> bool {
+        Self::split_prefix(query).is_some()
+    }
+
+    fn search(&self, query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+        if !self.enabled {
+            return Ok(Vec::new());
+        }
+
+        let results = self
+            .transforms(query)
+            .into_iter()
+            .enumerate()
+            .map(|(i, transform)| {
+                let kind = format!(
+                    "{} {}",
+                    transform.codec.label(),
+                    transform.direction.label()
+                );
+                match transform.output {
+                    Ok(output) => {
+                        PluginResult::new(output.clone(), String::new(), self.name().to_string())
+                            .with_subtitle(format!("{} • Enter to copy", kind))
+                            .with_icon("edit-copy".to_string())
+                            .with_activation(ResultActivation::Copy(output))
+                    }
+                    Err(e) => PluginResult::new(
+                        format!("Can't {}", kind),
+                        String::new(),
+                        self.name().to_string(),
+                    )
+                    .with_subtitle(e.to_string())
+                    .with_icon("dialog-error".to_string()),
+                }
+                .with_score(10000 - i as i64)
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    fn priority(&self) -> i32 {
+        500
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_round_trip() {
+        let text = "Hello, wörld! 👋";
+        let encoded = Codec::Base64.encode(text);
+        assert_eq!(encoded, "SGVsbG8sIHfDtnJsZCEg8J+Riw==");
+        assert_eq!(Codec::Base64.decode(&encoded).unwrap(), text);
+        assert!(Codec::Base64.decode("not base64!").is_err());
+
+        // Encoding text that is already base64 offers decoding it as well
+        let plugin = EncoderPlugin::new();
+        let transforms = plugin.transforms("@encode base64 aGVsbG8=");
+        assert_eq!(transforms.len(), 2);
+        assert_eq!(transforms[0].output.as_ref().unwrap(), "YUdWc2JHOD0=");
+        assert_eq!(transforms[1].direction, Direction::Decode);
+        assert_eq!(transforms[1].output.as_ref().unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_url_decoding_special_characters() {
+        assert_eq!(
+            Codec::Url
+                .decode("Hello%20World%21%20a%2Bb%3Dc%26d%3F%C3%A9")
+                .unwrap(),
+            "Hello World! a+b=c&d?é"
+        );
+        assert_eq!(Codec::Url.encode("a b&c/é"), "a%20b%26c%2F%C3%A9");
+        assert!(Codec::Url.decode("%FF%FE").is_err());
+        assert_eq!(Codec::Hex.decode("68 69").unwrap(), "hi");
+        assert!(Codec::Hex.decode("6").is_err());
+
+        let plugin = EncoderPlugin::new();
+        let config = crate::config::Config::default();
+        let context = PluginContext::new(10, &config);
+        let results = plugin
+            .search("@decode url Hello%20World", &context)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Hello World");
+
+        // Activating copies the output
+        assert!(results[0].command.is_empty());
+        assert!(
+            matches!(&results[0].activation, Some(ResultActivation::Copy(text)) if text == "Hello World")
+        );
+
+        // Any whitespace separates the codec name, and the prefix must be a word
+        let results = plugin
+            .search("@decode\turl\tHello%20World", &context)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Hello World");
+        assert!(plugin.search("@decode url", &context).unwrap().is_empty());
+        assert!(!plugin.should_handle("@encoder"));
+
+        // Invalid input explains itself instead of failing
+        let results = plugin.search("@decode hex zz", &context).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Can't hex decode");
+        assert!(results[0].command.is_empty());
+        assert_eq!(results[0].subtitle.as_deref(), Some("Not valid hex: 'zz'"));
+    }
+}
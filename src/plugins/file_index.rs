@@ -13,6 +13,7 @@ use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::path::{Component, Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use tracing::{debug, warn};
@@ -92,6 +93,8 @@ pub struct FileIndexService {
     max_results: usize,
     /// Search timeout
     timeout: Duration,
+    /// Set while a `prime` run is in flight
+    priming: Arc<AtomicBool>,
 }
 
 impl std::fmt::Debug for FileIndexService {
@@ -117,6 +120,7 @@ impl FileIndexService {
             cache_ttl: Duration::from_secs(120), // 2 minutes
             max_results: 50,
             timeout: Duration::from_secs(3),
+            priming: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -465,6 +469,19 @@ impl FileIndexService {
     /// * `None` - Search running in background, callback will be called
     #[allow(dead_code)]
     pub fn search_async<F>(&self, query: String, callback: F) -> Option<Vec<PathBuf>>
+    where
+        F: FnOnce(Result<Vec<PathBuf>>) + Send + 'static,
+    {
+        self.search_in_background(query, move |results| {
+            gtk4::glib::idle_add_once(move || callback(results));
+        })
+    }
+
+    /// Like [`search_async`](Self::search_async), but without GTK: the callback
+    /// runs on the background thread itself, so this works without a GTK main
+    /// loop (headless use, tests)
+    #[allow(dead_code)]
+    pub fn search_in_background<F>(&self, query: String, callback: F) -> Option<Vec<PathBuf>>
     where
         F: FnOnce(Result<Vec<PathBuf>>) + Send + 'static,
     {
@@ -528,10 +545,7 @@ impl FileIndexService {
             // Sort results by relevance
             let final_results = results.map(|paths| Self::sort_by_relevance_static(paths, &query));
 
-            // Call callback on GTK main thread
-            gtk4::glib::idle_add_once(move || {
-                callback(final_results);
-            });
+            callback(final_results);
         });
 
         None // Results will arrive via callback
@@ -693,6 +707,37 @@ impl FileIndexService {
         }
     }
 
+    /// Read the locate database in the background so it is in the page cache
+    /// before the first search; nothing to prime for fd and find
+    pub fn prime(&self) {
+        let backend = self.backend;
+        let is_locate = matches!(
+            backend,
+            IndexBackend::Plocate | IndexBackend::Mlocate | IndexBackend::Locate
+        );
+        if !is_locate || self.priming.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let priming = self.priming.clone();
+        std::thread::spawn(move || {
+            let start = std::time::Instant::now();
+            let primed = Command::new(backend.command())
+                .arg("--limit")
+                .arg("1")
+                .arg("--basename")
+                .arg("native-launcher")
+                .output();
+            debug!(
+                "Primed {} database in {:?} ({})",
+                backend.command(),
+                start.elapsed(),
+                if primed.is_ok() { "ok" } else { "failed" }
+            );
+            priming.store(false, Ordering::Release);
+        });
+    }
+
     /// Get backend info for debugging
     pub fn backend_info(&self) -> String {
         format!(
@@ -1,11 +1,16 @@
 use super::file_index::FileIndexService;
-use super::traits::{Plugin, PluginContext, PluginResult};
+use super::traits::{Plugin, PluginContext, PluginResult, ResultAction};
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
 
-use crate::utils::build_open_command;
+use crate::desktop::SharedArena;
+use crate::utils::mime::{mime_for_extension, mime_for_path};
+use crate::utils::{build_open_command, expand_exec};
 
 /// Recent file entry from recently-used.xbel
 #[derive(Debug, Clone)]
@@ -18,8 +23,31 @@ struct RecentFile {
     #[allow(dead_code)]
     mime_type: Option<String>,
     /// Last modified timestamp
-    #[allow(dead_code)]
     modified: Option<i64>,
+    /// Last access timestamp
+    accessed: Option<i64>,
+}
+
+/// Order of recent files (`[plugins.files] recent_sort`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecentSort {
+    /// As listed in recently-used.xbel
+    Xbel,
+    /// Most recently modified first
+    Modified,
+    /// Most recently accessed first
+    Accessed,
+}
+
+impl RecentSort {
+    /// Order for a `recent_sort` value; unknown values mean `Xbel`
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "modified" => Self::Modified,
+            "accessed" => Self::Accessed,
+            _ => Self::Xbel,
+        }
+    }
 }
 
 /// Plugin for file browsing and recent files
@@ -31,12 +59,22 @@ pub struct FileBrowserPlugin {
     max_recent: usize,
     /// System-wide file index service
     file_index: FileIndexService,
+    /// Minimum search term length before querying the system-wide index
+    min_query_length: usize,
+    /// Installed apps, offered as "open with" sub-results of file results
+    entries: Option<SharedArena>,
+    /// MIME types reported by `xdg-mime`, keyed by extension
+    mime_cache: Mutex<HashMap<String, Option<String>>>,
 }
 
+/// Default minimum search term length for system-wide file search
+const DEFAULT_MIN_QUERY_LENGTH: usize = 3;
+
 impl FileBrowserPlugin {
-    /// Create a new file browser plugin
-    pub fn new(enabled: bool) -> Self {
-        let recent_files = Self::load_recent_files(20).unwrap_or_else(|e| {
+    /// Create a file browser plugin listing only recent files under `allow_dirs`
+    /// (`[plugins.files] recent_allow_dirs`, empty allows all), in `sort` order
+    pub fn with_recent_options(enabled: bool, allow_dirs: &[PathBuf], sort: RecentSort) -> Self {
+        let recent_files = Self::load_recent_files(20, allow_dirs, sort).unwrap_or_else(|e| {
             warn!("Failed to load recent files: {}", e);
             Vec::new()
         });
@@ -54,11 +92,92 @@ impl FileBrowserPlugin {
             enabled,
             max_recent: 20,
             file_index,
+            min_query_length: DEFAULT_MIN_QUERY_LENGTH,
+            entries: None,
+            mime_cache: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Load recent files from GTK's recently-used.xbel
-    fn load_recent_files(max_count: usize) -> Result<Vec<RecentFile>> {
+    /// Offer the apps that can open a file as its sub-results
+    pub fn with_entries(mut self, entries: SharedArena) -> Self {
+        self.entries = Some(entries);
+        self
+    }
+
+    /// Override the minimum search term length for system-wide file search
+    pub fn with_min_query_length(mut self, min_query_length: usize) -> Self {
+        self.min_query_length = min_query_length;
+        self
+    }
+
+    /// MIME type of a file; `xdg-mime` is asked at most once per unknown extension
+    fn file_mime(&self, path: &Path) -> Option<String> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if let Some(mime) = mime_for_extension(&extension) {
+            return Some(mime.to_string());
+        }
+
+        let mut cache = self.mime_cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .entry(extension)
+            .or_insert_with(|| mime_for_path(path))
+            .clone()
+    }
+
+    /// Apps declaring the file's MIME type, each launching the app with the file
+    fn open_with_results(&self, path: &Path) -> Vec<PluginResult> {
+        let Some(entries) = &self.entries else {
+            return Vec::new();
+        };
+        let Some(mime) = self.file_mime(path) else {
+            return Vec::new();
+        };
+
+        let file = path.to_string_lossy();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| file.to_string());
+
+        let mut apps: Vec<_> = entries
+            .load()
+            .iter()
+            .filter(|entry| !entry.hidden && entry.handles_mime(&mime))
+            .cloned()
+            .collect();
+        apps.sort_by_key(|entry| entry.name.to_lowercase());
+
+        apps.iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let mut result = PluginResult::new(
+                    entry.name.clone(),
+                    expand_exec(&entry.exec, &[&file]),
+                    self.name().to_string(),
+                )
+                .with_subtitle(format!("Open {} with {}", file_name, entry.name))
+                .with_terminal(entry.terminal)
+                .with_score(500 - i as i64);
+                if let Some(icon) = &entry.icon {
+                    result = result.with_icon(icon.clone());
+                }
+                result
+            })
+            .collect()
+    }
+
+    /// Whether a search term is long enough to query the system-wide index
+    fn wants_system_search(&self, search_term: &str) -> bool {
+        search_term.chars().count() >= self.min_query_length
+    }
+
+    /// Load recent files from GTK's recently-used.xbel, keeping those under
+    /// `allow_dirs` (all when empty)
+    fn load_recent_files(
+        max_count: usize,
+        allow_dirs: &[PathBuf],
+        sort: RecentSort,
+    ) -> Result<Vec<RecentFile>> {
         let xbel_path = dirs::data_local_dir()
             .context("Failed to get local data directory")?
             .join("recently-used.xbel");
@@ -71,7 +190,24 @@ impl FileBrowserPlugin {
         debug!("Loading recent files from: {}", xbel_path.display());
         let content =
             fs::read_to_string(&xbel_path).context("Failed to read recently-used.xbel")?;
+        let files = Self::parse_recent_files(&content, max_count, allow_dirs, sort);
 
+        debug!("Loaded {} recent files", files.len());
+        Ok(files)
+    }
+
+    /// Existing files bookmarked in recently-used.xbel `content`, limited to
+    /// `allow_dirs` (all when empty); the `max_count` newest are kept when
+    /// sorting by timestamp
+    fn parse_recent_files(
+        content: &str,
+        max_count: usize,
+        allow_dirs: &[PathBuf],
+        sort: RecentSort,
+    ) -> Vec<RecentFile> {
+        let allowed = |path: &Path| {
+            allow_dirs.is_empty() || allow_dirs.iter().any(|dir| path.starts_with(dir))
+        };
         let mut files = Vec::new();
 
         // Simple XML parsing (looking for bookmark tags)
@@ -83,21 +219,27 @@ impl FileBrowserPlugin {
                 if let Some(url) = Self::extract_href(line) {
                     // Convert file:// URL to path
                     if let Some(path) = Self::url_to_path(&url) {
-                        if path.exists() {
+                        if allowed(&path) && path.exists() {
                             let name = path
                                 .file_name()
                                 .and_then(|n| n.to_str())
                                 .unwrap_or("Unknown")
                                 .to_string();
 
+                            let metadata = fs::metadata(&path).ok();
+                            let timestamp = |time: std::io::Result<SystemTime>| {
+                                let duration = time.ok()?.duration_since(UNIX_EPOCH).ok()?;
+                                Some(duration.as_secs() as i64)
+                            };
                             files.push(RecentFile {
-                                path: path.clone(),
+                                path,
                                 name,
                                 mime_type: None,
-                                modified: Self::get_modified_time(&path),
+                                modified: metadata.as_ref().and_then(|m| timestamp(m.modified())),
+                                accessed: metadata.as_ref().and_then(|m| timestamp(m.accessed())),
                             });
 
-                            if files.len() >= max_count {
+                            if sort == RecentSort::Xbel && files.len() >= max_count {
                                 break;
                             }
                         }
@@ -106,8 +248,14 @@ impl FileBrowserPlugin {
             }
         }
 
-        debug!("Loaded {} recent files", files.len());
-        Ok(files)
+        // Newest first; files without a timestamp go last
+        match sort {
+            RecentSort::Xbel => {}
+            RecentSort::Modified => files.sort_by_key(|file| std::cmp::Reverse(file.modified)),
+            RecentSort::Accessed => files.sort_by_key(|file| std::cmp::Reverse(file.accessed)),
+        }
+        files.truncate(max_count);
+        files
     }
 
     /// Extract href attribute from bookmark tag
@@ -128,14 +276,6 @@ impl FileBrowserPlugin {
         }
     }
 
-    /// Get file modified time as Unix timestamp
-    fn get_modified_time(path: &Path) -> Option<i64> {
-        let metadata = fs::metadata(path).ok()?;
-        let modified = metadata.modified().ok()?;
-        let duration = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
-        Some(duration.as_secs() as i64)
-    }
-
     /// Get icon for file based on extension or type
     fn get_file_icon(path: &Path) -> String {
         if path.is_dir() {
@@ -246,7 +386,8 @@ impl FileBrowserPlugin {
                 let open_command = build_open_command(path.to_string_lossy());
 
                 // Determine badge based on file type
-                let badge_icon = if path.is_dir() {
+                let is_directory = path.is_dir();
+                let badge_icon = if is_directory {
                     Some("folder-symbolic".to_string())
                 } else {
                     Some("document-symbolic".to_string())
@@ -264,6 +405,15 @@ impl FileBrowserPlugin {
                     parent_app: None,
                     desktop_path: None,
                     badge_icon,
+                    is_directory,
+                    target_path: Some(path.to_string_lossy().to_string()),
+                    confirm: None,
+                    icon_data: None,
+                    actions: vec![ResultAction::Trash],
+                    url: None,
+                    badges: Vec::new(),
+                    completion: None,
+                    activation: None,
                 });
 
                 if results.len() >= max_results {
@@ -307,8 +457,9 @@ impl Plugin for FileBrowserPlugin {
             return query.starts_with("@file") || query.starts_with("@recent");
         }
 
-        // Always participate in global search (query length >= 2 for performance)
-        query.len() >= 2
+        // Always participate in global search (query length >= 2 for performance,
+        // unless a lower minimum is configured)
+        query.chars().count() >= self.min_query_length.min(2)
     }
 
     fn search(&self, query: &str, context: &PluginContext) -> Result<Vec<PluginResult>> {
@@ -323,7 +474,7 @@ impl Plugin for FileBrowserPlugin {
         let is_command_query = query.starts_with('@');
         let is_file_command =
             query_lower.starts_with("@recent") || query_lower.starts_with("@file");
-        let is_path_query = query.starts_with('/') || query.starts_with("~/");
+        let is_path_query = is_path_query(query);
 
         // For global search (no @ command), search files
         let search_files = !is_command_query || is_file_command;
@@ -386,7 +537,8 @@ impl Plugin for FileBrowserPlugin {
                 let open_command = build_open_command(file.path.to_string_lossy());
 
                 // Determine badge based on file type
-                let badge_icon = if file.path.is_dir() {
+                let is_directory = file.path.is_dir();
+                let badge_icon = if is_directory {
                     Some("folder-symbolic".to_string())
                 } else {
                     Some("document-symbolic".to_string())
@@ -404,6 +556,15 @@ impl Plugin for FileBrowserPlugin {
                     parent_app: None,
                     desktop_path: None,
                     badge_icon,
+                    is_directory,
+                    target_path: Some(file.path.to_string_lossy().to_string()),
+                    confirm: None,
+                    icon_data: None,
+                    actions: vec![ResultAction::Trash],
+                    url: None,
+                    badges: Vec::new(),
+                    completion: None,
+                    activation: None,
                 });
 
                 if results.len() >= context.max_results {
@@ -446,7 +607,7 @@ impl Plugin for FileBrowserPlugin {
             }
         }
 
-        // SYSTEM-WIDE FILE SEARCH (for queries >= 3 chars, not paths)
+        // SYSTEM-WIDE FILE SEARCH (for queries >= min_query_length chars, not paths)
         // This uses locate/find to search the entire filesystem
         //
         // SMART TRIGGERING: Skip file search if there are already good app matches
@@ -455,7 +616,7 @@ impl Plugin for FileBrowserPlugin {
         let has_good_app_matches = context.app_results_count >= 2;
         let should_skip_file_search = has_good_app_matches && !is_file_command;
 
-        if !is_path_query && query.len() >= 3 && search_files && !should_skip_file_search {
+        if !is_path_query && search_files && !should_skip_file_search {
             let search_term = if is_file_command {
                 query_lower
                     .strip_prefix("@recent")
@@ -466,8 +627,8 @@ impl Plugin for FileBrowserPlugin {
                 query_lower.trim()
             };
 
-            // Only perform system search if term is meaningful (>= 3 chars)
-            if search_term.len() >= 3 {
+            // Only perform system search if term is meaningful (default >= 3 chars)
+            if self.wants_system_search(search_term) {
                 debug!("Performing system-wide file search for: {}", search_term);
 
                 match self.file_index.search(search_term) {
@@ -475,13 +636,10 @@ impl Plugin for FileBrowserPlugin {
                         debug!("Found {} files in system index", indexed_files.len());
 
                         for path in indexed_files.iter().take(20) {
+                            // Files also found in recent files are merged by the
+                            // plugin manager, keeping the higher-scored result
                             let open_command = build_open_command(path.to_string_lossy());
 
-                            // Skip if already in results (from recent files)
-                            if results.iter().any(|r| r.command == open_command.as_str()) {
-                                continue;
-                            }
-
                             let file_name = path
                                 .file_name()
                                 .and_then(|n| n.to_str())
@@ -517,7 +675,8 @@ impl Plugin for FileBrowserPlugin {
                             };
 
                             // Determine badge based on file type
-                            let badge_icon = if path.is_dir() {
+                            let is_directory = path.is_dir();
+                            let badge_icon = if is_directory {
                                 Some("folder-symbolic".to_string())
                             } else {
                                 Some("document-symbolic".to_string())
@@ -535,6 +694,15 @@ impl Plugin for FileBrowserPlugin {
                                 parent_app: None,
                                 desktop_path: None,
                                 badge_icon,
+                                is_directory,
+                                target_path: Some(path.to_string_lossy().to_string()),
+                                confirm: None,
+                                icon_data: None,
+                                actions: vec![ResultAction::Trash],
+                                url: None,
+                                badges: Vec::new(),
+                                completion: None,
+                                activation: None,
                             });
 
                             if results.len() >= context.max_results {
@@ -549,6 +717,13 @@ impl Plugin for FileBrowserPlugin {
             }
         }
 
+        // Apps that can open each file, under "Open With…" in its actions menu
+        for result in results.iter_mut().filter(|r| !r.is_directory) {
+            if let Some(path) = &result.target_path {
+                result.sub_results = self.open_with_results(Path::new(path));
+            }
+        }
+
         // Sort by score
         results.sort_by(|a, b| b.score.cmp(&a.score));
 
@@ -605,12 +780,50 @@ impl Plugin for FileBrowserPlugin {
 
         KeyboardAction::None
     }
+
+    fn warm_up(&self) {
+        if self.enabled {
+            self.file_index.prime();
+        }
+    }
+}
+
+/// Whether a query is an absolute or home-relative path (`/usr`, `~/Documents`)
+fn is_path_query(query: &str) -> bool {
+    query.starts_with('/') || query.starts_with("~/")
+}
+
+/// Query that lists the contents of directory `dir_name` from the current path query
+/// (`~/Doc` + `Documents` -> `~/Documents/`)
+pub fn descend_path_query(query: &str, dir_name: &str) -> Option<String> {
+    if !is_path_query(query) {
+        return None;
+    }
+
+    let base = &query[..=query.rfind('/')?];
+    Some(format!("{}{}/", base, dir_name))
+}
+
+/// Query for the parent directory of a path query ending at a directory
+/// (`~/Documents/notes/` -> `~/Documents/`). Returns `None` at `/` and `~/`.
+pub fn ascend_path_query(query: &str) -> Option<String> {
+    if !is_path_query(query) || !query.ends_with('/') {
+        return None;
+    }
+
+    let trimmed = &query[..query.len() - 1];
+    let parent_end = trimmed.rfind('/')?;
+    Some(query[..=parent_end].to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn create_plugin(enabled: bool) -> FileBrowserPlugin {
+        FileBrowserPlugin::with_recent_options(enabled, &[], RecentSort::Xbel)
+    }
+
     #[test]
     fn test_extract_href() {
         let line =
@@ -619,6 +832,87 @@ mod tests {
         assert_eq!(href, Some("file:///home/user/document.pdf".to_string()));
     }
 
+    #[test]
+    fn test_recent_files_outside_allowed_dirs_are_dropped() {
+        let dir =
+            std::env::temp_dir().join(format!("native-launcher-recent-{}", std::process::id()));
+        let (projects, private) = (dir.join("Projects"), dir.join("Private"));
+        fs::create_dir_all(&projects).unwrap();
+        fs::create_dir_all(&private).unwrap();
+        fs::write(projects.join("notes.md"), "").unwrap();
+        fs::write(private.join("diary.md"), "").unwrap();
+        let xbel: String = [projects.join("notes.md"), private.join("diary.md")]
+            .iter()
+            .map(|path| {
+                format!(
+                    "  <bookmark href=\"file://{}\" added=\"2024-10-20T12:00:00Z\">\n",
+                    path.display()
+                )
+            })
+            .collect();
+
+        let names = |allow_dirs: &[PathBuf]| -> Vec<String> {
+            FileBrowserPlugin::parse_recent_files(&xbel, 20, allow_dirs, RecentSort::Xbel)
+                .into_iter()
+                .map(|file| file.name)
+                .collect()
+        };
+        assert_eq!(names(&[]), vec!["notes.md", "diary.md"]);
+        assert_eq!(names(std::slice::from_ref(&projects)), vec!["notes.md"]);
+        // Prefixes match whole path components only
+        assert!(names(&[dir.join("Proj")]).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recent_files_sorted_by_modified_time() {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-recent-sort-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let epoch = UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let paths: Vec<PathBuf> = [("old.md", 0), ("newest.md", 300), ("middle.md", 100)]
+            .iter()
+            .map(|(name, offset)| {
+                let path = dir.join(name);
+                let file = fs::File::create(&path).unwrap();
+                file.set_modified(epoch + std::time::Duration::from_secs(*offset))
+                    .unwrap();
+                path
+            })
+            .collect();
+        let xbel: String = paths
+            .iter()
+            .map(|path| format!("  <bookmark href=\"file://{}\">\n", path.display()))
+            .collect();
+
+        let names = |max_count: usize, sort: RecentSort| -> Vec<String> {
+            FileBrowserPlugin::parse_recent_files(&xbel, max_count, &[], sort)
+                .into_iter()
+                .map(|file| file.name)
+                .collect()
+        };
+        assert_eq!(
+            names(20, RecentSort::Xbel),
+            vec!["old.md", "newest.md", "middle.md"]
+        );
+        assert_eq!(
+            names(20, RecentSort::Modified),
+            vec!["newest.md", "middle.md", "old.md"]
+        );
+        // The newest are kept, not the first listed
+        assert_eq!(
+            names(2, RecentSort::Modified),
+            vec!["newest.md", "middle.md"]
+        );
+        assert_eq!(RecentSort::parse("modified"), RecentSort::Modified);
+        assert_eq!(RecentSort::parse("unknown"), RecentSort::Xbel);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_url_to_path() {
         let url = "file:///home/user/test.txt";
@@ -652,7 +946,7 @@ mod tests {
 
     #[test]
     fn test_should_handle() {
-        let plugin = FileBrowserPlugin::new(true);
+        let plugin = create_plugin(true);
 
         // File browser participates in global search for all queries >= 2 chars
         assert!(plugin.should_handle("/home/user"));
@@ -666,7 +960,188 @@ mod tests {
         assert!(!plugin.should_handle(""));
 
         // Disabled plugin doesn't handle
-        let disabled = FileBrowserPlugin::new(false);
+        let disabled = create_plugin(false);
         assert!(!disabled.should_handle("test"));
     }
+
+    #[test]
+    fn test_configured_min_query_length() {
+        let plugin = create_plugin(true);
+        assert!(!plugin.wants_system_search("ab"));
+        assert!(plugin.wants_system_search("abc"));
+
+        let plugin = create_plugin(true).with_min_query_length(5);
+        assert!(!plugin.wants_system_search("abcd"));
+        assert!(plugin.wants_system_search("abcde"));
+
+        let plugin = create_plugin(true).with_min_query_length(1);
+        assert!(plugin.should_handle("a"));
+        assert!(plugin.wants_system_search("a"));
+    }
+
+    #[test]
+    fn test_directory_results_set_flag() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("native-launcher-files-{}", nanos));
+        fs::create_dir_all(dir.join("projects")).unwrap();
+        fs::write(dir.join("notes.txt"), "hello").unwrap();
+
+        let results = FileBrowserPlugin::search_directory(&dir, "", 10).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let projects = results.iter().find(|r| r.title == "projects").unwrap();
+        assert!(projects.is_directory);
+        let notes = results.iter().find(|r| r.title == "notes.txt").unwrap();
+        assert!(!notes.is_directory);
+    }
+
+    #[test]
+    fn test_file_results_expose_target_path() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("native-launcher-target-{}", nanos));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("report.pdf"), "pdf").unwrap();
+
+        let results = FileBrowserPlugin::search_directory(&dir, "report", 10).unwrap();
+        let target = results[0]
+            .target_path
+            .clone()
+            .expect("file result has a target");
+        let folder = crate::utils::containing_folder(&target);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(PathBuf::from(&target), dir.join("report.pdf"));
+        assert_eq!(PathBuf::from(folder), dir);
+    }
+
+    #[test]
+    fn test_file_action_menu() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("native-launcher-actions-{}", nanos));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("it's here.txt"), "notes").unwrap();
+
+        let results = FileBrowserPlugin::search_directory(&dir, "here", 10).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        let file = &results[0];
+
+        assert_eq!(
+            file.action_menu(),
+            vec![
+                ResultAction::Open,
+                ResultAction::OpenFolder,
+                ResultAction::CopyPath,
+                ResultAction::Trash,
+            ]
+        );
+
+        assert!(file.has_actions());
+        assert_eq!(
+            ResultAction::Trash.confirm_prompt(file).as_deref(),
+            Some("move it's here.txt to the trash")
+        );
+        assert_eq!(ResultAction::Open.confirm_prompt(file), None);
+
+        let path = dir.join("it's here.txt").to_string_lossy().to_string();
+        match ResultAction::Trash.keyboard_action(file) {
+            crate::plugins::KeyboardAction::Execute { command, terminal } => {
+                assert_eq!(
+                    command,
+                    format!("gio trash -- '{}'", path.replace('\'', r"'\''"))
+                );
+                assert!(!terminal);
+            }
+            other => panic!("Expected Execute action, got {:?}", other),
+        }
+        assert!(matches!(
+            ResultAction::CopyPath.keyboard_action(file),
+            crate::plugins::KeyboardAction::CopyPath(copied) if copied == path
+        ));
+    }
+
+    #[test]
+    fn test_png_opens_with_image_apps() {
+        use crate::desktop::{DesktopEntry, DesktopEntryArena};
+
+        fn app(name: &str, exec: &str, mime_types: &[&str]) -> DesktopEntry {
+            DesktopEntry {
+                name: name.to_string(),
+                generic_name: None,
+                comment: None,
+                exec: exec.to_string(),
+                icon: Some(name.to_lowercase()),
+                categories: vec![],
+                keywords: vec![],
+                terminal: false,
+                path: PathBuf::from(format!("/usr/share/applications/{}.desktop", name)),
+                no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: mime_types.iter().map(|m| m.to_string()).collect(),
+                actions: vec![],
+            }
+        }
+
+        let arena = DesktopEntryArena::from_vec(vec![
+            app("GIMP", "gimp-2.10 %U", &["image/png", "image/jpeg"]),
+            app("Text Editor", "gnome-text-editor %U", &["text/plain"]),
+            app("Image Viewer", "loupe %U", &["image/*"]),
+        ]);
+        let plugin = create_plugin(true).with_entries(SharedArena::new(arena));
+
+        let results = plugin.open_with_results(Path::new("/home/me/Pictures/cat photo.png"));
+        let apps: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(apps, vec!["GIMP", "Image Viewer"]);
+        assert_eq!(
+            results[0].command,
+            "gimp-2.10 '/home/me/Pictures/cat photo.png'"
+        );
+        assert_eq!(
+            results[1].subtitle.as_deref(),
+            Some("Open cat photo.png with Image Viewer")
+        );
+
+        let notes = plugin.open_with_results(Path::new("/home/me/notes.txt"));
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].title, "Text Editor");
+    }
+
+    #[test]
+    fn test_descend_path_query() {
+        assert_eq!(
+            descend_path_query("~/Doc", "Documents"),
+            Some("~/Documents/".to_string())
+        );
+        assert_eq!(
+            descend_path_query("/usr/", "share"),
+            Some("/usr/share/".to_string())
+        );
+        assert_eq!(descend_path_query("/", "etc"), Some("/etc/".to_string()));
+        assert_eq!(descend_path_query("docs", "Documents"), None);
+    }
+
+    #[test]
+    fn test_ascend_path_query() {
+        assert_eq!(
+            ascend_path_query("~/Documents/notes/"),
+            Some("~/Documents/".to_string())
+        );
+        assert_eq!(ascend_path_query("/usr/"), Some("/".to_string()));
+        assert_eq!(ascend_path_query("~/Documents/"), Some("~/".to_string()));
+        assert_eq!(ascend_path_query("~/"), None);
+        assert_eq!(ascend_path_query("/"), None);
+        assert_eq!(ascend_path_query("/usr/sh"), None);
+        assert_eq!(ascend_path_query("notes/"), None);
+    }
 }
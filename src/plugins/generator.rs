@@ -0,0 +1,335 @@
+//! Generator plugin (`@uuid`, `@random`, `@password`)
+//!
+//! Produces a v4 UUID, a random integer in a range (`@random 1 100`) or a random
+//! password (`@password 20 lower digits`). Values are generated once per query
+//! and reused while the query stays the same, so re-renders show the same value;
+//! editing the query rolls new ones. Activating a result copies the value.
+
+use super::traits::{Plugin, PluginContext, PluginResult, ResultActivation};
+use anyhow::{anyhow, Result};
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Password length when the query doesn't give one
+const DEFAULT_PASSWORD_LENGTH: usize = 16;
+
+/// Longest password generated
+const MAX_PASSWORD_LENGTH: usize = 256;
+
+/// Character classes a password can draw from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digits,
+    Symbols,
+}
+
+impl CharClass {
+    const ALL: [CharClass; 4] = [
+        CharClass::Lower,
+        CharClass::Upper,
+        CharClass::Digits,
+        CharClass::Symbols,
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "lower" | "lowercase" => Some(CharClass::Lower),
+            "upper" | "uppercase" => Some(CharClass::Upper),
+            "digits" | "numbers" => Some(CharClass::Digits),
+            "symbols" | "special" => Some(CharClass::Symbols),
+            _ => None,
+        }
+    }
+
+    fn chars(self) -> &'static [u8] {
+        match self {
+            CharClass::Lower => b"abcdefghijklmnopqrstuvwxyz",
+            CharClass::Upper => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            CharClass::Digits => b"0123456789",
+            CharClass::Symbols => b"!@#$%^&*()-_=+[]{};:,.<>?/",
+        }
+    }
+}
+
+/// New random v4 UUID
+pub fn uuid_v4() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Random integer in `min..=max` (the bounds may come in either order)
+pub fn random_in_range(min: i64, max: i64) -> i64 {
+    let (low, high) = if min <= max { (min, max) } else { (max, min) };
+    rand::rng().random_range(low..=high)
+}
+
+/// Random password of `length` characters with at least one from each class
+pub fn password(length: usize, classes: &[CharClass]) -> Result<String> {
+    let classes = if classes.is_empty() {
+        &CharClass::ALL[..]
+    } else {
+        classes
+    };
+    if length < classes.len() || length > MAX_PASSWORD_LENGTH {
+        return Err(anyhow!(
+            "Length must be between {} and {}",
+            classes.len(),
+            MAX_PASSWORD_LENGTH
+        ));
+    }
+
+    let mut rng = rand::rng();
+    let pool: Vec<u8> = classes.iter().flat_map(|c| c.chars()).copied().collect();
+    let mut bytes: Vec<u8> = classes
+        .iter()
+        .filter_map(|class| class.chars().choose(&mut rng).copied())
+        .collect();
+    while bytes.len() < length {
+        bytes.extend(pool.choose(&mut rng));
+    }
+    bytes.shuffle(&mut rng);
+
+    Ok(String::from_utf8(bytes).expect("character classes are ASCII"))
+}
+
+/// Generated value (or why none could be generated) for the current query
+#[derive(Debug, Clone)]
+struct Generated {
+    kind: &'static str,
+    value: Result<String, String>,
+}
+
+#[derive(Debug)]
+pub struct GeneratorPlugin {
+    enabled: bool,
+    /// Values for the last query, kept until the query changes
+    cache: Mutex<Option<(String, Vec<Generated>)>>,
+}
+
+impl GeneratorPlugin {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            cache: Mutex::new(None),
+        }
+    }
+
+    fn generate(query: &str) -> Vec<Generated> {
+        let mut words = query.split_whitespace();
+        let prefix = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+
+        match prefix {
+            "@uuid" => vec![Generated {
+                kind: "UUID v4",
+                value: Ok(uuid_v4()),
+            }],
+            "@random" => {
+                let bounds: Result<Vec<i64>, _> = args.iter().map(|a| a.parse()).collect();
+                let value = match bounds.as_deref() {
+                    Ok([]) => Ok(random_in_range(1, 100)),
+                    Ok([max]) => Ok(random_in_range(1, *max)),
+                    Ok([min, max]) => Ok(random_in_range(*min, *max)),
+                    _ => Err("Usage: @random [min] max".to_string()),
+                };
+                vec![Generated {
+                    kind: "Random number",
+                    value: value.map(|n| n.to_string()),
+                }]
+            }
+            "@password" => {
+                let mut length = DEFAULT_PASSWORD_LENGTH;
+                let mut classes = Vec::new();
+                let mut unknown = None;
+                for arg in args {
+                    if let Ok(n) = arg.parse() {
+                        length = n;
+                    } else if let Some(class) = CharClass::from_name(arg) {
+                        classes.push(class);
+                    } else {
+                        unknown = Some(arg);
+                    }
+                }
+                let value = match unknown {
+                    Some(arg) => Err(format!(
+                        "Unknown option '{}' (use lower, upper, digits, symbols)",
+                        arg
+                    )),
+                    None => password(length, &classes).map_err(|e| e.to_string()),
+                };
+                vec![Generated {
+                    kind: "Password",
+                    value,
+                }]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Values for `query`, generating new ones only when the query changed
+    fn values_for(&self, query: &str) -> Vec<Generated> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        match cache.as_ref() {
+            Some((cached, values)) if cached == query => values.clone(),
+            _ => {
+                let values = Self::generate(query);
+                *cache = Some((query.to_string(), values.clone()));
+                values
+            }
+        }
+    }
+}
+
+impl Default for GeneratorPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for GeneratorPlugin {
+    fn name(&self) -> &str {
+        "generator"
+    }
+
+    fn description(&self) -> &str {
+        "UUIDs, random numbers and passwords via @uuid, @random and @password"
+    }
+
+    fn command_prefixes(&self) -> Vec<&str> {
+        vec!["@uuid", "@random", "@password"]
+    }
+
+    fn should_handle(&self, query: &str) -> bool {
+        self.command_prefixes().iter().any(|prefix| {
+            query
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        })
+    }
+
+    fn search(&self, query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+        if !self.enabled || !self.should_handle(query) {
+            return Ok(Vec::new());
+        }
+
+        let results = self
+            .values_for(query)
+            .into_iter()
+            .map(|generated| match generated.value {
+                Ok(value) => {
+                    PluginResult::new(value.clone(), String::new(), self.name().to_string())
+                        .with_subtitle(format!("{} • Enter to copy", generated.kind))
+                        .with_icon("edit-copy".to_string())
+                        .with_activation(ResultActivation::Copy(value))
+                        .with_score(10000)
+                }
+                Err(message) => PluginResult::new(
+                    format!("Can't generate {}", generated.kind.to_lowercase()),
+                    String::new(),
+                    self.name().to_string(),
+                )
+                .with_subtitle(message)
+                .with_icon("dialog-error".to_string())
+                .with_score(10000),
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    fn priority(&self) -> i32 {
+        500
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_format() {
+        let uuid = uuid_v4();
+        assert_eq!(uuid.len(), 36);
+        let groups: Vec<&str> = uuid.split('-').collect();
+        assert_eq!(
+            groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+        assert!(uuid
+            .chars()
+            .all(|c| c == '-' || c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+        assert!(groups[2].starts_with('4'));
+        assert!(matches!(
+            groups[3].chars().next(),
+            Some('8' | '9' | 'a' | 'b')
+        ));
+
+        // Stable while the query is unchanged, fresh once it changes
+        let plugin = GeneratorPlugin::new();
+        let config = crate::config::Config::default();
+        let context = PluginContext::new(10, &config);
+        let first = plugin.search("@uuid", &context).unwrap()[0].title.clone();
+        assert_eq!(plugin.search("@uuid", &context).unwrap()[0].title, first);
+        plugin.search("@uuid ", &context).unwrap();
+        assert_ne!(plugin.search("@uuid", &context).unwrap()[0].title, first);
+
+        // Activating copies the value; it never ends up in a command line
+        let result = plugin.search("@password", &context).unwrap().remove(0);
+        assert!(result.command.is_empty());
+        assert!(
+            matches!(result.activation, Some(ResultActivation::Copy(value)) if value == result.title)
+        );
+    }
+
+    #[test]
+    fn test_random_range_bounds() {
+        for _ in 0..1000 {
+            let n = random_in_range(-3, 3);
+            assert!((-3..=3).contains(&n));
+        }
+        assert_eq!(random_in_range(7, 7), 7);
+        assert!((1..=5).contains(&random_in_range(5, 1)));
+
+        let values = GeneratorPlugin::generate("@random 10 20");
+        let n: i64 = values[0].value.as_ref().unwrap().parse().unwrap();
+        assert!((10..=20).contains(&n));
+        assert!(GeneratorPlugin::generate("@random ten")
+            .pop()
+            .unwrap()
+            .value
+            .is_err());
+    }
+
+    #[test]
+    fn test_password_length() {
+        for length in [4, 16, 20, 64] {
+            assert_eq!(password(length, &[]).unwrap().len(), length);
+        }
+
+        let digits = password(12, &[CharClass::Digits]).unwrap();
+        assert_eq!(digits.len(), 12);
+        assert!(digits.chars().all(|c| c.is_ascii_digit()));
+
+        // Every requested class shows up at least once
+        let mixed = password(4, &CharClass::ALL).unwrap();
+        assert!(mixed.chars().any(|c| c.is_ascii_lowercase()));
+        assert!(mixed.chars().any(|c| c.is_ascii_uppercase()));
+        assert!(mixed.chars().any(|c| c.is_ascii_digit()));
+        assert!(mixed.chars().any(|c| c.is_ascii_punctuation()));
+
+        assert!(password(3, &[]).is_err());
+        assert!(password(MAX_PASSWORD_LENGTH + 1, &[]).is_err());
+
+        let values = GeneratorPlugin::generate("@password 20 lower upper");
+        let generated = values[0].value.as_ref().unwrap();
+        assert_eq!(generated.len(), 20);
+        assert!(generated.chars().all(|c| c.is_ascii_alphabetic()));
+    }
+}
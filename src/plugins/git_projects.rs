@@ -269,6 +269,15 @@ impl Plugin for GitProjectsPlugin {
                     parent_app: None,
                     desktop_path: None,
                     badge_icon: Some("folder-symbolic".to_string()), // Git repo badge
+                    is_directory: false,
+                    target_path: None,
+                    confirm: None,
+                    icon_data: None,
+                    actions: vec![],
+                    url: None,
+                    badges: Vec::new(),
+                    completion: None,
+                    activation: None,
                 })
             })
             .take(context.max_results)
@@ -0,0 +1,297 @@
+//! Hash plugin (`@hash <algorithm> <text or file>`)
+//!
+//! Digests the typed text with md5, sha1, sha256 or sha512, e.g.
+//! `@hash sha256 hello`. When the text is the path of an existing file
+//! (`/…` or `~/…`) its contents are hashed instead; files are streamed on a
+//! worker thread once the result is activated, so neither typing nor copying
+//! the digest of a large ISO blocks the UI. Activating a result copies the
+//! digest to the clipboard.
+
+use super::traits::{Plugin, PluginContext, PluginResult, ResultActivation};
+use anyhow::{Context, Result};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Supported digest algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    const ALL: [HashAlgorithm; 4] = [
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha512,
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('-', "").as_str() {
+            "md5" => Some(HashAlgorithm::Md5),
+            "sha1" => Some(HashAlgorithm::Sha1),
+            "sha256" => Some(HashAlgorithm::Sha256),
+            "sha512" => Some(HashAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Hex digest of `text`
+    pub fn digest_text(self, text: &str) -> String {
+        self.digest_reader(text.as_bytes())
+            .expect("reading from memory cannot fail")
+    }
+
+    /// Hex digest of the file at `path`, read in chunks
+    pub fn digest_file(self, path: &Path) -> Result<String> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        self.digest_reader(file)
+            .with_context(|| format!("Failed to read {}", path.display()))
+    }
+
+    fn digest_reader(self, reader: impl Read) -> io::Result<String> {
+        match self {
+            HashAlgorithm::Md5 => stream::<Md5>(reader),
+            HashAlgorithm::Sha1 => stream::<Sha1>(reader),
+            HashAlgorithm::Sha256 => stream::<Sha256>(reader),
+            HashAlgorithm::Sha512 => stream::<Sha512>(reader),
+        }
+    }
+}
+
+fn stream<D: Digest>(mut reader: impl Read) -> io::Result<String> {
+    let mut hasher = D::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// What a query asks to hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Input {
+    Text(String),
+    File(PathBuf),
+}
+
+#[derive(Debug)]
+pub struct HashPlugin {
+    enabled: bool,
+}
+
+impl HashPlugin {
+    pub fn new() -> Self {
+        Self { enabled: true }
+    }
+
+    /// Algorithms and input named by `query`
+    fn parse(&self, query: &str) -> Option<(Vec<HashAlgorithm>, Input)> {
+        let rest = query.strip_prefix("@hash")?;
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        // "@hash sha256 hello" names the algorithm; "@hash hello" shows them all
+        let rest = rest.trim();
+        let (name, named_input) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let (algorithms, input) = match HashAlgorithm::from_name(name) {
+            Some(algorithm) => (vec![algorithm], named_input.trim()),
+            None => (HashAlgorithm::ALL.to_vec(), rest),
+        };
+        if input.is_empty() {
+            return None;
+        }
+
+        let path = if let Some(relative) = input.strip_prefix("~/") {
+            dirs::home_dir().map(|home| home.join(relative))
+        } else if input.starts_with('/') {
+            Some(PathBuf::from(input))
+        } else {
+            None
+        };
+        let input = match path.filter(|path| path.is_file()) {
+            Some(path) => Input::File(path),
+            None => Input::Text(input.to_string()),
+        };
+
+        Some((algorithms, input))
+    }
+}
+
+impl Default for HashPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for HashPlugin {
+    fn name(&self) -> &str {
+        "hash"
+    }
+
+    fn description(&self) -> &str {
+        "md5/sha1/sha256/sha512 digests of text or files via @hash"
+    }
+
+    fn command_prefixes(&self) -> Vec<&str> {
+        vec!["@hash"]
+    }
+
+    fn should_handle(&self, query: &str) -> bool {
+        query
+            .strip_prefix("@hash")
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    }
+
+    fn search(&self, query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+        if !self.enabled {
+            return Ok(Vec::new());
+        }
+        let Some((algorithms, input)) = self.parse(query) else {
+            return Ok(Vec::new());
+        };
+
+        let results = algorithms
+            .into_iter()
+            .enumerate()
+            .map(|(i, algorithm)| {
+                let result = match &input {
+                    Input::Text(text) => {
+                        let digest = algorithm.digest_text(text);
+                        let subtitle = format!("{} of text • Enter to copy", algorithm.label());
+                        PluginResult::new(digest.clone(), String::new(), self.name().to_string())
+                            .with_subtitle(subtitle)
+                            .with_activation(ResultActivation::Copy(digest))
+                    }
+                    Input::File(path) => {
+                        let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+                        let title = format!("{} of {}", algorithm.label(), path.display());
+                        let path = path.clone();
+                        PluginResult::new(title, String::new(), self.name().to_string())
+                            .with_subtitle(format!("{} bytes • Enter to hash and copy", size))
+                            .with_activation(ResultActivation::CopyComputed(Arc::new(move || {
+                                algorithm.digest_file(&path)
+                            })))
+                    }
+                };
+                result
+                    .with_icon("dialog-password".to_string())
+                    .with_score(10000 - i as i64)
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    fn priority(&self) -> i32 {
+        500
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests_of_fixed_string() {
+        assert_eq!(
+            HashAlgorithm::Md5.digest_text("hello"),
+            "5d41402abc4b2a76b9719d911017c592"
+        );
+        assert_eq!(
+            HashAlgorithm::Sha1.digest_text("hello"),
+            "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d"
+        );
+        assert_eq!(
+            HashAlgorithm::Sha256.digest_text("hello"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(
+            HashAlgorithm::Sha512.digest_text("hello"),
+            "9b71d224bd62f3785d96d46ad3ea3d73319bfbc2890caadae2dff72519673ca7\
+             2323c3d99ba5c11d7c7acc6e14b8c5da0c4663475c2e5c3adef46f73bcdec043"
+        );
+
+        let plugin = HashPlugin::new();
+        let config = crate::config::Config::default();
+        let results = plugin
+            .search("@hash md5 hello", &PluginContext::new(10, &config))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "5d41402abc4b2a76b9719d911017c592");
+        assert!(matches!(
+            &results[0].activation,
+            Some(ResultActivation::Copy(digest)) if digest == &results[0].title
+        ));
+
+        let tabbed = plugin
+            .search("@hash\tmd5\thello", &PluginContext::new(10, &config))
+            .unwrap();
+        assert_eq!(tabbed[0].title, results[0].title);
+        assert!(!plugin.should_handle("@hashtag"));
+    }
+
+    #[test]
+    fn test_file_hashing() {
+        let path =
+            std::env::temp_dir().join(format!("native-launcher-hash-{}", std::process::id()));
+        // Larger than the read buffer, so the file is hashed in several chunks
+        let contents = "hello\n".repeat(20_000);
+        std::fs::write(&path, &contents).unwrap();
+
+        let digest = HashAlgorithm::Sha256.digest_file(&path).unwrap();
+        assert_eq!(digest, HashAlgorithm::Sha256.digest_text(&contents));
+
+        let plugin = HashPlugin::new();
+        let query = format!("@hash sha256 {}", path.display());
+        let config = crate::config::Config::default();
+        let results = plugin
+            .search(&query, &PluginContext::new(10, &config))
+            .unwrap();
+        // Searching only names the file; the digest is computed on activation
+        let copied = match &results[0].activation {
+            Some(ResultActivation::CopyComputed(compute)) => compute().ok(),
+            _ => None,
+        };
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].title.starts_with("sha256 of /"));
+        assert!(results[0].command.is_empty());
+        assert_eq!(copied, Some(digest));
+        assert!(HashAlgorithm::Md5.digest_file(&path).is_err());
+    }
+}
@@ -114,7 +114,8 @@ impl Plugin for LauncherPlugin {
                 .with_subtitle("Run uninstall script (use with caution)".to_string())
                 .with_icon("user-trash".to_string())
                 .with_terminal(true)
-                .with_score(7000),
+                .with_score(7000)
+                .with_confirm("uninstall native-launcher".to_string()),
             );
         }
 
@@ -1,24 +1,32 @@
+use super::calculator::{NumberFormat, AMBIENT_SCORE};
+use super::currency_rates::CurrencyRates;
+use super::files::RecentSort;
 use super::traits::{Plugin, PluginContext, PluginResult};
 use super::LauncherPlugin;
 use super::{
     AdvancedCalculatorPlugin, ApplicationsPlugin, BrowserHistoryPlugin, CalculatorPlugin,
-    ClipboardPlugin, EditorsPlugin, EmojiPlugin, FileBrowserPlugin, GitProjectsPlugin,
-    RecentDocumentsPlugin, ScreenshotPlugin, SessionSwitcherPlugin, ShellPlugin, SshPlugin,
-    ThemeSwitcherPlugin, WebSearchPlugin, WindowManagementPlugin,
+    ClipboardPlugin, EditorsPlugin, EmojiPlugin, EncoderPlugin, FileBrowserPlugin, GeneratorPlugin,
+    GitProjectsPlugin, HashPlugin, QrCodePlugin, RecentDocumentsPlugin, ScreenshotPlugin,
+    SessionSwitcherPlugin, ShellPlugin, SshPlugin, ThemeSwitcherPlugin, WebSearchPlugin,
+    WindowList, WindowManagementPlugin,
 };
 use crate::config::Config;
-use crate::desktop::DesktopEntryArena;
+use crate::desktop::{DesktopEntryArena, SharedArena};
 use crate::pins::PinsStore;
+use crate::search::{usage_boost, OrderStrategy, ScoreThresholds};
 use crate::usage::UsageTracker;
+use crate::utils::build_open_command;
 use crate::utils::exec::{register_open_handler, CommandOpenHandler, OpenHandlerPriority};
 use anyhow::Result;
 use dirs::home_dir;
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
-use tracing::debug;
+use tracing::{debug, info, warn};
 use urlencoding::decode;
 
 /// Performance metrics for a plugin
@@ -26,6 +34,8 @@ use urlencoding::decode;
 struct PluginMetrics {
     total_time: Duration,
     call_count: u32,
+    last_time: Duration,
+    last_results: usize,
 }
 
 impl PluginMetrics {
@@ -33,12 +43,16 @@ impl PluginMetrics {
         Self {
             total_time: Duration::ZERO,
             call_count: 0,
+            last_time: Duration::ZERO,
+            last_results: 0,
         }
     }
 
-    fn record(&mut self, duration: Duration) {
+    fn record(&mut self, duration: Duration, results: usize) {
         self.total_time += duration;
         self.call_count += 1;
+        self.last_time = duration;
+        self.last_results = results;
     }
 
     fn average_ms(&self) -> f64 {
@@ -49,6 +63,29 @@ impl PluginMetrics {
     }
 }
 
+/// Search timing of one plugin, as shown in the debug overlay
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginTiming {
+    pub plugin: String,
+    /// Duration of the most recent search
+    pub last_ms: f64,
+    /// Mean duration over all searches
+    pub average_ms: f64,
+    pub calls: u32,
+    /// Results returned by the most recent search
+    pub last_results: usize,
+}
+
+impl PluginTiming {
+    /// One overlay line: `files  3.20ms (avg 2.85ms, 14 calls)  12 results`
+    pub fn summary(&self) -> String {
+        format!(
+            "{}  {:.2}ms (avg {:.2}ms, {} calls)  {} results",
+            self.plugin, self.last_ms, self.average_ms, self.calls, self.last_results
+        )
+    }
+}
+
 fn ensure_builtin_open_handlers_registered() {
     static REGISTERED: OnceLock<()> = OnceLock::new();
     REGISTERED.get_or_init(|| {
@@ -121,11 +158,94 @@ fn resolve_filesystem_path(target: &str) -> Option<PathBuf> {
     None
 }
 
+/// Cancellation token for incremental searches
+///
+/// Wraps a shared generation counter and remembers the generation the search
+/// started with. Bumping the counter (e.g. on a new keystroke) marks every search
+/// started earlier as superseded.
+#[derive(Debug, Clone)]
+pub struct SearchToken {
+    generation: Arc<AtomicU64>,
+    started_at: u64,
+}
+
+impl SearchToken {
+    /// Create a token bound to the counter's current generation
+    pub fn new(generation: Arc<AtomicU64>) -> Self {
+        let started_at = generation.load(Ordering::SeqCst);
+        Self {
+            generation,
+            started_at,
+        }
+    }
+
+    /// Whether a newer search has superseded this one
+    pub fn is_cancelled(&self) -> bool {
+        self.generation.load(Ordering::SeqCst) != self.started_at
+    }
+}
+
+/// Plugins whose results are calculations, previewed first as the query is typed
+const CALCULATOR_PLUGINS: &[&str] = &["calculator", "advanced_calculator"];
+
+/// Environment variable enabling ranking explanations (`NL_EXPLAIN=1`)
+const EXPLAIN_ENV_VAR: &str = "NL_EXPLAIN";
+
+/// Whether an `NL_EXPLAIN` value turns explanations on
+fn explain_flag_enabled(value: Option<&str>) -> bool {
+    matches!(value.map(str::trim), Some("1") | Some("true") | Some("yes"))
+}
+
+/// Why a result ended up at its position in a search (`NL_EXPLAIN=1`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultExplanation {
+    /// 1-based position in the returned results
+    pub rank: usize,
+    pub title: String,
+    /// Plugin that produced the result
+    pub plugin: String,
+    /// Score as returned by the plugin (already includes any usage boost)
+    pub score: i64,
+    /// Usage score for application results; the applications plugin multiplies
+    /// its fuzzy score by [`usage_boost`] of it
+    pub usage_score: Option<f64>,
+    /// How this result compares to the one ranked directly below it
+    pub rationale: String,
+}
+
+/// A plugin whose `search` failed during the most recent search
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginSearchError {
+    pub plugin: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for PluginSearchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed: {}", self.plugin, self.message)
+    }
+}
+
 /// Manages all plugins and coordinates search across them
+///
+/// Searching never touches GTK, so a manager works without GTK being
+/// initialized (headless use, benchmarks, tests). It isn't `Send`: create it
+/// on the thread that searches.
 pub struct PluginManager {
     plugins: Vec<Box<dyn Plugin>>,
     performance_metrics: RefCell<HashMap<String, PluginMetrics>>,
     config: Config,
+    /// Log why each result won (`NL_EXPLAIN=1`)
+    explain: bool,
+    /// Usage data snapshot used to report usage boosts in explanations
+    usage_tracker: Option<UsageTracker>,
+    /// Explanations for the most recent search (only filled when explaining)
+    last_explanation: RefCell<Vec<ResultExplanation>>,
+    /// Plugins that failed during the most recent search; their results are
+    /// missing but the others are still shown
+    last_errors: RefCell<Vec<PluginSearchError>>,
+    /// Entries searched by the applications plugin, swappable after a rescan
+    entries: SharedArena,
 }
 
 impl PluginManager {
@@ -145,21 +265,42 @@ impl PluginManager {
         };
 
         let mut plugins: Vec<Box<dyn Plugin>> = Vec::new();
-
-        // Applications plugin (always enabled, highest priority)
-        let apps_plugin =
-            ApplicationsPlugin::with_usage_and_pins(entry_arena.clone(), usage_tracker, pins);
+        let explain_usage = usage_tracker.clone();
+
+        // Applications plugin (always enabled, highest priority); with window
+        // management on, running apps also get a "Switch to" result
+        let mut apps_plugin =
+            ApplicationsPlugin::with_usage_and_pins(entry_arena.clone(), usage_tracker, pins)
+                .with_score_thresholds(ScoreThresholds::from_config(&config.search))
+                .with_order_strategy(OrderStrategy::from_config(&config.search));
+        if config.plugins.window_management {
+            apps_plugin = apps_plugin.with_window_list(Arc::new(WindowList::new()));
+        }
+        let entries = apps_plugin.shared_entries();
         plugins.push(Box::new(apps_plugin));
 
         // Calculator plugin (basic math)
-        if config.plugins.calculator {
-            plugins.push(Box::new(CalculatorPlugin::new()));
-        }
+        if config.plugins.calculator.enabled {
+            let mut calculator = CalculatorPlugin::new();
+            if config.plugins.calculator.group_digits {
+                calculator = calculator.with_digit_grouping(
+                    NumberFormat::current(),
+                    config.plugins.calculator.copy_grouped,
+                );
+            }
+
+            // Advanced calculator plugin (time, units, currency, timezone)
+            // Registered alongside the basic calculator unless disabled on its own;
+            // it lists the calculator's recent calculations for a bare "@calc"
+            if config.plugins.advanced_calculator {
+                let advanced_calculator = AdvancedCalculatorPlugin::new().with_currency_rates(
+                    CurrencyRates::new().with_auto_refresh(config.plugins.live_currency_rates),
+                );
+                calculator = calculator.with_history(advanced_calculator.history());
+                plugins.push(Box::new(advanced_calculator));
+            }
 
-        // Advanced calculator plugin (time, units, currency, timezone)
-        // Always enabled alongside basic calculator
-        if config.plugins.calculator {
-            plugins.push(Box::new(AdvancedCalculatorPlugin::new()));
+            plugins.push(Box::new(calculator));
         }
 
         // Shell plugin
@@ -174,8 +315,15 @@ impl PluginManager {
         }
 
         // File browser plugin
-        if config.plugins.files {
-            plugins.push(Box::new(FileBrowserPlugin::new(true)));
+        if config.plugins.files.enabled {
+            let allow_dirs = config.plugins.files.recent_allow_paths();
+            let sort = RecentSort::parse(&config.plugins.files.recent_sort);
+            let mut files = FileBrowserPlugin::with_recent_options(true, &allow_dirs, sort)
+                .with_entries(entries.clone());
+            if let Some(min_len) = config.search.min_query_length {
+                files = files.with_min_query_length(min_len);
+            }
+            plugins.push(Box::new(files));
         }
 
         // Web search plugin
@@ -190,7 +338,11 @@ impl PluginManager {
 
         // SSH plugin
         if config.plugins.ssh {
-            plugins.push(Box::new(SshPlugin::new(true)));
+            let mut ssh = SshPlugin::new(true);
+            if config.search.usage_ranking {
+                ssh = ssh.with_usage(super::ssh::host_usage_path());
+            }
+            plugins.push(Box::new(ssh));
         }
 
         // Screenshot plugin
@@ -209,8 +361,12 @@ impl PluginManager {
         }
 
         // Browser history plugin
-        if config.plugins.browser_history {
-            plugins.push(Box::new(BrowserHistoryPlugin::new()));
+        if config.plugins.browser_history.enabled {
+            let mut browser = BrowserHistoryPlugin::new(&config.plugins.browser_history);
+            if let Some(min_len) = config.search.min_query_length {
+                browser = browser.with_min_query_length(min_len);
+            }
+            plugins.push(Box::new(browser));
         }
 
         // Recent documents plugin
@@ -233,32 +389,130 @@ impl PluginManager {
             plugins.push(Box::new(GitProjectsPlugin::new(true)));
         }
 
-        // Theme switcher plugin (always enabled)
-        plugins.push(Box::new(ThemeSwitcherPlugin::new(config.clone())));
+        // Theme switcher plugin
+        if config.plugins.theme_switcher {
+            plugins.push(Box::new(ThemeSwitcherPlugin::new(config.clone())));
+        }
+
+        // QR code plugin
+        if config.plugins.qr_code {
+            plugins.push(Box::new(QrCodePlugin::new()));
+        }
+
+        // Encoder plugin
+        if config.plugins.encoder {
+            plugins.push(Box::new(EncoderPlugin::new()));
+        }
+
+        // Hash plugin
+        if config.plugins.hash {
+            plugins.push(Box::new(HashPlugin::new()));
+        }
+
+        // Generator plugin
+        if config.plugins.generator {
+            plugins.push(Box::new(GeneratorPlugin::new()));
+        }
 
         // Sort plugins by priority (highest first)
-        plugins.sort_by(|a, b| b.priority().cmp(&a.priority()));
+        Self::sort_by_priority(&mut plugins, config);
 
         Self {
             plugins,
             performance_metrics: RefCell::new(HashMap::new()),
             config: config.clone(),
+            explain: explain_flag_enabled(std::env::var(EXPLAIN_ENV_VAR).ok().as_deref()),
+            usage_tracker: explain_usage,
+            last_explanation: RefCell::new(Vec::new()),
+            last_errors: RefCell::new(Vec::new()),
+            entries,
         }
     }
 
+    /// Handle to the application entries; `store` a rescanned arena through it
+    /// (from any thread) to make new or removed apps searchable immediately
+    pub fn shared_entries(&self) -> SharedArena {
+        self.entries.clone()
+    }
+
+    /// Plugins that failed during the most recent `search` / `search_incremental`
+    pub fn last_errors(&self) -> Vec<PluginSearchError> {
+        self.last_errors.borrow().clone()
+    }
+
+    /// Results of a plugin search, recording a failure instead of propagating it
+    /// so one broken plugin doesn't hide everyone else's results
+    fn results_or_record(
+        &self,
+        plugin: &dyn Plugin,
+        results: Result<Vec<PluginResult>>,
+    ) -> Vec<PluginResult> {
+        results.unwrap_or_else(|e| {
+            warn!("Plugin {} failed to search: {:#}", plugin.name(), e);
+            self.last_errors.borrow_mut().push(PluginSearchError {
+                plugin: plugin.name().to_string(),
+                message: format!("{:#}", e),
+            });
+            Vec::new()
+        })
+    }
+
     /// Register a dynamic plugin
     /// Plugins are automatically sorted by priority after registration
     pub fn register_plugin(&mut self, plugin: Box<dyn Plugin>) {
         self.plugins.push(plugin);
         // Re-sort by priority
-        self.plugins.sort_by(|a, b| b.priority().cmp(&a.priority()));
+        Self::sort_by_priority(&mut self.plugins, &self.config);
+    }
+
+    /// Order plugins by priority, highest first, honoring `[plugins.priority]`
+    fn sort_by_priority(plugins: &mut [Box<dyn Plugin>], config: &Config) {
+        plugins.sort_by_key(|plugin| {
+            std::cmp::Reverse(config.plugins.priority_of(plugin.name(), plugin.priority()))
+        });
+    }
+
+    /// Order merged results by score, then by the priority of the plugin that
+    /// produced them, then by title
+    fn sort_results(&self, results: &mut [PluginResult]) {
+        let priorities: HashMap<&str, i32> = self
+            .plugins
+            .iter()
+            .map(|plugin| {
+                let priority = self
+                    .config
+                    .plugins
+                    .priority_of(plugin.name(), plugin.priority());
+                (plugin.name(), priority)
+            })
+            .collect();
+        let priority = |result: &PluginResult| {
+            priorities
+                .get(result.plugin_name.as_str())
+                .copied()
+                .unwrap_or(0)
+        };
+
+        results.sort_unstable_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| priority(b).cmp(&priority(a)))
+                .then_with(|| a.title.cmp(&b.title))
+        });
     }
 
     /// Search across all plugins
     /// If query starts with @ or $, route to specific plugin(s) matching the command prefix
     /// Otherwise, perform global search across all plugins
     pub fn search(&self, query: &str, max_results: usize) -> Result<Vec<PluginResult>> {
+        let normalized = self.normalize_query(query);
+        let query = &*self.expand_prefix_alias(&normalized);
+        self.last_errors.borrow_mut().clear();
         let mut context = PluginContext::new(max_results, &self.config);
+
+        if let Some(scope) = parse_scope(query) {
+            return Ok(self.search_scope(&scope, &context));
+        }
         // Pre-allocate for max_results * 2 to reduce reallocations during plugin aggregation
         let mut all_results = Vec::with_capacity(max_results * 2);
 
@@ -279,8 +533,8 @@ impl PluginManager {
                     .any(|prefix| query.starts_with(prefix));
 
                 if matches_prefix {
-                    let results = plugin.search(query, &context)?;
-                    all_results.extend(results);
+                    let results = plugin.search(query, &context);
+                    all_results.extend(self.results_or_record(plugin.as_ref(), results));
                 }
             }
         } else {
@@ -295,7 +549,8 @@ impl PluginManager {
             for plugin in &self.plugins {
                 if plugin.enabled() && plugin.name() == "applications" {
                     if plugin.should_handle(query) {
-                        let results = plugin.search(query, &context)?;
+                        let results = Self::search_capped(plugin.as_ref(), query, &context);
+                        let results = self.results_or_record(plugin.as_ref(), results);
                         // Count high-quality app matches (score >= 700)
                         app_results_count = results.iter().filter(|r| r.score >= 700).count();
                         all_results.extend(results);
@@ -313,23 +568,249 @@ impl PluginManager {
                     && plugin.name() != "applications"
                     && plugin.should_handle(query)
                 {
-                    let results = plugin.search(query, &context)?;
-                    all_results.extend(results);
+                    let results = Self::search_capped(plugin.as_ref(), query, &context);
+                    all_results.extend(self.results_or_record(plugin.as_ref(), results));
                 }
             }
         }
 
         // Sort all results by score (descending)
-        // Use unstable sort for better performance (order of equal elements doesn't matter)
-        all_results
-            .sort_unstable_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        self.sort_results(&mut all_results);
+
+        // Drop lower-scored copies of results another plugin also returned
+        Self::dedup_results(&mut all_results, &mut HashSet::new());
+        Self::pin_calculation_first(query, &mut all_results);
 
         // Limit to max_results
-        Ok(all_results.into_iter().take(max_results).collect())
+        all_results.truncate(max_results);
+
+        if all_results.is_empty() {
+            all_results.extend(self.web_fallback_result(query));
+        }
+
+        if self.explain {
+            self.explain_results(query, &all_results);
+        }
+
+        Ok(all_results)
+    }
+
+    /// `query` cleaned up by [`normalize_query`]; shell commands keep their
+    /// inner spacing, which may be intentional (`> printf 'a  b'`)
+    fn normalize_query<'q>(&self, query: &'q str) -> Cow<'q, str> {
+        let mut shell_prefixes = vec![self.config.plugins.shell_prefix.as_str()];
+        if let Some(shell) = self.plugins.iter().find(|p| p.name() == "shell") {
+            shell_prefixes.extend(shell.command_prefixes());
+        }
+        if let Some(alias) = self.config.prefixes.get("shell") {
+            shell_prefixes.push(alias.as_str());
+        }
+
+        let start = query.trim_start();
+        let is_shell = shell_prefixes
+            .iter()
+            .any(|prefix| !prefix.is_empty() && start.starts_with(prefix));
+        normalize_query(query, is_shell)
+    }
+
+    /// Rewrite a query starting with a `[prefixes]` alias to start with the
+    /// plugin's own prefix instead ("=2+2" -> "@cal 2+2"), so it is routed like
+    /// a command and the plugin strips the prefix as usual
+    ///
+    /// The longest matching alias wins; aliases of disabled or unknown plugins,
+    /// and of plugins without a prefix, are ignored.
+    fn expand_prefix_alias<'q>(&self, query: &'q str) -> Cow<'q, str> {
+        let expansion = self
+            .config
+            .prefixes
+            .iter()
+            .filter(|(_, alias)| !alias.is_empty() && query.starts_with(alias.as_str()))
+            .filter_map(|(name, alias)| {
+                let plugin = self
+                    .plugins
+                    .iter()
+                    .find(|p| p.enabled() && p.name() == name)?;
+                let prefix = plugin.command_prefixes().first()?.to_string();
+                Some((alias, prefix))
+            })
+            .max_by_key(|(alias, _)| alias.len());
+
+        match expansion {
+            Some((alias, prefix)) => {
+                let rest = query[alias.len()..].trim_start();
+                debug!("Prefix alias '{}' routes to '{}'", alias, prefix);
+                Cow::Owned(format!("{} {}", prefix, rest))
+            }
+            None => Cow::Borrowed(query),
+        }
+    }
+
+    /// Results of the scope's plugins only, sorted and capped like a global
+    /// search; plugins see the query without the scope prefix
+    fn search_scope(&self, scope: &Scope, context: &PluginContext) -> Vec<PluginResult> {
+        debug!("Search scoped to {:?}: '{}'", scope.plugins, scope.rest);
+        let mut results = Vec::with_capacity(context.max_results);
+
+        for plugin in &self.plugins {
+            if !plugin.enabled() || !scope.includes(plugin.as_ref()) {
+                continue;
+            }
+
+            if plugin.should_handle(scope.rest) {
+                let found = plugin.search(scope.rest, context);
+                results.extend(self.results_or_record(plugin.as_ref(), found));
+            }
+        }
+
+        self.sort_results(&mut results);
+        Self::dedup_results(&mut results, &mut HashSet::new());
+        results.truncate(context.max_results);
+        results
+    }
+
+    /// "Search the web" row offered when nothing else matched the query.
+    /// Gated by `search.web_fallback` and never shown for `@`/`$` commands.
+    fn web_fallback_result(&self, query: &str) -> Option<PluginResult> {
+        let query = query.trim();
+        if !self.config.search.web_fallback
+            || query.is_empty()
+            || query.starts_with('@')
+            || query.starts_with('$')
+        {
+            return None;
+        }
+
+        let url = WebSearchPlugin::new().default_search_url(query);
+        Some(
+            PluginResult::new(
+                format!("Search the web for '{}'", query),
+                build_open_command(&url),
+                "web_search".to_string(),
+            )
+            .with_subtitle(url)
+            .with_icon("web-browser".to_string())
+            .with_badge_icon("web-browser-symbolic".to_string())
+            .with_score(0),
+        )
+    }
+
+    /// Log and record why each result landed where it did
+    fn explain_results(&self, query: &str, results: &[PluginResult]) {
+        let explanations: Vec<ResultExplanation> = results
+            .iter()
+            .enumerate()
+            .map(|(i, result)| {
+                let usage_score = match (&self.usage_tracker, &result.desktop_path) {
+                    (Some(tracker), Some(path)) => Some(tracker.get_score(path)),
+                    _ => None,
+                };
+
+                let rationale = match results.get(i + 1) {
+                    None => "last result".to_string(),
+                    Some(next) if result.score > next.score => {
+                        format!("score {} > {} ({})", result.score, next.score, next.title)
+                    }
+                    // Incremental search appends slow results below the fast ones
+                    Some(next) if result.score < next.score => format!(
+                        "score {} < {} ({}), shown first with the fast results",
+                        result.score, next.score, next.title
+                    ),
+                    Some(next) => format!(
+                        "tied at {} with {}, ordered by plugin priority, then title",
+                        result.score, next.title
+                    ),
+                };
+
+                ResultExplanation {
+                    rank: i + 1,
+                    title: result.title.clone(),
+                    plugin: result.plugin_name.clone(),
+                    score: result.score,
+                    usage_score,
+                    rationale,
+                }
+            })
+            .collect();
+
+        info!(
+            "[explain] '{}': {} results, ordered by score (desc), plugin priority, then title",
+            query,
+            explanations.len()
+        );
+        for e in &explanations {
+            let usage = e
+                .usage_score
+                .map(|u| format!(", usage {:.2} (x{:.2})", u, usage_boost(u)))
+                .unwrap_or_default();
+            info!(
+                "[explain] #{} {} [{}] score {}{} - {}",
+                e.rank, e.title, e.plugin, e.score, usage, e.rationale
+            );
+        }
+
+        *self.last_explanation.borrow_mut() = explanations;
+    }
+
+    /// Keep the first (highest-scored, as `results` is sorted) result for each
+    /// command, e.g. a file found by both recent files and the file index
+    ///
+    /// `seen` holds commands already shown, so incremental search can drop slow
+    /// results duplicating fast ones. Results without a command (messages) are
+    /// never merged.
+    fn dedup_results(results: &mut Vec<PluginResult>, seen: &mut HashSet<String>) {
+        results.retain(|result| {
+            let keep = result.command.is_empty() || seen.insert(result.command.clone());
+            if !keep {
+                debug!(
+                    "Dropping duplicate '{}' from {}",
+                    result.title, result.plugin_name
+                );
+            }
+            keep
+        });
+    }
+
+    /// Move the best calculator answer to the top, ahead of even exact app
+    /// matches, so a calculation being typed previews its value in the first row
+    ///
+    /// A bare number ("2048") is more likely part of an app name than a sum, so
+    /// its echo from the calculator keeps its normal rank, as does arithmetic
+    /// found inside another query ("firefox 2+2").
+    fn pin_calculation_first(query: &str, results: &mut [PluginResult]) {
+        if query.trim().parse::<f64>().is_ok() {
+            return;
+        }
+        let calculation = results.iter().position(|r| {
+            CALCULATOR_PLUGINS.contains(&r.plugin_name.as_str()) && r.score > AMBIENT_SCORE
+        });
+        if let Some(index) = calculation {
+            results[..=index].rotate_right(1);
+        }
+    }
+
+    /// Run a plugin for global search, enforcing its `[plugins.max_results]` cap
+    /// (the plugin sees the lowered limit, and any excess results are dropped)
+    fn search_capped(
+        plugin: &dyn Plugin,
+        query: &str,
+        context: &PluginContext,
+    ) -> Result<Vec<PluginResult>> {
+        let Some(cap) = context.config.plugins.result_cap(plugin.name()) else {
+            return plugin.search(query, context);
+        };
+
+        let mut results = plugin.search(query, &context.for_plugin(plugin.name()))?;
+        if results.len() > cap {
+            results.sort_by_key(|r| std::cmp::Reverse(r.score));
+            results.truncate(cap);
+        }
+        Ok(results)
     }
 
     /// Incremental search - returns fast results immediately, then slow results
     /// Dynamically categorizes plugins based on their actual performance (measured timing)
+    /// The token is checked between plugins; once cancelled, remaining plugins are
+    /// skipped and no further callbacks fire, so stale results never reach the UI.
     /// Callbacks:
     /// - on_fast_results: Called with results from fast plugins (< 10ms average)
     /// - on_slow_results: Called with results from slow plugins (>= 10ms average)
@@ -337,6 +818,7 @@ impl PluginManager {
         &self,
         query: &str,
         max_results: usize,
+        token: &SearchToken,
         on_fast_results: F1,
         on_slow_results: F2,
     ) -> Result<()>
@@ -345,8 +827,31 @@ impl PluginManager {
         F2: FnOnce(Vec<PluginResult>),
     {
         const FAST_THRESHOLD_MS: f64 = 10.0; // Plugins faster than 10ms are "fast"
+        let normalized = self.normalize_query(query);
+        let query = &*self.expand_prefix_alias(&normalized);
+        self.last_errors.borrow_mut().clear();
         let mut context = PluginContext::new(max_results, &self.config);
 
+        // Scoped searches only run the scope's plugins, all at once
+        if let Some(scope) = parse_scope(query) {
+            let results = self.search_scope(&scope, &context);
+            if self.explain {
+                self.explain_results(query, &results);
+            }
+            on_fast_results(results);
+            on_slow_results(Vec::new());
+            return Ok(());
+        }
+
+        let is_command_query = query.starts_with('@') || query.starts_with('$');
+        let run_plugin = |plugin: &dyn Plugin, context: &PluginContext| {
+            if is_command_query {
+                plugin.search(query, context)
+            } else {
+                Self::search_capped(plugin, query, context)
+            }
+        };
+
         // Categorize plugins based on their historical performance
         let num_plugins = self.plugins.len();
         let mut fast_plugins = Vec::with_capacity(num_plugins);
@@ -366,14 +871,14 @@ impl PluginManager {
                     .map(|m| m.average_ms())
                     .unwrap_or(0.0);
 
+                // Calculators always answer in the first batch, so the preview of a
+                // calculation never waits for slow plugins.
                 // If no historical data, assume Applications and calculators are fast
                 // Everything else starts as slow until measured
-                if avg_time == 0.0 {
-                    if plugin_name == "Applications"
-                        || plugin_name == "calculator"
-                        || plugin_name == "advanced_calculator"
-                        || plugin_name == "web_search"
-                    {
+                if CALCULATOR_PLUGINS.contains(&plugin_name) {
+                    fast_plugins.push(plugin.as_ref());
+                } else if avg_time == 0.0 {
+                    if plugin_name == "Applications" || plugin_name == "web_search" {
                         fast_plugins.push(plugin.as_ref());
                     } else {
                         slow_plugins.push(plugin.as_ref());
@@ -391,10 +896,16 @@ impl PluginManager {
         let mut app_results_count = 0;
 
         for plugin in fast_plugins {
+            if token.is_cancelled() {
+                debug!("Search for '{}' superseded during fast phase", query);
+                return Ok(());
+            }
+
             if plugin.should_handle(query) {
                 let start = Instant::now();
-                let results = plugin.search(query, &context)?;
+                let results = run_plugin(plugin, &context);
                 let elapsed = start.elapsed();
+                let results = self.results_or_record(plugin, results);
 
                 // Record timing
                 {
@@ -402,7 +913,7 @@ impl PluginManager {
                     metrics
                         .entry(plugin.name().to_string())
                         .or_insert_with(PluginMetrics::new)
-                        .record(elapsed);
+                        .record(elapsed, results.len());
                 }
 
                 // Track app matches for smart triggering
@@ -414,10 +925,19 @@ impl PluginManager {
             }
         }
 
-        // Sort and limit fast results - use unstable sort for performance
-        fast_results
-            .sort_unstable_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        // Sort and limit fast results
+        self.sort_results(&mut fast_results);
+        Self::dedup_results(&mut fast_results, &mut HashSet::new());
+        Self::pin_calculation_first(query, &mut fast_results);
         let fast_results: Vec<_> = fast_results.into_iter().take(max_results).collect();
+        let mut shown_commands: HashSet<String> =
+            fast_results.iter().map(|r| r.command.clone()).collect();
+        let fast_count = fast_results.len();
+        let mut explained = if self.explain {
+            fast_results.clone()
+        } else {
+            Vec::new()
+        };
 
         // Call fast callback immediately
         on_fast_results(fast_results);
@@ -427,10 +947,16 @@ impl PluginManager {
         let mut slow_results = Vec::with_capacity(max_results);
 
         for plugin in slow_plugins {
+            if token.is_cancelled() {
+                debug!("Search for '{}' superseded during slow phase", query);
+                return Ok(());
+            }
+
             if plugin.should_handle(query) {
                 let start = Instant::now();
-                let results = plugin.search(query, &context)?;
+                let results = run_plugin(plugin, &context);
                 let elapsed = start.elapsed();
+                let results = self.results_or_record(plugin, results);
 
                 // Record timing
                 {
@@ -438,18 +964,34 @@ impl PluginManager {
                     metrics
                         .entry(plugin.name().to_string())
                         .or_insert_with(PluginMetrics::new)
-                        .record(elapsed);
+                        .record(elapsed, results.len());
                 }
 
                 slow_results.extend(results);
             }
         }
 
-        // Sort and limit slow results - use unstable sort for performance
-        slow_results
-            .sort_unstable_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        // Sort and limit slow results
+        self.sort_results(&mut slow_results);
+        // Fast results are already on screen, so a slow duplicate is dropped even
+        // when it scores higher
+        Self::dedup_results(&mut slow_results, &mut shown_commands);
+
+        let mut slow_results: Vec<_> = slow_results.into_iter().take(max_results).collect();
+        if fast_count == 0 && slow_results.is_empty() {
+            slow_results.extend(self.web_fallback_result(query));
+        }
+
+        if token.is_cancelled() {
+            debug!("Dropping slow results for superseded search '{}'", query);
+            return Ok(());
+        }
 
-        let slow_results: Vec<_> = slow_results.into_iter().take(max_results).collect();
+        // Explain the whole list as shown: fast results, then slow ones below
+        if self.explain {
+            explained.extend(slow_results.iter().cloned());
+            self.explain_results(query, &explained);
+        }
 
         // Call slow callback
         on_slow_results(slow_results);
@@ -464,9 +1006,32 @@ impl PluginManager {
         &self,
         event: &super::traits::KeyboardEvent,
     ) -> super::traits::KeyboardAction {
+        // Plugins see the query exactly as it was searched, and only the
+        // scope's plugins see a scoped one
+        let normalized = self.normalize_query(&event.query);
+        let query = self.expand_prefix_alias(&normalized);
+        let scope = parse_scope(&query);
+        let searched = scope.as_ref().map_or(&*query, |scope| scope.rest);
+        let expanded;
+        let event = if searched == event.query {
+            event
+        } else {
+            expanded = super::traits::KeyboardEvent {
+                query: searched.to_string(),
+                ..event.clone()
+            };
+            &expanded
+        };
+
         // Dispatch to plugins in priority order (already sorted)
         for plugin in &self.plugins {
-            if !plugin.enabled() {
+            if !plugin.enabled() || !plugin.handles_keyboard_events() {
+                continue;
+            }
+            if scope
+                .as_ref()
+                .is_some_and(|scope| !scope.includes(plugin.as_ref()))
+            {
                 continue;
             }
 
@@ -480,6 +1045,14 @@ impl PluginManager {
         super::traits::KeyboardAction::None
     }
 
+    /// Start each enabled plugin's background warm-up (see [`Plugin::warm_up`]);
+    /// returns without waiting for any of it
+    pub fn warm_up(&self) {
+        for plugin in self.plugins.iter().filter(|p| p.enabled()) {
+            plugin.warm_up();
+        }
+    }
+
     /// Get list of enabled plugins
     pub fn enabled_plugins(&self) -> Vec<&str> {
         self.plugins
@@ -501,6 +1074,96 @@ impl PluginManager {
         result.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         result
     }
+
+    /// Last and average search time of every plugin searched so far, slowest
+    /// on average first (for the debug overlay)
+    pub fn timing_snapshot(&self) -> Vec<PluginTiming> {
+        let metrics = self.performance_metrics.borrow();
+        let mut timings: Vec<PluginTiming> = metrics
+            .iter()
+            .map(|(name, m)| PluginTiming {
+                plugin: name.clone(),
+                last_ms: m.last_time.as_micros() as f64 / 1000.0,
+                average_ms: m.average_ms(),
+                calls: m.call_count,
+                last_results: m.last_results,
+            })
+            .collect();
+
+        timings.sort_by(|a, b| {
+            b.average_ms
+                .partial_cmp(&a.average_ms)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.plugin.cmp(&b.plugin))
+        });
+        timings
+    }
+}
+
+/// Clean up a typed or pasted query before it is dispatched: drop zero-width and
+/// control characters, trim whitespace (including NBSP) and turn each inner
+/// whitespace run into one plain space, or into as many spaces as it had with
+/// `keep_spacing`
+///
+/// The zero-width joiner and non-joiner stay; emoji sequences and some scripts
+/// need them.
+fn normalize_query(query: &str, keep_spacing: bool) -> Cow<'_, str> {
+    let is_invisible = |c: char| {
+        matches!(c, '\u{200B}' | '\u{2060}' | '\u{FEFF}') || (c.is_control() && !c.is_whitespace())
+    };
+
+    let mut normalized = String::with_capacity(query.len());
+    let mut spaces = 0;
+    for c in query.chars().filter(|c| !is_invisible(*c)) {
+        if c.is_whitespace() {
+            spaces += 1;
+            continue;
+        }
+        if spaces > 0 && !normalized.is_empty() {
+            let run = if keep_spacing { spaces } else { 1 };
+            normalized.extend(std::iter::repeat_n(' ', run));
+        }
+        spaces = 0;
+        normalized.push(c);
+    }
+
+    if normalized == query {
+        Cow::Borrowed(query)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Reserved scope prefixes and the plugins a search starting with one is
+/// restricted to ("@apps firefox" searches applications only). Plugins with a
+/// command prefix of their own (`@files`, `@web`) are already searched alone.
+const SCOPES: &[(&str, &[&str])] = &[("@apps", &["applications"])];
+
+/// A query starting with a scope prefix
+#[derive(Debug)]
+struct Scope<'q> {
+    /// Plugins the search is restricted to
+    plugins: &'static [&'static str],
+    /// The query after the prefix
+    rest: &'q str,
+}
+
+impl Scope<'_> {
+    fn includes(&self, plugin: &dyn Plugin) -> bool {
+        self.plugins.contains(&plugin.name())
+    }
+}
+
+/// Scope named by the first word of `query`, if any
+fn parse_scope(query: &str) -> Option<Scope<'_>> {
+    let (word, rest) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    SCOPES
+        .iter()
+        .find(|(prefix, _)| *prefix == word)
+        .map(|&(_, plugins)| Scope {
+            plugins,
+            rest: rest.trim_start(),
+        })
 }
 
 #[cfg(test)]
@@ -526,6 +1189,7 @@ mod tests {
         DesktopEntry {
             name: name.to_string(),
             generic_name: None,
+            comment: None,
             exec: format!("{}", name.to_lowercase()),
             icon: None,
             categories: vec![],
@@ -533,6 +1197,11 @@ mod tests {
             terminal: false,
             path: PathBuf::from(format!("/{}.desktop", name)),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         }
     }
@@ -557,6 +1226,429 @@ mod tests {
         reset_handlers_to_builtin();
     }
 
+    #[test]
+    fn test_disabled_plugins_are_not_registered() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let arena = DesktopEntryArena::from_vec(vec![create_test_entry("Firefox")]);
+        let toggled = [
+            "calculator",
+            "advanced_calculator",
+            "files",
+            "ssh",
+            "web_search",
+            "theme-switcher",
+        ];
+
+        // Registered by default, so their absence below comes from the config
+        let mut config = create_test_config();
+        let manager = PluginManager::new(arena.clone(), None, None, &config);
+        let enabled = manager.enabled_plugins();
+        for name in toggled {
+            assert!(enabled.contains(&name), "{} should be registered", name);
+        }
+        let results = manager.search("2+2", 10).unwrap();
+        assert!(results.iter().any(|r| r.plugin_name == "calculator"));
+
+        config.plugins.calculator.enabled = false;
+        config.plugins.files.enabled = false;
+        config.plugins.ssh = false;
+        config.plugins.web_search = false;
+        config.plugins.theme_switcher = false;
+        let manager = PluginManager::new(arena, None, None, &config);
+
+        let enabled = manager.enabled_plugins();
+        for disabled in toggled {
+            assert!(
+                !enabled.contains(&disabled),
+                "{} should not be registered",
+                disabled
+            );
+        }
+        let results = manager.search("2+2", 10).unwrap();
+        assert!(results.iter().all(|r| r.plugin_name != "calculator"));
+        assert!(enabled.contains(&"applications"));
+        assert!(enabled.contains(&"shell"));
+        assert!(enabled.contains(&"editors"));
+        reset_handlers_to_builtin();
+    }
+
+    /// Test plugin that always returns more results than requested
+    #[derive(Debug)]
+    struct ChattyPlugin;
+
+    impl Plugin for ChattyPlugin {
+        fn name(&self) -> &str {
+            "chatty"
+        }
+
+        fn description(&self) -> &str {
+            "Returns many results"
+        }
+
+        fn command_prefixes(&self) -> Vec<&str> {
+            vec!["@chatty"]
+        }
+
+        fn should_handle(&self, _query: &str) -> bool {
+            true
+        }
+
+        fn search(&self, _query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+            Ok((0..5)
+                .map(|i| {
+                    PluginResult::new(
+                        format!("chatty {}", i),
+                        format!("true {}", i),
+                        "chatty".into(),
+                    )
+                    .with_score(5000 + i)
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_per_plugin_result_cap() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let mut config = create_test_config();
+        config.plugins.max_results.insert("chatty".to_string(), 1);
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        manager.register_plugin(Box::new(ChattyPlugin));
+
+        let count_chatty =
+            |results: &[PluginResult]| results.iter().filter(|r| r.plugin_name == "chatty").count();
+
+        let results = manager.search("something", 10).unwrap();
+        assert_eq!(count_chatty(&results), 1);
+        // The highest-scored result survives the cap
+        assert!(results.iter().any(|r| r.title == "chatty 4"));
+
+        // Incremental search applies the same cap in whichever tier the plugin lands
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let (mut fast, mut slow) = (Vec::new(), Vec::new());
+        manager
+            .search_incremental("something", 10, &token, |r| fast = r, |r| slow = r)
+            .unwrap();
+        assert_eq!(count_chatty(&fast) + count_chatty(&slow), 1);
+
+        // Explicit command queries are not capped
+        let results = manager.search("@chatty", 10).unwrap();
+        assert_eq!(count_chatty(&results), 5);
+        reset_handlers_to_builtin();
+    }
+
+    /// Test plugin returning one file result, like recent files and the file index
+    #[derive(Debug)]
+    struct ReportPlugin {
+        name: &'static str,
+        score: i64,
+    }
+
+    impl Plugin for ReportPlugin {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Returns the same file as other plugins"
+        }
+
+        fn command_prefixes(&self) -> Vec<&str> {
+            vec!["@report"]
+        }
+
+        fn should_handle(&self, _query: &str) -> bool {
+            true
+        }
+
+        fn search(&self, _query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+            Ok(vec![PluginResult::new(
+                "report.pdf".into(),
+                "xdg-open /tmp/report.pdf".into(),
+                self.name.into(),
+            )
+            .with_score(self.score)])
+        }
+    }
+
+    /// Test plugin returning one result scored like every other instance's
+    #[derive(Debug)]
+    struct TiedPlugin {
+        name: &'static str,
+        priority: i32,
+    }
+
+    impl Plugin for TiedPlugin {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Returns a result tied with other plugins"
+        }
+
+        fn command_prefixes(&self) -> Vec<&str> {
+            vec!["@tied"]
+        }
+
+        fn should_handle(&self, _query: &str) -> bool {
+            true
+        }
+
+        fn search(&self, _query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+            Ok(vec![PluginResult::new(
+                format!("notes from {}", self.name),
+                format!("xdg-open /tmp/{}.txt", self.name),
+                self.name.into(),
+            )
+            .with_score(700)])
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    #[test]
+    fn test_priority_override_orders_plugins_and_ties() {
+        let names = |config: &Config, query: &str| -> Vec<String> {
+            let mut config = config.clone();
+            config.plugins.files.enabled = false;
+            let mut manager =
+                PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+            manager.register_plugin(Box::new(TiedPlugin {
+                name: "files",
+                priority: 650,
+            }));
+            manager.register_plugin(Box::new(TiedPlugin {
+                name: "applications_like",
+                priority: 1000,
+            }));
+            // Plugins are consulted (e.g. for keys) in the same order
+            let order = manager.enabled_plugins();
+            let position = |name| order.iter().position(|p| *p == name).unwrap();
+            assert_eq!(
+                position("files") < position("applications"),
+                config.plugins.priority.contains_key("files")
+            );
+            manager
+                .search(query, 10)
+                .unwrap()
+                .into_iter()
+                .filter(|r| r.title.starts_with("notes from"))
+                .map(|r| r.plugin_name)
+                .collect()
+        };
+
+        let mut config = create_test_config();
+        assert_eq!(names(&config, "notes"), vec!["applications_like", "files"]);
+
+        config.plugins.priority.insert("files".to_string(), 1100);
+        assert_eq!(names(&config, "notes"), vec!["files", "applications_like"]);
+        assert_eq!(names(&config, "@tied"), vec!["files", "applications_like"]);
+    }
+
+    /// Test plugin whose search always fails
+    #[derive(Debug)]
+    struct FailingPlugin;
+
+    impl Plugin for FailingPlugin {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn description(&self) -> &str {
+            "Fails every search"
+        }
+
+        fn command_prefixes(&self) -> Vec<&str> {
+            vec!["@report"]
+        }
+
+        fn should_handle(&self, _query: &str) -> bool {
+            true
+        }
+
+        fn search(&self, _query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+            Err(anyhow::anyhow!("database is locked"))
+        }
+    }
+
+    #[test]
+    fn test_plugin_error_recorded_without_dropping_results() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let config = create_test_config();
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        manager.register_plugin(Box::new(FailingPlugin));
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "recent",
+            score: 700,
+        }));
+        let expected = vec![PluginSearchError {
+            plugin: "failing".to_string(),
+            message: "database is locked".to_string(),
+        }];
+
+        for query in ["report", "@report"] {
+            let results = manager.search(query, 10).unwrap();
+            assert!(results.iter().any(|r| r.title == "report.pdf"), "{}", query);
+            assert_eq!(manager.last_errors(), expected);
+        }
+        assert_eq!(
+            manager.last_errors()[0].to_string(),
+            "failing failed: database is locked"
+        );
+
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let (mut fast, mut slow) = (Vec::new(), Vec::new());
+        manager
+            .search_incremental("report", 10, &token, |r| fast = r, |r| slow = r)
+            .unwrap();
+        assert!(fast.iter().chain(&slow).any(|r| r.title == "report.pdf"));
+        assert_eq!(manager.last_errors(), expected);
+        reset_handlers_to_builtin();
+    }
+
+    #[test]
+    fn test_identical_commands_are_merged() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let config = create_test_config();
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "recent",
+            score: 700,
+        }));
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "index",
+            score: 900,
+        }));
+
+        let reports = |results: &[PluginResult]| -> Vec<String> {
+            results
+                .iter()
+                .filter(|r| r.command == "xdg-open /tmp/report.pdf")
+                .map(|r| r.plugin_name.clone())
+                .collect()
+        };
+
+        // The highest-scored copy wins
+        let results = manager.search("report", 10).unwrap();
+        assert_eq!(reports(&results), vec!["index"]);
+        let results = manager.search("@report", 10).unwrap();
+        assert_eq!(reports(&results), vec!["index"]);
+
+        // Incremental search never shows the same command in both tiers
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let (mut fast, mut slow) = (Vec::new(), Vec::new());
+        manager
+            .search_incremental("report", 10, &token, |r| fast = r, |r| slow = r)
+            .unwrap();
+        assert_eq!(reports(&fast).len() + reports(&slow).len(), 1);
+        reset_handlers_to_builtin();
+    }
+
+    #[test]
+    fn test_timing_snapshot_has_entry_per_searched_plugin() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let config = create_test_config();
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "recent",
+            score: 700,
+        }));
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "index",
+            score: 900,
+        }));
+        assert!(manager.timing_snapshot().is_empty());
+
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        for query in ["report", "reports"] {
+            manager
+                .search_incremental(query, 10, &token, |_| {}, |_| {})
+                .unwrap();
+        }
+
+        let snapshot = manager.timing_snapshot();
+        for name in ["recent", "index"] {
+            let timing: Vec<_> = snapshot.iter().filter(|t| t.plugin == name).collect();
+            assert_eq!(timing.len(), 1, "{}", name);
+            assert_eq!(timing[0].calls, 2);
+            assert_eq!(timing[0].last_results, 1);
+            assert!(timing[0].summary().starts_with(name));
+        }
+        // Same plugins as the logged metrics, one entry each
+        assert_eq!(snapshot.len(), manager.get_performance_metrics().len());
+        reset_handlers_to_builtin();
+    }
+
+    #[test]
+    fn test_explain_records_each_result() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let arena = DesktopEntryArena::from_vec(vec![create_test_entry("Firefox")]);
+        let config = create_test_config();
+        let mut manager = PluginManager::new(arena, None, None, &config);
+        manager.register_plugin(Box::new(ChattyPlugin));
+
+        // Off by default (unless NL_EXPLAIN is set in the environment)
+        manager.explain = false;
+        manager.search("fire", 10).unwrap();
+        assert!(manager.last_explanation.borrow().is_empty());
+
+        manager.explain = true;
+        let results = manager.search("fire", 10).unwrap();
+        let explanation = manager.last_explanation.borrow().clone();
+
+        assert_eq!(explanation.len(), results.len());
+        for (i, (record, result)) in explanation.iter().zip(&results).enumerate() {
+            assert_eq!(record.rank, i + 1);
+            assert_eq!(record.title, result.title);
+            assert_eq!(record.plugin, result.plugin_name);
+            assert_eq!(record.score, result.score);
+        }
+        let top_chatty = explanation.iter().find(|e| e.title == "chatty 4").unwrap();
+        assert_eq!(top_chatty.plugin, "chatty");
+        assert_eq!(top_chatty.rationale, "score 5004 > 5003 (chatty 3)");
+        assert_eq!(explanation.last().unwrap().rationale, "last result");
+
+        // Incremental search explains the fast and slow results as shown
+        manager.last_explanation.borrow_mut().clear();
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let (mut fast, mut slow) = (Vec::new(), Vec::new());
+        manager
+            .search_incremental("fire", 10, &token, |r| fast = r, |r| slow = r)
+            .unwrap();
+        let explained: Vec<String> = manager
+            .last_explanation
+            .borrow()
+            .iter()
+            .map(|e| e.title.clone())
+            .collect();
+        let shown: Vec<String> = fast.iter().chain(&slow).map(|r| r.title.clone()).collect();
+        assert!(!shown.is_empty());
+        assert_eq!(explained, shown);
+        reset_handlers_to_builtin();
+    }
+
+    #[test]
+    fn test_explain_flag_parsing() {
+        assert!(explain_flag_enabled(Some("1")));
+        assert!(explain_flag_enabled(Some("true")));
+        assert!(!explain_flag_enabled(Some("0")));
+        assert!(!explain_flag_enabled(Some("")));
+        assert!(!explain_flag_enabled(None));
+    }
+
     #[test]
     fn test_calculator_search() {
         let _guard = open_handler_test_lock().lock().unwrap();
@@ -572,6 +1664,338 @@ mod tests {
         reset_handlers_to_builtin();
     }
 
+    #[test]
+    fn test_prefix_alias_routes_to_plugin() {
+        use crate::plugins::traits::{KeyboardAction, KeyboardEvent};
+        use gtk4::gdk::{Key, ModifierType};
+
+        let mut config = create_test_config();
+        config
+            .prefixes
+            .insert("calculator".to_string(), "=".to_string());
+        let manager = PluginManager::new(
+            DesktopEntryArena::from_vec(vec![create_test_entry("2048 Game")]),
+            None,
+            None,
+            &config,
+        );
+
+        let results = manager.search("=2+2", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].plugin_name, "calculator");
+        assert_eq!(results[0].title, "4");
+        assert_eq!(results[0].subtitle.as_deref(), Some("= 2+2"));
+
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let mut fast = Vec::new();
+        manager
+            .search_incremental("= 6*7", 10, &token, |r| fast = r, |_| {})
+            .unwrap();
+        assert_eq!(fast[0].title, "42");
+
+        // Enter copies the value, as for "@cal 6*7"
+        let event =
+            KeyboardEvent::new(Key::Return, ModifierType::empty(), "=6*7".to_string(), true)
+                .with_selected_command(Some(fast[0].command.clone()));
+        assert!(matches!(
+            manager.dispatch_keyboard_event(&event),
+            KeyboardAction::CopyToClipboard(value) if value == "42"
+        ));
+
+        // The built-in prefix keeps working, and other queries are untouched
+        assert_eq!(manager.search("@cal 2+2", 10).unwrap()[0].title, "4");
+        assert_eq!(manager.expand_prefix_alias("2048"), "2048");
+    }
+
+    #[test]
+    fn test_scope_prefix_restricts_plugins() {
+        use crate::plugins::traits::{KeyboardAction, KeyboardEvent};
+        use gtk4::gdk::{Key, ModifierType};
+
+        let config = create_test_config();
+        let mut manager = PluginManager::new(
+            DesktopEntryArena::from_vec(vec![
+                create_test_entry("Code"),
+                create_test_entry("Firefox"),
+            ]),
+            None,
+            None,
+            &config,
+        );
+        // Matches every query, like files and editors results would
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "editors",
+            score: 50000,
+        }));
+
+        let results = manager.search("code", 10).unwrap();
+        assert!(results.iter().any(|r| r.plugin_name == "editors"));
+
+        let results = manager.search("@apps code", 10).unwrap();
+        assert_eq!(results[0].title, "Code");
+        assert!(results.iter().all(|r| r.plugin_name == "applications"));
+
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let (mut fast, mut slow) = (Vec::new(), Vec::new());
+        manager
+            .search_incremental("@apps code", 10, &token, |r| fast = r, |r| slow = r)
+            .unwrap();
+        assert_eq!(fast[0].title, "Code");
+        assert!(fast.iter().all(|r| r.plugin_name == "applications"));
+        assert!(slow.is_empty());
+
+        // Enter on a scoped query only reaches the scope's plugins: the
+        // calculator copies "42" for "6*7" but never sees "@apps 6*7"
+        for (query, calculator_sees_it) in [("6*7", true), ("@apps 6*7", false)] {
+            let event =
+                KeyboardEvent::new(Key::Return, ModifierType::empty(), query.to_string(), true)
+                    .with_selected_command(Some("echo '42'".to_string()));
+            let copied = matches!(
+                manager.dispatch_keyboard_event(&event),
+                KeyboardAction::CopyToClipboard(value) if value == "42"
+            );
+            assert_eq!(copied, calculator_sees_it, "{}", query);
+        }
+
+        // Only a whole first word is a scope, and plugin prefixes aren't scopes
+        assert!(parse_scope("@appsearch").is_none());
+        assert!(parse_scope("@web rust").is_none());
+        assert!(parse_scope("@files notes").is_none());
+        let scope = parse_scope("@apps  fire fox").unwrap();
+        assert_eq!(scope.plugins, ["applications"]);
+        assert_eq!(scope.rest, "fire fox");
+    }
+
+    #[test]
+    fn test_pasted_query_is_normalized() {
+        assert_eq!(
+            normalize_query(
+                "\u{00A0}\u{200B}@calc\u{00A0}\u{00A0}2 +\u{200B} 3\u{FEFF}\t",
+                false
+            ),
+            "@calc 2 + 3"
+        );
+        assert_eq!(
+            normalize_query("rust  lang\u{2003}book", false),
+            "rust lang book"
+        );
+        // Shell commands keep their spacing, emoji keep their joiners
+        assert_eq!(
+            normalize_query(" > printf 'a  b'\u{00A0}", true),
+            "> printf 'a  b'"
+        );
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(normalize_query(family, false), family);
+        assert!(matches!(
+            normalize_query("firefox", false),
+            Cow::Borrowed("firefox")
+        ));
+
+        // The pasted query still routes to the scoped plugin
+        let config = create_test_config();
+        let mut manager = PluginManager::new(
+            DesktopEntryArena::from_vec(vec![create_test_entry("Code")]),
+            None,
+            None,
+            &config,
+        );
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "editors",
+            score: 50000,
+        }));
+        let pasted = "\u{00A0}\u{200B}@apps\u{00A0}code\u{200B}";
+        let results = manager.search(pasted, 10).unwrap();
+        assert_eq!(results[0].title, "Code");
+        assert!(results.iter().all(|r| r.plugin_name == "applications"));
+
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let mut fast = Vec::new();
+        manager
+            .search_incremental(pasted, 10, &token, |r| fast = r, |_| {})
+            .unwrap();
+        assert_eq!(fast[0].title, "Code");
+    }
+
+    #[test]
+    fn test_shell_prefixes_keep_spacing_and_enter_sees_normalized_query() {
+        use crate::plugins::traits::{KeyboardAction, KeyboardEvent};
+        use gtk4::gdk::{Key, ModifierType};
+
+        let config = create_test_config();
+        let manager = PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        for query in ["> echo 'a  b'", "$ echo 'a  b'", "@shell echo 'a  b'"] {
+            assert_eq!(
+                manager.normalize_query(&format!(" {}\u{00A0}", query)),
+                query
+            );
+        }
+        assert_eq!(manager.normalize_query("echo  'a  b'"), "echo 'a b'");
+
+        // Enter is dispatched with the query as searched, so the calculation on
+        // the selected row is the one copied
+        let typed = "\u{00A0}@cal 6*7\u{200B}";
+        let results = manager.search(typed, 10).unwrap();
+        let calculation = results
+            .iter()
+            .find(|r| r.plugin_name == "calculator")
+            .unwrap();
+        let event = KeyboardEvent::new(Key::Return, ModifierType::empty(), typed.to_string(), true)
+            .with_selected_command(Some(calculation.command.clone()));
+        match manager.dispatch_keyboard_event(&event) {
+            KeyboardAction::CopyToClipboard(text) => assert_eq!(text, calculation.title),
+            other => panic!("Expected CopyToClipboard action, got {:?}", other),
+        }
+    }
+
+    /// Test plugin whose warm-up keeps running until the test releases it
+    #[derive(Debug)]
+    struct WarmingPlugin {
+        calls: Arc<AtomicU64>,
+        release: Arc<std::sync::Mutex<std::sync::mpsc::Receiver<()>>>,
+        done: std::sync::mpsc::Sender<()>,
+    }
+
+    impl Plugin for WarmingPlugin {
+        fn name(&self) -> &str {
+            "warming"
+        }
+
+        fn description(&self) -> &str {
+            "Warms up on a background thread"
+        }
+
+        fn should_handle(&self, _query: &str) -> bool {
+            false
+        }
+
+        fn search(&self, _query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+            Ok(Vec::new())
+        }
+
+        fn warm_up(&self) {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let (release, done) = (self.release.clone(), self.done.clone());
+            std::thread::spawn(move || {
+                let _ = release.lock().unwrap().recv_timeout(Duration::from_secs(5));
+                let _ = done.send(());
+            });
+        }
+    }
+
+    #[test]
+    fn test_warm_up_runs_hooks_without_blocking() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let config = create_test_config();
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        let calls = Arc::new(AtomicU64::new(0));
+        let (release_tx, release_rx) = std::sync::mpsc::channel();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        manager.register_plugin(Box::new(WarmingPlugin {
+            calls: calls.clone(),
+            release: Arc::new(std::sync::Mutex::new(release_rx)),
+            done: done_tx,
+        }));
+
+        manager.warm_up();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        // Returned while the warm-up is still waiting to be released
+        assert!(done_rx.try_recv().is_err());
+
+        release_tx.send(()).unwrap();
+        done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        reset_handlers_to_builtin();
+    }
+
+    #[test]
+    fn test_partial_calculation_ranked_first() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let config = create_test_config();
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        // Outscores the calculator; "web_search" is searched in the fast tier
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "web_search",
+            score: 50000,
+        }));
+
+        let results = manager.search("(2+3)*(4", 10).unwrap();
+        assert_eq!(results[0].plugin_name, "calculator");
+        assert_eq!(results[0].title, "20");
+        assert_eq!(results[1].title, "report.pdf");
+
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let mut fast = Vec::new();
+        manager
+            .search_incremental("12*3+", 10, &token, |r| fast = r, |_| {})
+            .unwrap();
+        assert_eq!(fast[0].plugin_name, "calculator");
+        assert_eq!(fast[0].title, "36");
+
+        // A plain number is left where its score puts it
+        let results = manager.search("2048", 10).unwrap();
+        assert_eq!(results[0].title, "report.pdf");
+        reset_handlers_to_builtin();
+    }
+
+    #[test]
+    fn test_ambient_calculation_stays_below_apps() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let config = create_test_config();
+        let mut manager =
+            PluginManager::new(DesktopEntryArena::from_vec(vec![]), None, None, &config);
+        // Stands in for an app matching the words around the arithmetic;
+        // "web_search" is searched in the fast tier, like applications
+        manager.register_plugin(Box::new(ReportPlugin {
+            name: "web_search",
+            score: 700,
+        }));
+
+        let results = manager.search("firefox 2+2", 10).unwrap();
+        assert_eq!(results[0].title, "report.pdf");
+        assert!(results.iter().any(|r| r.title == "4"));
+
+        let token = SearchToken::new(Arc::new(AtomicU64::new(0)));
+        let mut fast = Vec::new();
+        manager
+            .search_incremental("firefox 2+2", 10, &token, |r| fast = r, |_| {})
+            .unwrap();
+        assert_eq!(fast[0].title, "report.pdf");
+        reset_handlers_to_builtin();
+    }
+
+    #[test]
+    fn test_copy_action_dispatched() {
+        use crate::plugins::traits::{KeyboardAction, KeyboardEvent};
+        use gtk4::gdk::{Key, ModifierType};
+
+        let arena = DesktopEntryArena::from_vec(vec![create_test_entry("Firefox")]);
+        let config = create_test_config();
+        let manager = PluginManager::new(arena, None, None, &config);
+
+        let event = KeyboardEvent::new(
+            Key::Return,
+            ModifierType::SHIFT_MASK,
+            "12*12".to_string(),
+            true,
+        )
+        .with_selected_command(Some("echo '144'".to_string()));
+        match manager.dispatch_keyboard_event(&event) {
+            KeyboardAction::CopyToClipboard(text) => assert_eq!(text, "144"),
+            other => panic!("Expected CopyToClipboard action, got {:?}", other),
+        }
+
+        let event =
+            KeyboardEvent::new(Key::Return, ModifierType::empty(), "fire".to_string(), true);
+        assert!(matches!(
+            manager.dispatch_keyboard_event(&event),
+            KeyboardAction::None
+        ));
+    }
+
     #[test]
     fn test_shell_search() {
         let _guard = open_handler_test_lock().lock().unwrap();
@@ -587,6 +2011,47 @@ mod tests {
         reset_handlers_to_builtin();
     }
 
+    #[test]
+    fn cancelled_search_skips_slow_callback() {
+        let _guard = open_handler_test_lock().lock().unwrap();
+        reset_handlers_to_builtin();
+        let arena = DesktopEntryArena::from_vec(vec![create_test_entry("Firefox")]);
+        let config = create_test_config();
+        let manager = PluginManager::new(arena, None, None, &config);
+
+        let generation = Arc::new(AtomicU64::new(0));
+        let token = SearchToken::new(generation.clone());
+        let fast_called = std::cell::Cell::new(false);
+        let slow_called = std::cell::Cell::new(false);
+
+        manager
+            .search_incremental(
+                "fire",
+                10,
+                &token,
+                |_| {
+                    fast_called.set(true);
+                    // Simulate a new keystroke arriving while slow plugins are pending
+                    generation.fetch_add(1, Ordering::SeqCst);
+                },
+                |_| slow_called.set(true),
+            )
+            .unwrap();
+
+        assert!(fast_called.get());
+        assert!(!slow_called.get());
+        assert!(token.is_cancelled());
+
+        // A fresh token runs to completion
+        let fresh = SearchToken::new(generation.clone());
+        let slow_called = std::cell::Cell::new(false);
+        manager
+            .search_incremental("fire", 10, &fresh, |_| {}, |_| slow_called.set(true))
+            .unwrap();
+        assert!(slow_called.get());
+        reset_handlers_to_builtin();
+    }
+
     #[test]
     fn registers_filesystem_handler_once() {
         let _guard = open_handler_test_lock().lock().unwrap();
@@ -635,4 +2100,41 @@ mod tests {
         // Relative paths without scheme should be ignored
         assert!(resolve_filesystem_path("relative/path").is_none());
     }
+
+    #[test]
+    fn test_web_fallback_only_when_nothing_matches() {
+        let mut config = create_test_config();
+        // Disable plugins that answer arbitrary text so only apps can match
+        config.plugins.web_search = false;
+        config.plugins.files.enabled = false;
+        config.plugins.browser_history.enabled = false;
+        config.plugins.recent_documents = false;
+        config.plugins.git_projects = false;
+        config.plugins.editors = false;
+        config.plugins.ssh = false;
+        config.plugins.window_management = false;
+        config.plugins.session_switcher = false;
+        let arena = DesktopEntryArena::from_vec(vec![create_test_entry("Firefox")]);
+        let manager = PluginManager::new(arena, None, None, &config);
+
+        let results = manager.search("qwzxvb", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Search the web for 'qwzxvb'");
+        assert!(results[0].command.contains("qwzxvb"));
+
+        let results = manager.search("firefox", 10).unwrap();
+        assert!(!results.is_empty());
+        assert!(results
+            .iter()
+            .all(|r| !r.title.starts_with("Search the web")));
+
+        // Prefix commands never fall back
+        let results = manager.search("@nosuchcommand qwzxvb", 10).unwrap();
+        assert!(results.is_empty());
+
+        config.search.web_fallback = false;
+        let arena = DesktopEntryArena::from_vec(vec![create_test_entry("Firefox")]);
+        let manager = PluginManager::new(arena, None, None, &config);
+        assert!(manager.search("qwzxvb", 10).unwrap().is_empty());
+    }
 }
@@ -4,14 +4,19 @@ pub mod browser_history;
 mod browser_index;
 pub mod calculator;
 pub mod clipboard;
+pub mod currency_rates;
 pub mod dynamic;
 pub mod editors;
 pub mod emoji;
+pub mod encoder;
 pub mod file_index;
 pub mod files;
+pub mod generator;
 pub mod git_projects;
+pub mod hash;
 pub mod launcher;
 pub mod manager;
+pub mod qr_code;
 pub mod recent;
 pub mod screenshot;
 #[allow(dead_code)] // Complete but not yet integrated - see docs/SCRIPT_PLUGIN_SYSTEM.md
@@ -23,6 +28,7 @@ pub mod theme_switcher;
 pub mod traits;
 pub mod web_search;
 pub mod window_management;
+pub mod windows;
 
 pub use advanced_calc::AdvancedCalculatorPlugin;
 pub use applications::ApplicationsPlugin;
@@ -33,10 +39,14 @@ pub use clipboard::ClipboardPlugin;
 pub use dynamic::{load_plugins, PluginMetrics};
 pub use editors::EditorsPlugin;
 pub use emoji::EmojiPlugin;
+pub use encoder::EncoderPlugin;
 pub use files::FileBrowserPlugin;
+pub use generator::GeneratorPlugin;
 pub use git_projects::GitProjectsPlugin;
+pub use hash::HashPlugin;
 pub use launcher::LauncherPlugin;
-pub use manager::PluginManager;
+pub use manager::{PluginManager, PluginTiming, SearchToken};
+pub use qr_code::QrCodePlugin;
 pub use recent::RecentDocumentsPlugin;
 // Script plugin system is complete but not integrated yet - uncomment when ready to use
 // pub use script_plugin::{ScriptPlugin, ScriptPluginManager};
@@ -45,6 +55,9 @@ pub use session_switcher::SessionSwitcherPlugin;
 pub use shell::ShellPlugin;
 pub use ssh::SshPlugin;
 pub use theme_switcher::ThemeSwitcherPlugin;
-pub use traits::{KeyboardAction, KeyboardEvent, PluginResult};
+pub use traits::{
+    KeyboardAction, KeyboardEvent, PluginResult, ResultAction, ResultActivation, ResultBadge,
+};
 pub use web_search::WebSearchPlugin;
 pub use window_management::WindowManagementPlugin;
+pub use windows::WindowList;
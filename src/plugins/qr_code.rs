@@ -0,0 +1,327 @@
+//! QR code plugin (`@qr <text>`)
+//!
+//! Encodes the typed text as a QR code and shows it as the result's icon, kept
+//! in memory while typing. Activating a result saves it as a PNG in the cache
+//! directory and opens it in the image viewer (or copies it to the clipboard)
+//! for scanning with a phone.
+
+use super::traits::{Plugin, PluginContext, PluginResult, ResultActivation};
+use crate::utils::clipboard::{self, ClipboardTool};
+use crate::utils::{open_command_line, shell_escape};
+use anyhow::{anyhow, Context, Result};
+use gtk4::gdk_pixbuf::{Colorspace, Pixbuf};
+use qrcode::types::QrError;
+use qrcode::{Color, EcLevel, QrCode};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Most bytes of text a QR code can hold (version 40, low error correction)
+pub const MAX_QR_BYTES: usize = 2953;
+
+/// Light modules around the code, as required by the QR spec
+const QUIET_ZONE: usize = 4;
+
+/// Pixels per module in the saved PNG
+const MODULE_PIXELS: usize = 8;
+
+/// Square grid of QR modules, `true` for dark
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrMatrix {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Encode `text`, preferring medium error correction and dropping to low
+    /// when that is the only way the text fits
+    pub fn encode(text: &str) -> Result<Self> {
+        let code = match QrCode::with_error_correction_level(text, EcLevel::M) {
+            Err(QrError::DataTooLong) => QrCode::with_error_correction_level(text, EcLevel::L),
+            other => other,
+        };
+        let code = code.map_err(|e| match e {
+            QrError::DataTooLong => anyhow!(
+                "Too long for a QR code: {} bytes (at most {})",
+                text.len(),
+                MAX_QR_BYTES
+            ),
+            other => anyhow!("Can't encode as a QR code: {}", other),
+        })?;
+
+        Ok(Self {
+            width: code.width(),
+            modules: code
+                .to_colors()
+                .into_iter()
+                .map(|color| color == Color::Dark)
+                .collect(),
+        })
+    }
+
+    /// Whether the module at (`x`, `y`) is dark
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.width + x]
+    }
+
+    /// Encode as a black-on-white PNG with a quiet zone
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let side = (self.width + 2 * QUIET_ZONE) * MODULE_PIXELS;
+        let mut pixels = vec![0xff_u8; side * side * 3];
+        for y in 0..self.width {
+            for x in 0..self.width {
+                if !self.is_dark(x, y) {
+                    continue;
+                }
+                let (left, top) = (
+                    (x + QUIET_ZONE) * MODULE_PIXELS,
+                    (y + QUIET_ZONE) * MODULE_PIXELS,
+                );
+                for row in top..top + MODULE_PIXELS {
+                    let start = (row * side + left) * 3;
+                    pixels[start..start + MODULE_PIXELS * 3].fill(0);
+                }
+            }
+        }
+
+        let side = side as i32;
+        let pixbuf =
+            Pixbuf::from_mut_slice(pixels, Colorspace::Rgb, false, 8, side, side, side * 3);
+        pixbuf
+            .save_to_bufferv("png", &[])
+            .context("Failed to encode QR code as PNG")
+    }
+}
+
+#[derive(Debug)]
+pub struct QrCodePlugin {
+    enabled: bool,
+    output_dir: PathBuf,
+    clipboard: Option<ClipboardTool>,
+}
+
+impl QrCodePlugin {
+    pub fn new() -> Self {
+        let output_dir = dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join("native-launcher")
+            .join("qr");
+
+        Self {
+            enabled: true,
+            output_dir,
+            clipboard: clipboard::detect_tool(),
+        }
+    }
+
+    fn strip_prefix<'a>(&self, query: &'a str) -> &'a str {
+        query.strip_prefix("@qr").unwrap_or(query)
+    }
+
+    /// PNG file for `text`, named after its hash so repeated queries reuse it
+    fn png_path(&self, text: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        self.output_dir
+            .join(format!("qr-{:016x}.png", hasher.finish()))
+    }
+
+    fn message(&self, title: &str, subtitle: String, icon: &str) -> PluginResult {
+        PluginResult::new(title.to_string(), String::new(), self.name().to_string())
+            .with_subtitle(subtitle)
+            .with_icon(icon.to_string())
+            .with_score(1000)
+    }
+
+    fn results_for(&self, text: &str) -> Result<Vec<PluginResult>> {
+        let matrix = match QrMatrix::encode(text) {
+            Ok(matrix) => matrix,
+            Err(e) => {
+                return Ok(vec![self.message(
+                    "No QR code",
+                    e.to_string(),
+                    "dialog-error",
+                )])
+            }
+        };
+        let png = matrix.to_png()?;
+        let path = self.png_path(text);
+
+        let mut results = vec![PluginResult::new(
+            "Open QR code".to_string(),
+            open_command_line(&path.to_string_lossy()),
+            self.name().to_string(),
+        )
+        .with_subtitle(format!("{} • {} bytes", preview(text), text.len()))
+        .with_icon("view-barcode-qr".to_string())
+        .with_icon_data(png.clone())
+        .with_activation(ResultActivation::SaveIcon(path.clone()))
+        .with_score(2000)];
+
+        if let Some(tool) = self.clipboard {
+            results.push(
+                PluginResult::new(
+                    "Copy QR code".to_string(),
+                    format!("sh -c {}", shell_escape(&tool.copy_image_command(&path))),
+                    self.name().to_string(),
+                )
+                .with_subtitle(format!("Copy the image with {}", tool.display_name()))
+                .with_icon("edit-copy".to_string())
+                .with_icon_data(png)
+                .with_activation(ResultActivation::SaveIcon(path))
+                .with_score(1900),
+            );
+        }
+
+        Ok(results)
+    }
+}
+
+impl Default for QrCodePlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for QrCodePlugin {
+    fn name(&self) -> &str {
+        "qr_code"
+    }
+
+    fn description(&self) -> &str {
+        "Generate QR codes via @qr"
+    }
+
+    fn command_prefixes(&self) -> Vec<&str> {
+        vec!["@qr"]
+    }
+
+    fn should_handle(&self, query: &str) -> bool {
+        query.starts_with("@qr")
+    }
+
+    fn search(&self, query: &str, _context: &PluginContext) -> Result<Vec<PluginResult>> {
+        if !self.enabled {
+            return Ok(Vec::new());
+        }
+
+        let text = self.strip_prefix(query).trim();
+        if text.is_empty() {
+            return Ok(vec![self.message(
+                "QR code",
+                "Type text or a URL after @qr to encode it".to_string(),
+                "dialog-information",
+            )]);
+        }
+
+        self.results_for(text)
+    }
+
+    fn priority(&self) -> i32 {
+        750
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Single-line, shortened text for the subtitle
+fn preview(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() > 60 || line.len() < text.len() {
+        format!("{}…", line.chars().take(60).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// "https://example.com" at medium error correction (version 2, 25×25)
+    const EXPECTED: [&str; 25] = [
+        "#######.#.#.#.#...#######",
+        "#.....#..##.###.#.#.....#",
+        "#.###.#.###..#..#.#.###.#",
+        "#.###.#..#..###...#.###.#",
+        "#.###.#..#.#..#...#.###.#",
+        "#.....#.##.##.#...#.....#",
+        "#######.#.#.#.#.#.#######",
+        ".........#..#.###........",
+        "#.#...##..#####.#..#..#.#",
+        "##.###..######.#.###.#.##",
+        "#..#.###.###...#.#..###.#",
+        ".#..##..######..#..#.#...",
+        "...######.#.##.##.##....#",
+        ".##.#...###.#..##.##...##",
+        "###.###.#...#######..##.#",
+        ".......#.#.##.#.##.###...",
+        "##..#.##.#...##.#####..#.",
+        "........#....##.#...#...#",
+        "#######.#.##....#.#.#...#",
+        "#.....#......#.##...#..##",
+        "#.###.#..#.###.######..##",
+        "#.###.#..#..#....#..#.##.",
+        "#.###.#.##..######.###.##",
+        "#.....#..#.##.#######....",
+        "#######.###..##.#.#..#..#",
+    ];
+
+    fn render(matrix: &QrMatrix) -> Vec<String> {
+        (0..matrix.width)
+            .map(|y| {
+                (0..matrix.width)
+                    .map(|x| if matrix.is_dark(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_known_input_gives_deterministic_matrix() {
+        let matrix = QrMatrix::encode("https://example.com").unwrap();
+        assert_eq!(matrix, QrMatrix::encode("https://example.com").unwrap());
+        assert_eq!(render(&matrix), EXPECTED);
+
+        // Typing keeps the image in memory; it's saved only once activated
+        let plugin = QrCodePlugin::new();
+        let config = crate::config::Config::default();
+        let text = format!("native-launcher qr test {}", std::process::id());
+        let results = plugin
+            .search(&format!("@qr {}", text), &PluginContext::new(10, &config))
+            .unwrap();
+        assert!(results[0].icon_data.is_some());
+        assert!(matches!(
+            &results[0].activation,
+            Some(ResultActivation::SaveIcon(path)) if *path == plugin.png_path(&text) && !path.exists()
+        ));
+    }
+
+    #[test]
+    fn test_over_capacity_text_is_rejected() {
+        let longest = "x".repeat(MAX_QR_BYTES);
+        assert_eq!(QrMatrix::encode(&longest).unwrap().width, 177);
+
+        let error = QrMatrix::encode(&"x".repeat(MAX_QR_BYTES + 1)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Too long for a QR code: 2954 bytes (at most 2953)"
+        );
+
+        let plugin = QrCodePlugin::new();
+        let config = crate::config::Config::default();
+        let query = format!("@qr {}", "x".repeat(MAX_QR_BYTES + 1));
+        let results = plugin
+            .search(&query, &PluginContext::new(10, &config))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].command.is_empty());
+        assert!(results[0]
+            .subtitle
+            .as_deref()
+            .unwrap()
+            .starts_with("Too long for a QR code"));
+    }
+}
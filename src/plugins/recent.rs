@@ -1,14 +1,15 @@
 use super::traits::{Plugin, PluginContext, PluginResult};
+use crate::utils::open_command_line;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use dirs::home_dir;
+use dirs::data_local_dir;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::OnceLock;
 use tracing::{debug, warn};
 
 /// Recent documents plugin that aggregates recently accessed files
-/// Parses ~/.local/share/recently-used.xbel (freedesktop standard)
+/// Parses $XDG_DATA_HOME/recently-used.xbel (freedesktop standard)
 /// Uses lazy loading - entries are loaded on first search
 #[derive(Debug)]
 pub struct RecentDocumentsPlugin {
@@ -71,8 +72,8 @@ impl RecentDocumentsPlugin {
 
     /// Get path to recently-used.xbel
     fn recently_used_path() -> Result<PathBuf> {
-        let home = home_dir().context("Could not determine home directory")?;
-        Ok(home.join(".local/share/recently-used.xbel"))
+        let data_dir = data_local_dir().context("Could not determine data directory")?;
+        Ok(data_dir.join("recently-used.xbel"))
     }
 
     /// Parse XBEL XML format
@@ -282,11 +283,7 @@ impl Plugin for RecentDocumentsPlugin {
             let time_str = Self::time_ago(&entry.modified);
 
             // Build command to open file with default handler
-            let command = if entry.path.is_dir() {
-                format!("xdg-open '{}'", entry.path.display())
-            } else {
-                format!("xdg-open '{}'", entry.path.display())
-            };
+            let command = open_command_line(&entry.path.to_string_lossy());
 
             let subtitle = format!(
                 "{} • {} • {}",
@@ -309,7 +306,8 @@ impl Plugin for RecentDocumentsPlugin {
 
             let result = PluginResult::new(filename.to_string(), command, self.name().to_string())
                 .with_subtitle(subtitle)
-                .with_score(score);
+                .with_score(score)
+                .with_target_path(entry.path.to_string_lossy().to_string());
 
             results.push(result);
         }
@@ -1,4 +1,7 @@
 use super::traits::{Plugin, PluginContext, PluginResult};
+use crate::tr;
+use crate::utils::clipboard::{self, ClipboardTool};
+use crate::utils::shell_escape;
 use anyhow::{Context, Result};
 use chrono::Local;
 use dirs::{home_dir, picture_dir};
@@ -21,7 +24,7 @@ impl ScreenshotPlugin {
     pub fn new() -> Self {
         let backend = detect_backend();
         let output_dir = default_output_directory();
-        let clipboard = detect_clipboard_tool();
+        let clipboard = clipboard::detect_tool();
         let annotator = detect_annotator_tool();
 
         if let Some(ref backend) = backend {
@@ -116,12 +119,13 @@ impl ScreenshotPlugin {
 
     fn no_backend_result(&self) -> PluginResult {
         PluginResult::new(
-            "No screenshot utility detected".to_string(),
+            tr!("No screenshot utility detected").to_string(),
             String::new(),
             self.name().to_string(),
         )
         .with_subtitle(
-            "Install grimshot, hyprshot, gnome-screenshot, spectacle, maim, or scrot".to_string(),
+            tr!("Install grimshot, hyprshot, gnome-screenshot, spectacle, maim, or scrot")
+                .to_string(),
         )
         .with_icon("dialog-warning".to_string())
         .with_score(1000)
@@ -130,13 +134,13 @@ impl ScreenshotPlugin {
     fn no_results_message(&self, filter: &str) -> PluginResult {
         let filter = filter.trim();
         let subtitle = if filter.is_empty() {
-            "No screenshot options available".to_string()
+            tr!("No screenshot options available").to_string()
         } else {
             format!("No screenshot mode matches \"{}\"", filter)
         };
 
         PluginResult::new(
-            "No matching screenshot option".to_string(),
+            tr!("No matching screenshot option").to_string(),
             String::new(),
             self.name().to_string(),
         )
@@ -227,7 +231,7 @@ impl Plugin for ScreenshotPlugin {
                                 base_command, swappy_cmd, escaped_path
                             );
                             if let Some(ref clipboard) = self.clipboard {
-                                let copy_command = clipboard.command(&escaped_path);
+                                let copy_command = clipboard.copy_image_command(&output_path);
                                 let combined = format!("{} && {}", annotate_cmd, copy_command);
                                 format!("sh -c {}", shell_escape(&combined))
                             } else {
@@ -240,7 +244,7 @@ impl Plugin for ScreenshotPlugin {
                     continue;
                 }
             } else if let Some(ref clipboard) = self.clipboard {
-                let copy_command = clipboard.command(&escaped_path);
+                let copy_command = clipboard.copy_image_command(&output_path);
                 let combined = format!("{} && {}", base_command, copy_command);
                 format!("sh -c {}", shell_escape(&combined))
             } else {
@@ -541,48 +545,11 @@ impl ScreenshotBackend {
     }
 }
 
-#[derive(Debug, Clone)]
-enum ClipboardTool {
-    WlCopy { command: String },
-    Xclip { command: String },
-    Xsel { command: String },
-}
-
 #[derive(Debug, Clone)]
 enum AnnotatorTool {
     Swappy { command: String },
 }
 
-impl ClipboardTool {
-    fn command(&self, escaped_path: &str) -> String {
-        match self {
-            ClipboardTool::WlCopy { command } => {
-                format!("{} --type image/png < {}", command, escaped_path)
-            }
-            ClipboardTool::Xclip { command } => {
-                format!(
-                    "{} -selection clipboard -target image/png < {}",
-                    command, escaped_path
-                )
-            }
-            ClipboardTool::Xsel { command } => {
-                format!(
-                    "{} --clipboard --input --mime-type image/png < {}",
-                    command, escaped_path
-                )
-            }
-        }
-    }
-
-    fn display_name(&self) -> &'static str {
-        match self {
-            ClipboardTool::WlCopy { .. } => "wl-copy",
-            ClipboardTool::Xclip { .. } => "xclip",
-            ClipboardTool::Xsel { .. } => "xsel",
-        }
-    }
-}
-
 impl AnnotatorTool {
     fn display_name(&self) -> &'static str {
         match self {
@@ -664,22 +631,6 @@ fn detect_backend() -> Option<ScreenshotBackend> {
     None
 }
 
-fn detect_clipboard_tool() -> Option<ClipboardTool> {
-    if let Some(cmd) = command_path("wl-copy") {
-        return Some(ClipboardTool::WlCopy { command: cmd });
-    }
-
-    if let Some(cmd) = command_path("xclip") {
-        return Some(ClipboardTool::Xclip { command: cmd });
-    }
-
-    if let Some(cmd) = command_path("xsel") {
-        return Some(ClipboardTool::Xsel { command: cmd });
-    }
-
-    None
-}
-
 fn detect_annotator_tool() -> Option<AnnotatorTool> {
     if let Some(cmd) = command_path("swappy") {
         return Some(AnnotatorTool::Swappy { command: cmd });
@@ -704,23 +655,6 @@ fn command_path(command: &str) -> Option<String> {
         })
 }
 
-fn shell_escape(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_string();
-    }
-
-    let mut escaped = String::from("'");
-    for ch in value.chars() {
-        if ch == '\'' {
-            escaped.push_str("'\\''");
-        } else {
-            escaped.push(ch);
-        }
-    }
-    escaped.push('\'');
-    escaped
-}
-
 fn friendly_path(path: &Path) -> String {
     let display = path.to_string_lossy().to_string();
     if let Some(home) = home_dir() {
@@ -810,9 +744,7 @@ mod tests {
         let output = temp_output_dir();
         let backend = ScreenshotBackend::grimshot("grimshot".to_string());
         let mut plugin = ScreenshotPlugin::with_backend(Some(backend), output.clone());
-        plugin.clipboard = Some(ClipboardTool::WlCopy {
-            command: "wl-copy".to_string(),
-        });
+        plugin.clipboard = Some(ClipboardTool::WlCopy);
 
         let config = Config::default();
         let ctx = PluginContext::new(5, &config);
@@ -907,9 +839,7 @@ mod tests {
         plugin.annotator = Some(AnnotatorTool::Swappy {
             command: "swappy".to_string(),
         });
-        plugin.clipboard = Some(ClipboardTool::WlCopy {
-            command: "wl-copy".to_string(),
-        });
+        plugin.clipboard = Some(ClipboardTool::WlCopy);
 
         let config = Config::default();
         let ctx = PluginContext::new(10, &config);
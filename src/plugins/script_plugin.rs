@@ -344,11 +344,16 @@ impl ScriptPluginManager {
 
     /// Get list of directories to scan for plugins
     fn get_plugin_directories() -> Vec<PathBuf> {
+        Self::plugin_directories(dirs::config_dir().as_deref())
+    }
+
+    /// Directories to scan for plugins, with user plugins under `config_dir`
+    pub(crate) fn plugin_directories(config_dir: Option<&Path>) -> Vec<PathBuf> {
         let mut dirs = Vec::new();
 
-        // User plugins: ~/.config/native-launcher/plugins/
-        if let Some(config_dir) = dirs::config_dir() {
-            dirs.push(config_dir.join("native-launcher").join("plugins"));
+        // User plugins: $XDG_CONFIG_HOME/native-launcher/plugins/
+        if let Some(config_dir) = config_dir {
+            dirs.push(super::dynamic::user_plugins_dir(config_dir));
         }
 
         // System plugins: /usr/share/native-launcher/plugins/
@@ -250,8 +250,7 @@ impl SessionSwitcherPlugin {
 
     fn get_vscode_workspaces(&self) -> Option<Vec<SessionItem>> {
         // Check common VS Code workspace locations
-        let home = std::env::var("HOME").ok()?;
-        let vscode_storage = format!("{}/.config/Code/User/workspaceStorage", home);
+        let vscode_storage = dirs::config_dir()?.join("Code/User/workspaceStorage");
 
         let entries = std::fs::read_dir(&vscode_storage).ok()?;
         let mut items = Vec::new();
@@ -371,6 +370,15 @@ impl Plugin for SessionSwitcherPlugin {
                 parent_app: None,
                 desktop_path: None,
                 badge_icon: None,
+                is_directory: false,
+                target_path: None,
+                confirm: None,
+                icon_data: None,
+                actions: vec![],
+                url: None,
+                badges: Vec::new(),
+                completion: None,
+                activation: None,
             }]);
         }
 
@@ -431,6 +439,15 @@ impl Plugin for SessionSwitcherPlugin {
                     parent_app: None,
                     desktop_path: None,
                     badge_icon: None, // No badge for sessions
+                    is_directory: false,
+                    target_path: None,
+                    confirm: None,
+                    icon_data: None,
+                    actions: vec![],
+                    url: None,
+                    badges: Vec::new(),
+                    completion: None,
+                    activation: None,
                 })
             })
             .take(context.max_results)
@@ -1,19 +1,33 @@
 use super::traits::{Plugin, PluginContext, PluginResult};
+use crate::utils::FileCache;
 use anyhow::Result;
+use std::collections::HashSet;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::debug;
+
+/// Most history suggestions shown below the "Run:" result
+const MAX_HISTORY_RESULTS: usize = 5;
+
+/// Most completion candidates listed between the "Run:" result and history
+const MAX_COMPLETION_RESULTS: usize = 8;
 
 /// Plugin for executing shell commands
 #[derive(Debug)]
 pub struct ShellPlugin {
     enabled: bool,
     prefix: String,
+    /// Shell history, most recent first; read on the first shell query and
+    /// again whenever the shell has appended to it
+    history: FileCache<Vec<String>>,
+    /// Executable names on `$PATH`, sorted; read on the first completion
+    binaries: OnceLock<Vec<String>>,
 }
 
 impl ShellPlugin {
     pub fn new() -> Self {
-        Self {
-            enabled: true,
-            prefix: ">".to_string(),
-        }
+        Self::with_prefix(">".to_string())
     }
 
     /// Create with custom prefix
@@ -21,8 +35,360 @@ impl ShellPlugin {
         Self {
             enabled: true,
             prefix,
+            history: FileCache::new(history_path().unwrap_or_default(), load_history),
+            binaries: OnceLock::new(),
+        }
+    }
+
+    fn binaries(&self) -> &[String] {
+        self.binaries
+            .get_or_init(|| binaries_in(&std::env::var("PATH").unwrap_or_default()))
+    }
+
+    /// Completions of the last word of `command`: executables for the first
+    /// word, paths (relative to home, where the terminal starts) for arguments
+    fn completion_candidates(&self, command: &str) -> Vec<String> {
+        let token = last_word(command);
+        if token.len() == command.len() {
+            binary_candidates(self.binaries(), token)
+        } else {
+            let home = dirs::home_dir().unwrap_or_default();
+            path_candidates(token, &home)
+        }
+    }
+
+    /// Tab targets for `command`: the common prefix of all candidates on the
+    /// "Run:" result (returned separately) and a row per candidate
+    ///
+    /// `query` is the full search text, prefix included, which completions replace.
+    fn completion_results(
+        &self,
+        query: &str,
+        command: &str,
+    ) -> (Option<String>, Vec<PluginResult>) {
+        let token = last_word(command);
+        let candidates = self.completion_candidates(command);
+        let query = query.trim_end();
+        let head = &query[..query.len() - token.len()];
+        let command_head = &command[..command.len() - token.len()];
+
+        let common = common_prefix(&candidates);
+        let run_completion = (common.len() > token.len()).then(|| format!("{}{}", head, common));
+
+        let rows = candidates
+            .iter()
+            .filter(|candidate| candidate.trim_end() != token)
+            .take(MAX_COMPLETION_RESULTS)
+            .enumerate()
+            .map(|(rank, candidate)| {
+                let icon = if candidate.ends_with('/') {
+                    "folder"
+                } else if command_head.is_empty() {
+                    "application-x-executable"
+                } else {
+                    "text-x-generic"
+                };
+                let completed = format!("{}{}", command_head, candidate.trim_end());
+                guard(
+                    PluginResult::new(
+                        candidate.trim_end().to_string(),
+                        completed,
+                        self.name().to_string(),
+                    )
+                    .with_subtitle("Tab to complete".to_string())
+                    .with_icon(icon.to_string())
+                    .with_terminal(true)
+                    .with_score(9500 - rank as i64)
+                    .with_completion(format!("{}{}", head, candidate)),
+                )
+            })
+            .collect();
+        (run_completion, rows)
+    }
+
+    /// Recent history commands containing `command`, most recent first
+    fn history_results(&self, command: &str) -> Vec<PluginResult> {
+        let needle = command.to_lowercase();
+        self.history
+            .get()
+            .iter()
+            .filter(|entry| entry.as_str() != command && entry.to_lowercase().contains(&needle))
+            .take(MAX_HISTORY_RESULTS)
+            .enumerate()
+            .map(|(rank, entry)| {
+                guard(
+                    PluginResult::new(entry.clone(), entry.clone(), self.name().to_string())
+                        .with_subtitle("From shell history".to_string())
+                        .with_icon("document-open-recent".to_string())
+                        .with_terminal(true)
+                        .with_score(9000 - rank as i64),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Warn about a dangerous command in the result's subtitle and make running it
+/// need a confirming second Enter
+fn guard(result: PluginResult) -> PluginResult {
+    match danger(&result.command) {
+        Some(danger) => result
+            .with_subtitle(format!("Warning: this will {}", danger))
+            .with_confirm(danger.to_string()),
+        None => result,
+    }
+}
+
+/// What an obviously destructive command would do, `None` for anything else
+///
+/// Deliberately narrow so ordinary commands never need confirming: recursive
+/// `rm` of `/` or the home directory, `dd` onto a device, `mkfs` and the classic
+/// fork bomb.
+fn danger(command: &str) -> Option<&'static str> {
+    let compact: String = command.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.contains(":(){:|:&};:") {
+        return Some("start a fork bomb");
+    }
+
+    command.split([';', '&', '|', '\n']).find_map(|segment| {
+        let mut words = segment
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+            .skip_while(|word| matches!(*word, "sudo" | "doas" | "exec"));
+        let program = words.next()?.rsplit('/').next()?;
+        let args: Vec<&str> = words.collect();
+        match program {
+            "rm" => rm_danger(&args),
+            "dd" => args
+                .iter()
+                .any(|arg| arg.starts_with("of=/dev/") && *arg != "of=/dev/null")
+                .then_some("overwrite a device"),
+            _ if program == "mkfs" || program.starts_with("mkfs.") => Some("format a filesystem"),
+            _ => None,
+        }
+    })
+}
+
+/// Recursive `rm` of the root or home directory
+fn rm_danger(args: &[&str]) -> Option<&'static str> {
+    let recursive = args.iter().any(|arg| {
+        *arg == "--recursive"
+            || (arg.starts_with('-') && !arg.starts_with("--") && arg.contains(['r', 'R']))
+    });
+    if !recursive {
+        return None;
+    }
+
+    let targets = || args.iter().filter(|arg| !arg.starts_with('-'));
+    if args.contains(&"--no-preserve-root") || targets().any(|t| matches!(*t, "/" | "/*")) {
+        return Some("recursively delete the root filesystem");
+    }
+    targets()
+        .any(|target| {
+            matches!(
+                target.trim_end_matches(['/', '*']),
+                "~" | "$HOME" | "${HOME}"
+            )
+        })
+        .then_some("recursively delete your home directory")
+}
+
+/// Last whitespace-separated word of `command` (all of it for a single word)
+fn last_word(command: &str) -> &str {
+    command
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or(command)
+}
+
+/// Executable file names in the `:`-separated `path_var` directories, sorted
+/// and deduplicated
+fn binaries_in(path_var: &str) -> Vec<String> {
+    let mut binaries: Vec<String> = std::env::split_paths(path_var)
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            entry.metadata().is_ok_and(|metadata| {
+                metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+            })
+        })
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    binaries.sort();
+    binaries.dedup();
+    binaries
+}
+
+/// Executables starting with `prefix`, each followed by a space for its arguments
+fn binary_candidates(binaries: &[String], prefix: &str) -> Vec<String> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    binaries
+        .iter()
+        .filter(|name| name.starts_with(prefix))
+        .map(|name| format!("{} ", name))
+        .collect()
+}
+
+/// Completions of the path `token`, sorted: directories end in `/`, files in a
+/// space; relative paths and `~/` resolve against `home`
+///
+/// Hidden entries are only offered when the typed name starts with a dot, and
+/// quoted or escaped tokens are left alone.
+fn path_candidates(token: &str, home: &Path) -> Vec<String> {
+    if token.is_empty() || token.contains(['"', '\'', '\\']) {
+        return Vec::new();
+    }
+    if token == "~" {
+        return vec!["~/".to_string()];
+    }
+
+    let (dir_part, name_prefix) = match token.rfind('/') {
+        Some(slash) => token.split_at(slash + 1),
+        None => ("", token),
+    };
+    let dir = match dir_part.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => home.join(dir_part),
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            if !name.starts_with(name_prefix)
+                || (name.starts_with('.') && !name_prefix.starts_with('.'))
+            {
+                return None;
+            }
+            let end = if entry.path().is_dir() { "/" } else { " " };
+            Some(format!("{}{}{}", dir_part, name.replace(' ', "\\ "), end))
+        })
+        .collect();
+    candidates.sort();
+    candidates
+}
+
+/// Longest prefix shared by all `candidates` (empty when there are none)
+fn common_prefix(candidates: &[String]) -> &str {
+    let Some((first, rest)) = candidates.split_first() else {
+        return "";
+    };
+    let mut len = first.len();
+    for candidate in rest {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((i, a), b)| *i < len && a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0);
+    }
+    &first[..len]
+}
+
+/// History file of the user's shell (`$SHELL`): zsh or bash
+fn history_path() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    let shell = std::env::var("SHELL").unwrap_or_default();
+    if shell.ends_with("zsh") {
+        Some(home.join(".zsh_history"))
+    } else {
+        Some(home.join(".bash_history"))
+    }
+}
+
+fn load_history(path: &Path) -> Vec<String> {
+    // zsh stores some bytes "metafied", so don't insist on valid UTF-8
+    match std::fs::read(path) {
+        Ok(bytes) => {
+            let history = parse_history(&String::from_utf8_lossy(&bytes));
+            debug!(
+                "Loaded {} shell history entries from {}",
+                history.len(),
+                path.display()
+            );
+            history
+        }
+        Err(e) => {
+            debug!("No shell history at {}: {}", path.display(), e);
+            Vec::new()
+        }
+    }
+}
+
+/// Parse a bash or zsh history file into unique commands, most recent first
+///
+/// Handles zsh extended history (`: 1700000000:0;git status`), bash timestamp
+/// comments (`#1700000000`, written with `HISTTIMEFORMAT`) and zsh's multi-line
+/// entries, whose lines end in a backslash.
+fn parse_history(content: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut pending: Option<String> = None;
+
+    for line in content.lines() {
+        let line = match pending.take() {
+            Some(mut previous) => {
+                previous.push('\n');
+                previous.push_str(line);
+                previous
+            }
+            None => {
+                if is_timestamp_comment(line) {
+                    continue;
+                }
+                strip_extended_header(line).to_string()
+            }
+        };
+
+        if let Some(continued) = line.strip_suffix('\\') {
+            pending = Some(continued.to_string());
+            continue;
+        }
+
+        let command = line.trim();
+        if !command.is_empty() {
+            commands.push(command.to_string());
         }
     }
+    if let Some(last) = pending {
+        commands.push(last.trim().to_string());
+    }
+
+    // Newest entries are at the end of the file
+    let mut seen = HashSet::new();
+    commands
+        .into_iter()
+        .rev()
+        .filter(|command| !command.is_empty() && seen.insert(command.clone()))
+        .collect()
+}
+
+/// `: <start>:<elapsed>;command` -> `command`
+fn strip_extended_header(line: &str) -> &str {
+    let Some(rest) = line.strip_prefix(": ") else {
+        return line;
+    };
+    match rest.split_once(';') {
+        Some((stamp, command))
+            if stamp
+                .split(':')
+                .all(|part| part.chars().all(|c| c.is_ascii_digit())) =>
+        {
+            command
+        }
+        _ => line,
+    }
+}
+
+fn is_timestamp_comment(line: &str) -> bool {
+    line.strip_prefix('#')
+        .is_some_and(|stamp| !stamp.is_empty() && stamp.chars().all(|c| c.is_ascii_digit()))
 }
 
 impl Default for ShellPlugin {
@@ -71,7 +437,8 @@ impl Plugin for ShellPlugin {
             return Ok(vec![]);
         }
 
-        Ok(vec![PluginResult::new(
+        let (run_completion, completions) = self.completion_results(query, command);
+        let mut run = PluginResult::new(
             format!("Run: {}", command),
             command.to_string(),
             self.name().to_string(),
@@ -79,7 +446,15 @@ impl Plugin for ShellPlugin {
         .with_subtitle("Execute in terminal".to_string())
         .with_icon("utilities-terminal".to_string())
         .with_terminal(true)
-        .with_score(10000)]) // Very high score to show first
+        .with_score(10000); // Very high score to show first
+        if let Some(completion) = run_completion {
+            run = run.with_completion(completion);
+        }
+
+        let mut results = vec![guard(run)];
+        results.extend(completions);
+        results.extend(self.history_results(command));
+        Ok(results)
     }
 
     fn priority(&self) -> i32 {
@@ -95,6 +470,13 @@ impl Plugin for ShellPlugin {
 mod tests {
     use super::*;
 
+    /// Shell plugin that never reads the user's shell history
+    fn shell_without_history() -> ShellPlugin {
+        let mut shell = ShellPlugin::new();
+        shell.history = FileCache::new(PathBuf::new(), |_| Vec::new());
+        shell
+    }
+
     #[test]
     fn test_should_handle() {
         let shell = ShellPlugin::new();
@@ -108,7 +490,7 @@ mod tests {
     fn test_search() {
         use crate::config::Config;
 
-        let shell = ShellPlugin::new();
+        let shell = shell_without_history();
         let config = Config::default();
         let ctx = PluginContext::new(10, &config);
 
@@ -117,4 +499,204 @@ mod tests {
         assert!(results[0].title.contains("ls -la"));
         assert!(results[0].terminal);
     }
+
+    #[test]
+    fn test_dangerous_commands_are_flagged() {
+        for command in [
+            "rm -rf ~",
+            "rm -rf ~/",
+            "sudo rm -rf /",
+            "rm -fr /*",
+            "rm --recursive --force \"$HOME\"",
+            "cd /tmp && rm -r ~/*",
+            "rm -rf --no-preserve-root /",
+            "dd if=image.iso of=/dev/sda bs=4M",
+            "sudo mkfs.ext4 /dev/sdb1",
+            ":(){ :|:& };:",
+        ] {
+            assert!(danger(command).is_some(), "{} should be flagged", command);
+        }
+        assert_eq!(
+            danger("rm -rf ~"),
+            Some("recursively delete your home directory")
+        );
+    }
+
+    #[test]
+    fn test_ordinary_commands_are_not_flagged() {
+        for command in [
+            "ls",
+            "ls -la",
+            "git status",
+            "rm notes.txt",
+            "rm -rf ./target",
+            "rm -rf ~/Downloads/old",
+            "rm ~",
+            "dd if=/dev/zero of=disk.img bs=1M count=10",
+            "dd if=/dev/urandom of=/dev/null count=1",
+            "echo mkfs",
+            "cat /etc/hosts | grep -r localhost",
+        ] {
+            assert!(
+                danger(command).is_none(),
+                "{} should not be flagged",
+                command
+            );
+        }
+    }
+
+    #[test]
+    fn test_dangerous_command_needs_confirmation() {
+        use crate::config::Config;
+
+        let shell = shell_without_history();
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = shell.search("> rm -rf ~", &ctx).unwrap();
+        assert_eq!(
+            results[0].subtitle.as_deref(),
+            Some("Warning: this will recursively delete your home directory")
+        );
+        assert!(results[0].confirm.is_some());
+
+        let results = shell.search("> git status", &ctx).unwrap();
+        assert_eq!(results[0].subtitle.as_deref(), Some("Execute in terminal"));
+        assert!(results[0].confirm.is_none());
+    }
+
+    #[test]
+    fn test_path_completion_candidates() {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-shell-complete-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(dir.join("projects")).unwrap();
+        std::fs::create_dir_all(dir.join("photos")).unwrap();
+        std::fs::write(dir.join("plan.txt"), "").unwrap();
+        std::fs::write(dir.join("my notes.md"), "").unwrap();
+        std::fs::write(dir.join(".profile"), "").unwrap();
+        let root = dir.display().to_string();
+
+        assert_eq!(
+            path_candidates(&format!("{}/p", root), Path::new("/")),
+            vec![
+                format!("{}/photos/", root),
+                format!("{}/plan.txt ", root),
+                format!("{}/projects/", root),
+            ]
+        );
+        // Relative to home, with ~/, hidden files and escaped spaces
+        assert_eq!(path_candidates("pr", &dir), vec!["projects/"]);
+        assert_eq!(path_candidates("~/pl", &dir), vec!["~/plan.txt "]);
+        assert_eq!(path_candidates("~/m", &dir), vec!["~/my\\ notes.md "]);
+        assert_eq!(path_candidates(".pr", &dir), vec![".profile "]);
+        assert!(path_candidates("x", &dir).is_empty());
+        assert!(path_candidates("'pr", &dir).is_empty());
+        assert_eq!(path_candidates("~", &dir), vec!["~/"]);
+
+        let candidates = path_candidates("p", &dir);
+        assert_eq!(common_prefix(&candidates), "p");
+        assert_eq!(common_prefix(&path_candidates("pro", &dir)), "projects/");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_tab_completes_binaries_and_paths() {
+        use crate::config::Config;
+
+        let shell = shell_without_history();
+        let _ = shell.binaries.set(vec![
+            "gimp".to_string(),
+            "git".to_string(),
+            "git-lfs".to_string(),
+        ]);
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = shell.search("> gi", &ctx).unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["Run: gi", "gimp", "git", "git-lfs"]);
+        // "gi" is already the common prefix, so Tab on "Run:" has nothing to add
+        assert_eq!(results[0].completion, None);
+        assert_eq!(results[2].completion.as_deref(), Some("> git "));
+        assert_eq!(results[2].command, "git");
+
+        let results = shell.search(">git-l", &ctx).unwrap();
+        assert_eq!(results[0].completion.as_deref(), Some(">git-lfs "));
+
+        let results = shell.search("> ls /tm", &ctx).unwrap();
+        assert_eq!(results[0].completion.as_deref(), Some("> ls /tmp/"));
+        assert_eq!(results[1].title, "/tmp/");
+        assert_eq!(results[1].command, "ls /tmp/");
+    }
+
+    #[test]
+    fn test_parse_bash_history() {
+        let history =
+            parse_history("ls -la\ngit status\n#1700000000\ncargo build\ngit status\n\ngit push\n");
+        assert_eq!(
+            history,
+            vec!["git push", "git status", "cargo build", "ls -la"]
+        );
+    }
+
+    #[test]
+    fn test_parse_zsh_extended_history() {
+        let history = parse_history(
+            ": 1700000000:0;git status\n\
+             : 1700000010:2;for f in *; do\\\n  echo $f\\\ndone\n\
+             : 1700000020:0;git commit -m 'a; b'\n\
+             : 1700000030:0;git status\n",
+        );
+        assert_eq!(
+            history,
+            vec![
+                "git status",
+                "git commit -m 'a; b'",
+                "for f in *; do\n  echo $f\ndone",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_suggestions_follow_run_result() {
+        use crate::config::Config;
+
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-shell-history-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".bash_history");
+        std::fs::write(&path, "git status\nls\ngit push\n").unwrap();
+
+        let mut shell = ShellPlugin::new();
+        shell.history = FileCache::new(path.clone(), load_history);
+        let _ = shell.binaries.set(Vec::new());
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = shell.search("> gi", &ctx).unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["Run: gi", "git push", "git status"]);
+        assert!(results[1].terminal);
+        assert_eq!(results[1].command, "git push");
+        assert!(results[1].score > results[2].score);
+
+        // Commands run since are suggested without restarting the launcher
+        std::fs::write(&path, "git status\nls\ngit push\ngit log\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(120))
+            .unwrap();
+        let results = shell.search("> gi", &ctx).unwrap();
+        assert_eq!(results[1].title, "git log");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -1,8 +1,38 @@
 use super::traits::{Plugin, PluginContext, PluginResult};
+use crate::usage::UsageTracker;
+use crate::utils::FileCache;
 use anyhow::{Context, Result};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
 use std::fs;
+use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
+/// Largest bonus usage adds to a host's score, kept below the gap between match
+/// tiers so a frequently used host never outranks a better textual match
+const MAX_USAGE_BONUS: i64 = 99;
+
+/// Usage store for SSH hosts, kept apart from app usage and keyed by host alias
+pub fn host_usage_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("native-launcher")
+        .join("ssh_usage.bin")
+}
+
+/// Load the SSH host usage store at `path`
+fn load_host_usage(path: &Path) -> UsageTracker {
+    UsageTracker::load_from(path.to_path_buf()).unwrap_or_else(|e| {
+        warn!("Failed to load SSH usage data: {} - starting fresh", e);
+        UsageTracker::with_cache_path(path.to_path_buf())
+    })
+}
+
+/// Record a connection to `host` (the alias shown as the result title)
+pub fn record_host_usage(host: &str) {
+    load_host_usage(&host_usage_path()).record_launch(host);
+}
+
 /// SSH host configuration
 #[derive(Debug, Clone)]
 struct SshHost {
@@ -16,6 +46,10 @@ struct SshHost {
     port: u16,
     /// Identity file path (optional)
     identity_file: Option<String>,
+    /// Jump hosts to connect through (`ProxyJump a,b`), in order
+    proxy_jump: Vec<String>,
+    /// Inline `# comments` on the host's lines
+    comments: Vec<String>,
 }
 
 impl SshHost {
@@ -35,6 +69,12 @@ impl SshHost {
             cmd.push(identity.clone());
         }
 
+        // The config's ProxyJump only applies to the alias, not the hostname used here
+        if !self.proxy_jump.is_empty() {
+            cmd.push("-J".to_string());
+            cmd.push(self.proxy_jump.join(","));
+        }
+
         // Build user@host or just host
         let target = if let Some(ref user) = self.user {
             format!("{}@{}", user, self.hostname)
@@ -46,6 +86,59 @@ impl SshHost {
 
         cmd.join(" ")
     }
+
+    /// Resolved target for the subtitle: `user@hostname:port via jump → jump`
+    fn subtitle(&self) -> String {
+        let mut subtitle = match &self.user {
+            Some(user) => format!("{}@{}", user, self.hostname),
+            None => self.hostname.clone(),
+        };
+        if self.port != 22 {
+            subtitle.push_str(&format!(":{}", self.port));
+        }
+        if !self.proxy_jump.is_empty() {
+            subtitle.push_str(&format!(" via {}", self.proxy_jump.join(" → ")));
+        }
+        subtitle
+    }
+
+    /// Connection details besides the alias a query can match
+    fn detail_terms(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.hostname.as_str())
+            .chain(self.user.as_deref())
+            .chain(self.proxy_jump.iter().map(String::as_str))
+            .chain(self.comments.iter().map(String::as_str))
+    }
+
+    /// Score tier of `query` (lowercase) against this host, if it matches at all
+    ///
+    /// Alias matches rank first, then the host's details, then fuzzy matches.
+    /// Global search only matches the alias and HostName; the other details
+    /// (User, ProxyJump, comments) and fuzzy matches need a `matcher`, given for
+    /// explicit SSH queries. Tiers are 100+ apart so usage never reorders them.
+    fn match_score(&self, query: &str, matcher: Option<&SkimMatcherV2>) -> Option<i64> {
+        let contains = |term: &str| term.to_lowercase().contains(query);
+        let name = self.name.to_lowercase();
+
+        if name == query {
+            Some(1000)
+        } else if name.starts_with(query) {
+            Some(800)
+        } else if name.contains(query) {
+            Some(600)
+        } else if contains(&self.hostname) {
+            Some(400)
+        } else {
+            let matcher = matcher?;
+            if self.detail_terms().any(contains) {
+                return Some(400);
+            }
+            std::iter::once(self.name.as_str())
+                .chain(self.detail_terms())
+                .any(|term| matcher.fuzzy_match(term, query).is_some())
+                .then_some(200)
+        }
+    }
 }
 
 /// Plugin for SSH connections
@@ -53,6 +146,9 @@ impl SshHost {
 pub struct SshPlugin {
     hosts: Vec<SshHost>,
     enabled: bool,
+    /// Connection counts per host alias, used to rank frequently used hosts
+    /// first; re-read when a connection is recorded
+    usage: Option<FileCache<UsageTracker>>,
 }
 
 impl SshPlugin {
@@ -65,7 +161,25 @@ impl SshPlugin {
 
         debug!("SSH plugin initialized with {} hosts", hosts.len());
 
-        Self { hosts, enabled }
+        Self {
+            hosts,
+            enabled,
+            usage: None,
+        }
+    }
+
+    /// Rank hosts by how often (and how recently) they were connected to,
+    /// according to the usage store at `path`
+    pub fn with_usage(mut self, path: PathBuf) -> Self {
+        self.usage = Some(FileCache::new(path, load_host_usage));
+        self
+    }
+
+    /// Score bonus from past connections to `host`
+    fn usage_bonus(usage: Option<&UsageTracker>, host: &SshHost) -> i64 {
+        usage
+            .map(|usage| ((usage.get_score(&host.name) * 10.0) as i64).min(MAX_USAGE_BONUS))
+            .unwrap_or(0)
     }
 
     /// Parse SSH config file
@@ -83,10 +197,26 @@ impl SshPlugin {
         debug!("Parsing SSH config from: {}", config_path.display());
         let content = fs::read_to_string(&config_path).context("Failed to read SSH config")?;
 
+        let hosts = Self::parse_config(&content);
+        debug!("Parsed {} SSH hosts", hosts.len());
+        Ok(hosts)
+    }
+
+    /// Parse the contents of an SSH config file
+    ///
+    /// `Match` blocks end the current `Host` block. Their conditions aren't
+    /// evaluated, so their options are skipped rather than guessed onto hosts.
+    fn parse_config(content: &str) -> Vec<SshHost> {
         let mut hosts = Vec::new();
         let mut current_host: Option<SshHost> = None;
+        let mut in_match = false;
 
         for line in content.lines() {
+            // Split off an inline comment ("HostName 10.0.0.5  # primary db")
+            let (line, comment) = match line.find(" #").or_else(|| line.find("\t#")) {
+                Some(pos) => (&line[..pos], Some(line[pos + 2..].trim())),
+                None => (line, None),
+            };
             let line = line.trim();
 
             // Skip comments and empty lines
@@ -94,21 +224,26 @@ impl SshPlugin {
                 continue;
             }
 
-            // Parse key-value pairs
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() < 2 {
+            // Parse key-value pairs ("Key value" or "Key=value")
+            let (key, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+                Some((key, value)) => (
+                    key.to_lowercase(),
+                    value.trim_start_matches(['=', ' ', '\t']),
+                ),
+                None => continue,
+            };
+            let value = value.trim().to_string();
+            if value.is_empty() {
                 continue;
             }
 
-            let key = parts[0].to_lowercase();
-            let value = parts[1..].join(" ");
-
             match key.as_str() {
                 "host" => {
                     // Save previous host if exists
                     if let Some(host) = current_host.take() {
                         hosts.push(host);
                     }
+                    in_match = false;
 
                     // Skip wildcards
                     if !value.contains('*') && !value.contains('?') {
@@ -118,9 +253,30 @@ impl SshPlugin {
                             user: None,
                             port: 22,
                             identity_file: None,
+                            proxy_jump: Vec::new(),
+                            comments: comment.into_iter().map(str::to_string).collect(),
                         });
                     }
+                    continue;
                 }
+                "match" => {
+                    if let Some(host) = current_host.take() {
+                        hosts.push(host);
+                    }
+                    in_match = true;
+                    continue;
+                }
+                _ if in_match => continue,
+                _ => {}
+            }
+
+            if let (Some(host), Some(comment)) = (current_host.as_mut(), comment) {
+                if !comment.is_empty() {
+                    host.comments.push(comment.to_string());
+                }
+            }
+
+            match key.as_str() {
                 "hostname" => {
                     if let Some(ref mut host) = current_host {
                         host.hostname = value;
@@ -155,6 +311,19 @@ impl SshPlugin {
                         host.identity_file = Some(expanded);
                     }
                 }
+                "proxyjump" => {
+                    if let Some(ref mut host) = current_host {
+                        // "none" disables a ProxyJump set by an earlier block
+                        host.proxy_jump = if value.eq_ignore_ascii_case("none") {
+                            Vec::new()
+                        } else {
+                            value
+                                .split(',')
+                                .map(|jump| jump.trim().to_string())
+                                .collect()
+                        };
+                    }
+                }
                 _ => {}
             }
         }
@@ -164,8 +333,7 @@ impl SshPlugin {
             hosts.push(host);
         }
 
-        debug!("Parsed {} SSH hosts", hosts.len());
-        Ok(hosts)
+        hosts
     }
 
     /// Parse known_hosts for additional hosts
@@ -238,12 +406,13 @@ impl Plugin for SshPlugin {
             return query.starts_with("@ssh");
         }
 
-        // Trigger on "ssh" prefix or if query matches host name
+        // Trigger on "ssh" prefix or if query matches a host's alias or details
+        let query_lower = query.to_lowercase();
         query.starts_with("ssh")
-            || self.hosts.iter().any(|h| {
-                h.name.to_lowercase().contains(&query.to_lowercase())
-                    || h.hostname.to_lowercase().contains(&query.to_lowercase())
-            })
+            || self
+                .hosts
+                .iter()
+                .any(|h| h.match_score(&query_lower, None).is_some())
     }
 
     fn search(&self, query: &str, context: &PluginContext) -> Result<Vec<PluginResult>> {
@@ -258,42 +427,28 @@ impl Plugin for SshPlugin {
             .unwrap_or(&query_lower)
             .trim();
 
+        // Fuzzy matches only for explicit SSH queries, so app searches aren't flooded
+        let explicit = query_lower.starts_with("@ssh") || query_lower.starts_with("ssh");
+        let matcher = explicit.then(SkimMatcherV2::default);
+
+        let usage = self.usage.as_ref().map(FileCache::get);
         let mut results = Vec::new();
 
         for host in &self.hosts {
-            // Skip if no match
-            if !search_query.is_empty() {
-                let name_match = host.name.to_lowercase().contains(search_query);
-                let hostname_match = host.hostname.to_lowercase().contains(search_query);
-
-                if !name_match && !hostname_match {
-                    continue;
-                }
-            }
-
-            // Calculate score
-            let score = if search_query.is_empty() {
+            // Calculate score; usage only reorders hosts within a match tier
+            let tier = if search_query.is_empty() {
                 500 // Default score for "ssh" query
-            } else if host.name.to_lowercase() == search_query {
-                1000 // Exact match
-            } else if host.name.to_lowercase().starts_with(search_query) {
-                800 // Prefix match
             } else {
-                600 // Contains match
+                match host.match_score(search_query, matcher.as_ref()) {
+                    Some(tier) => tier,
+                    None => continue,
+                }
             };
-
-            // Build subtitle
-            let mut subtitle_parts = vec![host.hostname.clone()];
-            if let Some(ref user) = host.user {
-                subtitle_parts.insert(0, format!("{}@", user));
-            }
-            if host.port != 22 {
-                subtitle_parts.push(format!(":{}", host.port));
-            }
+            let score = tier + Self::usage_bonus(usage.as_deref(), host);
 
             let result = PluginResult {
                 title: host.name.clone(),
-                subtitle: Some(subtitle_parts.join("")),
+                subtitle: Some(host.subtitle()),
                 icon: Some("network-server".to_string()),
                 command: host.to_command(),
                 terminal: true, // SSH always runs in terminal
@@ -303,17 +458,23 @@ impl Plugin for SshPlugin {
                 parent_app: None,
                 desktop_path: None,
                 badge_icon: Some("utilities-terminal-symbolic".to_string()), // Terminal badge for SSH
+                is_directory: false,
+                target_path: None,
+                confirm: None,
+                icon_data: None,
+                actions: Vec::new(),
+                url: None,
+                badges: Vec::new(),
+                completion: None,
+                activation: None,
             };
 
             results.push(result);
-
-            if results.len() >= context.max_results {
-                break;
-            }
         }
 
-        // Sort by score descending
+        // Sort by score descending before truncating so used hosts aren't cut off
         results.sort_by(|a, b| b.score.cmp(&a.score));
+        results.truncate(context.max_results);
 
         Ok(results)
     }
@@ -331,6 +492,8 @@ mod tests {
             user: Some("john".to_string()),
             port: 22,
             identity_file: None,
+            proxy_jump: Vec::new(),
+            comments: Vec::new(),
         };
 
         assert_eq!(host.to_command(), "ssh john@example.com");
@@ -344,6 +507,8 @@ mod tests {
             user: Some("john".to_string()),
             port: 2222,
             identity_file: None,
+            proxy_jump: Vec::new(),
+            comments: Vec::new(),
         };
 
         assert_eq!(host.to_command(), "ssh -p 2222 john@example.com");
@@ -357,6 +522,8 @@ mod tests {
             user: Some("john".to_string()),
             port: 22,
             identity_file: Some("/home/user/.ssh/id_rsa".to_string()),
+            proxy_jump: Vec::new(),
+            comments: Vec::new(),
         };
 
         assert_eq!(
@@ -365,6 +532,156 @@ mod tests {
         );
     }
 
+    fn host(name: &str) -> SshHost {
+        SshHost {
+            name: name.to_string(),
+            hostname: format!("{}.example.com", name),
+            user: None,
+            port: 22,
+            identity_file: None,
+            proxy_jump: Vec::new(),
+            comments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_used_host_listed_first() {
+        let path = std::env::temp_dir()
+            .join(format!("native-launcher-ssh-usage-{}", std::process::id()))
+            .join("ssh_usage.bin");
+        let mut usage = UsageTracker::load_from(path.clone()).unwrap();
+        usage.record_launch("prod-db");
+        usage.record_launch("prod-db");
+        usage.record_launch("prod-db");
+        usage.record_launch("staging");
+
+        let plugin = SshPlugin {
+            hosts: vec![
+                host("backup"),
+                host("prod"),
+                host("staging"),
+                host("prod-db"),
+            ],
+            enabled: true,
+            usage: None,
+        }
+        .with_usage(path.clone());
+        let config = crate::config::Config::default();
+        let context = PluginContext::new(10, &config);
+
+        let results = plugin.search("@ssh", &context).unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["prod-db", "staging", "backup", "prod"]);
+
+        // Usage never lifts a host above a better textual match
+        let results = plugin.search("@ssh prod", &context).unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["prod", "prod-db"]);
+
+        // Only the most used hosts survive a small limit
+        let small = PluginContext::new(1, &config);
+        let results = plugin.search("@ssh", &small).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "prod-db");
+
+        // Connections recorded later (by the UI) are picked up without a restart
+        for _ in 0..5 {
+            usage.record_launch("backup");
+        }
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(120))
+            .unwrap();
+        let results = plugin.search("@ssh", &context).unwrap();
+        assert_eq!(results[0].title, "backup");
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    const CONFIG: &str = "\
+Host bastion
+    HostName bastion.example.com
+    User jump
+
+Host db   # primary database
+    HostName 10.20.0.15
+    User admin
+    ProxyJump bastion,edge.example.com
+    Port=2222
+
+Match host *.internal exec \"test -f ~/.vpn\"
+    ProxyJump gateway.example.com
+    User ops
+
+Host web
+    HostName web.example.com
+";
+
+    fn plugin_for(config: &str) -> SshPlugin {
+        SshPlugin {
+            hosts: SshPlugin::parse_config(config),
+            enabled: true,
+            usage: None,
+        }
+    }
+
+    #[test]
+    fn test_hostname_match_finds_alias() {
+        let plugin = plugin_for(CONFIG);
+        let config = crate::config::Config::default();
+        let context = PluginContext::new(10, &config);
+
+        // "10.20" appears only in db's HostName
+        let results = plugin.search("@ssh 10.20", &context).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "db");
+        assert_eq!(
+            results[0].subtitle.as_deref(),
+            Some("admin@10.20.0.15:2222 via bastion → edge.example.com")
+        );
+        assert!(plugin.should_handle("10.20.0"));
+
+        // Inline comments and users are searchable, but only with @ssh
+        let results = plugin.search("@ssh primary", &context).unwrap();
+        assert_eq!(results[0].title, "db");
+        let results = plugin.search("@ssh admin", &context).unwrap();
+        assert_eq!(results[0].title, "db");
+        assert!(!plugin.should_handle("primary"));
+        assert!(!plugin.should_handle("admin"));
+        assert!(plugin.search("admin", &context).unwrap().is_empty());
+
+        // The bastion's own alias outranks hosts that merely jump through it
+        let results = plugin.search("@ssh bastion", &context).unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["bastion", "db"]);
+    }
+
+    #[test]
+    fn test_proxy_jump_parsed() {
+        let hosts = SshPlugin::parse_config(CONFIG);
+        let names: Vec<_> = hosts.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["bastion", "db", "web"]);
+
+        let db = &hosts[1];
+        assert_eq!(db.proxy_jump, vec!["bastion", "edge.example.com"]);
+        assert_eq!(db.port, 2222);
+        assert_eq!(db.comments, vec!["primary database"]);
+        assert_eq!(
+            db.to_command(),
+            "ssh -p 2222 -J bastion,edge.example.com admin@10.20.0.15"
+        );
+
+        // Match options don't leak into the Host block before them, nor onto
+        // hosts the Match may not even apply to
+        assert_eq!(db.user.as_deref(), Some("admin"));
+        let matcher = SkimMatcherV2::default();
+        assert_eq!(hosts[2].match_score("gateway", Some(&matcher)), None);
+        assert_eq!(hosts[2].match_score("ops", Some(&matcher)), None);
+        assert_eq!(hosts[2].match_score("web.example", None), Some(400));
+    }
+
     #[test]
     fn test_ssh_plugin_should_handle() {
         let plugin = SshPlugin::new(true);
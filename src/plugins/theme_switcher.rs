@@ -1,6 +1,7 @@
 use super::traits::{Plugin, PluginContext, PluginResult};
 use crate::config::Config;
-use std::path::PathBuf;
+use crate::ui::theme::{discover_user_themes, user_themes_dir, validate_theme};
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
 /// Theme switcher plugin - allows switching themes in real-time
@@ -12,12 +13,12 @@ pub struct ThemeSwitcherPlugin {
 
 impl ThemeSwitcherPlugin {
     pub fn new(_config: Config) -> Self {
-        let themes = Self::scan_themes();
+        let themes = Self::scan_themes(user_themes_dir().as_deref());
         Self { themes }
     }
 
-    /// Scan the themes/ directory for available themes
-    fn scan_themes() -> Vec<String> {
+    /// Scan the built-in themes/ directory plus the user themes directory
+    fn scan_themes(user_dir: Option<&Path>) -> Vec<String> {
         let themes_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("themes");
         let mut themes = Vec::new();
 
@@ -45,8 +46,14 @@ impl ThemeSwitcherPlugin {
             ];
         }
 
+        // User themes (~/.config/native-launcher/themes/*.css); same-name ones override built-ins
+        if let Some(dir) = user_dir {
+            themes.extend(discover_user_themes(dir));
+        }
+
         // Sort themes alphabetically for consistent ordering
         themes.sort();
+        themes.dedup();
         info!("Loaded {} themes: {:?}", themes.len(), themes);
         themes
     }
@@ -72,19 +79,45 @@ impl ThemeSwitcherPlugin {
                         100 // Contains match
                     };
 
-                    Some(PluginResult {
-                        title: format!("Theme: {}", theme),
-                        subtitle: Some(format!("Switch to {} theme", theme)),
-                        icon: Some("preferences-desktop-theme".to_string()),
-                        command: format!("@theme:{}", theme),
-                        terminal: false,
-                        score,
-                        plugin_name: "theme-switcher".to_string(),
-                        sub_results: vec![],
-                        parent_app: None,
-                        desktop_path: None,
-                        badge_icon: None, // No badge for theme switching
-                    })
+                    let result = match validate_theme(theme) {
+                        Ok(()) => PluginResult {
+                            title: format!("Theme: {}", theme),
+                            subtitle: Some(format!("Switch to {} theme", theme)),
+                            icon: Some("preferences-desktop-theme".to_string()),
+                            command: format!("@theme:{}", theme),
+                            terminal: false,
+                            score,
+                            plugin_name: "theme-switcher".to_string(),
+                            sub_results: vec![],
+                            parent_app: None,
+                            desktop_path: None,
+                            badge_icon: None, // No badge for theme switching
+                            is_directory: false,
+                            target_path: None,
+                            confirm: None,
+                            icon_data: None,
+                            actions: vec![],
+                            url: None,
+                            badges: Vec::new(),
+                            completion: None,
+                            activation: None,
+                        },
+                        Err(e) => {
+                            warn!("Theme '{}' is broken: {}", theme, e);
+                            // Broken themes stay visible but use a command that can't be applied
+                            PluginResult::new(
+                                format!("Theme: {}", theme),
+                                format!("@theme-invalid:{}", theme),
+                                "theme-switcher".to_string(),
+                            )
+                            .with_subtitle(format!("Broken theme: {}", e))
+                            .with_icon("preferences-desktop-theme".to_string())
+                            .with_badge_icon("dialog-warning-symbolic".to_string())
+                            .with_score(score)
+                        }
+                    };
+
+                    Some(result)
                 } else {
                     None
                 }
@@ -100,7 +133,7 @@ impl ThemeSwitcherPlugin {
 
         if let Some(theme_name) = command.strip_prefix("@theme:") {
             info!("Switching to theme: {}", theme_name);
-            load_theme_with_name(theme_name);
+            load_theme_with_name(theme_name)?;
 
             // Persist theme to config file
             if let Err(e) = self.persist_theme_to_config(theme_name) {
@@ -207,6 +240,35 @@ mod tests {
         assert!(!plugin.should_handle("calculator"));
     }
 
+    #[test]
+    fn test_user_themes_are_listed() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("native-launcher-user-themes-{}", nanos));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ocean.css"), "window { color: #0af; }").unwrap();
+        std::fs::write(dir.join("nord.css"), "window { color: #88c0d0; }").unwrap();
+
+        let themes = ThemeSwitcherPlugin::scan_themes(Some(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(themes.contains(&"ocean".to_string()));
+        assert!(themes.contains(&"dark".to_string()));
+        assert_eq!(themes.iter().filter(|t| *t == "nord").count(), 1);
+    }
+
+    #[test]
+    fn test_valid_themes_are_selectable() {
+        let plugin = create_test_plugin();
+        let config = Config::default();
+        let context = PluginContext::new(10, &config);
+        let results = plugin.search("@theme", &context).unwrap();
+        assert!(results.iter().all(|r| r.command.starts_with("@theme:")));
+        assert!(results.iter().all(|r| r.badge_icon.is_none()));
+    }
+
     #[test]
     #[ignore] // Requires GTK to be initialized
     fn test_execute_theme_change() {
@@ -1,7 +1,10 @@
 use crate::config::Config;
+use crate::utils::{copyable_command, shell_escape};
 use anyhow::Result;
 use gtk4::gdk::{Key, ModifierType};
 use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Keyboard event passed to plugins
 #[derive(Debug, Clone)]
@@ -14,6 +17,8 @@ pub struct KeyboardEvent {
     pub query: String,
     /// Whether there's a selected result
     pub has_selection: bool,
+    /// Command of the selected result, when known
+    pub selected_command: Option<String>,
 }
 
 impl KeyboardEvent {
@@ -23,9 +28,16 @@ impl KeyboardEvent {
             modifiers,
             query,
             has_selection,
+            selected_command: None,
         }
     }
 
+    /// Attach the selected result's command so plugins can act on that row
+    pub fn with_selected_command(mut self, command: Option<String>) -> Self {
+        self.selected_command = command;
+        self
+    }
+
     /// Check if Ctrl modifier is pressed
     pub fn has_ctrl(&self) -> bool {
         self.modifiers.contains(ModifierType::CONTROL_MASK)
@@ -67,6 +79,141 @@ pub enum KeyboardAction {
     OpenFolder(String),
     /// Copy path to clipboard
     CopyPath(String),
+    /// Copy text (e.g. a calculator result) to clipboard; closes the window
+    /// unless Shift is held
+    CopyToClipboard(String),
+}
+
+/// Entry of a result's actions menu (Tab/→ or the Menu key on a result)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultAction {
+    /// Run the result's command, same as Enter
+    Open,
+    /// List the apps that can open the file (the result's sub-results)
+    OpenWith,
+    /// Open the folder containing the target path
+    OpenFolder,
+    /// Copy the target path to clipboard
+    CopyPath,
+    /// Copy the command line to clipboard
+    CopyCommand,
+    /// Move the target path to the trash
+    Trash,
+}
+
+impl ResultAction {
+    /// Menu label
+    pub fn label(self) -> &'static str {
+        match self {
+            ResultAction::Open => "Open",
+            ResultAction::OpenWith => "Open With…",
+            ResultAction::OpenFolder => "Open Containing Folder",
+            ResultAction::CopyPath => "Copy Path",
+            ResultAction::CopyCommand => "Copy Command",
+            ResultAction::Trash => "Move to Trash",
+        }
+    }
+
+    /// Menu icon name
+    pub fn icon(self) -> &'static str {
+        match self {
+            ResultAction::Open => "document-open",
+            ResultAction::OpenWith => "system-run",
+            ResultAction::OpenFolder => "folder-open",
+            ResultAction::CopyPath | ResultAction::CopyCommand => "edit-copy",
+            ResultAction::Trash => "user-trash",
+        }
+    }
+
+    /// Menu row for this action on `source`
+    pub fn menu_row(self, source: &PluginResult) -> PluginResult {
+        PluginResult::new(
+            self.label().to_string(),
+            String::new(),
+            source.plugin_name.clone(),
+        )
+        .with_subtitle(source.title.clone())
+        .with_icon(self.icon().to_string())
+    }
+
+    /// Prompt to confirm before carrying out this action on `source`
+    ///
+    /// Trashing always asks; `Open` asks whatever the result itself asks.
+    pub fn confirm_prompt(self, source: &PluginResult) -> Option<String> {
+        match self {
+            ResultAction::Open => source.confirm.clone(),
+            ResultAction::Trash => Some(format!("move {} to the trash", source.title)),
+            _ => None,
+        }
+    }
+
+    /// What activating this action on `source` does
+    ///
+    /// `Open` maps to [`KeyboardAction::None`]: the result is activated as if
+    /// Enter was pressed on it. `OpenWith` is [`KeyboardAction::Handled`]; the UI
+    /// shows the sub-results itself.
+    pub fn keyboard_action(self, source: &PluginResult) -> KeyboardAction {
+        let target = source.target_path.clone();
+        match self {
+            ResultAction::Open => KeyboardAction::None,
+            ResultAction::OpenWith => KeyboardAction::Handled,
+            ResultAction::OpenFolder => target
+                .map(KeyboardAction::OpenFolder)
+                .unwrap_or(KeyboardAction::Handled),
+            ResultAction::CopyPath => target
+                .map(KeyboardAction::CopyPath)
+                .unwrap_or(KeyboardAction::Handled),
+            ResultAction::CopyCommand => copyable_command(&source.command)
+                .map(KeyboardAction::CopyToClipboard)
+                .unwrap_or(KeyboardAction::Handled),
+            ResultAction::Trash => target
+                .map(|path| KeyboardAction::Execute {
+                    command: format!("gio trash -- {}", shell_escape(&path)),
+                    terminal: false,
+                })
+                .unwrap_or(KeyboardAction::Handled),
+        }
+    }
+}
+
+/// Where a result comes from, shown as a small label at the end of its row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultBadge {
+    /// Application installed through Flatpak
+    Flatpak,
+    /// Browser bookmark (as opposed to a history entry)
+    Bookmark,
+    /// Application with an open window
+    Running,
+    /// Pinned by the user (added by the results list from the pins store)
+    Pinned,
+}
+
+/// Text computed on a worker thread when the result is activated
+pub type ComputeText = Arc<dyn Fn() -> Result<String> + Send + Sync>;
+
+/// What activating a result (Enter or click) does instead of running its command
+#[derive(Clone)]
+pub enum ResultActivation {
+    /// Copy the text to clipboard; closes the window unless Shift is held.
+    /// The text never ends up in a command line or the log.
+    Copy(String),
+    /// Copy text that is too slow to compute while searching (hashing a large
+    /// file); the window stays open until it is ready
+    CopyComputed(ComputeText),
+    /// Write the result's `icon_data` to the file, then run the command as usual
+    /// (a QR code saved only once it is opened)
+    SaveIcon(PathBuf),
+}
+
+impl Debug for ResultActivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResultActivation::Copy(text) => f.debug_tuple("Copy").field(text).finish(),
+            ResultActivation::CopyComputed(_) => f.write_str("CopyComputed(..)"),
+            ResultActivation::SaveIcon(path) => f.debug_tuple("SaveIcon").field(path).finish(),
+        }
+    }
 }
 
 /// Represents a result from a plugin search
@@ -97,6 +244,29 @@ pub struct PluginResult {
     /// Optional badge icon name (e.g., "terminal-symbolic", "folder-symbolic", "web-browser-symbolic")
     /// Uses GTK symbolic icon names for small overlay indicators
     pub badge_icon: Option<String>,
+    /// Whether this result is a directory the UI can drill into (files plugin)
+    pub is_directory: bool,
+    /// File or folder this result opens (files, recent documents, editor workspaces);
+    /// lets the UI resolve its containing folder
+    pub target_path: Option<String>,
+    /// Prompt shown before running a destructive command; activating the result
+    /// once shows it, activating again runs the command
+    pub confirm: Option<String>,
+    /// Inline PNG icon (dynamic plugins); shown instead of `icon` when set
+    pub icon_data: Option<Arc<[u8]>>,
+    /// Actions offered in the result's actions menu on top of the ones every
+    /// result gets from its fields (see [`PluginResult::action_menu`])
+    pub actions: Vec<ResultAction>,
+    /// Web page this result opens (browser history, bookmarks); Ctrl+L copies it
+    /// as a markdown link
+    pub url: Option<String>,
+    /// Provenance labels (Flatpak, bookmark, running) in display order; the UI
+    /// picks their icon and text
+    pub badges: Vec<ResultBadge>,
+    /// Query Tab replaces the search text with (shell command and path completion)
+    pub completion: Option<String>,
+    /// Done on activation instead of running `command` (copying generated values)
+    pub activation: Option<ResultActivation>,
 }
 
 impl PluginResult {
@@ -115,6 +285,15 @@ impl PluginResult {
             parent_app: None,
             desktop_path: None,
             badge_icon: None,
+            is_directory: false,
+            target_path: None,
+            confirm: None,
+            icon_data: None,
+            actions: Vec::new(),
+            url: None,
+            badges: Vec::new(),
+            completion: None,
+            activation: None,
         }
     }
 
@@ -130,6 +309,12 @@ impl PluginResult {
         self
     }
 
+    /// Set an inline PNG icon
+    pub fn with_icon_data(mut self, data: Vec<u8>) -> Self {
+        self.icon_data = Some(data.into());
+        self
+    }
+
     /// Set terminal flag
     pub fn with_terminal(mut self, terminal: bool) -> Self {
         self.terminal = terminal;
@@ -174,6 +359,12 @@ impl PluginResult {
         self
     }
 
+    /// Ask for confirmation with `prompt` before running the command
+    pub fn with_confirm(mut self, prompt: String) -> Self {
+        self.confirm = Some(prompt);
+        self
+    }
+
     /// Set badge icon (symbolic icon name for small indicator)
     /// Common badges: "terminal-symbolic", "folder-symbolic", "web-browser-symbolic",
     /// "document-symbolic", "video-symbolic", "audio-symbolic"
@@ -181,6 +372,88 @@ impl PluginResult {
         self.badge_icon = Some(badge);
         self
     }
+
+    /// Add a provenance badge
+    pub fn with_badge(mut self, badge: ResultBadge) -> Self {
+        if !self.badges.contains(&badge) {
+            self.badges.push(badge);
+        }
+        self
+    }
+
+    /// Set the query Tab completes the search text to
+    pub fn with_completion(mut self, query: String) -> Self {
+        self.completion = Some(query);
+        self
+    }
+
+    /// Set what activating the result does instead of running its command
+    pub fn with_activation(mut self, activation: ResultActivation) -> Self {
+        self.activation = Some(activation);
+        self
+    }
+
+    /// Set the file or folder this result opens
+    pub fn with_target_path(mut self, path: String) -> Self {
+        self.target_path = Some(path);
+        self
+    }
+
+    /// Set the web page this result opens
+    pub fn with_url(mut self, url: String) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    /// `[title](url)` for results with a URL; brackets in the title are escaped
+    /// and parentheses and spaces in the URL percent-encoded so the link stays intact
+    pub fn markdown_link(&self) -> Option<String> {
+        let url = self.url.as_deref()?;
+        let mut title = String::with_capacity(self.title.len());
+        for c in self.title.chars() {
+            if matches!(c, '[' | ']' | '\\') {
+                title.push('\\');
+            }
+            title.push(c);
+        }
+        let url = url
+            .replace(' ', "%20")
+            .replace('(', "%28")
+            .replace(')', "%29");
+        Some(format!("[{}]({})", title, url))
+    }
+
+    /// Whether the actions menu offers more than opening the result and
+    /// copying its command
+    pub fn has_actions(&self) -> bool {
+        !self.sub_results.is_empty() || self.target_path.is_some() || !self.actions.is_empty()
+    }
+
+    /// Entries of the result's actions menu
+    ///
+    /// Every result with a command can be opened, results with sub-results can
+    /// be opened with another app, and results with a target path offer its
+    /// folder and copying the path; actions declared by the plugin follow.
+    pub fn action_menu(&self) -> Vec<ResultAction> {
+        let mut menu = Vec::new();
+        if !self.command.is_empty() {
+            menu.push(ResultAction::Open);
+        }
+        if !self.sub_results.is_empty() {
+            menu.push(ResultAction::OpenWith);
+        }
+        if self.target_path.is_some() {
+            menu.extend([ResultAction::OpenFolder, ResultAction::CopyPath]);
+        } else if copyable_command(&self.command).is_some() {
+            menu.push(ResultAction::CopyCommand);
+        }
+        for action in &self.actions {
+            if !menu.contains(action) {
+                menu.push(*action);
+            }
+        }
+        menu
+    }
 }
 
 /// Context provided to plugins during search
@@ -214,6 +487,15 @@ impl<'a> PluginContext<'a> {
         self.app_results_count = count;
         self
     }
+
+    /// Context for one plugin in global search, with `max_results` lowered to its configured cap
+    pub fn for_plugin(&self, plugin_name: &str) -> Self {
+        let mut context = self.clone();
+        if let Some(cap) = self.config.plugins.result_cap(plugin_name) {
+            context.max_results = context.max_results.min(cap);
+        }
+        context
+    }
 }
 
 /// Plugin trait that all plugins must implement
@@ -250,6 +532,12 @@ pub trait Plugin: Debug + Send + Sync {
         true
     }
 
+    /// Whether `handle_keyboard_event` should be called at all; plugins that
+    /// never handle keys return false to skip dispatch
+    fn handles_keyboard_events(&self) -> bool {
+        true
+    }
+
     /// Handle keyboard events
     /// Return KeyboardAction::None if this plugin doesn't handle the event
     /// Events are dispatched to plugins in priority order (highest first)
@@ -257,4 +545,9 @@ pub trait Plugin: Debug + Send + Sync {
     fn handle_keyboard_event(&self, _event: &KeyboardEvent) -> KeyboardAction {
         KeyboardAction::None
     }
+
+    /// Refresh whatever the next search will need (caches, indexes, icons)
+    /// Called on the UI thread when the daemon window is shown, so it must return
+    /// immediately and do the work on a background thread
+    fn warm_up(&self) {}
 }
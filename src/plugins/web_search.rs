@@ -1,7 +1,9 @@
 use super::traits::{KeyboardAction, KeyboardEvent, Plugin, PluginContext, PluginResult};
 use anyhow::Result;
 use gtk4::gdk::Key;
+use regex::Regex;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 use crate::utils::build_open_command;
 
@@ -71,6 +73,16 @@ impl WebSearchPlugin {
             .map(|template| template.replace("{}", &urlencoding::encode(query)))
     }
 
+    /// Search URL for `query` on the default engine
+    pub fn default_search_url(&self, query: &str) -> String {
+        self.build_url(DEFAULT_ENGINE, query).unwrap_or_else(|| {
+            format!(
+                "https://www.google.com/search?q={}",
+                urlencoding::encode(query)
+            )
+        })
+    }
+
     /// Build web search URL from query (handles both explicit engine and fallback)
     pub fn build_search_url(&self, query: &str) -> Option<(String, String, String)> {
         // Try explicit engine first (e.g., "google rust")
@@ -93,6 +105,68 @@ impl WebSearchPlugin {
     }
 }
 
+/// Engine used when a query doesn't name one
+pub const DEFAULT_ENGINE: &str = "google";
+
+/// Top-level domains recognised for bare-domain queries.
+/// Deliberately excludes TLDs that collide with common file extensions (.rs, .sh, .py, .md).
+const KNOWN_TLDS: &[&str] = &[
+    "com", "org", "net", "io", "dev", "app", "edu", "gov", "mil", "int", "co", "tv", "gg", "info",
+    "biz", "xyz", "tech", "site", "online", "blog", "cloud", "page", "wiki", "news", "uk", "us",
+    "ca", "au", "de", "fr", "nl", "eu", "es", "se", "no", "fi", "dk", "ch", "at", "be", "cz", "pt",
+    "ie", "ru", "ua", "jp", "cn", "kr", "tw", "hk", "sg", "br", "mx", "ar", "nz", "za",
+];
+
+/// TLDs that are also file extensions (.pl, .ai, .in, .it, .me); these only
+/// count with a `www.` prefix or a port or path after them ("wp.pl/news").
+const FILE_EXTENSION_TLDS: &[&str] = &["pl", "ai", "in", "it", "me"];
+
+fn scheme_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)^(https?|ftp)://[^\s/?#]+[^\s]*$").unwrap())
+}
+
+fn bare_domain_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)^(?:www\.)?(?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\.)+([a-z]{2,24})(?::\d{1,5})?(?:[/?#]\S*)?$",
+        )
+        .unwrap()
+    })
+}
+
+/// Detect URL-like queries and return the URL to open.
+///
+/// Accepts full URLs with an explicit http(s)/ftp scheme, or bare domains whose TLD is in
+/// [`KNOWN_TLDS`] (e.g. "github.com/rust-lang/rust"), which get an `https://` scheme.
+/// Domains ending in one of [`FILE_EXTENSION_TLDS`] need a `www.` prefix or a path.
+pub fn detect_url(query: &str) -> Option<String> {
+    let query = query.trim();
+    if query.is_empty() || query.contains(char::is_whitespace) {
+        return None;
+    }
+
+    if scheme_regex().is_match(query) {
+        return Some(query.to_string());
+    }
+
+    let caps = bare_domain_regex().captures(query)?;
+    let tld_match = caps.get(1)?;
+    let tld = tld_match.as_str().to_ascii_lowercase();
+    let qualified = tld_match.end() < query.len()
+        || query
+            .get(..4)
+            .is_some_and(|start| start.eq_ignore_ascii_case("www."));
+    if KNOWN_TLDS.contains(&tld.as_str())
+        || (qualified && FILE_EXTENSION_TLDS.contains(&tld.as_str()))
+    {
+        Some(format!("https://{}", query))
+    } else {
+        None
+    }
+}
+
 impl Default for WebSearchPlugin {
     fn default() -> Self {
         Self::new()
@@ -155,14 +229,22 @@ impl Plugin for WebSearchPlugin {
             .with_score(9000)]); // High score for explicit web searches
         }
 
+        // URL-like query: offer to open it directly, above other results
+        if let Some(url) = detect_url(clean_query) {
+            return Ok(vec![PluginResult::new(
+                format!("Open {}", url),
+                build_open_command(&url),
+                self.name().to_string(),
+            )
+            .with_subtitle("Open in browser".to_string())
+            .with_icon("web-browser".to_string())
+            .with_badge_icon("web-browser-symbolic".to_string())
+            .with_score(9500)]);
+        }
+
         // Fallback: Offer Google search for any query (lower priority)
         // This ensures there's always a web search option even if no results match
-        let url = self.build_url("google", clean_query).unwrap_or_else(|| {
-            format!(
-                "https://www.google.com/search?q={}",
-                urlencoding::encode(clean_query)
-            )
-        });
+        let url = self.default_search_url(clean_query);
         let command = build_open_command(&url);
 
         Ok(vec![PluginResult::new(
@@ -261,6 +343,78 @@ mod tests {
         assert!(results[0].title.contains("rust"));
     }
 
+    #[test]
+    fn test_detect_url() {
+        assert_eq!(
+            detect_url("example.com"),
+            Some("https://example.com".to_string())
+        );
+        assert_eq!(
+            detect_url("github.com/rust-lang/rust"),
+            Some("https://github.com/rust-lang/rust".to_string())
+        );
+        assert_eq!(
+            detect_url("https://my.notes/page?x=1"),
+            Some("https://my.notes/page?x=1".to_string())
+        );
+        assert_eq!(
+            detect_url("HTTP://Example.org"),
+            Some("HTTP://Example.org".to_string())
+        );
+
+        // Filenames and plain words are not URLs
+        assert_eq!(detect_url("my.notes"), None);
+        assert_eq!(detect_url("main.rs"), None);
+        assert_eq!(detect_url("install.sh"), None);
+        assert_eq!(detect_url("firefox"), None);
+        assert_eq!(detect_url("example.com is great"), None);
+        assert_eq!(detect_url("https://"), None);
+        for file in [
+            "script.pl",
+            "logo.ai",
+            "config.h.in",
+            "notes.it",
+            "README.me",
+        ] {
+            assert_eq!(detect_url(file), None, "{}", file);
+        }
+
+        // Those TLDs still work with a path, port or www.
+        assert_eq!(
+            detect_url("wp.pl/news"),
+            Some("https://wp.pl/news".to_string())
+        );
+        assert_eq!(
+            detect_url("www.repubblica.it"),
+            Some("https://www.repubblica.it".to_string())
+        );
+        assert_eq!(
+            detect_url("example.ai:8080"),
+            Some("https://example.ai:8080".to_string())
+        );
+    }
+
+    #[test]
+    fn test_search_url_result() {
+        use crate::config::Config;
+
+        let web = WebSearchPlugin::new();
+        let config = Config::default();
+        let ctx = PluginContext::new(10, &config);
+
+        let results = web.search("example.com", &ctx).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Open https://example.com");
+        assert_eq!(
+            results[0].command,
+            build_open_command("https://example.com")
+        );
+        assert!(results[0].score > 1000);
+
+        let results = web.search("my.notes", &ctx).unwrap();
+        assert!(results[0].title.starts_with("Search Google"));
+    }
+
     #[test]
     fn test_keyboard_event_ctrl_enter() {
         use super::super::traits::{KeyboardAction, KeyboardEvent, Plugin};
@@ -1,6 +1,6 @@
 use super::traits::{Plugin, PluginContext, PluginResult};
+use super::windows::Compositor;
 use anyhow::Result;
-use std::process::Command;
 use tracing::{debug, warn};
 
 /// Window management plugin for Hyprland/Sway compositors
@@ -11,39 +11,19 @@ pub struct WindowManagementPlugin {
     enabled: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Compositor {
-    Hyprland,
-    Sway,
-}
-
-impl Compositor {
-    fn command(&self) -> &'static str {
-        match self {
-            Compositor::Hyprland => "hyprctl",
-            Compositor::Sway => "swaymsg",
-        }
-    }
-
-    fn dispatch_prefix(&self) -> &'static str {
-        match self {
-            Compositor::Hyprland => "hyprctl dispatch",
-            Compositor::Sway => "swaymsg",
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 struct WindowAction {
     title: String,
     subtitle: String,
     command: String,
     keywords: Vec<&'static str>,
+    /// Prompt shown before running it (closing may lose unsaved work)
+    confirm: Option<&'static str>,
 }
 
 impl WindowManagementPlugin {
     pub fn new() -> Self {
-        let compositor = Self::detect_compositor();
+        let compositor = Compositor::detect();
 
         if let Some(comp) = compositor {
             debug!("window management plugin detected compositor: {:?}", comp);
@@ -57,29 +37,6 @@ impl WindowManagementPlugin {
         }
     }
 
-    /// Detect which compositor is running
-    fn detect_compositor() -> Option<Compositor> {
-        // Check for Hyprland first
-        if Self::command_exists("hyprctl") {
-            return Some(Compositor::Hyprland);
-        }
-
-        // Check for Sway
-        if Self::command_exists("swaymsg") {
-            return Some(Compositor::Sway);
-        }
-
-        None
-    }
-
-    fn command_exists(cmd: &str) -> bool {
-        Command::new("which")
-            .arg(cmd)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
-    }
-
     /// Get available window actions for the detected compositor
     fn get_actions(&self, compositor: Compositor) -> Vec<WindowAction> {
         let prefix = compositor.dispatch_prefix();
@@ -91,84 +48,98 @@ impl WindowManagementPlugin {
                     subtitle: "Move active window to workspace 1".to_string(),
                     command: format!("{} movetoworkspace 1", prefix),
                     keywords: vec!["move", "workspace", "1"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 2".to_string(),
                     subtitle: "Move active window to workspace 2".to_string(),
                     command: format!("{} movetoworkspace 2", prefix),
                     keywords: vec!["move", "workspace", "2"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 3".to_string(),
                     subtitle: "Move active window to workspace 3".to_string(),
                     command: format!("{} movetoworkspace 3", prefix),
                     keywords: vec!["move", "workspace", "3"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 4".to_string(),
                     subtitle: "Move active window to workspace 4".to_string(),
                     command: format!("{} movetoworkspace 4", prefix),
                     keywords: vec!["move", "workspace", "4"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 5".to_string(),
                     subtitle: "Move active window to workspace 5".to_string(),
                     command: format!("{} movetoworkspace 5", prefix),
                     keywords: vec!["move", "workspace", "5"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Center Window".to_string(),
                     subtitle: "Center the active window".to_string(),
                     command: format!("{} centerwindow", prefix),
                     keywords: vec!["center", "window"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Toggle Fullscreen".to_string(),
                     subtitle: "Toggle fullscreen for active window".to_string(),
                     command: format!("{} fullscreen 0", prefix),
                     keywords: vec!["fullscreen", "full", "toggle"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Toggle Floating".to_string(),
                     subtitle: "Toggle floating mode for active window".to_string(),
                     command: format!("{} togglefloating", prefix),
                     keywords: vec!["float", "floating", "toggle"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Pin Window".to_string(),
                     subtitle: "Pin window to all workspaces".to_string(),
                     command: format!("{} pin active", prefix),
                     keywords: vec!["pin", "sticky", "all"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Close Window".to_string(),
                     subtitle: "Close the active window".to_string(),
                     command: format!("{} killactive", prefix),
                     keywords: vec!["close", "kill", "quit"],
+                    confirm: Some("close the active window"),
                 },
                 WindowAction {
                     title: "Move Window Left".to_string(),
                     subtitle: "Move focus and window left".to_string(),
                     command: format!("{} movewindow l", prefix),
                     keywords: vec!["move", "left"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window Right".to_string(),
                     subtitle: "Move focus and window right".to_string(),
                     command: format!("{} movewindow r", prefix),
                     keywords: vec!["move", "right"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window Up".to_string(),
                     subtitle: "Move focus and window up".to_string(),
                     command: format!("{} movewindow u", prefix),
                     keywords: vec!["move", "up"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window Down".to_string(),
                     subtitle: "Move focus and window down".to_string(),
                     command: format!("{} movewindow d", prefix),
                     keywords: vec!["move", "down"],
+                    confirm: None,
                 },
             ],
             Compositor::Sway => vec![
@@ -177,78 +148,91 @@ impl WindowManagementPlugin {
                     subtitle: "Move active window to workspace 1".to_string(),
                     command: format!("{} move container to workspace 1", prefix),
                     keywords: vec!["move", "workspace", "1"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 2".to_string(),
                     subtitle: "Move active window to workspace 2".to_string(),
                     command: format!("{} move container to workspace 2", prefix),
                     keywords: vec!["move", "workspace", "2"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 3".to_string(),
                     subtitle: "Move active window to workspace 3".to_string(),
                     command: format!("{} move container to workspace 3", prefix),
                     keywords: vec!["move", "workspace", "3"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 4".to_string(),
                     subtitle: "Move active window to workspace 4".to_string(),
                     command: format!("{} move container to workspace 4", prefix),
                     keywords: vec!["move", "workspace", "4"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window to Workspace 5".to_string(),
                     subtitle: "Move active window to workspace 5".to_string(),
                     command: format!("{} move container to workspace 5", prefix),
                     keywords: vec!["move", "workspace", "5"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Toggle Fullscreen".to_string(),
                     subtitle: "Toggle fullscreen for active window".to_string(),
                     command: format!("{} fullscreen toggle", prefix),
                     keywords: vec!["fullscreen", "full", "toggle"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Toggle Floating".to_string(),
                     subtitle: "Toggle floating mode for active window".to_string(),
                     command: format!("{} floating toggle", prefix),
                     keywords: vec!["float", "floating", "toggle"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Toggle Sticky".to_string(),
                     subtitle: "Pin window to all workspaces".to_string(),
                     command: format!("{} sticky toggle", prefix),
                     keywords: vec!["pin", "sticky", "all"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Close Window".to_string(),
                     subtitle: "Close the active window".to_string(),
                     command: format!("{} kill", prefix),
                     keywords: vec!["close", "kill", "quit"],
+                    confirm: Some("close the active window"),
                 },
                 WindowAction {
                     title: "Move Window Left".to_string(),
                     subtitle: "Move focus and window left".to_string(),
                     command: format!("{} move left", prefix),
                     keywords: vec!["move", "left"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window Right".to_string(),
                     subtitle: "Move focus and window right".to_string(),
                     command: format!("{} move right", prefix),
                     keywords: vec!["move", "right"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window Up".to_string(),
                     subtitle: "Move focus and window up".to_string(),
                     command: format!("{} move up", prefix),
                     keywords: vec!["move", "up"],
+                    confirm: None,
                 },
                 WindowAction {
                     title: "Move Window Down".to_string(),
                     subtitle: "Move focus and window down".to_string(),
                     command: format!("{} move down", prefix),
                     keywords: vec!["move", "down"],
+                    confirm: None,
                 },
             ],
         }
@@ -315,7 +299,7 @@ impl Plugin for WindowManagementPlugin {
             let filter_bonus = if !filter.is_empty() { 2000 } else { 0 };
             let score = 8500 + filter_bonus - (idx as i64 * 10);
 
-            let result = PluginResult::new(
+            let mut result = PluginResult::new(
                 action.title.clone(),
                 action.command.clone(),
                 self.name().to_string(),
@@ -323,6 +307,10 @@ impl Plugin for WindowManagementPlugin {
             .with_subtitle(action.subtitle.clone())
             .with_score(score);
 
+            if let Some(prompt) = action.confirm {
+                result = result.with_confirm(prompt.to_string());
+            }
+
             results.push(result);
         }
 
@@ -373,7 +361,7 @@ mod tests {
     #[test]
     fn test_compositor_detection() {
         // Just test that detection doesn't crash
-        let comp = WindowManagementPlugin::detect_compositor();
+        let comp = Compositor::detect();
         // Will be Some on Hyprland/Sway systems, None otherwise
         assert!(comp.is_some() || comp.is_none());
     }
@@ -424,6 +412,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_only_closing_asks_for_confirmation() {
+        let plugin = WindowManagementPlugin::new();
+        for compositor in [Compositor::Hyprland, Compositor::Sway] {
+            let confirmed: Vec<_> = plugin
+                .get_actions(compositor)
+                .into_iter()
+                .filter(|action| action.confirm.is_some())
+                .map(|action| action.title)
+                .collect();
+            assert_eq!(confirmed, ["Close Window"]);
+        }
+    }
+
     #[test]
     fn test_search_workspace_filter() {
         let plugin = WindowManagementPlugin::new();
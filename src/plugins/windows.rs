@@ -0,0 +1,392 @@
+//! Shared list of open windows
+//!
+//! Enumerates toplevel windows through the compositor's IPC (`hyprctl clients -j`,
+//! `swaymsg -t get_tree`) so plugins can tell which apps are already running. The
+//! list is cached briefly: typing a query runs a search per keystroke, and each
+//! of those should not have to query the compositor again.
+
+use crate::desktop::DesktopEntry;
+use serde_json::Value;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// How long an enumerated window list is reused
+const CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Compositors with an IPC for listing and focusing windows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compositor {
+    Hyprland,
+    Sway,
+}
+
+impl Compositor {
+    /// Detect which compositor is running
+    pub fn detect() -> Option<Self> {
+        // Check for Hyprland first
+        if command_exists("hyprctl") {
+            return Some(Compositor::Hyprland);
+        }
+
+        // Check for Sway
+        if command_exists("swaymsg") {
+            return Some(Compositor::Sway);
+        }
+
+        None
+    }
+
+    /// IPC command of the compositor
+    pub fn command(&self) -> &'static str {
+        match self {
+            Compositor::Hyprland => "hyprctl",
+            Compositor::Sway => "swaymsg",
+        }
+    }
+
+    /// Prefix for dispatching window commands
+    pub fn dispatch_prefix(&self) -> &'static str {
+        match self {
+            Compositor::Hyprland => "hyprctl dispatch",
+            Compositor::Sway => "swaymsg",
+        }
+    }
+
+    /// Shell command that focuses `window`
+    pub fn focus_command(&self, window: &OpenWindow) -> String {
+        match self {
+            Compositor::Hyprland => format!("hyprctl dispatch focuswindow address:{}", window.id),
+            Compositor::Sway => format!("swaymsg '[con_id={}] focus'", window.id),
+        }
+    }
+}
+
+fn command_exists(cmd: &str) -> bool {
+    Command::new("which")
+        .arg(cmd)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// A toplevel window reported by the compositor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenWindow {
+    /// Window class (X11) or app id (Wayland)
+    pub class: String,
+    /// Window title
+    pub title: String,
+    /// Compositor handle used to focus the window (Hyprland address, Sway con id)
+    pub id: String,
+}
+
+impl OpenWindow {
+    /// Whether this window belongs to `entry`
+    ///
+    /// Best-effort: `StartupWMClass` decides when the entry sets it; otherwise the
+    /// class is compared to the desktop file id (`org.gnome.Nautilus`), its last
+    /// segment (`nautilus`) and the executable name, all case-insensitively.
+    pub fn matches_entry(&self, entry: &DesktopEntry) -> bool {
+        let class = self.class.to_lowercase();
+        if class.is_empty() {
+            return false;
+        }
+
+        if let Some(wm_class) = &entry.startup_wm_class {
+            return wm_class.to_lowercase() == class;
+        }
+
+        if let Some(file_id) = entry.path.file_stem().and_then(|stem| stem.to_str()) {
+            let file_id = file_id.to_lowercase();
+            if file_id == class || file_id.rsplit('.').next() == Some(class.as_str()) {
+                return true;
+            }
+        }
+
+        exec_name(&entry.exec).is_some_and(|name| name == class)
+    }
+}
+
+/// Lowercased program name of an `Exec` line, skipping `env` and variable assignments
+fn exec_name(exec: &str) -> Option<String> {
+    let program = exec
+        .split_whitespace()
+        .find(|arg| *arg != "env" && !arg.contains('='))?;
+    let name = program.trim_matches('"').rsplit('/').next()?;
+    Some(name.to_lowercase())
+}
+
+/// Open windows of the running compositor, shared between plugins
+#[derive(Debug)]
+pub struct WindowList {
+    compositor: Option<Compositor>,
+    /// Fixed list that is never refreshed (tests)
+    fixed: Option<Arc<Vec<OpenWindow>>>,
+    cache: Mutex<Option<(Instant, Arc<Vec<OpenWindow>>)>>,
+}
+
+impl WindowList {
+    /// Detect the compositor; without a supported one the list is always empty
+    pub fn new() -> Self {
+        Self {
+            compositor: Compositor::detect(),
+            fixed: None,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Use a fixed set of windows instead of querying the compositor
+    #[allow(dead_code)]
+    pub fn with_windows(compositor: Compositor, windows: Vec<OpenWindow>) -> Self {
+        Self {
+            compositor: Some(compositor),
+            fixed: Some(Arc::new(windows)),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Current open windows (cached for a couple of seconds)
+    pub fn windows(&self) -> Arc<Vec<OpenWindow>> {
+        if let Some(fixed) = &self.fixed {
+            return fixed.clone();
+        }
+        let Some(compositor) = self.compositor else {
+            return Arc::new(Vec::new());
+        };
+
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((fetched, windows)) = cache.as_ref() {
+            if fetched.elapsed() < CACHE_TTL {
+                return windows.clone();
+            }
+        }
+
+        let windows = Arc::new(enumerate(compositor));
+        *cache = Some((Instant::now(), windows.clone()));
+        windows
+    }
+
+    /// First open window belonging to `entry`, with the command that focuses it
+    pub fn find_for_entry(
+        &self,
+        windows: &[OpenWindow],
+        entry: &DesktopEntry,
+    ) -> Option<(OpenWindow, String)> {
+        let compositor = self.compositor?;
+        windows
+            .iter()
+            .find(|window| window.matches_entry(entry))
+            .map(|window| (window.clone(), compositor.focus_command(window)))
+    }
+}
+
+impl Default for WindowList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ask the compositor for its windows; failures give an empty list
+fn enumerate(compositor: Compositor) -> Vec<OpenWindow> {
+    let args: &[&str] = match compositor {
+        Compositor::Hyprland => &["clients", "-j"],
+        Compositor::Sway => &["-t", "get_tree"],
+    };
+
+    let output = match Command::new(compositor.command()).args(args).output() {
+        Ok(output) if output.status.success() => output.stdout,
+        Ok(output) => {
+            debug!("{} exited with {}", compositor.command(), output.status);
+            return Vec::new();
+        }
+        Err(e) => {
+            debug!(
+                "Failed to list windows with {}: {}",
+                compositor.command(),
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    let json = String::from_utf8_lossy(&output);
+    match compositor {
+        Compositor::Hyprland => parse_hyprland_clients(&json),
+        Compositor::Sway => parse_sway_tree(&json),
+    }
+}
+
+/// Parse `hyprctl clients -j`
+fn parse_hyprland_clients(json: &str) -> Vec<OpenWindow> {
+    let Ok(Value::Array(clients)) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+
+    clients
+        .iter()
+        .filter_map(|client| {
+            Some(OpenWindow {
+                class: client.get("class")?.as_str()?.to_string(),
+                title: client
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                id: client.get("address")?.as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `swaymsg -t get_tree`, collecting every view (nodes with an app id or
+/// X11 class) from the tiling and floating trees
+fn parse_sway_tree(json: &str) -> Vec<OpenWindow> {
+    fn walk(node: &Value, windows: &mut Vec<OpenWindow>) {
+        let class = node.get("app_id").and_then(Value::as_str).or_else(|| {
+            node.get("window_properties")
+                .and_then(|props| props.get("class"))
+                .and_then(Value::as_str)
+        });
+        if let (Some(class), Some(id)) = (class, node.get("id").and_then(Value::as_i64)) {
+            windows.push(OpenWindow {
+                class: class.to_string(),
+                title: node
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                id: id.to_string(),
+            });
+        }
+
+        for key in ["nodes", "floating_nodes"] {
+            if let Some(Value::Array(children)) = node.get(key) {
+                for child in children {
+                    walk(child, windows);
+                }
+            }
+        }
+    }
+
+    let Ok(tree) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+    let mut windows = Vec::new();
+    walk(&tree, &mut windows);
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(path: &str, exec: &str, wm_class: Option<&str>) -> DesktopEntry {
+        DesktopEntry {
+            name: "App".to_string(),
+            generic_name: None,
+            comment: None,
+            exec: exec.to_string(),
+            icon: None,
+            categories: vec![],
+            keywords: vec![],
+            terminal: false,
+            path: PathBuf::from(path),
+            no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: wm_class.map(str::to_string),
+            mime_types: vec![],
+            actions: vec![],
+        }
+    }
+
+    fn window(class: &str) -> OpenWindow {
+        OpenWindow {
+            class: class.to_string(),
+            title: "Window".to_string(),
+            id: "0x1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_window_class_maps_to_desktop_entry() {
+        let nautilus = entry(
+            "/usr/share/applications/org.gnome.Nautilus.desktop",
+            "nautilus --new-window",
+            None,
+        );
+        assert!(window("org.gnome.Nautilus").matches_entry(&nautilus));
+        assert!(window("nautilus").matches_entry(&nautilus));
+
+        let firefox = entry(
+            "/usr/share/applications/browser.desktop",
+            "/usr/bin/firefox %u",
+            None,
+        );
+        assert!(window("firefox").matches_entry(&firefox));
+        assert!(!window("firefox-esr").matches_entry(&firefox));
+
+        // StartupWMClass is authoritative when set
+        let code = entry(
+            "/usr/share/applications/code.desktop",
+            "code %F",
+            Some("Code"),
+        );
+        assert!(window("code").matches_entry(&code));
+        let chrome_app = entry(
+            "/usr/share/applications/chrome-app.desktop",
+            "env GDK_BACKEND=x11 chromium --app-id=xyz",
+            Some("crx_xyz"),
+        );
+        assert!(!window("chromium").matches_entry(&chrome_app));
+        assert!(window("crx_xyz").matches_entry(&chrome_app));
+
+        let list = WindowList::with_windows(
+            Compositor::Hyprland,
+            vec![window("kitty"), window("org.gnome.Nautilus")],
+        );
+        let (found, focus) = list.find_for_entry(&list.windows(), &nautilus).unwrap();
+        assert_eq!(found.class, "org.gnome.Nautilus");
+        assert_eq!(focus, "hyprctl dispatch focuswindow address:0x1");
+        assert!(list.find_for_entry(&list.windows(), &firefox).is_none());
+    }
+
+    #[test]
+    fn test_parse_compositor_window_lists() {
+        let hyprland = parse_hyprland_clients(
+            r#"[{"address": "0x55aa", "class": "firefox", "title": "Mozilla Firefox"}]"#,
+        );
+        assert_eq!(
+            hyprland,
+            vec![OpenWindow {
+                class: "firefox".to_string(),
+                title: "Mozilla Firefox".to_string(),
+                id: "0x55aa".to_string(),
+            }]
+        );
+
+        let sway = parse_sway_tree(
+            r#"{"id": 1, "name": "root", "nodes": [
+                {"id": 4, "name": "ws", "nodes": [
+                    {"id": 7, "name": "~", "app_id": "foot", "nodes": []}
+                ], "floating_nodes": [
+                    {"id": 9, "name": "Gimp", "app_id": null,
+                     "window_properties": {"class": "Gimp"}, "nodes": []}
+                ]}
+            ]}"#,
+        );
+        let classes: Vec<_> = sway
+            .iter()
+            .map(|w| (w.class.as_str(), w.id.as_str()))
+            .collect();
+        assert_eq!(classes, vec![("foot", "7"), ("Gimp", "9")]);
+        assert_eq!(
+            Compositor::Sway.focus_command(&sway[0]),
+            "swaymsg '[con_id=7] focus'"
+        );
+    }
+}
@@ -1,8 +1,121 @@
-use crate::desktop::{DesktopEntry, DesktopEntryArena, SharedDesktopEntry};
+use crate::config::SearchConfig;
+use crate::desktop::{DesktopEntry, DesktopEntryArena, SearchKeys, SharedDesktopEntry};
 use crate::usage::UsageTracker;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 
+/// Minimum fuzzy scores a match must beat, by query length
+///
+/// Short queries (1-2 chars) match almost anything, so they need a higher score
+/// to reduce false positives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreThresholds {
+    /// Threshold for queries up to `short_query_len` characters
+    pub short: i64,
+    /// Threshold for longer queries
+    pub long: i64,
+    /// Longest query (in characters) that counts as short
+    pub short_query_len: usize,
+}
+
+impl ScoreThresholds {
+    /// Thresholds from `search.min_score_short` / `min_score_long` / `short_query_len`
+    pub fn from_config(config: &SearchConfig) -> Self {
+        Self {
+            short: config.min_score_short,
+            long: config.min_score_long,
+            short_query_len: config.short_query_len,
+        }
+    }
+
+    /// Threshold that applies to `query`
+    pub fn for_query(&self, query: &str) -> i64 {
+        if query.chars().count() <= self.short_query_len {
+            self.short
+        } else {
+            self.long
+        }
+    }
+}
+
+impl Default for ScoreThresholds {
+    fn default() -> Self {
+        Self::from_config(&SearchConfig::default())
+    }
+}
+
+/// How entries are ordered when the query gives no ranking of its own: the
+/// empty-query list and ties between equally scored matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderStrategy {
+    /// Ties by name; the empty-query list by usage, as there is no match score
+    #[default]
+    Score,
+    /// Most used first (frecency score from the usage tracker)
+    Usage,
+    /// Alphabetical by name
+    Alpha,
+    /// Most recently launched first
+    Recent,
+}
+
+impl OrderStrategy {
+    /// Strategy named by `search.order_strategy`, `None` for unknown names
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "score" => Some(Self::Score),
+            "usage" => Some(Self::Usage),
+            "alpha" => Some(Self::Alpha),
+            "recent" => Some(Self::Recent),
+            _ => None,
+        }
+    }
+
+    /// Strategy from `search.order_strategy`, falling back to the default
+    pub fn from_config(config: &SearchConfig) -> Self {
+        Self::parse(&config.order_strategy).unwrap_or_default()
+    }
+
+    /// Strategy for the empty query, where there is no match score to rank by:
+    /// "score" falls back to usage
+    pub fn for_empty_query(self) -> Self {
+        match self {
+            Self::Score => Self::Usage,
+            order => order,
+        }
+    }
+
+    /// Compare two entries the match score can't tell apart
+    ///
+    /// Usage and recency need a tracker; without one every strategy sorts by name.
+    pub fn compare(
+        self,
+        tracker: Option<&UsageTracker>,
+        a: &DesktopEntry,
+        b: &DesktopEntry,
+    ) -> std::cmp::Ordering {
+        let by_strategy = match (self, tracker) {
+            (Self::Usage, Some(tracker)) => {
+                let score_a = tracker.get_score(&a.path.to_string_lossy());
+                let score_b = tracker.get_score(&b.path.to_string_lossy());
+                score_b
+                    .partial_cmp(&score_a)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }
+            (Self::Recent, Some(tracker)) => {
+                let last_used = |entry: &DesktopEntry| {
+                    tracker
+                        .get_usage(&entry.path.to_string_lossy())
+                        .map_or(0, |usage| usage.last_used)
+                };
+                last_used(b).cmp(&last_used(a))
+            }
+            _ => std::cmp::Ordering::Equal,
+        };
+        by_strategy.then_with(|| a.name.cmp(&b.name))
+    }
+}
+
 /// Search engine for desktop entries with fuzzy matching and usage tracking
 pub struct SearchEngine {
     entries: DesktopEntryArena,
@@ -11,6 +124,8 @@ pub struct SearchEngine {
     matcher: SkimMatcherV2,
     #[allow(dead_code)]
     usage_tracker: Option<UsageTracker>,
+    thresholds: ScoreThresholds,
+    order: OrderStrategy,
 }
 
 impl SearchEngine {
@@ -24,9 +139,25 @@ impl SearchEngine {
             usage_enabled,
             matcher: SkimMatcherV2::default(),
             usage_tracker,
+            thresholds: ScoreThresholds::default(),
+            order: OrderStrategy::default(),
         }
     }
 
+    /// Use custom minimum scores (see [`ScoreThresholds::from_config`])
+    #[allow(dead_code)]
+    pub fn with_score_thresholds(mut self, thresholds: ScoreThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Use a different ordering for the empty query and ties (see [`OrderStrategy::from_config`])
+    #[allow(dead_code)]
+    pub fn with_order_strategy(mut self, order: OrderStrategy) -> Self {
+        self.order = order;
+        self
+    }
+
     /// Create a new search engine with the given entries
     #[allow(dead_code)]
     pub fn new(entries: DesktopEntryArena, usage_enabled: bool) -> Self {
@@ -59,53 +190,43 @@ impl SearchEngine {
         };
 
         if query.is_empty() {
-            // When query is empty, sort by usage if available, otherwise by name
+            let order = self.order.for_empty_query();
             let mut results: Vec<_> = self.entries.iter().cloned().collect();
-
-            if let Some(tracker) = usage_tracker {
-                // Sort by usage score (descending), then by name
-                results.sort_by(|a, b| {
-                    let score_a = tracker.get_score(&a.path.to_string_lossy());
-                    let score_b = tracker.get_score(&b.path.to_string_lossy());
-                    score_b
-                        .partial_cmp(&score_a)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                        .then_with(|| a.name.cmp(&b.name))
-                });
-            } else {
-                results.sort_by(|a, b| a.name.cmp(&b.name));
-            }
+            results.sort_by(|a, b| order.compare(usage_tracker, a, b));
 
             return results.into_iter().take(max_results).collect();
         }
 
-        let query_lower = query.to_lowercase();
+        // 'term: literal substring matches on the name only, no fuzzy scoring
+        let exact_term = exact_match_term(query);
+        let query_lower = exact_term.clone().unwrap_or_else(|| query.to_lowercase());
+        let query_mask = SearchKeys::char_mask(&query_lower);
 
         // Minimum score threshold to reduce false positives
-        // For short queries (1-2 chars), require higher scores
-        let min_score = if query.len() <= 2 {
-            50 // Higher threshold for short queries
+        let min_score = if exact_term.is_some() {
+            0
         } else {
-            20 // Lower threshold for longer queries
+            self.thresholds.for_query(query)
         };
 
         // Score entries using fuzzy matching + usage boost
         let mut results: Vec<(SharedDesktopEntry, f64)> = self
             .entries
-            .iter()
-            .filter(|entry| !entry.no_display) // Filter out hidden entries
-            .filter_map(|entry| {
-                let entry_ref = entry.as_ref();
+            .iter_with_keys()
+            .filter(|(entry, _)| !entry.no_display && !entry.hidden) // Filter out hidden entries
+            .filter(|(_, keys)| keys.may_match(query_mask)) // Skip entries missing query chars
+            .filter_map(|(entry, keys)| {
                 // Calculate fuzzy match score
-                let fuzzy_score = self.calculate_fuzzy_score(entry_ref, &query_lower);
+                let fuzzy_score = match &exact_term {
+                    Some(term) => exact_match_score(&keys.name, term).unwrap_or(0),
+                    None => self.calculate_fuzzy_score(entry, keys, &query_lower),
+                };
 
                 if fuzzy_score > min_score {
                     // Apply usage boost if tracking is enabled
                     let final_score = if let Some(tracker) = usage_tracker {
                         let usage_score = tracker.get_score(&entry.path.to_string_lossy());
-                        // Boost formula: fuzzy_score * (1 + usage_score * 0.1)
-                        // This gives 10% boost per usage point
-                        fuzzy_score as f64 * (1.0 + usage_score * 0.1)
+                        fuzzy_score as f64 * usage_boost(usage_score)
                     } else {
                         fuzzy_score as f64
                     };
@@ -117,12 +238,19 @@ impl SearchEngine {
             })
             .collect();
 
-        // Sort by final score (descending), then by name
+        // Last resort for badly misspelled names ("fierfix"), off the hot path
+        if results.is_empty() && exact_term.is_none() {
+            return did_you_mean(self.entries.iter_with_keys(), &query_lower)
+                .into_iter()
+                .collect();
+        }
+
+        // Sort by final score (descending), then by the order strategy
         results.sort_by(|(entry_a, score_a), (entry_b, score_b)| {
             score_b
                 .partial_cmp(score_a)
                 .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| entry_a.name.cmp(&entry_b.name))
+                .then_with(|| self.order.compare(usage_tracker, entry_a, entry_b))
         });
 
         // Return top results
@@ -136,11 +264,11 @@ impl SearchEngine {
     /// Calculate fuzzy match score for an entry
     #[allow(dead_code)]
     #[inline(always)] // Force inlining for hot path
-    fn calculate_fuzzy_score(&self, entry: &DesktopEntry, query: &str) -> i64 {
+    fn calculate_fuzzy_score(&self, entry: &DesktopEntry, keys: &SearchKeys, query: &str) -> i64 {
         let mut best_score = 0i64;
 
-        // Cache lowercase conversions for performance
-        let name_lower = entry.name.to_lowercase();
+        // Entry fields are lowercased once in the arena; only the query is converted here
+        let name_lower = keys.name.as_str();
         let query_lower = query.to_lowercase();
 
         // 1. Try exact match first (highest priority)
@@ -181,7 +309,7 @@ impl SearchEngine {
         }
 
         // 3. Word boundary matching (e.g., "vs" matches "Visual Studio")
-        let word_boundary_score = self.match_word_boundaries(&entry.name, &query_lower);
+        let word_boundary_score = self.match_word_boundaries(name_lower, &query_lower);
         if word_boundary_score > 0 {
             best_score = best_score.max(7000 + word_boundary_score);
         }
@@ -192,14 +320,13 @@ impl SearchEngine {
         }
 
         // 5. Fuzzy match on generic name (secondary field)
-        if let Some(ref generic) = entry.generic_name {
-            let generic_lower = generic.to_lowercase();
+        if let (Some(generic), Some(generic_lower)) = (&entry.generic_name, &keys.generic_name) {
             // Check for exact match in generic name too
             if generic_lower.contains(&query_lower) {
                 best_score = best_score.max(5000);
 
                 // Word boundary bonus for generic name
-                let generic_word_score = self.match_word_boundaries(generic, &query_lower);
+                let generic_word_score = self.match_word_boundaries(generic_lower, &query_lower);
                 if generic_word_score > 0 {
                     best_score = best_score.max(5500 + generic_word_score);
                 }
@@ -211,19 +338,15 @@ impl SearchEngine {
         }
 
         // 6. Match on exec field (for technical users searching by command name)
-        if query.len() >= 3 {
-            let exec_lower = entry.exec.to_lowercase();
-            if exec_lower.contains(&query_lower) {
-                // Lower priority than name matches but still relevant
-                best_score = best_score.max(3000);
-            }
+        if query.len() >= 3 && keys.exec.contains(&query_lower) {
+            // Lower priority than name matches but still relevant
+            best_score = best_score.max(3000);
         }
 
         // 7. Fuzzy match on keywords (tertiary field)
-        for keyword in &entry.keywords {
-            let keyword_lower = keyword.to_lowercase();
+        for (keyword, keyword_lower) in entry.keywords.iter().zip(&keys.keywords) {
             // Exact keyword match gets priority
-            if keyword_lower == query_lower {
+            if *keyword_lower == query_lower {
                 best_score = best_score.max(4000);
             } else if keyword_lower.contains(&query_lower) {
                 best_score = best_score.max(2000);
@@ -243,6 +366,12 @@ impl SearchEngine {
             }
         }
 
+        // 9. Desktop file id segments (e.g. "org.kde" for org.kde.konsole.desktop),
+        // kept below keyword and exec matches to avoid noisy results
+        if keys.matches_file_id(&query_lower) {
+            best_score = best_score.max(1000);
+        }
+
         best_score
     }
 
@@ -293,8 +422,7 @@ impl SearchEngine {
 
     /// Match word boundaries (e.g., "code" matches "Visual Studio Code")
     #[inline]
-    fn match_word_boundaries(&self, text: &str, query_lower: &str) -> i64 {
-        let text_lower = text.to_lowercase();
+    fn match_word_boundaries(&self, text_lower: &str, query_lower: &str) -> i64 {
         let words: Vec<&str> = text_lower.split_whitespace().collect();
 
         for (idx, word) in words.iter().enumerate() {
@@ -328,6 +456,95 @@ impl SearchEngine {
     }
 }
 
+/// Shortest query that gets "did you mean" suggestions
+const SUGGESTION_MIN_QUERY_LEN: usize = 4;
+
+/// Leading character that turns off fuzzy matching for the rest of the query
+/// (as in fzf): `'fire` only finds names containing "fire"
+pub const EXACT_MATCH_PREFIX: char = '\'';
+
+/// Factor an app's fuzzy score is multiplied by for its usage score: a 10%
+/// boost per usage point
+pub fn usage_boost(usage_score: f64) -> f64 {
+    1.0 + usage_score * 0.1
+}
+
+/// Lowercased term of an exact-match query, `None` for a normal fuzzy query
+pub fn exact_match_term(query: &str) -> Option<String> {
+    query
+        .strip_prefix(EXACT_MATCH_PREFIX)
+        .map(|term| term.trim().to_lowercase())
+}
+
+/// Score of `name_lower` containing `term_lower` literally, `None` if it doesn't
+///
+/// Whole-name matches rank first, then prefix matches, then shorter names.
+pub fn exact_match_score(name_lower: &str, term_lower: &str) -> Option<i64> {
+    if !name_lower.contains(term_lower) {
+        return None;
+    }
+
+    let mut score = 10000 + (1000 / (name_lower.len() as i64 + 1));
+    if name_lower.starts_with(term_lower) {
+        score += 5000;
+    }
+    if name_lower == term_lower {
+        score += 10000;
+    }
+    Some(score)
+}
+
+/// Closest app to a badly misspelled query ("fierfix" -> Firefox)
+///
+/// Last resort for when fuzzy matching finds nothing: compares the query with each
+/// visible entry's name and the words in it by edit distance, allowing up to a
+/// third of the query length (rounded up, at most 3) for queries of 4+ characters.
+pub fn did_you_mean<'a>(
+    entries: impl IntoIterator<Item = (&'a SharedDesktopEntry, &'a SearchKeys)>,
+    query_lower: &str,
+) -> Option<SharedDesktopEntry> {
+    let query: Vec<char> = query_lower.trim().chars().collect();
+    if query.len() < SUGGESTION_MIN_QUERY_LEN {
+        return None;
+    }
+    let max_distance = query.len().div_ceil(3).min(3);
+
+    entries
+        .into_iter()
+        .filter(|(entry, _)| !entry.no_display && !entry.hidden)
+        .filter_map(|(entry, keys)| {
+            std::iter::once(keys.name.as_str())
+                .chain(keys.name.split_whitespace())
+                .filter_map(|candidate| bounded_edit_distance(&query, candidate, max_distance))
+                .min()
+                .map(|distance| (distance, entry))
+        })
+        .min_by(|(dist_a, a), (dist_b, b)| dist_a.cmp(dist_b).then_with(|| a.name.cmp(&b.name)))
+        .map(|(_, entry)| entry.clone())
+}
+
+/// Levenshtein distance between `query` and `candidate`, or `None` above `max`
+fn bounded_edit_distance(query: &[char], candidate: &str, max: usize) -> Option<usize> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    if candidate.len().abs_diff(query.len()) > max {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=candidate.len()).collect();
+    let mut current = vec![0; candidate.len() + 1];
+    for (i, q) in query.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, c) in candidate.iter().enumerate() {
+            let substitution = previous[j] + usize::from(q != c);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[candidate.len()];
+    (distance <= max).then_some(distance)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,6 +560,7 @@ mod tests {
         DesktopEntry {
             name: name.to_string(),
             generic_name: generic_name.map(|s| s.to_string()),
+            comment: None,
             exec: "test".to_string(),
             icon: None,
             categories: vec![],
@@ -350,10 +568,61 @@ mod tests {
             terminal: false,
             path: PathBuf::from("/test"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         }
     }
 
+    #[test]
+    fn test_short_query_threshold_suppresses_borderline_match() {
+        let arena = DesktopEntryArena::from_vec(vec![create_test_entry("Firefox", None, vec![])]);
+        let engine = SearchEngine::new(arena.clone(), false);
+
+        // "fx" only fuzzy-matches Firefox, just above the default short threshold
+        let (entry, keys) = arena.iter_with_keys().next().unwrap();
+        let score = engine.calculate_fuzzy_score(entry, keys, "fx");
+        assert!(score > ScoreThresholds::default().short);
+        assert_eq!(engine.search("fx", 10).len(), 1);
+
+        let strict = SearchEngine::new(arena, false).with_score_thresholds(ScoreThresholds {
+            short: score,
+            ..ScoreThresholds::default()
+        });
+        assert!(strict.search("fx", 10).is_empty());
+        // Longer queries keep using the long threshold
+        assert_eq!(strict.search("firef", 10).len(), 1);
+    }
+
+    #[test]
+    fn test_did_you_mean_for_misspelled_name() {
+        let entries = vec![
+            create_test_entry("Firefox", Some("Web Browser"), vec![]),
+            create_test_entry("Files", Some("File Manager"), vec![]),
+            create_test_entry("Thunderbird Mail", None, vec![]),
+        ];
+        let engine = SearchEngine::new(DesktopEntryArena::from_vec(entries), false);
+
+        let results = engine.search("fierfix", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "Firefox");
+
+        // Words inside longer names are compared too
+        let results = engine.search("thunderbrid", 10);
+        assert_eq!(results[0].name, "Thunderbird Mail");
+
+        // Too far off, or too short to guess from
+        assert!(engine.search("qwzxvb", 10).is_empty());
+        assert!(engine.search("fzx", 10).is_empty());
+
+        assert_eq!(bounded_edit_distance(&['a', 'b', 'c'], "abc", 1), Some(0));
+        assert_eq!(bounded_edit_distance(&['a', 'b', 'c'], "axcd", 2), Some(2));
+        assert_eq!(bounded_edit_distance(&['a', 'b', 'c'], "xyz", 2), None);
+    }
+
     #[test]
     fn test_fuzzy_search_exact_match() {
         let entries = vec![
@@ -369,6 +638,40 @@ mod tests {
         assert_eq!(results[0].name, "Firefox");
     }
 
+    #[test]
+    fn test_exact_match_prefix_skips_fuzzy_candidates() {
+        let entries = vec![
+            create_test_entry("Firefox", Some("Web Browser"), vec![]),
+            create_test_entry("Campfire", None, vec![]),
+            // Fuzzy-only: f-i-r-e appear in order but not together
+            create_test_entry("File Roller Extractor", None, vec![]),
+            create_test_entry("Fire", None, vec![]),
+            // Only the keyword mentions fire
+            create_test_entry("Burner", None, vec!["fire"]),
+        ];
+        let engine = SearchEngine::new(DesktopEntryArena::from_vec(entries), false);
+
+        // The fuzzy search finds the scattered match too
+        let fuzzy: Vec<_> = engine
+            .search("fire", 10)
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect();
+        assert!(fuzzy.contains(&"File Roller Extractor".to_string()));
+
+        let exact: Vec<_> = engine
+            .search("'fire", 10)
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect();
+        assert_eq!(exact, vec!["Fire", "Firefox", "Campfire"]);
+
+        // Nothing literal: no "did you mean" guess either
+        assert!(engine.search("'fierfix", 10).is_empty());
+        assert_eq!(exact_match_term("'Fire "), Some("fire".to_string()));
+        assert_eq!(exact_match_term("fire"), None);
+    }
+
     #[test]
     fn test_fuzzy_search_partial_match() {
         let entries = vec![
@@ -438,6 +741,7 @@ mod tests {
             DesktopEntry {
                 name: "Alpha Editor".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "alpha".to_string(),
                 icon: None,
                 categories: vec![],
@@ -445,11 +749,17 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/alpha.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
             DesktopEntry {
                 name: "Beta Browser".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "beta".to_string(),
                 icon: None,
                 categories: vec![],
@@ -457,6 +767,11 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/beta.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
         ];
@@ -485,6 +800,88 @@ mod tests {
         );
     }
 
+    /// Tracker with fixed launch counts and last-launch ages (in seconds)
+    fn tracker_with_usage(usage: &[(&str, usize, u64)]) -> UsageTracker {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let data: std::collections::HashMap<String, crate::usage::AppUsage> = usage
+            .iter()
+            .map(|&(path, launch_count, age)| {
+                let usage = crate::usage::AppUsage {
+                    launch_count,
+                    last_used: now - age,
+                    first_used: now - age,
+                    launch_history: Vec::new(),
+                };
+                (path.to_string(), usage)
+            })
+            .collect();
+        UsageTracker::from_usage(data)
+    }
+
+    #[test]
+    fn test_order_strategies() {
+        let entry = |name: &str, path: &str| DesktopEntry {
+            path: PathBuf::from(path),
+            ..create_test_entry(name, None, vec![])
+        };
+        let arena = DesktopEntryArena::from_vec(vec![
+            entry("Term B", "/b.desktop"),
+            entry("Term C", "/c.desktop"),
+            entry("Term A", "/a.desktop"),
+        ]);
+        // B is used most, C most recently; A was never launched
+        let tracker = tracker_with_usage(&[("/b.desktop", 5, 2 * 86400), ("/c.desktop", 1, 60)]);
+        let names = |strategy: OrderStrategy, query: &str| -> Vec<String> {
+            SearchEngine::with_usage_tracking(arena.clone(), tracker.clone())
+                .with_order_strategy(strategy)
+                .search(query, 10)
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            names(OrderStrategy::Score, ""),
+            ["Term B", "Term C", "Term A"]
+        );
+        assert_eq!(
+            names(OrderStrategy::Usage, ""),
+            ["Term B", "Term C", "Term A"]
+        );
+        assert_eq!(
+            names(OrderStrategy::Alpha, ""),
+            ["Term A", "Term B", "Term C"]
+        );
+        assert_eq!(
+            names(OrderStrategy::Recent, ""),
+            ["Term C", "Term B", "Term A"]
+        );
+
+        // Ties: launched but with no usage score left, C matches as well as A
+        let tracker = tracker_with_usage(&[("/c.desktop", 0, 60)]);
+        let names = |strategy: OrderStrategy| -> Vec<String> {
+            SearchEngine::with_usage_tracking(arena.clone(), tracker.clone())
+                .with_order_strategy(strategy)
+                .search("term", 10)
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect()
+        };
+        assert_eq!(names(OrderStrategy::Score), ["Term A", "Term B", "Term C"]);
+        assert_eq!(names(OrderStrategy::Alpha), ["Term A", "Term B", "Term C"]);
+        assert_eq!(names(OrderStrategy::Recent), ["Term C", "Term A", "Term B"]);
+
+        let config = SearchConfig {
+            order_strategy: "recent".to_string(),
+            ..SearchConfig::default()
+        };
+        assert_eq!(OrderStrategy::from_config(&config), OrderStrategy::Recent);
+        assert_eq!(OrderStrategy::parse("random"), None);
+    }
+
     #[test]
     fn test_acronym_matching() {
         let entries = vec![
@@ -535,6 +932,7 @@ mod tests {
             DesktopEntry {
                 name: "Firefox".to_string(),
                 generic_name: Some("Web Browser".to_string()),
+                comment: None,
                 exec: "firefox %u".to_string(),
                 icon: None,
                 categories: vec![],
@@ -542,11 +940,17 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/firefox.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
             DesktopEntry {
                 name: "Chrome".to_string(),
                 generic_name: Some("Web Browser".to_string()),
+                comment: None,
                 exec: "google-chrome %u".to_string(),
                 icon: None,
                 categories: vec![],
@@ -554,6 +958,11 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/chrome.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
         ];
@@ -567,6 +976,51 @@ mod tests {
         assert_eq!(results[0].name, "Chrome");
     }
 
+    #[test]
+    fn test_desktop_file_id_matching() {
+        let with_path = |name: &str, exec: &str, path: &str| DesktopEntry {
+            exec: exec.to_string(),
+            path: PathBuf::from(path),
+            ..create_test_entry(name, None, vec![])
+        };
+        let entries = vec![
+            with_path(
+                "Terminal Emulator",
+                "konsole",
+                "/usr/share/applications/org.kde.konsole.desktop",
+            ),
+            with_path(
+                "KDE Connect",
+                "kdeconnect-app",
+                "/usr/share/applications/kdeconnect.app.desktop",
+            ),
+            with_path(
+                "Text Editor",
+                "gedit",
+                "/usr/share/applications/gedit.desktop",
+            ),
+        ];
+
+        let arena = DesktopEntryArena::from_vec(entries);
+        let engine = SearchEngine::new(arena.clone(), false);
+
+        // The reverse-DNS id fragment finds the entry whose Name doesn't mention it
+        let results = engine.search("org.kde", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "Terminal Emulator");
+
+        // Name matches still outrank file id matches
+        let results = engine.search("kde", 10);
+        assert_eq!(results[0].name, "KDE Connect");
+        assert!(results.iter().any(|e| e.name == "Terminal Emulator"));
+
+        // Only segment starts match: "ole" is inside "konsole", not a segment
+        let (_, konsole) = arena.iter_with_keys().next().unwrap();
+        assert!(konsole.matches_file_id("kde.konsole"));
+        assert!(!konsole.matches_file_id("ole"));
+        assert!(!konsole.matches_file_id("or"));
+    }
+
     #[test]
     fn test_case_sensitivity_bonus() {
         let entries = vec![
@@ -649,4 +1103,136 @@ mod tests {
         assert!(!results.is_empty());
         assert_eq!(results[0].name, "Inkscape");
     }
+
+    #[test]
+    fn test_precomputed_keys_keep_scores() {
+        // Scores recorded before entry fields were lowercased up front
+        let entries = vec![
+            DesktopEntry {
+                name: "Visual Studio Code".to_string(),
+                generic_name: Some("Text Editor".to_string()),
+                comment: None,
+                exec: "/usr/bin/Code --unity-launch %F".to_string(),
+                icon: None,
+                categories: vec!["Development".to_string(), "IDE".to_string()],
+                keywords: vec!["VSCode".to_string(), "editor".to_string()],
+                terminal: false,
+                path: PathBuf::from("/code.desktop"),
+                no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
+                actions: vec![],
+            },
+            create_test_entry("Firefox", Some("Web Browser"), vec!["Internet", "WWW"]),
+            create_test_entry("GNOME Terminal", Some("Terminal"), vec!["shell", "CLI"]),
+        ];
+        let expected: [(&str, [i64; 3]); 11] = [
+            ("code", [10052, 0, 0]),
+            ("vsc", [9500, 0, 0]),
+            ("Firefox", [0, 25125, 0]),
+            ("fire", [0, 15125, 0]),
+            ("www", [0, 4000, 0]),
+            ("term", [0, 0, 10066]),
+            ("shell", [0, 0, 4000]),
+            ("develop", [74, 0, 0]),
+            ("editor", [6400, 0, 0]),
+            ("unity", [3000, 0, 0]),
+            ("xyz", [0, 0, 0]),
+        ];
+
+        let arena = DesktopEntryArena::from_vec(entries);
+        let engine = SearchEngine::new(arena.clone(), false);
+        for (query, scores) in expected {
+            let query_lower = query.to_lowercase();
+            let actual: Vec<i64> = arena
+                .iter_with_keys()
+                .map(|(entry, keys)| engine.calculate_fuzzy_score(entry, keys, &query_lower))
+                .collect();
+            assert_eq!(actual, scores, "scores changed for {:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_prefilter_never_drops_scored_entries() {
+        let entries = vec![
+            DesktopEntry {
+                name: "Visual Studio Code".to_string(),
+                generic_name: Some("Text Editor".to_string()),
+                comment: None,
+                exec: "/usr/bin/code --unity-launch %F".to_string(),
+                icon: None,
+                categories: vec!["Development".to_string(), "IDE".to_string()],
+                keywords: vec!["VSCode".to_string(), "editor".to_string()],
+                terminal: false,
+                path: PathBuf::from("/code.desktop"),
+                no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
+                actions: vec![],
+            },
+            create_test_entry("Firefox", Some("Web Browser"), vec!["Internet", "WWW"]),
+            create_test_entry("GNOME Terminal", Some("Terminal"), vec!["shell", "CLI"]),
+            create_test_entry("LibreOffice Writer", Some("Word Processor"), vec!["docx"]),
+            create_test_entry("Łódź Viewer", Some("Ünïcode Tool"), vec!["ßtraße"]),
+        ];
+        let queries = [
+            "c",
+            "co",
+            "vsc",
+            "vs code",
+            "firefx",
+            "ffx",
+            "www",
+            "term",
+            "gt",
+            "shell",
+            "develop",
+            "editor",
+            "unity",
+            "usr/bin",
+            "lo writer",
+            "wp",
+            "docx",
+            "łódź",
+            "ünï",
+            "straße",
+            "xyz",
+            "qq",
+            "--unity",
+            "%f",
+            "Firefox",
+        ];
+
+        let arena = DesktopEntryArena::from_vec(entries);
+        let engine = SearchEngine::new(arena.clone(), false);
+        let mut rejected = 0;
+        for query in queries {
+            let query_lower = query.to_lowercase();
+            let query_mask = SearchKeys::char_mask(&query_lower);
+            for (entry, keys) in arena.iter_with_keys() {
+                if keys.may_match(query_mask) {
+                    continue;
+                }
+                rejected += 1;
+                assert_eq!(
+                    engine.calculate_fuzzy_score(entry, keys, &query_lower),
+                    0,
+                    "prefilter dropped {:?} for {:?}",
+                    entry.name,
+                    query
+                );
+            }
+        }
+        assert!(rejected > 0, "prefilter should reject something");
+
+        // Search results are unchanged for matching entries
+        let results = engine.search("vsc", 10);
+        assert_eq!(results[0].name, "Visual Studio Code");
+    }
 }
@@ -0,0 +1,87 @@
+//! Application details shown in place of the results (Ctrl+I)
+//!
+//! The rows are read-only: they carry no command, so activating one does
+//! nothing, and editing the query (or Ctrl+I again) brings the results back.
+//! Kept free of GTK so the gathered fields can be unit tested.
+
+use crate::desktop::DesktopEntry;
+use crate::plugins::PluginResult;
+
+/// Plugin name of the info rows, to tell them apart from real results
+pub const APP_INFO_SOURCE: &str = "app_info";
+
+/// Labelled details of `entry`, skipping fields the desktop file leaves empty
+pub fn app_info_fields(entry: &DesktopEntry) -> Vec<(&'static str, String)> {
+    let mut fields = vec![("Exec", entry.exec.clone())];
+    if !entry.categories.is_empty() {
+        fields.push(("Categories", entry.categories.join(", ")));
+    }
+    if !entry.mime_types.is_empty() {
+        fields.push(("MimeTypes", entry.mime_types.join(", ")));
+    }
+    if entry.terminal {
+        fields.push(("Terminal", "Runs in a terminal".to_string()));
+    }
+    fields.push(("Desktop file", entry.path.display().to_string()));
+    fields
+}
+
+/// Info rows for `entry`: the value as title, the field name as subtitle
+pub fn app_info_results(entry: &DesktopEntry) -> Vec<PluginResult> {
+    let icon = entry
+        .icon
+        .clone()
+        .unwrap_or_else(|| "dialog-information".to_string());
+
+    app_info_fields(entry)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, value))| {
+            PluginResult::new(value, String::new(), APP_INFO_SOURCE.to_string())
+                .with_subtitle(format!("{} • {}", entry.name, label))
+                .with_icon(icon.clone())
+                .with_score(1000 - i as i64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_info_gathers_entry_fields() {
+        let dir =
+            std::env::temp_dir().join(format!("native-launcher-app-info-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("org.gnome.eog.desktop");
+        fs::write(
+            &path,
+            "[Desktop Entry]\nType=Application\nName=Image Viewer\nExec=eog %U\n\
+             Icon=org.gnome.eog\nCategories=Graphics;Viewer;\n\
+             MimeType=image/png;image/jpeg;\n",
+        )
+        .unwrap();
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            app_info_fields(&entry),
+            vec![
+                ("Exec", "eog %U".to_string()),
+                ("Categories", "Graphics, Viewer".to_string()),
+                ("MimeTypes", "image/png, image/jpeg".to_string()),
+                ("Desktop file", path.display().to_string()),
+            ]
+        );
+
+        let results = app_info_results(&entry);
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|result| result.command.is_empty()));
+        assert_eq!(results[0].title, "eog %U");
+        assert_eq!(results[0].subtitle.as_deref(), Some("Image Viewer • Exec"));
+        assert_eq!(results[0].icon.as_deref(), Some("org.gnome.eog"));
+        assert!(results[0].score > results[3].score);
+    }
+}
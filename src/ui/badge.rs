@@ -0,0 +1,116 @@
+//! Provenance and pin badges shown at the end of result rows
+//!
+//! Plugins only say where a result comes from ([`ResultBadge`]) and the results
+//! list adds the pin; the icon, text and CSS class are picked here. Kept free of GTK so the mapping can be unit
+//! tested.
+
+use crate::plugins::{PluginResult, ResultBadge};
+
+/// How a badge is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadgeView {
+    /// Stable identifier, also the suffix of the `badge-*` CSS class
+    pub id: &'static str,
+    /// Text shown next to the icon
+    pub label: &'static str,
+    /// Symbolic icon name
+    pub icon: &'static str,
+}
+
+impl BadgeView {
+    /// CSS class for styling this badge on its own (`badge-bookmark`, ...)
+    pub fn css_class(&self) -> String {
+        format!("badge-{}", self.id)
+    }
+}
+
+/// Icon and text for a badge
+pub fn badge_view(badge: ResultBadge) -> BadgeView {
+    match badge {
+        ResultBadge::Flatpak => BadgeView {
+            id: "flatpak",
+            label: "Flatpak",
+            icon: "package-x-generic-symbolic",
+        },
+        ResultBadge::Bookmark => BadgeView {
+            id: "bookmark",
+            label: "Bookmark",
+            icon: "starred-symbolic",
+        },
+        ResultBadge::Running => BadgeView {
+            id: "running",
+            label: "Running",
+            icon: "media-playback-start-symbolic",
+        },
+        ResultBadge::Pinned => BadgeView {
+            id: "pinned",
+            label: "Pinned",
+            icon: "view-pin-symbolic",
+        },
+    }
+}
+
+/// Badges to draw for a result, in order; a pinned result leads with the pin
+pub fn result_badge_views(result: &PluginResult, pinned: bool) -> Vec<BadgeView> {
+    let mut badges = Vec::with_capacity(result.badges.len() + 1);
+    if pinned {
+        badges.push(ResultBadge::Pinned);
+    }
+    for &badge in &result.badges {
+        if !badges.contains(&badge) {
+            badges.push(badge);
+        }
+    }
+    badges.into_iter().map(badge_view).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bookmark_result_renders_bookmark_badge() {
+        let result = PluginResult::new(
+            "Rust".to_string(),
+            "xdg-open https://www.rust-lang.org".to_string(),
+            "browser".to_string(),
+        )
+        .with_badge(ResultBadge::Bookmark);
+
+        let views = result_badge_views(&result, false);
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].id, "bookmark");
+        assert_eq!(views[0].label, "Bookmark");
+        assert_eq!(views[0].css_class(), "badge-bookmark");
+
+        let plain = PluginResult::new("Rust".to_string(), "rust".to_string(), "x".to_string());
+        assert!(result_badge_views(&plain, false).is_empty());
+
+        // A running Flatpak app shows both, neither replacing the other
+        let app = plain
+            .with_badge(ResultBadge::Flatpak)
+            .with_badge(ResultBadge::Running)
+            .with_badge(ResultBadge::Flatpak);
+        let ids: Vec<_> = result_badge_views(&app, false)
+            .iter()
+            .map(|v| v.id)
+            .collect();
+        assert_eq!(ids, ["flatpak", "running"]);
+    }
+
+    #[test]
+    fn test_pinned_result_leads_with_pinned_badge() {
+        let result = PluginResult::new("Rust".to_string(), "rust".to_string(), "x".to_string())
+            .with_badge(ResultBadge::Flatpak);
+
+        let views = result_badge_views(&result, true);
+        let ids: Vec<_> = views.iter().map(|v| v.id).collect();
+        assert_eq!(ids, ["pinned", "flatpak"]);
+        assert_eq!(views[0].label, "Pinned");
+        assert_eq!(views[0].css_class(), "badge-pinned");
+
+        // Shown once even if a plugin already marked the result
+        let marked = result.with_badge(ResultBadge::Pinned);
+        assert_eq!(result_badge_views(&marked, true).len(), 2);
+    }
+}
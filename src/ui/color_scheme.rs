@@ -0,0 +1,205 @@
+//! System light/dark preference detection
+//!
+//! Reads `org.freedesktop.appearance color-scheme` from the XDG desktop portal,
+//! falling back to GNOME's `org.gnome.desktop.interface color-scheme` GSetting.
+//! Used to pick `ui.theme_light` / `ui.theme_dark` when `ui.auto_theme` is on.
+
+use crate::config::UIConfig;
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use tracing::{debug, info};
+
+const PORTAL_BUS_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_OBJECT_PATH: &str = "/org/freedesktop/portal/desktop";
+const PORTAL_SETTINGS_INTERFACE: &str = "org.freedesktop.portal.Settings";
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+
+/// System color-scheme preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    NoPreference,
+    Dark,
+    Light,
+}
+
+impl ColorScheme {
+    /// Map the portal's `color-scheme` value (0 = no preference, 1 = dark, 2 = light)
+    pub fn from_portal_value(value: u32) -> Self {
+        match value {
+            1 => Self::Dark,
+            2 => Self::Light,
+            _ => Self::NoPreference,
+        }
+    }
+
+    /// Map GNOME's `color-scheme` GSetting ("default", "prefer-dark", "prefer-light")
+    pub fn from_gsettings_value(value: &str) -> Self {
+        match value {
+            "prefer-dark" => Self::Dark,
+            "prefer-light" => Self::Light,
+            _ => Self::NoPreference,
+        }
+    }
+}
+
+/// Theme to apply for a detected scheme
+///
+/// With `auto_theme` off, or when the system has no preference, this is `ui.theme`.
+pub fn theme_for_scheme(ui: &UIConfig, scheme: ColorScheme) -> &str {
+    if !ui.auto_theme {
+        return &ui.theme;
+    }
+
+    match scheme {
+        ColorScheme::Dark => &ui.theme_dark,
+        ColorScheme::Light => &ui.theme_light,
+        ColorScheme::NoPreference => &ui.theme,
+    }
+}
+
+/// Theme to apply right now, detecting the system scheme only when `auto_theme` is on
+pub fn current_theme(ui: &UIConfig) -> &str {
+    if !ui.auto_theme {
+        return &ui.theme;
+    }
+
+    let scheme = detect_color_scheme();
+    info!("Detected system color scheme: {:?}", scheme);
+    theme_for_scheme(ui, scheme)
+}
+
+fn portal_proxy() -> Option<gio::DBusProxy> {
+    gio::DBusProxy::for_bus_sync(
+        gio::BusType::Session,
+        gio::DBusProxyFlags::DO_NOT_LOAD_PROPERTIES,
+        None::<&gio::DBusInterfaceInfo>,
+        PORTAL_BUS_NAME,
+        PORTAL_OBJECT_PATH,
+        PORTAL_SETTINGS_INTERFACE,
+        gio::Cancellable::NONE,
+    )
+    .map_err(|e| debug!("Settings portal unavailable: {}", e))
+    .ok()
+}
+
+/// Unwrap nested `v` variants down to the `u32` color-scheme value
+fn variant_to_scheme(value: &glib::Variant) -> Option<ColorScheme> {
+    let mut value = value.clone();
+    while let Some(inner) = value.as_variant() {
+        value = inner;
+    }
+    value.get::<u32>().map(ColorScheme::from_portal_value)
+}
+
+fn read_portal_scheme(proxy: &gio::DBusProxy) -> Option<ColorScheme> {
+    let args = (APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY).to_variant();
+
+    // ReadOne is the current API; older portals only implement the deprecated Read
+    ["ReadOne", "Read"].iter().find_map(|method| {
+        proxy
+            .call_sync(
+                method,
+                Some(&args),
+                gio::DBusCallFlags::NONE,
+                1000,
+                gio::Cancellable::NONE,
+            )
+            .map_err(|e| debug!("Portal {} failed: {}", method, e))
+            .ok()
+            .and_then(|reply| variant_to_scheme(&reply.child_value(0)))
+    })
+}
+
+fn read_gsettings_scheme() -> Option<ColorScheme> {
+    const SCHEMA: &str = "org.gnome.desktop.interface";
+
+    // Settings::new aborts on unknown schemas/keys, so check they exist first
+    let schema = gio::SettingsSchemaSource::default()?.lookup(SCHEMA, true)?;
+    if !schema.has_key(COLOR_SCHEME_KEY) {
+        return None;
+    }
+
+    let value = gio::Settings::new(SCHEMA).string(COLOR_SCHEME_KEY);
+    Some(ColorScheme::from_gsettings_value(&value))
+}
+
+/// Detect the system light/dark preference
+pub fn detect_color_scheme() -> ColorScheme {
+    portal_proxy()
+        .and_then(|proxy| read_portal_scheme(&proxy))
+        .or_else(read_gsettings_scheme)
+        .unwrap_or(ColorScheme::NoPreference)
+}
+
+/// Call `on_change` whenever the portal reports a new color scheme
+///
+/// Returns the portal proxy; keep it alive for as long as updates are wanted.
+pub fn watch_color_scheme<F: Fn(ColorScheme) + 'static>(on_change: F) -> Option<gio::DBusProxy> {
+    let proxy = portal_proxy()?;
+
+    proxy.connect_local("g-signal", false, move |args| {
+        let signal_name = args.get(2)?.get::<String>().ok()?;
+        let parameters = args.get(3)?.get::<glib::Variant>().ok()?;
+
+        // SettingChanged(namespace: s, key: s, value: v)
+        if signal_name == "SettingChanged" && parameters.n_children() == 3 {
+            let namespace = parameters.child_value(0).get::<String>();
+            let key = parameters.child_value(1).get::<String>();
+            if namespace.as_deref() == Some(APPEARANCE_NAMESPACE)
+                && key.as_deref() == Some(COLOR_SCHEME_KEY)
+            {
+                if let Some(scheme) = variant_to_scheme(&parameters.child_value(2)) {
+                    info!("System color scheme changed: {:?}", scheme);
+                    on_change(scheme);
+                }
+            }
+        }
+
+        None
+    });
+
+    Some(proxy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme_to_theme_mapping() {
+        let mut ui = UIConfig {
+            theme: "nord".to_string(),
+            theme_light: "light".to_string(),
+            theme_dark: "dracula".to_string(),
+            ..UIConfig::default()
+        };
+
+        // Auto disabled: always the single ui.theme
+        assert_eq!(theme_for_scheme(&ui, ColorScheme::Dark), "nord");
+        assert_eq!(theme_for_scheme(&ui, ColorScheme::Light), "nord");
+
+        ui.auto_theme = true;
+        assert_eq!(
+            theme_for_scheme(&ui, ColorScheme::from_portal_value(1)),
+            "dracula"
+        );
+        assert_eq!(
+            theme_for_scheme(&ui, ColorScheme::from_portal_value(2)),
+            "light"
+        );
+        assert_eq!(
+            theme_for_scheme(&ui, ColorScheme::from_portal_value(0)),
+            "nord"
+        );
+        assert_eq!(
+            theme_for_scheme(&ui, ColorScheme::from_gsettings_value("prefer-dark")),
+            "dracula"
+        );
+        assert_eq!(
+            theme_for_scheme(&ui, ColorScheme::from_gsettings_value("default")),
+            "nord"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Two-step activation for destructive results
+//!
+//! Results carrying a `confirm` prompt run only when activated twice in a row
+//! with the same key; the first activation arms the gate and shows the prompt on
+//! the result's row.
+//! Kept free of GTK so the state machine can be unit tested.
+
+/// What activating a result should do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Activation {
+    /// Run the result's command
+    Run,
+    /// Show this confirmation text and wait for another activation
+    Confirm(String),
+}
+
+/// Remembers which result (and key) is waiting for its confirming activation
+#[derive(Debug, Default)]
+pub struct ConfirmGate {
+    armed: Option<(String, String)>,
+}
+
+impl ConfirmGate {
+    /// Activate the result running `command` with `key` (`"Enter"`,
+    /// `"Ctrl+Shift+R"`, ...); `prompt` is its `confirm` text
+    ///
+    /// Only the same key confirms, so a result armed for running as root can't
+    /// be confirmed by a plain Enter and the other way round.
+    pub fn activate(&mut self, command: &str, prompt: Option<&str>, key: &str) -> Activation {
+        let Some(prompt) = prompt else {
+            self.armed = None;
+            return Activation::Run;
+        };
+
+        let pending = (command.to_string(), key.to_string());
+        if self.armed.as_ref() == Some(&pending) {
+            self.armed = None;
+            return Activation::Run;
+        }
+
+        self.armed = Some(pending);
+        Activation::Confirm(format!("Press {} again to confirm: {}", key, prompt))
+    }
+
+    /// Forget a pending confirmation (selection or results changed)
+    pub fn reset(&mut self) {
+        self.armed = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_flagged_result_needs_two_activations() {
+        let mut gate = ConfirmGate::default();
+        let prompt = Some("close the active window");
+
+        assert_eq!(
+            gate.activate("hyprctl dispatch killactive", prompt, "Enter"),
+            Activation::Confirm("Press Enter again to confirm: close the active window".into())
+        );
+        assert!(gate.armed.is_some());
+        assert_eq!(
+            gate.activate("hyprctl dispatch killactive", prompt, "Enter"),
+            Activation::Run
+        );
+        assert!(gate.armed.is_none());
+
+        // Confirming one result doesn't confirm another
+        gate.activate(
+            "trash-put ~/notes.txt",
+            Some("move notes.txt to the trash"),
+            "Enter",
+        );
+        assert!(matches!(
+            gate.activate("hyprctl dispatch killactive", prompt, "Enter"),
+            Activation::Confirm(_)
+        ));
+
+        // Moving the selection disarms the gate
+        gate.reset();
+        assert!(matches!(
+            gate.activate("hyprctl dispatch killactive", prompt, "Enter"),
+            Activation::Confirm(_)
+        ));
+
+        // Results without a prompt always run
+        assert_eq!(gate.activate("firefox", None, "Enter"), Activation::Run);
+    }
+
+    #[test]
+    fn test_confirm_flagged_result_not_run_elevated_on_first_activation() {
+        let mut gate = ConfirmGate::default();
+        let prompt = Some("recursively delete your home directory");
+
+        assert_eq!(
+            gate.activate("rm -rf ~", prompt, "Ctrl+Shift+R"),
+            Activation::Confirm(
+                "Press Ctrl+Shift+R again to confirm: recursively delete your home directory"
+                    .into()
+            )
+        );
+        // A different key neither runs nor confirms it
+        assert!(matches!(
+            gate.activate("rm -rf ~", prompt, "Enter"),
+            Activation::Confirm(_)
+        ));
+        assert!(matches!(
+            gate.activate("rm -rf ~", prompt, "Ctrl+Shift+R"),
+            Activation::Confirm(_)
+        ));
+        assert_eq!(
+            gate.activate("rm -rf ~", prompt, "Ctrl+Shift+R"),
+            Activation::Run
+        );
+    }
+}
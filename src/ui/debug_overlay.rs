@@ -0,0 +1,69 @@
+use crate::plugins::PluginTiming;
+use gtk4::prelude::*;
+use gtk4::{Align, Label};
+
+/// Environment variable that shows the overlay from startup
+const DEBUG_ENV_VAR: &str = "NL_DEBUG";
+
+/// Per-plugin search timings, for diagnosing slow searches (F12 or `NL_DEBUG=1`)
+///
+/// Lists each plugin's last and average search time and last result count,
+/// slowest first. Hidden unless toggled on; `update` is cheap while hidden.
+#[derive(Clone)]
+pub struct DebugOverlay {
+    pub label: Label,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        let label = Label::new(None);
+        label.add_css_class("debug-overlay");
+        label.set_halign(Align::Start);
+        label.set_xalign(0.0);
+        label.set_margin_start(16);
+        label.set_margin_end(16);
+        label.set_selectable(true);
+
+        let enabled = matches!(
+            std::env::var(DEBUG_ENV_VAR).ok().as_deref().map(str::trim),
+            Some("1") | Some("true") | Some("yes")
+        );
+        label.set_visible(enabled);
+
+        Self { label }
+    }
+
+    /// Whether the overlay is currently shown
+    pub fn is_visible(&self) -> bool {
+        self.label.is_visible()
+    }
+
+    /// Show or hide the overlay
+    pub fn toggle(&self) {
+        self.label.set_visible(!self.label.is_visible());
+    }
+
+    /// Redraw the timings; skipped while hidden
+    pub fn update(&self, timings: &[PluginTiming]) {
+        if !self.is_visible() {
+            return;
+        }
+
+        let text = if timings.is_empty() {
+            "No plugins searched yet".to_string()
+        } else {
+            timings
+                .iter()
+                .map(PluginTiming::summary)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        self.label.set_text(&text);
+    }
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,58 @@
+//! Closing the launcher when it loses focus (`window.hide_on_focus_loss`)
+//!
+//! Clicking elsewhere deactivates the window, which then closes like on
+//! Escape (in daemon mode closing only hides it). Kept free of GTK so the
+//! decision can be unit tested.
+
+/// Window state after its active (focused) flag changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusChange {
+    /// Whether the window is now the active one
+    pub is_active: bool,
+    /// Whether the window is shown; hiding it also deactivates it
+    pub is_visible: bool,
+    /// Whether one of the launcher's own windows (a dialog transient for it)
+    /// is open and took the focus
+    pub has_child_window: bool,
+}
+
+impl FocusChange {
+    /// Whether the launcher should close in response to this change
+    pub fn should_close(&self) -> bool {
+        !self.is_active && self.is_visible && !self.has_child_window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closes_only_when_focus_moves_elsewhere() {
+        let lost = FocusChange {
+            is_active: false,
+            is_visible: true,
+            has_child_window: false,
+        };
+        assert!(lost.should_close());
+
+        // Gaining focus, or losing it to our own dialog, keeps the window open
+        assert!(!FocusChange {
+            is_active: true,
+            ..lost
+        }
+        .should_close());
+        assert!(!FocusChange {
+            has_child_window: true,
+            ..lost
+        }
+        .should_close());
+
+        // Already hidden (e.g. closed via Escape, which deactivates it too)
+        assert!(!FocusChange {
+            is_visible: false,
+            ..lost
+        }
+        .should_close());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::tr;
 use gtk4::prelude::*;
 use gtk4::{Box as GtkBox, Label, Orientation};
 
@@ -32,34 +33,49 @@ impl KeyboardHints {
     }
 
     fn get_default_hints() -> String {
-        String::from(
-            "<span size='small' alpha='60%'>\
-            <b>↑↓</b> Navigate  •  \
-            <b>↵</b> Launch  •  \
-            <b>Alt+↵</b> Folder  •  \
-            <b>Ctrl+↵</b> Copy Path  •  \
-            <b>Ctrl+P</b> Pin  •  \
-            <b>ESC</b> Close\
-            </span>",
-        )
+        Self::hints_markup(&[
+            ("↑↓", tr!("Navigate")),
+            ("↵", tr!("Launch")),
+            ("Alt+↵", tr!("Folder")),
+            ("Ctrl+↵", tr!("Copy Path")),
+            ("Ctrl+P", tr!("Pin")),
+            ("Ctrl+Shift+C", tr!("Copy Command")),
+            ("ESC", tr!("Close")),
+        ])
     }
 
     #[allow(dead_code)]
 
     fn get_action_mode_hints() -> String {
-        String::from(
-            "<span size='small' alpha='60%'>\
-            <b>↑↓</b> Navigate  •  \
-            <b>↵</b> Run Action  •  \
-            <b>Ctrl+1</b> First  •  \
-            <b>Ctrl+P</b> Pin  •  \
-            <b>Ctrl+Enter</b> Web  •  \
-            <b>←</b> Back  •  \
-            <b>ESC</b> Close\
-            </span>",
+        Self::hints_markup(&[
+            ("↑↓", tr!("Navigate")),
+            ("↵", tr!("Run Action")),
+            ("Ctrl+1", tr!("First")),
+            ("Ctrl+P", tr!("Pin")),
+            ("Ctrl+Enter", tr!("Web")),
+            ("←", tr!("Back")),
+            ("ESC", tr!("Close")),
+        ])
+    }
+
+    /// `<b>key</b> label` pairs separated by bullets, in the hint style
+    fn hints_markup(hints: &[(&str, &str)]) -> String {
+        let hints: Vec<String> = hints
+            .iter()
+            .map(|(key, label)| format!("<b>{}</b> {}", key, gtk4::glib::markup_escape_text(label)))
+            .collect();
+        format!(
+            "<span size='small' alpha='60%'>{}</span>",
+            hints.join("  •  ")
         )
     }
 
+    /// Hint text as displayed, without markup
+    #[allow(dead_code)]
+    pub fn text(&self) -> String {
+        self.hint_label.text().to_string()
+    }
+
     /// Update hints based on context
     #[allow(dead_code)]
 
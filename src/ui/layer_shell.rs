@@ -0,0 +1,135 @@
+//! Layer-shell placement of the launcher window (`window.layer_shell`)
+//!
+//! On wlroots-style compositors the window is a layer surface: drawn above
+//! other windows, anchored by `window.position` and holding keyboard focus.
+//! This module only maps the config to those parameters; `LauncherWindow`
+//! applies them. Kept free of GTK so the mapping can be unit tested.
+
+use crate::config::WindowConfig;
+
+/// Gap between the anchored screen edge and the window, in pixels
+pub const EDGE_MARGIN: i32 = 120;
+
+/// Stacking layer of the surface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellLayer {
+    /// Above normal windows, below fullscreen ones
+    Top,
+    /// Above everything, fullscreen windows included
+    Overlay,
+}
+
+/// When the surface receives keyboard input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardInteractivity {
+    /// Grab the keyboard while shown
+    Exclusive,
+    /// Take focus like a normal window, so clicking elsewhere moves it away
+    OnDemand,
+}
+
+/// Screen edge the surface sticks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellAnchor {
+    Top,
+    /// No edge: the compositor centers the surface
+    Center,
+    Bottom,
+}
+
+/// Layer-shell parameters derived from `[window]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayerShellSettings {
+    pub layer: ShellLayer,
+    pub keyboard: KeyboardInteractivity,
+    pub anchor: ShellAnchor,
+    /// Distance from the anchored edge (0 when centered)
+    pub margin: i32,
+}
+
+impl LayerShellSettings {
+    /// Parameters for `config`, or `None` when `window.layer_shell` is off
+    ///
+    /// Unknown values fall back to the overlay layer, an exclusive keyboard
+    /// grab and a centered window.
+    pub fn from_config(config: &WindowConfig) -> Option<Self> {
+        if !config.layer_shell {
+            return None;
+        }
+
+        let layer = match config.layer.as_str() {
+            "top" => ShellLayer::Top,
+            _ => ShellLayer::Overlay,
+        };
+        let keyboard = match config.keyboard_mode.as_str() {
+            "on_demand" => KeyboardInteractivity::OnDemand,
+            _ => KeyboardInteractivity::Exclusive,
+        };
+        let anchor = match config.position.as_str() {
+            "top" => ShellAnchor::Top,
+            "bottom" => ShellAnchor::Bottom,
+            _ => ShellAnchor::Center,
+        };
+        let margin = match anchor {
+            ShellAnchor::Center => 0,
+            _ => EDGE_MARGIN,
+        };
+
+        Some(Self {
+            layer,
+            keyboard,
+            anchor,
+            margin,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_config_maps_to_layer_shell() {
+        let defaults = LayerShellSettings::from_config(&WindowConfig::default()).unwrap();
+        assert_eq!(
+            defaults,
+            LayerShellSettings {
+                layer: ShellLayer::Overlay,
+                keyboard: KeyboardInteractivity::Exclusive,
+                anchor: ShellAnchor::Center,
+                margin: 0,
+            }
+        );
+
+        let config = WindowConfig {
+            position: "top".to_string(),
+            layer: "top".to_string(),
+            keyboard_mode: "on_demand".to_string(),
+            ..WindowConfig::default()
+        };
+        assert_eq!(
+            LayerShellSettings::from_config(&config),
+            Some(LayerShellSettings {
+                layer: ShellLayer::Top,
+                keyboard: KeyboardInteractivity::OnDemand,
+                anchor: ShellAnchor::Top,
+                margin: EDGE_MARGIN,
+            })
+        );
+
+        let config = WindowConfig {
+            position: "bottom".to_string(),
+            layer: "sideways".to_string(),
+            ..WindowConfig::default()
+        };
+        let settings = LayerShellSettings::from_config(&config).unwrap();
+        assert_eq!(settings.anchor, ShellAnchor::Bottom);
+        assert_eq!(settings.layer, ShellLayer::Overlay);
+
+        let config = WindowConfig {
+            layer_shell: false,
+            ..WindowConfig::default()
+        };
+        assert_eq!(LayerShellSettings::from_config(&config), None);
+    }
+}
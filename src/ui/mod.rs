@@ -1,12 +1,25 @@
+pub mod app_info;
+pub mod badge;
+pub mod color_scheme;
+pub mod confirm;
+pub mod debug_overlay;
+pub mod focus;
 pub mod highlight;
 pub mod keyboard_hints;
+pub mod layer_shell;
+pub mod navigation;
 pub mod results_list;
 pub mod search_entry;
+pub mod status_bar;
 pub mod theme;
+pub mod truncate;
 pub mod window;
 
+pub use debug_overlay::DebugOverlay;
 pub use keyboard_hints::KeyboardHints;
+pub use navigation::Navigation;
 pub use results_list::ResultsList;
 pub use search_entry::SearchWidget;
+pub use status_bar::StatusBar;
 pub use theme::load_theme_with_name;
 pub use window::LauncherWindow;
@@ -0,0 +1,105 @@
+//! Keyboard navigation in the results list
+//!
+//! The index math is kept free of GTK so boundary behaviour can be unit tested;
+//! `ResultsList::navigate` applies the result to the list box.
+
+/// A keyboard movement through the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Navigation {
+    /// Down arrow
+    Next,
+    /// Up arrow
+    Previous,
+    /// Home
+    First,
+    /// End
+    Last,
+    /// Page Down: one visible page further
+    PageDown,
+    /// Page Up: one visible page back
+    PageUp,
+}
+
+/// Index to select after `navigation` from `current` in a list of `len` rows
+///
+/// `page` is the number of fully visible rows. With `wrap`, Next on the last row
+/// selects the first and Previous on the first selects the last; page moves
+/// always stop at the ends. Returns `None` when the selection doesn't change.
+pub fn target_index(
+    current: Option<usize>,
+    len: usize,
+    navigation: Navigation,
+    page: usize,
+    wrap: bool,
+) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let last = len - 1;
+    let page = page.max(1);
+
+    let target = match (navigation, current) {
+        (Navigation::First, _) => 0,
+        (Navigation::Last, _) => last,
+        // Nothing selected yet: any other move starts at the top
+        (_, None) => 0,
+        (Navigation::Next, Some(i)) if i >= last => {
+            if wrap {
+                0
+            } else {
+                last
+            }
+        }
+        (Navigation::Next, Some(i)) => i + 1,
+        (Navigation::Previous, Some(0)) => {
+            if wrap {
+                last
+            } else {
+                0
+            }
+        }
+        (Navigation::Previous, Some(i)) => (i - 1).min(last),
+        (Navigation::PageDown, Some(i)) => (i + page).min(last),
+        (Navigation::PageUp, Some(i)) => i.saturating_sub(page).min(last),
+    };
+
+    (Some(target) != current).then_some(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_index_at_boundaries() {
+        use Navigation::*;
+
+        // Without wrapping the ends are sticky
+        assert_eq!(target_index(Some(4), 5, Next, 3, false), None);
+        assert_eq!(target_index(Some(0), 5, Previous, 3, false), None);
+        assert_eq!(target_index(Some(3), 5, Next, 3, false), Some(4));
+        assert_eq!(target_index(Some(1), 5, Previous, 3, false), Some(0));
+
+        // With wrapping they loop around
+        assert_eq!(target_index(Some(4), 5, Next, 3, true), Some(0));
+        assert_eq!(target_index(Some(0), 5, Previous, 3, true), Some(4));
+        assert_eq!(target_index(Some(2), 5, Next, 3, true), Some(3));
+
+        // Home/End jump regardless of wrapping
+        assert_eq!(target_index(Some(3), 5, First, 3, true), Some(0));
+        assert_eq!(target_index(Some(1), 5, Last, 3, false), Some(4));
+        assert_eq!(target_index(Some(0), 5, First, 3, false), None);
+
+        // Pages clamp to the ends and never wrap
+        assert_eq!(target_index(Some(0), 10, PageDown, 4, true), Some(4));
+        assert_eq!(target_index(Some(8), 10, PageDown, 4, true), Some(9));
+        assert_eq!(target_index(Some(9), 10, PageDown, 4, true), None);
+        assert_eq!(target_index(Some(2), 10, PageUp, 4, true), Some(0));
+        assert_eq!(target_index(Some(0), 10, PageDown, 0, false), Some(1));
+
+        // Single row, empty list and no selection
+        assert_eq!(target_index(Some(0), 1, Next, 3, true), None);
+        assert_eq!(target_index(Some(0), 0, Next, 3, true), None);
+        assert_eq!(target_index(None, 5, Previous, 3, false), Some(0));
+    }
+}
@@ -1,15 +1,18 @@
 use crate::desktop::{DesktopAction, DesktopEntry};
 use crate::pins::PinsStore;
-use crate::plugins::PluginResult;
+use crate::plugins::{PluginResult, ResultAction, ResultBadge};
+use crate::ui::badge::{badge_view, result_badge_views, BadgeView};
+use crate::ui::confirm::{Activation, ConfirmGate};
 use crate::ui::highlight::apply_highlight;
-use crate::utils::icons::resolve_icon;
+use crate::ui::navigation::{self, Navigation};
+use crate::ui::truncate::{truncate, EllipsisMode};
+use crate::utils::expand_exec;
+use crate::utils::icons::{pixbuf_from_png, resolve_icon_in_background};
 use gtk4::prelude::*;
 use gtk4::{
-    pango::EllipsizeMode, Align, Box as GtkBox, Image, Label, ListBox, Orientation, Overlay,
-    ScrolledWindow,
+    pango::EllipsizeMode, Align, Box as GtkBox, Image, Label, ListBox, Orientation, ScrolledWindow,
 };
-use std::cell::RefCell;
-use std::path::PathBuf;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use tracing::{debug, info};
 
@@ -28,6 +31,11 @@ enum ListItem {
     },
     /// A plugin result (from plugin system) - includes workspaces, files, etc.
     PluginResult { result: PluginResult },
+    /// An entry of a result's actions menu; stands in for `source` when selected
+    ResultAction {
+        action: ResultAction,
+        source: PluginResult,
+    },
 }
 
 /// Results list widget
@@ -41,8 +49,29 @@ pub struct ResultsList {
     pins: Rc<RefCell<Option<std::sync::Arc<PinsStore>>>>,
     /// Hash of current results for fast change detection (optimization)
     results_hash: Rc<RefCell<u64>>,
+    /// Up/Down wrap around at the ends of the list (`ui.wrap_navigation`)
+    wrap_navigation: Rc<Cell<bool>>,
+    /// Select the top result whenever the results change (`ui.auto_select_first`)
+    auto_select_first: Rc<Cell<bool>>,
+    /// Icon size of result rows in pixels (`ui.icon_size`)
+    icon_size: Rc<Cell<i32>>,
+    /// Extra vertical space around each row in pixels (`ui.row_spacing`)
+    row_spacing: Rc<Cell<i32>>,
+    /// Longest title and subtitle shown, with the ellipsis placement
+    /// (`ui.max_title_length`, `ui.max_subtitle_length`, `ui.ellipsis`)
+    text_limits: Rc<Cell<(usize, usize, EllipsisMode)>>,
+    /// Result waiting for a second activation before its command runs
+    confirm: Rc<RefCell<ConfirmGate>>,
+    /// Row currently showing a confirmation prompt
+    confirm_row: Rc<Cell<Option<i32>>>,
 }
 
+/// Default icon size of result rows
+const DEFAULT_ICON_SIZE: i32 = 48;
+
+/// Row height beyond the icon (CSS padding), used to estimate row height
+const ROW_PADDING: i32 = 12;
+
 impl ResultsList {
     pub fn new() -> Self {
         let list = ListBox::builder()
@@ -72,9 +101,62 @@ impl ResultsList {
             current_query: Rc::new(RefCell::new(String::new())),
             pins: Rc::new(RefCell::new(None)),
             results_hash: Rc::new(RefCell::new(0)),
+            wrap_navigation: Rc::new(Cell::new(false)),
+            auto_select_first: Rc::new(Cell::new(true)),
+            icon_size: Rc::new(Cell::new(DEFAULT_ICON_SIZE)),
+            row_spacing: Rc::new(Cell::new(0)),
+            text_limits: Rc::new(Cell::new((0, 0, EllipsisMode::Auto))),
+            confirm: Rc::new(RefCell::new(ConfirmGate::default())),
+            confirm_row: Rc::new(Cell::new(None)),
         }
     }
 
+    /// Icon size for rows built from now on
+    pub fn set_icon_size(&self, size: i32) {
+        self.icon_size.set(size);
+    }
+
+    /// Extra vertical space around rows built from now on
+    pub fn set_row_spacing(&self, spacing: i32) {
+        self.row_spacing.set(spacing);
+    }
+
+    /// Shorten titles and subtitles of rows built from now on; 0 means no limit
+    pub fn set_text_limits(&self, max_title: usize, max_subtitle: usize, mode: EllipsisMode) {
+        self.text_limits.set((max_title, max_subtitle, mode));
+    }
+
+    /// Icon size of linked entries (workspaces, recent files): two thirds of the app icon
+    fn linked_icon_size(&self) -> i32 {
+        self.icon_size.get() * 2 / 3
+    }
+
+    /// Approximate row height, used for scrolling and page size
+    fn row_height(&self) -> f64 {
+        f64::from(self.icon_size.get() + ROW_PADDING + self.row_spacing.get())
+    }
+
+    /// Apply `row_spacing` as margins above and below a row's content
+    fn apply_row_spacing(&self, row: &GtkBox) {
+        let spacing = self.row_spacing.get();
+        row.set_margin_top(spacing / 2);
+        row.set_margin_bottom(spacing - spacing / 2);
+    }
+
+    /// Let Up/Down wrap from the last result to the first and back
+    pub fn set_wrap_navigation(&self, wrap: bool) {
+        self.wrap_navigation.set(wrap);
+    }
+
+    /// Select the top result after each results update, so plain Enter runs it
+    ///
+    /// Only changed results re-select: moving the selection by keyboard or mouse
+    /// sticks until the results change. When off, nothing is selected until the
+    /// user navigates.
+    pub fn set_auto_select_first(&self, auto_select: bool) {
+        self.auto_select_first.set(auto_select);
+    }
+
     /// Update the query used for highlighting matches
     pub fn set_query(&self, query: &str) {
         *self.current_query.borrow_mut() = query.to_string();
@@ -164,7 +246,7 @@ impl ResultsList {
             self.render_single_item(item);
         }
 
-        if was_empty {
+        if was_empty && self.auto_select_first.get() {
             if let Some(first_row) = self.list.first_child() {
                 if let Some(row) = first_row.downcast_ref::<gtk4::ListBoxRow>() {
                     self.list.select_row(Some(row));
@@ -179,6 +261,8 @@ impl ResultsList {
 
         // Store items for later use (e.g., getting selected command)
         *self.items.borrow_mut() = items;
+        self.confirm.borrow_mut().reset();
+        self.confirm_row.set(None);
 
         // Clear existing items from UI
         while let Some(child) = self.list.first_child() {
@@ -193,7 +277,9 @@ impl ResultsList {
         }
 
         // Select first row if available
-        if let Some(first_row) = self.list.first_child() {
+        if !self.auto_select_first.get() {
+            self.list.unselect_all();
+        } else if let Some(first_row) = self.list.first_child() {
             if let Some(row) = first_row.downcast_ref::<gtk4::ListBoxRow>() {
                 self.list.select_row(Some(row));
             }
@@ -206,6 +292,9 @@ impl ResultsList {
             ListItem::App { entry } => self.create_result_row(entry),
             ListItem::Action { action, .. } => self.create_action_row(action),
             ListItem::PluginResult { result } => self.create_plugin_result_row(result),
+            ListItem::ResultAction { action, source } => {
+                self.create_plugin_result_row(&action.menu_row(source))
+            }
         };
 
         // Create ListBoxRow and set the child
@@ -230,12 +319,15 @@ impl ResultsList {
 
         items_ref.get(selected_index).map(|item| {
             let (cmd, term) = match item {
-                ListItem::App { entry } => (entry.exec.clone(), entry.terminal),
+                ListItem::App { entry } => (expand_exec(&entry.exec, &[]), entry.terminal),
                 ListItem::Action {
                     action,
                     parent_entry,
-                } => (action.exec.clone(), parent_entry.terminal),
-                ListItem::PluginResult { result } => (result.command.clone(), result.terminal),
+                } => (expand_exec(&action.exec, &[]), parent_entry.terminal),
+                ListItem::PluginResult { result }
+                | ListItem::ResultAction { source: result, .. } => {
+                    (result.command.clone(), result.terminal)
+                }
             };
             (cmd, term)
         })
@@ -260,7 +352,75 @@ impl ResultsList {
                 Some(parent_entry.path.to_string_lossy().to_string())
             }
             // Plugin results may have desktop paths when representing apps
-            ListItem::PluginResult { result } => result.desktop_path.clone(),
+            ListItem::PluginResult { result } | ListItem::ResultAction { source: result, .. } => {
+                result.desktop_path.clone()
+            }
+        })
+    }
+
+    /// Get a copy of the currently selected plugin result (if any)
+    pub fn get_selected_result(&self) -> Option<PluginResult> {
+        let items_ref = self.items.borrow();
+        let selected_index = self.selected_index().map(|i| i as usize).unwrap_or(0);
+
+        match items_ref.get(selected_index) {
+            Some(
+                ListItem::PluginResult { result } | ListItem::ResultAction { source: result, .. },
+            ) => Some(result.clone()),
+            _ => None,
+        }
+    }
+
+    /// Actions menu entry under the selection and the result it acts on
+    pub fn get_selected_result_action(&self) -> Option<(ResultAction, PluginResult)> {
+        let items_ref = self.items.borrow();
+        let selected_index = self.selected_index().map(|i| i as usize).unwrap_or(0);
+
+        match items_ref.get(selected_index) {
+            Some(ListItem::ResultAction { action, source }) => Some((*action, source.clone())),
+            _ => None,
+        }
+    }
+
+    /// Replace the results with the selected result's actions menu
+    ///
+    /// Returns false (leaving the list alone) when nothing is selected, the
+    /// selection is already a menu entry, or the result offers no actions.
+    pub fn show_result_actions(&self) -> bool {
+        let source = {
+            let items_ref = self.items.borrow();
+            let selected_index = self.selected_index().map(|i| i as usize).unwrap_or(0);
+            match items_ref.get(selected_index) {
+                Some(ListItem::PluginResult { result }) => result.clone(),
+                _ => return false,
+            }
+        };
+
+        let actions = source.action_menu();
+        if actions.is_empty() {
+            return false;
+        }
+        debug!("Actions for {}: {:?}", source.title, actions);
+
+        let items = actions
+            .into_iter()
+            .map(|action| ListItem::ResultAction {
+                action,
+                source: source.clone(),
+            })
+            .collect();
+        // Searching again must rebuild the list even if the results are the same
+        *self.results_hash.borrow_mut() = 0;
+        self.render_items(items);
+        true
+    }
+
+    /// Get the pin key of the selected item: desktop file path for apps, command otherwise
+    pub fn get_selected_pin_key(&self) -> Option<String> {
+        self.get_selected_path().or_else(|| {
+            self.get_selected_result()
+                .map(|result| result.command)
+                .filter(|command| !command.is_empty())
         })
     }
 
@@ -278,17 +438,24 @@ impl ResultsList {
             .min(items_ref.len().saturating_sub(1));
 
         match items_ref.get(selected_index) {
-            Some(ListItem::PluginResult { result }) => Some(result.plugin_name.clone()),
+            Some(
+                ListItem::PluginResult { result } | ListItem::ResultAction { source: result, .. },
+            ) => Some(result.plugin_name.clone()),
             _ => None,
         }
     }
 
-    /// Create an icon placeholder box for alignment
-    fn create_icon_placeholder(&self, size: i32) -> GtkBox {
-        GtkBox::builder()
-            .width_request(size)
-            .height_request(size)
-            .build()
+    /// Get the command of the selected item if it runs a program: apps, their
+    /// actions and shell commands, not calculator, copy or other internal results
+    pub fn get_selected_executable_command(&self) -> Option<(String, bool)> {
+        let executable = self.get_selected_path().is_some()
+            || self.get_selected_plugin_name().as_deref() == Some("shell");
+        if !executable {
+            return None;
+        }
+
+        self.get_selected_command()
+            .filter(|(exec, _)| !exec.is_empty() && !exec.starts_with('@'))
     }
 
     /// Create a row for a desktop action
@@ -302,6 +469,7 @@ impl ResultsList {
             .margin_end(0)
             .build();
         row.add_css_class("inline-action-row");
+        self.apply_row_spacing(&row);
 
         let content_box = GtkBox::builder()
             .orientation(Orientation::Vertical)
@@ -340,6 +508,7 @@ impl ResultsList {
             .margin_start(if is_linked_entry { 8 } else { 0 }) // Subtle indent for linked entries
             .margin_end(0)
             .build();
+        self.apply_row_spacing(&row);
 
         if is_linked_entry {
             row.add_css_class("inline-action-row");
@@ -347,7 +516,11 @@ impl ResultsList {
         }
 
         // Add icon (emoji or standard icon with fallback)
-        let icon_size = if is_linked_entry { 32 } else { 48 };
+        let icon_size = if is_linked_entry {
+            self.linked_icon_size()
+        } else {
+            self.icon_size.get()
+        };
         let icon_widget: gtk4::Widget = {
             // Special-case: emoji icon marker
             let emoji_widget: Option<gtk4::Widget> = if let Some(icon_str) = result.icon.as_deref()
@@ -373,65 +546,23 @@ impl ResultsList {
 
             if let Some(widget) = emoji_widget {
                 widget
-            } else if let Some(icon_path) = Self::resolve_plugin_icon(result).or_else(|| {
-                use crate::utils::icons::get_default_icon;
-                Some(get_default_icon())
-            }) {
-                let image = Image::from_file(&icon_path);
+            } else {
+                // Inline PNG bytes from a plugin take precedence over its icon name
+                let inline_icon = result.icon_data.as_deref().and_then(|data| {
+                    pixbuf_from_png(data, icon_size)
+                        .map_err(|e| debug!("Ignoring inline icon of {}: {}", result.title, e))
+                        .ok()
+                });
+                let image = match inline_icon {
+                    Some(pixbuf) => Image::from_pixbuf(Some(&pixbuf)),
+                    None => Self::themed_image(Self::plugin_icon_candidates(result), icon_size),
+                };
                 image.set_pixel_size(icon_size);
                 image.add_css_class("app-icon");
                 if is_linked_entry {
                     image.add_css_class("workspace-icon");
                 }
-
-                // Wrap in overlay if pinned
-                if let Some(path) = result.desktop_path.as_ref() {
-                    if let Some(pins) = &*self.pins.borrow() {
-                        if pins.is_pinned(path) {
-                            let overlay = Overlay::new();
-                            overlay.set_child(Some(&image));
-                            let star = Label::new(Some("★"));
-                            star.add_css_class("pinned-star");
-                            star.set_halign(Align::End);
-                            star.set_valign(Align::Start);
-                            star.set_margin_end(2);
-                            star.set_margin_top(2);
-                            overlay.add_overlay(&star);
-                            overlay.upcast()
-                        } else {
-                            image.upcast()
-                        }
-                    } else {
-                        image.upcast()
-                    }
-                } else {
-                    image.upcast()
-                }
-            } else {
-                // Placeholder box (no icon found)
-                let placeholder = self.create_icon_placeholder(icon_size);
-                if let Some(path) = result.desktop_path.as_ref() {
-                    if let Some(pins) = &*self.pins.borrow() {
-                        if pins.is_pinned(path) {
-                            let overlay = Overlay::new();
-                            overlay.set_child(Some(&placeholder));
-                            let star = Label::new(Some("★"));
-                            star.add_css_class("pinned-star");
-                            star.set_halign(Align::End);
-                            star.set_valign(Align::Start);
-                            star.set_margin_end(2);
-                            star.set_margin_top(2);
-                            overlay.add_overlay(&star);
-                            overlay.upcast()
-                        } else {
-                            placeholder.upcast()
-                        }
-                    } else {
-                        placeholder.upcast()
-                    }
-                } else {
-                    placeholder.upcast()
-                }
+                image.upcast()
             }
         };
         row.append(&icon_widget);
@@ -450,7 +581,9 @@ impl ResultsList {
             .build();
 
         // Title
-        let name_markup = apply_highlight(&result.title, &self.current_query.borrow());
+        let (max_title, max_subtitle, ellipsis) = self.text_limits.get();
+        let title = truncate(&result.title, max_title, ellipsis);
+        let name_markup = apply_highlight(&title, &self.current_query.borrow());
         let name_label = Label::builder()
             .use_markup(true)
             .label(&name_markup)
@@ -474,11 +607,17 @@ impl ResultsList {
             title_row.append(&badge_icon);
         }
 
+        // Pin and provenance badges (Flatpak, bookmark, running)
+        for view in result_badge_views(result, self.is_result_pinned(result)) {
+            title_row.append(&Self::badge_widget(view));
+        }
+
         content_box.append(&title_row);
 
         // Subtitle (if available)
         if let Some(ref subtitle) = result.subtitle {
-            let subtitle_markup = apply_highlight(subtitle, &self.current_query.borrow());
+            let subtitle = truncate(subtitle, max_subtitle, ellipsis);
+            let subtitle_markup = apply_highlight(&subtitle, &self.current_query.borrow());
             let subtitle_label = Label::builder()
                 .use_markup(true)
                 .label(&subtitle_markup)
@@ -507,67 +646,14 @@ impl ResultsList {
             .margin_start(0)
             .margin_end(0)
             .build();
+        self.apply_row_spacing(&row);
 
         // Add icon with fallback to default
-        let icon_path = entry
-            .icon
-            .as_ref()
-            .and_then(|name| resolve_icon(name))
-            .or_else(|| {
-                use crate::utils::icons::get_default_icon;
-                Some(get_default_icon())
-            });
+        let icon_size = self.icon_size.get();
 
-        // Icon with optional pin overlay
-        let icon_widget: gtk4::Widget = {
-            if let Some(icon_path) = icon_path {
-                let image = Image::from_file(&icon_path);
-                image.set_pixel_size(48);
-                image.add_css_class("app-icon");
-                // Check pin state
-                if let Some(pins) = &*self.pins.borrow() {
-                    let path = entry.path.to_string_lossy().to_string();
-                    if pins.is_pinned(&path) {
-                        let overlay = Overlay::new();
-                        overlay.set_child(Some(&image));
-                        let star = Label::new(Some("★"));
-                        star.add_css_class("pinned-star");
-                        star.set_halign(Align::End);
-                        star.set_valign(Align::Start);
-                        star.set_margin_end(2);
-                        star.set_margin_top(2);
-                        overlay.add_overlay(&star);
-                        overlay.upcast()
-                    } else {
-                        image.upcast()
-                    }
-                } else {
-                    image.upcast()
-                }
-            } else {
-                let placeholder = self.create_icon_placeholder(48);
-                if let Some(pins) = &*self.pins.borrow() {
-                    let path = entry.path.to_string_lossy().to_string();
-                    if pins.is_pinned(&path) {
-                        let overlay = Overlay::new();
-                        overlay.set_child(Some(&placeholder));
-                        let star = Label::new(Some("★"));
-                        star.add_css_class("pinned-star");
-                        star.set_halign(Align::End);
-                        star.set_valign(Align::Start);
-                        star.set_margin_end(2);
-                        star.set_margin_top(2);
-                        overlay.add_overlay(&star);
-                        overlay.upcast()
-                    } else {
-                        placeholder.upcast()
-                    }
-                } else {
-                    placeholder.upcast()
-                }
-            }
-        };
-        row.append(&icon_widget);
+        let image = Self::themed_image(entry.icon.iter().cloned().collect(), icon_size);
+        image.add_css_class("app-icon");
+        row.append(&image);
 
         // Main content box (vertical layout for name and generic name)
         let content_box = GtkBox::builder()
@@ -576,8 +662,16 @@ impl ResultsList {
             .hexpand(true)
             .build();
 
+        // Title row (name + pin badge)
+        let title_row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+
         // Application name
-        let name_markup = apply_highlight(&entry.name, &self.current_query.borrow());
+        let (max_title, max_subtitle, ellipsis) = self.text_limits.get();
+        let name = truncate(&entry.name, max_title, ellipsis);
+        let name_markup = apply_highlight(&name, &self.current_query.borrow());
         let name_label = Label::builder()
             .use_markup(true)
             .label(&name_markup)
@@ -590,11 +684,24 @@ impl ResultsList {
         name_label.set_ellipsize(EllipsizeMode::End);
         name_label.set_max_width_chars(60);
 
-        content_box.append(&name_label);
+        title_row.append(&name_label);
+
+        let path = entry.path.to_string_lossy();
+        if self
+            .pins
+            .borrow()
+            .as_ref()
+            .is_some_and(|pins| pins.is_pinned(&path))
+        {
+            title_row.append(&Self::badge_widget(badge_view(ResultBadge::Pinned)));
+        }
+
+        content_box.append(&title_row);
 
         // Generic name (if available)
         if let Some(ref generic) = entry.generic_name {
-            let generic_markup = apply_highlight(generic, &self.current_query.borrow());
+            let generic = truncate(generic, max_subtitle, ellipsis);
+            let generic_markup = apply_highlight(&generic, &self.current_query.borrow());
             let generic_label = Label::builder()
                 .use_markup(true)
                 .label(&generic_markup)
@@ -619,45 +726,105 @@ impl ResultsList {
         self.list.selected_row().map(|row| row.index())
     }
 
-    /// Select the next item
-    pub fn select_next(&self) {
-        if let Some(current) = self.list.selected_row() {
-            let next_index = current.index() + 1;
+    /// Whether the selected result may run now
+    ///
+    /// The first activation of a result with a `confirm` prompt shows the prompt
+    /// on its row and returns false; activating it again returns true.
+    pub fn confirm_activation(&self) -> bool {
+        self.confirm_activation_with("Enter")
+    }
 
-            // Check if there's actually a next row before doing anything
-            if let Some(next_row) = self.list.row_at_index(next_index) {
-                self.list.select_row(Some(&next_row));
-                // Auto-scroll to make the selected row visible
-                self.scroll_to_selected();
+    /// Like [`Self::confirm_activation`] for another way of running the result
+    /// (`key` is named in the prompt and has to be pressed again to confirm)
+    pub fn confirm_activation_with(&self, key: &str) -> bool {
+        let Some(index) = self.selected_index() else {
+            return true;
+        };
+        // The selection moved (e.g. by mouse) since the prompt was shown
+        if self.confirm_row.get().is_some_and(|row| row != index) {
+            self.cancel_confirmation();
+        }
+        // Menu entries ask on their own row, keyed by action and result
+        let selected = match self.items.borrow().get(index as usize) {
+            Some(ListItem::PluginResult { result }) => Some((
+                result.clone(),
+                result.command.clone(),
+                result.confirm.clone(),
+            )),
+            Some(ListItem::ResultAction { action, source }) => Some((
+                action.menu_row(source),
+                format!("{}: {}", action.label(), source.command),
+                action.confirm_prompt(source),
+            )),
+            _ => None,
+        };
+        let Some((row, command, prompt)) = selected else {
+            self.cancel_confirmation();
+            return true;
+        };
 
-                info!("Selected next row (index {})", next_index);
-            } else {
-                // Already at the last item, do nothing to avoid unnecessary computation
-                debug!("Already at last item, ignoring Down arrow");
+        let activation = self
+            .confirm
+            .borrow_mut()
+            .activate(&command, prompt.as_deref(), key);
+        match activation {
+            Activation::Run => {
+                self.confirm_row.set(None);
+                true
+            }
+            Activation::Confirm(text) => {
+                let mut prompt = row;
+                prompt.subtitle = Some(text);
+                self.set_row_content(index, &prompt, true);
+                self.confirm_row.set(Some(index));
+                false
             }
         }
     }
 
-    /// Select the previous item
-    pub fn select_previous(&self) {
-        if let Some(current) = self.list.selected_row() {
-            let prev_index = current.index() - 1;
+    /// Drop a pending confirmation and restore the row that showed it
+    fn cancel_confirmation(&self) {
+        self.confirm.borrow_mut().reset();
+        let Some(index) = self.confirm_row.take() else {
+            return;
+        };
+
+        let result = match self.items.borrow().get(index as usize) {
+            Some(ListItem::PluginResult { result }) => result.clone(),
+            Some(ListItem::ResultAction { action, source }) => action.menu_row(source),
+            _ => return,
+        };
+        self.set_row_content(index, &result, false);
+    }
+
+    /// Rebuild the content of the row at `index` from `result`
+    fn set_row_content(&self, index: i32, result: &PluginResult, confirming: bool) {
+        if let Some(row) = self.list.row_at_index(index) {
+            let content = self.create_plugin_result_row(result);
+            if confirming {
+                content.add_css_class("confirm-row");
+            }
+            row.set_child(Some(&content));
+        }
+    }
 
-            if prev_index >= 0 {
-                if let Some(prev_row) = self.list.row_at_index(prev_index) {
-                    self.list.select_row(Some(&prev_row));
+    /// Move the selection (arrows, Home/End, Page Up/Down) and scroll it into view
+    pub fn navigate(&self, navigation: Navigation) {
+        self.cancel_confirmation();
+        let len = self.items.borrow().len();
+        let current = self.selected_index().map(|i| i as usize);
+        let page = (self.container.vadjustment().page_size() / self.row_height()) as usize;
+
+        match navigation::target_index(current, len, navigation, page, self.wrap_navigation.get()) {
+            Some(index) => {
+                if let Some(row) = self.list.row_at_index(index as i32) {
+                    self.list.select_row(Some(&row));
                     // Auto-scroll to make the selected row visible
                     self.scroll_to_selected();
-
-                    info!("Selected previous row (index {})", prev_index);
-                } else {
-                    // Already at the first item, do nothing
-                    debug!("Already at first item, ignoring Up arrow");
+                    info!("Selected row {} ({:?})", index, navigation);
                 }
-            } else {
-                // Already at the first item (index 0), do nothing
-                debug!("Already at first item, ignoring Up arrow");
             }
+            None => debug!("Selection unchanged for {:?}", navigation),
         }
     }
 
@@ -670,12 +837,26 @@ impl ResultsList {
         }
     }
 
+    /// Select the row whose pin key (desktop file path or command) matches `key`
+    pub fn select_pin_key(&self, key: &str) {
+        let index = self.items.borrow().iter().position(|item| match item {
+            ListItem::App { entry } => entry.path.to_string_lossy() == key,
+            ListItem::Action { .. } | ListItem::ResultAction { .. } => false,
+            ListItem::PluginResult { result } => Self::result_pin_key(result) == key,
+        });
+
+        if let Some(row) = index.and_then(|i| self.list.row_at_index(i as i32)) {
+            self.list.select_row(Some(&row));
+            self.scroll_to_selected();
+        }
+    }
+
     /// Scroll to the currently selected item
     fn scroll_to_selected(&self) {
         if let Some(selected_row) = self.list.selected_row() {
             // Get the adjustment from the scrolled window
             let vadj = self.container.vadjustment();
-            let row_height = 60.0; // Approximate row height
+            let row_height = self.row_height();
             let selected_y = selected_row.index() as f64 * row_height;
             let viewport_height = vadj.page_size();
             let current_scroll = vadj.value();
@@ -700,12 +881,12 @@ impl ResultsList {
 }
 
 impl ResultsList {
-    /// Provide pins store to this widget (for star indicators)
+    /// Provide pins store to this widget (for pinned badges)
     pub fn set_pins_store(&self, pins: std::sync::Arc<PinsStore>) {
         *self.pins.borrow_mut() = Some(pins);
     }
 
-    /// Re-render current items (used after toggling pins to refresh stars)
+    /// Re-render current items (used after toggling pins to refresh badges)
     #[allow(dead_code)]
     pub fn rerender(&self) {
         let selected = self.selected_index();
@@ -718,29 +899,67 @@ impl ResultsList {
         }
     }
 
-    fn resolve_plugin_icon(result: &PluginResult) -> Option<PathBuf> {
-        if let Some(icon_name) = result.icon.as_deref() {
-            if let Some(path) = resolve_icon(icon_name) {
-                return Some(path);
-            }
-        }
+    /// Pin key for a plugin result: desktop file path for apps, command otherwise
+    fn result_pin_key(result: &PluginResult) -> &str {
+        result.desktop_path.as_deref().unwrap_or(&result.command)
+    }
 
-        if let Some(parent_app) = result.parent_app.as_deref() {
-            if let Some(path) = Self::resolve_parent_app_icon(parent_app) {
-                return Some(path);
-            }
+    /// Whether a plugin result is pinned (app or command pin)
+    fn is_result_pinned(&self, result: &PluginResult) -> bool {
+        match &*self.pins.borrow() {
+            Some(pins) => pins.is_pinned(Self::result_pin_key(result)),
+            None => false,
         }
+    }
 
-        None
+    /// Small icon + text label for a pin or provenance badge
+    fn badge_widget(view: BadgeView) -> GtkBox {
+        let badge = GtkBox::new(Orientation::Horizontal, 4);
+        badge.add_css_class("result-source-badge");
+        badge.add_css_class(&view.css_class());
+        badge.set_valign(gtk4::Align::Center);
+        let icon = Image::from_icon_name(view.icon);
+        icon.set_pixel_size(12);
+        badge.append(&icon);
+        badge.append(&Label::new(Some(view.label)));
+        badge
     }
 
-    fn resolve_parent_app_icon(parent_app: &str) -> Option<PathBuf> {
-        for candidate in Self::icon_candidates_for_parent(parent_app) {
-            if let Some(path) = resolve_icon(candidate) {
-                return Some(path);
-            }
+    /// Icon names to try for a plugin result: its own icon, then its parent app's
+    fn plugin_icon_candidates(result: &PluginResult) -> Vec<String> {
+        let parent_icons = result
+            .parent_app
+            .as_deref()
+            .map(Self::icon_candidates_for_parent)
+            .unwrap_or_default();
+        result
+            .icon
+            .iter()
+            .cloned()
+            .chain(parent_icons.iter().map(|name| name.to_string()))
+            .collect()
+    }
+
+    /// Image of the first of `candidates` with an icon (else the default icon)
+    ///
+    /// Cached icons are set right away; uncached theme lookups run on a worker
+    /// thread and the image stays empty until the icon is set on the main loop,
+    /// so slow lookups don't stall typing.
+    fn themed_image(candidates: Vec<String>, size: i32) -> Image {
+        let image = Image::new();
+        image.set_pixel_size(size);
+        let weak = gtk4::glib::SendWeakRef::from(image.downgrade());
+        let cached = resolve_icon_in_background(candidates, size as u32, move |path| {
+            gtk4::glib::idle_add_once(move || {
+                if let Some(image) = weak.upgrade() {
+                    image.set_from_file(Some(&path));
+                }
+            });
+        });
+        if let Some(path) = cached {
+            image.set_from_file(Some(&path));
         }
-        None
+        image
     }
 
     fn icon_candidates_for_parent(parent_app: &str) -> &'static [&'static str] {
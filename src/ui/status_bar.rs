@@ -0,0 +1,84 @@
+use gtk4::glib::{self, SourceId};
+use gtk4::prelude::*;
+use gtk4::{Align, Label};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How long a status message stays visible
+const MESSAGE_DURATION: Duration = Duration::from_millis(2000);
+
+/// Matches the `.status-message` opacity transition in the theme CSS
+const FADE_DURATION: Duration = Duration::from_millis(250);
+
+/// Transient status line for action feedback ("Copied!", "Pinned", errors)
+///
+/// `show_status` displays a message, then fades it out and hides the label
+/// after a couple of seconds. A newer message replaces the current one and
+/// restarts the timer.
+#[derive(Clone)]
+pub struct StatusBar {
+    pub label: Label,
+    pending_clear: Rc<RefCell<Option<SourceId>>>,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        let label = Label::new(None);
+        label.add_css_class("status-message");
+        label.set_halign(Align::Center);
+        label.set_margin_start(16);
+        label.set_margin_end(16);
+        label.set_visible(false);
+
+        Self {
+            label,
+            pending_clear: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Show a message and schedule it to fade out
+    pub fn show_status(&self, message: &str) {
+        if let Some(source) = self.pending_clear.borrow_mut().take() {
+            source.remove();
+        }
+
+        self.label.set_text(message);
+        self.label.set_visible(true);
+        self.label.add_css_class("visible");
+
+        let label = self.label.clone();
+        let pending = self.pending_clear.clone();
+        let source = glib::timeout_add_local_once(MESSAGE_DURATION, move || {
+            label.remove_css_class("visible");
+
+            // Hide once the fade has finished, unless a new message arrived meanwhile
+            let pending_fade = pending.clone();
+            let fade = glib::timeout_add_local_once(FADE_DURATION, move || {
+                pending_fade.borrow_mut().take();
+                label.set_visible(false);
+                label.set_text("");
+            });
+            pending.borrow_mut().replace(fade);
+        });
+        self.pending_clear.borrow_mut().replace(source);
+    }
+
+    /// Currently displayed message (empty once cleared)
+    #[allow(dead_code)]
+    pub fn text(&self) -> String {
+        self.label.text().to_string()
+    }
+
+    /// Whether a message is shown and waiting to be cleared
+    #[allow(dead_code)]
+    pub fn is_clear_scheduled(&self) -> bool {
+        self.pending_clear.borrow().is_some()
+    }
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,8 +3,12 @@
 //! This module handles loading and applying CSS themes to the GTK application.
 //! Supports both built-in themes (from themes/ directory) and custom user themes.
 
+use anyhow::{bail, Context, Result};
 use gtk4::gdk::Display;
 use gtk4::CssProvider;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use tracing::{debug, error, info, warn};
 
 /// Available built-in themes
@@ -42,30 +46,47 @@ impl BuiltInTheme {
     }
 }
 
-/// Load and apply CSS theme to the application
+/// Directory holding user themes (`~/.config/native-launcher/themes/`)
+pub fn user_themes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|config| config.join("native-launcher").join("themes"))
+}
+
+/// Names of the `.css` themes in a user themes directory, sorted
+pub fn discover_user_themes(dir: &Path) -> Vec<String> {
+    let mut themes: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()
+                        .and_then(|name| name.strip_suffix(".css"))
+                        .map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    themes.sort();
+    themes
+}
+
+/// Resolve the CSS for a theme name
 ///
 /// Attempts to load theme in this order:
 /// 1. Custom theme file specified in config (absolute path)
-/// 2. Built-in theme by name (dark, light, dracula, nord, high-contrast)
-/// 3. Custom theme from `~/.config/native-launcher/theme.css`
-/// 4. Default built-in theme (style.css)
-///
-/// # Arguments
-/// * `theme_name` - Theme name from config (e.g., "dark", "dracula", or path to CSS file)
-///
-/// # Examples
-/// ```no_run
-/// use native_launcher::ui::theme::load_theme_with_name;
-/// // Load dark theme
-/// load_theme_with_name("dark");
-///
-/// // Load custom theme file
-/// load_theme_with_name("/path/to/custom.css");
-/// ```
-pub fn load_theme_with_name(theme_name: &str) {
-    let provider = CssProvider::new();
-    let mut css_loaded = false;
+/// 2. User theme by name from `~/.config/native-launcher/themes/<name>.css`
+/// 3. Built-in theme by name (dark, light, dracula, nord, high-contrast)
+/// 4. Custom theme from `~/.config/native-launcher/theme.css`
+/// 5. Default built-in theme (style.css)
+fn resolve_theme_css(theme_name: &str) -> String {
+    resolve_theme_css_in(theme_name, user_themes_dir().as_deref())
+}
 
+/// Resolve theme CSS, looking up named user themes in `user_dir`
+fn resolve_theme_css_in(theme_name: &str, user_dir: Option<&Path>) -> String {
     // Try 1: Check if it's an absolute path to a custom CSS file
     if theme_name.starts_with('/') || theme_name.starts_with("~/") {
         let path = if theme_name.starts_with("~/") {
@@ -83,10 +104,7 @@ pub fn load_theme_with_name(theme_name: &str) {
         if path.exists() {
             info!("Loading custom theme from: {}", path.display());
             match std::fs::read_to_string(&path) {
-                Ok(css_content) => {
-                    provider.load_from_data(&css_content);
-                    css_loaded = true;
-                }
+                Ok(css_content) => return css_content,
                 Err(e) => {
                     warn!("Failed to read custom theme file: {}", e);
                 }
@@ -96,48 +114,180 @@ pub fn load_theme_with_name(theme_name: &str) {
         }
     }
 
-    // Try 2: Check if it's a built-in theme name
-    if !css_loaded {
-        if let Some(built_in_theme) = BuiltInTheme::from_name(theme_name) {
-            info!("Loading built-in theme: {:?}", built_in_theme);
-            let css = built_in_theme.css_content();
-            provider.load_from_data(css);
-            css_loaded = true;
+    // Try 2: Check for a user theme with this name (takes precedence over built-ins)
+    if let Some(theme_path) = user_dir.map(|dir| dir.join(format!("{}.css", theme_name))) {
+        if theme_path.is_file() {
+            info!("Loading user theme from: {}", theme_path.display());
+            match std::fs::read_to_string(&theme_path) {
+                Ok(css_content) => return css_content,
+                Err(e) => {
+                    warn!("Failed to read user theme {}: {}", theme_path.display(), e);
+                }
+            }
+        }
+    }
+
+    // Try 3: Check if it's a built-in theme name
+    if let Some(built_in_theme) = BuiltInTheme::from_name(theme_name) {
+        info!("Loading built-in theme: {:?}", built_in_theme);
+        return built_in_theme.css_content().to_string();
+    }
+
+    // Try 4: Check for user's custom theme in config directory
+    let custom_theme_path =
+        dirs::config_dir().map(|config| config.join("native-launcher").join("theme.css"));
+
+    if let Some(theme_path) = custom_theme_path {
+        if theme_path.exists() {
+            info!("Loading custom theme from: {}", theme_path.display());
+            match std::fs::read_to_string(&theme_path) {
+                Ok(css_content) => return css_content,
+                Err(e) => {
+                    warn!(
+                        "Failed to read custom theme: {}, falling back to default",
+                        e
+                    );
+                }
+            }
+        } else {
+            debug!("No custom theme found at: {}", theme_path.display());
         }
     }
 
-    // Try 3: Check for user's custom theme in config directory
-    if !css_loaded {
-        let custom_theme_path =
-            dirs::config_dir().map(|config| config.join("native-launcher").join("theme.css"));
+    // Try 5: Fall back to built-in default CSS
+    info!("Loading default built-in theme (style.css)");
+    include_str!("style.css").to_string()
+}
+
+/// Find structural errors GTK's parser would trip over (unbalanced braces,
+/// unterminated comments or strings). Works without GTK being initialized.
+fn css_syntax_error(css: &str) -> Option<String> {
+    let mut chars = css.chars().peekable();
+    let mut line = 1;
+    let mut open_braces: Vec<usize> = Vec::new();
 
-        if let Some(theme_path) = custom_theme_path {
-            if theme_path.exists() {
-                info!("Loading custom theme from: {}", theme_path.display());
-                match std::fs::read_to_string(&theme_path) {
-                    Ok(css_content) => {
-                        provider.load_from_data(&css_content);
-                        css_loaded = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let start = line;
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    if c == '\n' {
+                        line += 1;
+                    } else if c == '*' && chars.peek() == Some(&'/') {
+                        chars.next();
+                        closed = true;
+                        break;
                     }
-                    Err(e) => {
-                        warn!(
-                            "Failed to read custom theme: {}, falling back to default",
-                            e
-                        );
+                }
+                if !closed {
+                    return Some(format!("unterminated comment starting on line {}", start));
+                }
+            }
+            '"' | '\'' => {
+                let start = line;
+                let mut closed = false;
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '\n' => break,
+                        _ if next == c => {
+                            closed = true;
+                            break;
+                        }
+                        _ => {}
                     }
                 }
-            } else {
-                debug!("No custom theme found at: {}", theme_path.display());
+                if !closed {
+                    return Some(format!("unterminated string on line {}", start));
+                }
             }
+            '{' => open_braces.push(line),
+            '}' => match open_braces.pop() {
+                Some(_) => {}
+                None => return Some(format!("unexpected '}}' on line {}", line)),
+            },
+            _ => {}
         }
     }
 
-    // Try 4: Fall back to built-in default CSS
-    if !css_loaded {
-        info!("Loading default built-in theme (style.css)");
-        let css = include_str!("style.css");
-        provider.load_from_data(css);
+    open_braces
+        .last()
+        .map(|start| format!("unclosed '{{' opened on line {}", start))
+}
+
+/// Check CSS for parse errors without applying it
+///
+/// Runs a structural check, then (when GTK is initialized) loads the CSS into a
+/// throwaway `CssProvider` and reports the first parsing error.
+pub fn validate_css(css: &str) -> Result<()> {
+    if let Some(error) = css_syntax_error(css) {
+        bail!(error);
+    }
+
+    if !gtk4::is_initialized_main_thread() {
+        return Ok(());
+    }
+
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let provider = CssProvider::new();
+    {
+        let errors = errors.clone();
+        provider.connect_parsing_error(move |_, section, error| {
+            errors.borrow_mut().push(format!(
+                "line {}: {}",
+                section.start_location().lines() + 1,
+                error.message()
+            ));
+        });
+    }
+    provider.load_from_data(css);
+
+    let errors = errors.borrow();
+    match errors.first() {
+        Some(first) if errors.len() > 1 => {
+            bail!("{} (and {} more errors)", first, errors.len() - 1)
+        }
+        Some(first) => bail!("{}", first),
+        None => Ok(()),
     }
+}
+
+/// Validate a theme by name without applying it
+pub fn validate_theme(theme_name: &str) -> Result<()> {
+    validate_css(&resolve_theme_css(theme_name))
+}
+
+/// Load and apply CSS theme to the application
+///
+/// The theme is resolved as described in [`resolve_theme_css`] and validated
+/// first; broken CSS is rejected without touching the current styling, so callers
+/// can keep (or revert to) the previous theme.
+///
+/// # Arguments
+/// * `theme_name` - Theme name from config (e.g., "dark", "dracula", or path to CSS file)
+///
+/// # Examples
+/// ```no_run
+/// use native_launcher::ui::theme::load_theme_with_name;
+/// // Load dark theme
+/// load_theme_with_name("dark").ok();
+///
+/// // Load custom theme file
+/// if let Err(e) = load_theme_with_name("/path/to/custom.css") {
+///     eprintln!("Broken theme: {}", e);
+/// }
+/// ```
+pub fn load_theme_with_name(theme_name: &str) -> Result<()> {
+    let css = resolve_theme_css(theme_name);
+    validate_css(&css).with_context(|| format!("Theme '{}' has invalid CSS", theme_name))?;
+
+    let provider = CssProvider::new();
+    provider.load_from_data(&css);
 
     // Apply theme to display
     if let Some(display) = Display::default() {
@@ -150,16 +300,83 @@ pub fn load_theme_with_name(theme_name: &str) {
     } else {
         error!("Failed to get default display for CSS loading");
     }
-}
 
-/// Load and apply CSS theme to the application using default theme name
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_load_theme_doesnt_panic() {
         // GTK might not be initialized in tests, but function shouldn't panic
         // This is more of a smoke test
         // Actual GTK display tests would require headless GTK setup
     }
+
+    #[test]
+    fn test_broken_css_is_invalid() {
+        let broken = "window {\n  background: #000;\n\n.result-row { color: red; }\n";
+        let err = validate_css(broken).unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{}", err);
+
+        assert!(validate_css("window { color: red; } }").is_err());
+        assert!(validate_css("/* never closed\nwindow {}").is_err());
+        assert!(validate_css("label { font-family: \"Inter; }").is_err());
+    }
+
+    #[test]
+    fn test_user_theme_discovered_and_resolved() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("native-launcher-themes-{}", nanos));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ocean.css"), "window { color: #0af; }").unwrap();
+        std::fs::write(dir.join("dark.css"), "window { color: #111; }").unwrap();
+        std::fs::write(dir.join("README.md"), "not a theme").unwrap();
+
+        let themes = discover_user_themes(&dir);
+        let ocean = resolve_theme_css_in("ocean", Some(&dir));
+        let dark = resolve_theme_css_in("dark", Some(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(themes, vec!["dark", "ocean"]);
+        assert_eq!(ocean, "window { color: #0af; }");
+        // User themes shadow built-ins of the same name
+        assert_eq!(dark, "window { color: #111; }");
+        assert_eq!(
+            resolve_theme_css_in("nord", Some(&dir)),
+            BuiltInTheme::Nord.css_content()
+        );
+    }
+
+    #[test]
+    fn test_builtin_themes_are_valid() {
+        for name in ["dark", "light", "dracula", "nord", "high-contrast"] {
+            assert!(validate_theme(name).is_ok(), "theme {} is invalid", name);
+        }
+        assert!(validate_css(include_str!("style.css")).is_ok());
+        assert!(validate_css("a { content: \"}\"; } /* { */").is_ok());
+    }
+
+    #[test]
+    fn test_builtin_themes_style_row_states() {
+        // Built-in themes replace style.css, so each styles these itself
+        for name in ["dark", "light", "dracula", "nord", "high-contrast"] {
+            let css = BuiltInTheme::from_name(name).unwrap().css_content();
+            for class in [
+                ".status-message",
+                ".confirm-row",
+                ".result-source-badge",
+                ".badge-bookmark",
+                ".badge-running",
+                ".badge-pinned",
+            ] {
+                assert!(css.contains(class), "theme {} lacks {}", name, class);
+            }
+        }
+    }
 }
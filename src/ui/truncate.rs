@@ -0,0 +1,106 @@
+//! Length limits for result titles and subtitles
+//!
+//! Applied when rows are built, so search and matching still see the full text.
+//! Lengths count graphemes, so accents and emoji never get split. Kept free of
+//! GTK so the truncation can be unit tested.
+
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+
+const ELLIPSIS: &str = "…";
+
+/// Where the ellipsis goes when a title or subtitle is too long (`ui.ellipsis`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EllipsisMode {
+    /// Middle for paths (text containing `/`), end for everything else
+    Auto,
+    /// Keep the start and the end: `/very/long/…/file.txt`
+    Middle,
+    /// Keep the start only: `A very long ti…`
+    End,
+}
+
+impl EllipsisMode {
+    /// Mode for a `ui.ellipsis` value; unknown values mean `Auto`
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "middle" => Self::Middle,
+            "end" => Self::End,
+            _ => Self::Auto,
+        }
+    }
+}
+
+/// Limit `text` to `max` graphemes (ellipsis included); 0 means no limit
+pub fn truncate(text: &str, max: usize, mode: EllipsisMode) -> Cow<'_, str> {
+    let middle = match mode {
+        EllipsisMode::Auto => text.contains('/'),
+        EllipsisMode::Middle => true,
+        EllipsisMode::End => false,
+    };
+    if middle {
+        truncate_middle(text, max)
+    } else {
+        truncate_end(text, max)
+    }
+}
+
+/// Keep the first `max - 1` graphemes and end with an ellipsis
+pub fn truncate_end(text: &str, max: usize) -> Cow<'_, str> {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    if max == 0 || graphemes.len() <= max {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = graphemes[..max - 1].concat();
+    out.push_str(ELLIPSIS);
+    Cow::Owned(out)
+}
+
+/// Keep both ends of `text` around an ellipsis, favouring the end (file names)
+pub fn truncate_middle(text: &str, max: usize) -> Cow<'_, str> {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    if max == 0 || graphemes.len() <= max {
+        return Cow::Borrowed(text);
+    }
+
+    let head = (max - 1) / 2;
+    let tail = max - 1 - head;
+    let mut out = graphemes[..head].concat();
+    out.push_str(ELLIPSIS);
+    out.push_str(&graphemes[graphemes.len() - tail..].concat());
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_middle_ellipsis_keeps_path_ends() {
+        let path = "/home/user/projects/native-launcher/assets/screenshots/file.txt";
+        let truncated = truncate_middle(path, 24);
+        assert_eq!(truncated, "/home/user/…ots/file.txt");
+        assert_eq!(truncated.graphemes(true).count(), 24);
+        assert_eq!(truncate(path, 24, EllipsisMode::Auto), truncated);
+
+        // Short text and no limit are left alone
+        assert!(matches!(
+            truncate_middle("file.txt", 24),
+            Cow::Borrowed("file.txt")
+        ));
+        assert_eq!(truncate_middle(path, 0), path);
+    }
+
+    #[test]
+    fn test_end_ellipsis_counts_graphemes() {
+        assert_eq!(truncate_end("Visual Studio Code", 10), "Visual St…");
+        // "e" + combining accent is a single grapheme
+        assert_eq!(truncate_end("cafe\u{301} crème", 5), "cafe\u{301}…");
+        assert_eq!(
+            truncate("Visual Studio Code", 10, EllipsisMode::Auto),
+            "Visual St…"
+        );
+        assert_eq!(truncate("a/b/c/d/e", 5, EllipsisMode::End), "a/b/…");
+    }
+}
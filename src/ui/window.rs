@@ -1,7 +1,10 @@
 use gtk4::prelude::*;
 use gtk4::{Application, ApplicationWindow};
 use gtk4_layer_shell::{Edge, KeyboardMode, Layer, LayerShell};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+use super::layer_shell::{KeyboardInteractivity, LayerShellSettings, ShellAnchor, ShellLayer};
+use crate::config::WindowConfig;
 
 #[allow(dead_code)]
 const APP_ID: &str = "com.github.native-launcher";
@@ -12,8 +15,9 @@ pub struct LauncherWindow {
 }
 
 impl LauncherWindow {
-    /// Create a new launcher window
-    pub fn new(app: &Application) -> Self {
+    /// Create a new launcher window, as a layer surface when `window.layer_shell`
+    /// is on and the compositor supports it
+    pub fn new(app: &Application, config: &WindowConfig) -> Self {
         info!("Creating launcher window");
 
         let window = ApplicationWindow::builder()
@@ -23,26 +27,51 @@ impl LauncherWindow {
             .default_height(550)
             .build();
 
-        // Initialize layer shell for Wayland
+        match LayerShellSettings::from_config(config) {
+            Some(settings) if gtk4_layer_shell::is_supported() => {
+                Self::configure_layer_shell(&window, settings);
+            }
+            Some(_) => {
+                warn!("Compositor doesn't support layer-shell, using a normal window");
+            }
+            None => debug!("Layer shell disabled, using a normal window"),
+        }
+
+        Self { window }
+    }
+
+    /// Turn the window into a layer surface
+    fn configure_layer_shell(window: &ApplicationWindow, settings: LayerShellSettings) {
         window.init_layer_shell();
 
-        // Configure layer shell
-        window.set_layer(Layer::Overlay);
-        window.set_keyboard_mode(KeyboardMode::Exclusive);
+        window.set_layer(match settings.layer {
+            ShellLayer::Top => Layer::Top,
+            ShellLayer::Overlay => Layer::Overlay,
+        });
+        window.set_keyboard_mode(match settings.keyboard {
+            KeyboardInteractivity::Exclusive => KeyboardMode::Exclusive,
+            KeyboardInteractivity::OnDemand => KeyboardMode::OnDemand,
+        });
         window.set_namespace("native-launcher");
 
-        // Don't anchor to any edges (this centers the window)
-        window.set_anchor(Edge::Top, false);
-        window.set_anchor(Edge::Bottom, false);
-        window.set_anchor(Edge::Left, false);
-        window.set_anchor(Edge::Right, false);
+        // Anchoring to no edge centers the window
+        for edge in [Edge::Top, Edge::Bottom, Edge::Left, Edge::Right] {
+            window.set_anchor(edge, false);
+        }
+        let anchored = match settings.anchor {
+            ShellAnchor::Top => Some(Edge::Top),
+            ShellAnchor::Bottom => Some(Edge::Bottom),
+            ShellAnchor::Center => None,
+        };
+        if let Some(edge) = anchored {
+            window.set_anchor(edge, true);
+            window.set_margin(edge, settings.margin);
+        }
 
         // Don't reserve exclusive space
         window.set_exclusive_zone(-1);
 
-        debug!("Layer shell configured");
-
-        Self { window }
+        debug!("Layer shell configured: {:?}", settings);
     }
 
     /// Show the window
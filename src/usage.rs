@@ -1,3 +1,5 @@
+use crate::utils::atomic_write;
+use crate::utils::backup::backup_corrupt_file;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -121,22 +123,39 @@ impl UsageTracker {
         }
     }
 
+    /// Create an empty usage tracker that saves to `cache_path`
+    pub fn with_cache_path(cache_path: PathBuf) -> Self {
+        Self {
+            usage_data: HashMap::new(),
+            cache_path,
+        }
+    }
+
     /// Load usage data from disk
     pub fn load() -> Result<Self> {
-        let cache_path = Self::default_cache_path();
+        Self::load_from(Self::default_cache_path())
+    }
 
+    /// Load usage data from a specific cache file (separate stores for
+    /// non-app items, e.g. SSH hosts); a missing file starts fresh, and so does
+    /// a corrupt one after it is moved to `<name>.bak`
+    pub fn load_from(cache_path: PathBuf) -> Result<Self> {
         if !cache_path.exists() {
-            info!("No usage cache found, starting fresh");
-            return Ok(Self {
-                usage_data: HashMap::new(),
-                cache_path,
-            });
+            info!("No usage cache found at {:?}, starting fresh", cache_path);
+            return Ok(Self::with_cache_path(cache_path));
         }
 
         debug!("Loading usage data from {:?}", cache_path);
 
         let data = fs::read(&cache_path)?;
-        let mut tracker: UsageTracker = bincode::deserialize(&data)?;
+        let mut tracker: UsageTracker = match bincode::deserialize(&data) {
+            Ok(tracker) => tracker,
+            Err(e) => {
+                error!("Usage data at {:?} is corrupt: {}", cache_path, e);
+                backup_corrupt_file(&cache_path)?;
+                return Ok(Self::with_cache_path(cache_path));
+            }
+        };
         tracker.cache_path = cache_path;
 
         info!("Loaded usage data for {} apps", tracker.usage_data.len());
@@ -153,7 +172,7 @@ impl UsageTracker {
         debug!("Saving usage data to {:?}", self.cache_path);
 
         let encoded = bincode::serialize(&self.usage_data)?;
-        fs::write(&self.cache_path, encoded)?;
+        atomic_write(&self.cache_path, encoded)?;
 
         debug!("Usage data saved successfully");
         Ok(())
@@ -184,6 +203,62 @@ impl UsageTracker {
             .unwrap_or(0.0)
     }
 
+    /// Desktop paths of the `n` most recently launched apps, most recent first
+    pub fn recent(&self, n: usize) -> Vec<String> {
+        let mut launched: Vec<(&String, &AppUsage)> = self
+            .usage_data
+            .iter()
+            .filter(|(_, usage)| usage.launch_count > 0)
+            .collect();
+        launched.sort_by(|(path_a, usage_a), (path_b, usage_b)| {
+            usage_b
+                .last_used
+                .cmp(&usage_a.last_used)
+                .then_with(|| path_a.cmp(path_b))
+        });
+
+        launched
+            .into_iter()
+            .take(n)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Desktop paths of the `n` apps with the highest usage score, best first
+    pub fn top(&self, n: usize) -> Vec<String> {
+        let mut scored: Vec<(&String, f64)> = self
+            .usage_data
+            .iter()
+            .map(|(path, usage)| (path, usage.score()))
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        scored.sort_by(|(path_a, score_a), (path_b, score_b)| {
+            score_b
+                .partial_cmp(score_a)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| path_a.cmp(path_b))
+        });
+
+        scored
+            .into_iter()
+            .take(n)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Tracker holding `usage_data`, for tests that need set launch times; it
+    /// saves to the temp dir, never to the user's cache
+    #[cfg(test)]
+    pub(crate) fn from_usage(usage_data: HashMap<String, AppUsage>) -> Self {
+        Self {
+            usage_data,
+            cache_path: std::env::temp_dir().join(format!(
+                "native-launcher-test-usage-{}.bin",
+                std::process::id()
+            )),
+        }
+    }
+
     /// Get usage stats for an application
     #[allow(dead_code)]
 
@@ -267,6 +342,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_recent_orders_by_last_launch() {
+        let now = current_timestamp();
+        let usage = |launch_count, age| AppUsage {
+            launch_count,
+            last_used: now - age,
+            first_used: now - 10 * 86400,
+            launch_history: Vec::new(),
+        };
+        let tracker = UsageTracker::from_usage(HashMap::from([
+            ("/test/frequent.desktop".to_string(), usage(50, 3600)),
+            ("/test/latest.desktop".to_string(), usage(1, 10)),
+            ("/test/oldest.desktop".to_string(), usage(3, 7 * 86400)),
+            ("/test/middle.desktop".to_string(), usage(2, 60)),
+        ]));
+
+        assert_eq!(
+            tracker.recent(3),
+            vec![
+                "/test/latest.desktop",
+                "/test/middle.desktop",
+                "/test/frequent.desktop"
+            ]
+        );
+        assert_eq!(tracker.recent(10).len(), 4);
+        assert!(UsageTracker::from_usage(HashMap::new())
+            .recent(3)
+            .is_empty());
+    }
+
     #[test]
     fn test_usage_score_nonzero() {
         let usage = AppUsage::new();
@@ -358,4 +463,30 @@ mod tests {
         assert!(score_fresh > 0.0);
         assert!(score_old > 0.0);
     }
+
+    #[test]
+    fn test_corrupt_file_backed_up_and_tracker_starts_fresh() {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-usage-corrupt-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("usage.bin");
+
+        let mut tracker = UsageTracker::with_cache_path(path.clone());
+        tracker.record_launch("/test/app1.desktop");
+        tracker.record_launch("/test/app2.desktop");
+        let data = fs::read(&path).unwrap();
+        fs::write(&path, &data[..data.len() - 4]).unwrap();
+
+        let tracker = UsageTracker::load_from(path.clone()).unwrap();
+        assert_eq!(tracker.app_count(), 0);
+        assert!(!path.exists());
+        assert!(dir.join("usage.bin.bak").exists());
+
+        // The fresh tracker saves back to the original file
+        tracker.save().unwrap();
+        assert!(path.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
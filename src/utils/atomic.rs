@@ -0,0 +1,91 @@
+//! Crash-safe replacement of state files
+//!
+//! Data goes to `<name>.tmp` first and is renamed over the target once it is
+//! fully on disk. The rename is atomic on the same filesystem, so readers see
+//! either the old file or the new one, never a half-written mix. A `.tmp` left
+//! behind by an interrupted save is simply overwritten by the next one.
+//!
+//! A symlinked target (say, a config.toml kept in a dotfiles repo) is resolved
+//! first, so the file it points to is replaced and the link stays a link.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Replace `path` with `bytes` atomically (the parent directory must exist)
+pub fn atomic_write(path: &Path, bytes: impl AsRef<[u8]>) -> io::Result<()> {
+    // Fails for a file that doesn't exist yet, which is then created as-is
+    let path = &fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let temp = write_temp(path, bytes.as_ref())?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+/// Write and flush the temporary sibling of `path`, returning its path
+fn write_temp(path: &Path, bytes: &[u8]) -> io::Result<PathBuf> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    let temp = path.with_file_name(name);
+
+    let mut file = File::create(&temp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    Ok(temp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interrupted_write_leaves_original_intact() {
+        let dir =
+            std::env::temp_dir().join(format!("native-launcher-atomic-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pins.json");
+        let temp = dir.join("pins.json.tmp");
+
+        atomic_write(&path, "old").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert!(!temp.exists());
+
+        // Dying between the temp write and the rename only leaves the temp file
+        write_temp(&path, b"new").unwrap();
+        assert_eq!(fs::read_to_string(&temp).unwrap(), "new");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        // The next save replaces the stale temp file and then the original
+        atomic_write(&path, "newer").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "newer");
+        assert!(!temp.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_symlinked_target_stays_a_link() {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-atomic-link-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("dotfiles")).unwrap();
+        let target = dir.join("dotfiles").join("config.toml");
+        let link = dir.join("config.toml");
+        fs::write(&target, "old").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        atomic_write(&link, "new").unwrap();
+        let is_link = fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink();
+        let contents = fs::read_to_string(&target).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(is_link);
+        assert_eq!(contents, "new");
+    }
+}
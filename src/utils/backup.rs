@@ -0,0 +1,23 @@
+//! Recovery for state files that can't be read back (e.g. truncated by a crash
+//! mid-write): the file is moved aside so the store can start fresh without
+//! losing the data for good
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Move a corrupt file to `<name>.bak`, replacing an older backup
+pub fn backup_corrupt_file(path: &Path) -> io::Result<PathBuf> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    let backup = path.with_file_name(name);
+
+    fs::rename(path, &backup)?;
+    warn!(
+        "Moved unreadable {} to {}",
+        path.display(),
+        backup.display()
+    );
+    Ok(backup)
+}
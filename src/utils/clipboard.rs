@@ -0,0 +1,189 @@
+//! Clipboard utilities
+//!
+//! Detects the available clipboard tool (`wl-copy` on Wayland, `xclip` or `xsel`
+//! on X11) and copies text or images with it. Used by the UI for
+//! `KeyboardAction::CopyToClipboard` / `KeyboardAction::CopyPath` and by plugins
+//! that build copy commands (screenshot, emoji).
+
+use super::shell_escape;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// Errors from copying to the clipboard
+#[derive(Debug, Error)]
+pub enum ClipboardError {
+    /// Neither wl-copy, xclip nor xsel is installed
+    #[error("Install wl-clipboard or xclip to copy")]
+    NoTool,
+    /// The clipboard tool could not be started
+    #[error("Failed to run {tool}: {source}")]
+    Spawn {
+        tool: &'static str,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Command-line clipboard tool used to copy text and images
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardTool {
+    /// `wl-copy` from wl-clipboard (Wayland)
+    WlCopy,
+    /// `xclip` (X11)
+    Xclip,
+    /// `xsel` (X11)
+    Xsel,
+}
+
+impl ClipboardTool {
+    /// Executable name of the tool
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ClipboardTool::WlCopy => "wl-copy",
+            ClipboardTool::Xclip => "xclip",
+            ClipboardTool::Xsel => "xsel",
+        }
+    }
+
+    /// Shell pipeline that copies `text` with this tool
+    pub fn copy_command(self, text: &str) -> String {
+        let quoted = shell_escape(text);
+        match self {
+            ClipboardTool::WlCopy => format!("printf '%s' {} | wl-copy", quoted),
+            ClipboardTool::Xclip => {
+                format!("printf '%s' {} | xclip -selection clipboard", quoted)
+            }
+            ClipboardTool::Xsel => format!("printf '%s' {} | xsel --clipboard --input", quoted),
+        }
+    }
+
+    /// Command that copies what is written to its stdin
+    fn stdin_command(self) -> Command {
+        let mut command = Command::new(self.display_name());
+        match self {
+            ClipboardTool::WlCopy => {}
+            ClipboardTool::Xclip => {
+                command.args(["-selection", "clipboard"]);
+            }
+            ClipboardTool::Xsel => {
+                command.args(["--clipboard", "--input"]);
+            }
+        }
+        command
+    }
+
+    /// Shell command that copies the PNG image at `path` with this tool
+    pub fn copy_image_command(self, path: &Path) -> String {
+        let quoted = shell_escape(&path.to_string_lossy());
+        match self {
+            ClipboardTool::WlCopy => format!("wl-copy --type image/png < {}", quoted),
+            ClipboardTool::Xclip => {
+                format!("xclip -selection clipboard -target image/png < {}", quoted)
+            }
+            ClipboardTool::Xsel => {
+                format!(
+                    "xsel --clipboard --input --mime-type image/png < {}",
+                    quoted
+                )
+            }
+        }
+    }
+}
+
+/// Detect the clipboard tool to use, preferring `wl-copy`
+pub fn detect_tool() -> Option<ClipboardTool> {
+    let available = |tool: &str| {
+        Command::new("which")
+            .arg(tool)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    };
+
+    [
+        ClipboardTool::WlCopy,
+        ClipboardTool::Xclip,
+        ClipboardTool::Xsel,
+    ]
+    .into_iter()
+    .find(|tool| available(tool.display_name()))
+}
+
+/// Copy text to the clipboard with the detected tool
+///
+/// The text is written to the tool's stdin, so it never shows up in a
+/// process's command line.
+pub fn copy_text(text: &str) -> Result<(), ClipboardError> {
+    copy_text_with(detect_tool(), text)
+}
+
+fn copy_text_with(tool: Option<ClipboardTool>, text: &str) -> Result<(), ClipboardError> {
+    let tool = tool.ok_or(ClipboardError::NoTool)?;
+    let spawn_error = |source| ClipboardError::Spawn {
+        tool: tool.display_name(),
+        source,
+    };
+
+    let mut child = tool
+        .stdin_command()
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes()).map_err(spawn_error)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_command_per_tool() {
+        assert_eq!(
+            ClipboardTool::WlCopy.copy_command("7006652"),
+            "printf '%s' '7006652' | wl-copy"
+        );
+        assert_eq!(
+            ClipboardTool::Xclip.copy_command("7006652"),
+            "printf '%s' '7006652' | xclip -selection clipboard"
+        );
+        assert_eq!(
+            ClipboardTool::Xsel.copy_command("7006652"),
+            "printf '%s' '7006652' | xsel --clipboard --input"
+        );
+
+        // Single quotes survive the shell round trip
+        assert_eq!(
+            ClipboardTool::WlCopy.copy_command("it's"),
+            r"printf '%s' 'it'\''s' | wl-copy"
+        );
+    }
+
+    #[test]
+    fn test_copy_image_command_per_tool() {
+        let path = Path::new("/tmp/shot 1.png");
+        assert_eq!(
+            ClipboardTool::WlCopy.copy_image_command(path),
+            "wl-copy --type image/png < '/tmp/shot 1.png'"
+        );
+        assert_eq!(
+            ClipboardTool::Xclip.copy_image_command(path),
+            "xclip -selection clipboard -target image/png < '/tmp/shot 1.png'"
+        );
+        assert_eq!(
+            ClipboardTool::Xsel.copy_image_command(path),
+            "xsel --clipboard --input --mime-type image/png < '/tmp/shot 1.png'"
+        );
+    }
+
+    #[test]
+    fn test_no_tool_error() {
+        let err = copy_text_with(None, "text").unwrap_err();
+        assert!(matches!(err, ClipboardError::NoTool));
+        assert_eq!(err.to_string(), "Install wl-clipboard or xclip to copy");
+    }
+}
@@ -1,6 +1,9 @@
+use crate::desktop::DesktopEntry;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
+use std::path::Path;
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock, RwLock};
 use tracing::{debug, error, info, warn};
 use urlencoding::{decode, encode};
@@ -8,6 +11,33 @@ use urlencoding::{decode, encode};
 /// Cached login-shell environment merged with the current process environment
 static LAUNCH_ENV: OnceLock<HashMap<String, String>> = OnceLock::new();
 
+/// Terminal emulator from `[terminal] command`; `None` auto-detects
+static CONFIGURED_TERMINAL: RwLock<Option<String>> = RwLock::new(None);
+
+/// `[terminal] hold_on_exit`: keep terminals open after their command exits
+static HOLD_ON_EXIT: AtomicBool = AtomicBool::new(false);
+
+/// `[general] open_command` when changed from the default; `None` opens with
+/// `gio open`, falling back to `xdg-open`
+static CONFIGURED_OPEN_COMMAND: RwLock<Option<String>> = RwLock::new(None);
+
+/// Opener used when `[general] open_command` is left at its default
+const DEFAULT_OPEN_COMMAND: &str = "xdg-open {}";
+
+/// `[shell] notify_on_error`: notify when a shell plugin command fails
+static NOTIFY_ON_ERROR: AtomicBool = AtomicBool::new(false);
+
+/// Most lines of a failed command's error output put in its notification
+const NOTIFY_STDERR_LINES: usize = 5;
+
+/// Variables `pkexec` strips that GUI apps need to reach the display
+const ELEVATED_GUI_ENV: &[&str] = &[
+    "DISPLAY",
+    "XAUTHORITY",
+    "WAYLAND_DISPLAY",
+    "XDG_RUNTIME_DIR",
+];
+
 pub const OPEN_COMMAND_PREFIX: &str = "open://";
 const LEGACY_OPEN_COMMAND_PREFIX: &str = "open-file://";
 
@@ -142,8 +172,69 @@ pub(crate) fn handler_counts_for_test() -> (usize, usize) {
     )
 }
 
+/// Use `command` as the terminal emulator (empty = auto-detect); with
+/// `hold_on_exit` terminal commands wait for Enter before the terminal closes
+pub fn configure_terminal(command: &str, hold_on_exit: bool) {
+    let command = command.trim();
+    *CONFIGURED_TERMINAL
+        .write()
+        .expect("terminal config poisoned") = (!command.is_empty()).then(|| command.to_string());
+    HOLD_ON_EXIT.store(hold_on_exit, Ordering::Relaxed);
+}
+
+/// Open files and URLs with `template` (`{}` marks the target); the default
+/// `xdg-open {}` keeps trying `gio open` first
+pub fn configure_open_command(template: &str) {
+    let template = template.trim();
+    *CONFIGURED_OPEN_COMMAND
+        .write()
+        .expect("open command config poisoned") =
+        (!template.is_empty() && template != DEFAULT_OPEN_COMMAND).then(|| template.to_string());
+}
+
+/// Shell command line opening `target` with the configured opener
+pub fn open_command_line(target: &str) -> String {
+    let configured = CONFIGURED_OPEN_COMMAND
+        .read()
+        .expect("open command config poisoned");
+    render_open_command(
+        configured.as_deref().unwrap_or(DEFAULT_OPEN_COMMAND),
+        target,
+    )
+}
+
+/// Replace each `{}` in `template` with the shell-quoted `target`, or append it
+fn render_open_command(template: &str, target: &str) -> String {
+    let quoted = shell_quote(target);
+    if template.contains("{}") {
+        template.replace("{}", &quoted)
+    } else {
+        format!("{} {}", template, quoted)
+    }
+}
+
+/// Notify (via `notify-send`) when a command run from the shell plugin exits
+/// with a non-zero status
+pub fn configure_error_notifications(enabled: bool) {
+    NOTIFY_ON_ERROR.store(enabled, Ordering::Relaxed);
+}
+
 /// Execute a desktop entry's command
 pub fn execute_command(exec: &str, terminal: bool, merge_login_env: bool) -> Result<()> {
+    execute_command_with_startup_id(exec, terminal, merge_login_env, None)
+}
+
+/// Execute a command, handing `startup_id` to the launched app
+///
+/// The id is exported as `DESKTOP_STARTUP_ID` (X11 startup notification) and
+/// `XDG_ACTIVATION_TOKEN` (Wayland xdg-activation) so the compositor focuses the
+/// new window. Without an id this is the same as [`execute_command`].
+pub fn execute_command_with_startup_id(
+    exec: &str,
+    terminal: bool,
+    merge_login_env: bool,
+    startup_id: Option<&str>,
+) -> Result<()> {
     debug!("Executing command: {} (terminal: {})", exec, terminal);
 
     // Clean up the exec string (remove field codes)
@@ -165,10 +256,137 @@ pub fn execute_command(exec: &str, terminal: bool, merge_login_env: bool) -> Res
     }
 
     if terminal {
-        return execute_in_terminal(&cleaned_exec, merge_login_env);
+        let hold = HOLD_ON_EXIT.load(Ordering::Relaxed);
+        return execute_in_terminal(&cleaned_exec, merge_login_env, startup_id, hold);
     }
 
-    execute_direct(&cleaned_exec, merge_login_env)
+    execute_direct(&cleaned_exec, merge_login_env, startup_id)
+}
+
+/// Startup notification id for launching `entry`
+///
+/// `request_id` (which asks the display for a token) is only called when the
+/// entry declares `StartupNotify=true`.
+pub fn startup_id_for(
+    entry: &DesktopEntry,
+    request_id: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    if entry.startup_notify {
+        request_id()
+    } else {
+        None
+    }
+}
+
+/// How a desktop entry is started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchMethod {
+    /// `org.freedesktop.Application.Activate` on this well-known bus name
+    DBus(String),
+    /// Spawn the expanded `Exec` line
+    Exec,
+}
+
+/// Pick the launch method for an entry
+///
+/// D-Bus activation needs `DBusActivatable=true` and a desktop file named after a
+/// valid bus name (`org.gnome.Nautilus.desktop`); terminal apps always use `Exec`.
+pub fn launch_method(entry: &DesktopEntry) -> LaunchMethod {
+    if !entry.dbus_activatable || entry.terminal {
+        return LaunchMethod::Exec;
+    }
+
+    entry
+        .path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|app_id| is_valid_bus_name(app_id))
+        .map(|app_id| LaunchMethod::DBus(app_id.to_string()))
+        .unwrap_or(LaunchMethod::Exec)
+}
+
+/// Launch a desktop entry, using D-Bus activation when the entry asks for it
+///
+/// Activation runs in a detached child process that falls back to the `Exec`
+/// line if it fails, so it survives the launcher exiting right after.
+/// `startup_id` comes from [`startup_id_for`].
+pub fn launch_entry(
+    entry: &DesktopEntry,
+    merge_login_env: bool,
+    startup_id: Option<String>,
+) -> Result<()> {
+    let exec = expand_exec(&entry.exec, &[]);
+
+    match launch_method(entry) {
+        LaunchMethod::Exec => execute_command_with_startup_id(
+            &exec,
+            entry.terminal,
+            merge_login_env,
+            startup_id.as_deref(),
+        ),
+        LaunchMethod::DBus(app_id) => {
+            info!("Activating {} via D-Bus", app_id);
+            let command = dbus_activation_command(&app_id, startup_id.as_deref(), &exec);
+            direct_command(&command, merge_login_env, startup_id.as_deref())
+                .spawn()
+                .context("Failed to start D-Bus activation")?;
+            Ok(())
+        }
+    }
+}
+
+/// Shell command calling `org.freedesktop.Application.Activate` on the session
+/// bus, running `exec` instead if activation fails
+fn dbus_activation_command(app_id: &str, startup_id: Option<&str>, exec: &str) -> String {
+    let activate = [
+        "gdbus",
+        "call",
+        "--session",
+        "--dest",
+        app_id,
+        "--object-path",
+        &dbus_object_path(app_id),
+        "--method",
+        "org.freedesktop.Application.Activate",
+        &activation_platform_data(startup_id),
+    ]
+    .iter()
+    .map(|arg| shell_quote(arg))
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let script = format!("{} >/dev/null 2>&1 || exec {}", activate, exec);
+    format!("sh -c {}", shell_quote(&script))
+}
+
+/// `platform-data` argument for `Activate`, in GVariant text format
+fn activation_platform_data(startup_id: Option<&str>) -> String {
+    match startup_id {
+        Some(id) => {
+            let id = id.replace('\\', "\\\\").replace('\'', "\\'");
+            format!("{{'desktop-startup-id': <'{id}'>, 'activation-token': <'{id}'>}}")
+        }
+        None => "{}".to_string(),
+    }
+}
+
+/// Object path for an application id (`org.gnome.Nautilus` -> `/org/gnome/Nautilus`)
+fn dbus_object_path(app_id: &str) -> String {
+    format!("/{}", app_id.replace('.', "/").replace('-', "_"))
+}
+
+/// Whether `name` is a valid well-known D-Bus name (at least two elements, none
+/// empty or starting with a digit, only `[A-Za-z0-9_-]`)
+fn is_valid_bus_name(name: &str) -> bool {
+    name.len() <= 255
+        && name.contains('.')
+        && name.split('.').all(|element| {
+            !element.is_empty()
+                && !element.starts_with(|c: char| c.is_ascii_digit())
+                && element
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
 }
 
 pub fn build_open_command(target: impl AsRef<str>) -> String {
@@ -176,27 +394,192 @@ pub fn build_open_command(target: impl AsRef<str>) -> String {
     format!("{}{}", OPEN_COMMAND_PREFIX, encoded)
 }
 
-/// Remove desktop entry field codes from exec string
-fn clean_exec_string(exec: &str) -> String {
-    let mut result = exec.to_string();
-
-    // Remove common field codes according to Desktop Entry Specification
-    let field_codes = [
-        "%f", "%F", // single/multiple files
-        "%u", "%U", // single/multiple URLs
-        "%d", "%D", // deprecated
-        "%n", "%N", // deprecated
-        "%i", // icon
-        "%c", // translated name
-        "%k", // location of desktop file
-        "%v", // deprecated
-        "%m", // deprecated
-    ];
+/// Folder to open for a path: the path itself if it's a directory, else its parent
+pub fn containing_folder(path: &str) -> String {
+    let path = std::path::Path::new(path);
+    if path.is_dir() {
+        return path.to_string_lossy().to_string();
+    }
+
+    path.parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+/// Shell command line for a result's command, as copied by Ctrl+Shift+C
+///
+/// Field codes are dropped and `open://` targets become a call to the configured
+/// opener (`xdg-open` by default).
+/// Returns `None` for the launcher's internal `@insert:`/`@theme:` commands.
+pub fn copyable_command(exec: &str) -> Option<String> {
+    let cleaned = clean_exec_string(exec);
+    if cleaned.is_empty() || cleaned.starts_with('@') {
+        return None;
+    }
+
+    let target = cleaned
+        .strip_prefix(OPEN_COMMAND_PREFIX)
+        .or_else(|| cleaned.strip_prefix(LEGACY_OPEN_COMMAND_PREFIX));
+    match target {
+        Some(encoded) => {
+            let decoded = decode(encoded).ok()?;
+            Some(open_command_line(&decoded))
+        }
+        None => Some(cleaned),
+    }
+}
+
+/// Expand a desktop entry `Exec` value into a shell command line
+///
+/// Follows the Exec key rules of the Desktop Entry Specification: arguments are
+/// split on unquoted whitespace, double-quoted arguments have `\"`, `` \` ``, `\$`
+/// and `\\` unescaped, `%%` becomes a literal `%`, `%f`/`%u` take the first entry of
+/// `files`, `%F`/`%U` expand to every entry, and all other field codes (including
+/// the deprecated ones) are dropped. Each resulting argument is re-quoted so it
+/// reaches the program unchanged through the launcher's `sh -c` invocation.
+pub fn expand_exec(exec: &str, files: &[&str]) -> String {
+    let mut args: Vec<String> = Vec::new();
+
+    for arg in split_exec_args(exec) {
+        match arg.as_str() {
+            "%f" | "%u" => args.extend(files.first().map(|f| f.to_string())),
+            "%F" | "%U" => args.extend(files.iter().map(|f| f.to_string())),
+            _ => {
+                if let Some(expanded) = expand_inline_field_codes(&arg, files) {
+                    args.push(expanded);
+                }
+            }
+        }
+    }
+
+    args.iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split an Exec value into arguments, honouring double quotes and backslash escapes
+///
+/// Single quotes are not part of the spec but are common in hand-written desktop
+/// files (`sh -c '...'`), so they are treated as literal quoting as well.
+fn split_exec_args(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = exec.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match quote {
+            Some('"') => match ch {
+                '"' => quote = None,
+                '\\' => match chars.peek() {
+                    Some(&next @ ('"' | '`' | '$' | '\\')) => {
+                        current.push(next);
+                        chars.next();
+                    }
+                    _ => current.push(ch),
+                },
+                _ => current.push(ch),
+            },
+            Some(q) => {
+                if ch == q {
+                    quote = None;
+                } else {
+                    current.push(ch);
+                }
+            }
+            None => match ch {
+                '"' | '\'' => {
+                    quote = Some(ch);
+                    in_arg = true;
+                }
+                c if c.is_whitespace() => {
+                    if in_arg {
+                        args.push(std::mem::take(&mut current));
+                        in_arg = false;
+                    }
+                }
+                _ => {
+                    current.push(ch);
+                    in_arg = true;
+                }
+            },
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
 
-    for code in &field_codes {
-        result = result.replace(code, "");
+    args
+}
+
+/// Expand field codes embedded inside a larger argument (e.g. `--file=%f`)
+///
+/// Returns `None` when the argument consisted only of dropped field codes.
+fn expand_inline_field_codes(arg: &str, files: &[&str]) -> Option<String> {
+    let mut result = String::with_capacity(arg.len());
+    let mut had_code = false;
+    let mut chars = arg.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            result.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('%') => result.push('%'),
+            Some('f') | Some('u') => {
+                had_code = true;
+                if let Some(file) = files.first() {
+                    result.push_str(file);
+                }
+            }
+            Some('F') | Some('U') => {
+                had_code = true;
+                result.push_str(&files.join(" "));
+            }
+            // %i, %c, %k and the deprecated codes are not supported
+            Some(_) | None => had_code = true,
+        }
     }
 
+    if had_code && result.is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+/// Quote an argument for `sh` when it contains anything beyond safe characters
+fn shell_quote(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+".contains(c));
+
+    if is_safe {
+        return arg.to_string();
+    }
+
+    shell_escape(arg)
+}
+
+/// Single-quote `value` for `sh`, whatever it contains
+pub fn shell_escape(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Remove stray desktop entry field codes from a command string
+///
+/// Desktop entries are expanded with [`expand_exec`] before they reach this point;
+/// this only guards plugin-provided commands, so it removes whole-word field codes
+/// and leaves things like `date +%d` untouched.
+fn clean_exec_string(exec: &str) -> String {
+    let mut result = exec.trim().to_string();
+
     // Remove quotes if the entire string is quoted
     if result.starts_with('"') && result.ends_with('"') && result.len() > 1 {
         result = result[1..result.len() - 1].to_string();
@@ -206,14 +589,66 @@ fn clean_exec_string(exec: &str) -> String {
         result = result[1..result.len() - 1].to_string();
     }
 
-    // Clean up extra whitespace
-    result.split_whitespace().collect::<Vec<_>>().join(" ")
+    // Field codes according to Desktop Entry Specification
+    const FIELD_CODES: [&str; 13] = [
+        "%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%i", "%c", "%k", "%v", "%m",
+    ];
+
+    // Clean up extra whitespace while dropping field-code words
+    result
+        .split_whitespace()
+        .filter(|word| !FIELD_CODES.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// Execute command directly with proper detachment
-fn execute_direct(exec: &str, merge_login_env: bool) -> Result<()> {
+fn execute_direct(exec: &str, merge_login_env: bool, startup_id: Option<&str>) -> Result<()> {
     info!("Launching: {}", exec);
 
+    direct_command(exec, merge_login_env, startup_id)
+        .spawn()
+        .context("Failed to execute command")?;
+
+    info!("Successfully launched: {}", exec);
+    Ok(())
+}
+
+/// Execute a command typed into the shell plugin, sending a notification if it
+/// fails when `[shell] notify_on_error` is set
+///
+/// The exit status is checked by a detached `sh` wrapper around the command, so
+/// nothing is left running in (or piped to) the launcher once it exits.
+pub fn execute_shell_command(exec: &str, terminal: bool, merge_login_env: bool) -> Result<()> {
+    if !NOTIFY_ON_ERROR.load(Ordering::Relaxed) {
+        return execute_command(exec, terminal, merge_login_env);
+    }
+
+    // In a terminal the error output stays on screen; otherwise it is kept for
+    // the notification
+    let wrapped = failure_notification_command(exec, !terminal);
+    execute_command(&wrapped, terminal, merge_login_env)
+}
+
+/// Wrap `exec` so it sends a `notify-send` notification with the command (and
+/// the end of its error output when `capture_stderr` is set) if it fails
+fn failure_notification_command(exec: &str, capture_stderr: bool) -> String {
+    let run = if capture_stderr {
+        format!(
+            r#"err=$(mktemp) || exit 1; sh -c "$1" 2>"$err"; status=$?; details=$(grep -v "^[[:space:]]*$" "$err" | tail -n {}); rm -f "$err""#,
+            NOTIFY_STDERR_LINES
+        )
+    } else {
+        r#"sh -c "$1"; status=$?; details="""#.to_string()
+    };
+    let notify = r#"[ "$status" -eq 0 ] || notify-send --app-name="Native Launcher" --urgency=critical --icon=dialog-error -- "Command failed (exit $status)" "$(printf "%s\n\n%s" "$1" "$details")"; exit "$status""#;
+
+    let script = format!("{}; {}", run, notify);
+    format!("sh -c {} sh {}", shell_quote(&script), shell_quote(exec))
+}
+
+/// Build the detached shell command used to launch `exec`
+fn direct_command(exec: &str, merge_login_env: bool, startup_id: Option<&str>) -> Command {
     // Use setsid to detach the process from the terminal
     // This prevents the child process from being killed when the launcher exits
     let full_command = format!("setsid -f {}", exec);
@@ -227,41 +662,59 @@ fn execute_direct(exec: &str, merge_login_env: bool) -> Result<()> {
         .stderr(Stdio::null());
 
     apply_launch_environment(&mut command, merge_login_env);
+    apply_startup_id(&mut command, startup_id);
+    command
+}
+
+/// Run a command in a terminal that stays open after it exits (Ctrl+Alt+Enter),
+/// whatever `[terminal] hold_on_exit` says
+pub fn execute_held_open(exec: &str, merge_login_env: bool) -> Result<()> {
+    let cleaned_exec = clean_exec_string(exec);
+    if cleaned_exec.is_empty() {
+        warn!("Empty command after cleaning, skipping execution");
+        return Ok(());
+    }
 
-    command.spawn().context("Failed to execute command")?;
+    execute_in_terminal(&cleaned_exec, merge_login_env, None, true)
+}
 
-    info!("Successfully launched: {}", exec);
-    Ok(())
+/// Wrap `exec` so the terminal waits for Enter once it finishes, showing the
+/// exit status so failures can be read before the window closes
+pub fn hold_open_command(exec: &str) -> String {
+    format!(
+        "{}; status=$?; echo; echo \"[exited with status $status] Press Enter to close\"; read -r _",
+        exec
+    )
 }
 
-/// Execute command in terminal
-fn execute_in_terminal(exec: &str, merge_login_env: bool) -> Result<()> {
+/// Execute command in terminal, holding it open afterwards when `hold` is set
+fn execute_in_terminal(
+    exec: &str,
+    merge_login_env: bool,
+    startup_id: Option<&str>,
+    hold: bool,
+) -> Result<()> {
     let terminal = detect_terminal()?;
     info!("Launching in terminal {}: {}", terminal, exec);
 
-    // Different terminals have different command-line syntax
-    let terminal_cmd = match terminal.as_str() {
-        "alacritty" => format!("{} -e sh -c '{}'", terminal, exec),
-        "kitty" => format!("{} sh -c '{}'", terminal, exec),
-        "wezterm" => format!("{} start sh -c '{}'", terminal, exec),
-        "foot" => format!("{} sh -c '{}'", terminal, exec),
-        "gnome-terminal" => format!("{} -- sh -c '{}'", terminal, exec),
-        "konsole" => format!("{} -e sh -c '{}'", terminal, exec),
-        "xterm" => format!("{} -e sh -c '{}'", terminal, exec),
-        _ => format!("{} -e sh -c '{}'", terminal, exec),
+    // The command reaches the terminal as separate arguments, so quoted paths
+    // with spaces survive; only shell syntax (and holding) needs `sh -c`
+    let argv = if hold {
+        shell_argv(&hold_open_command(exec))
+    } else {
+        command_argv(exec)
     };
 
-    let full_command = format!("setsid -f {}", terminal_cmd);
-
-    let mut command = Command::new("sh");
+    let mut command = Command::new("setsid");
     command
-        .arg("-c")
-        .arg(&full_command)
+        .arg("-f")
+        .args(terminal_argv(&terminal, argv))
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null());
 
     apply_launch_environment(&mut command, merge_login_env);
+    apply_startup_id(&mut command, startup_id);
 
     command
         .spawn()
@@ -271,8 +724,160 @@ fn execute_in_terminal(exec: &str, merge_login_env: bool) -> Result<()> {
     Ok(())
 }
 
-/// Detect available terminal emulator
+/// Command line that runs `exec` through `sh -c` inside `terminal`
+fn terminal_command(terminal: &str, exec: &str) -> String {
+    terminal_argv(terminal, shell_argv(exec))
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Arguments running `argv` inside `terminal` (which may carry its own options,
+/// e.g. `kitty --single-instance`)
+fn terminal_argv(terminal: &str, argv: Vec<String>) -> Vec<String> {
+    let mut args = split_exec_args(terminal);
+    let program = args.first().map(|program| {
+        Path::new(program)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+
+    // Different terminals have different command-line syntax
+    match program.as_deref() {
+        Some("kitty" | "foot") => {}
+        Some("wezterm") => args.push("start".to_string()),
+        Some("gnome-terminal") => args.push("--".to_string()),
+        _ => args.push("-e".to_string()),
+    }
+    args.extend(argv);
+    args
+}
+
+/// `exec` as program and arguments, or run through `sh -c` when it uses shell
+/// syntax
+fn command_argv(exec: &str) -> Vec<String> {
+    shell_words(exec).unwrap_or_else(|| shell_argv(exec))
+}
+
+fn shell_argv(exec: &str) -> Vec<String> {
+    vec!["sh".to_string(), "-c".to_string(), exec.to_string()]
+}
+
+/// Split a plain shell command line into words, undoing quotes and backslash
+/// escapes (as written by [`expand_exec`])
+///
+/// Returns `None` when the line needs a shell to mean the same: pipes, lists,
+/// redirections, substitutions, globs, `~`, comments or a `VAR=value` prefix.
+fn shell_words(command: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => current.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '$' | '`' => return None,
+                        '\\' => {
+                            let next = chars.next()?;
+                            if !matches!(next, '"' | '\\' | '$' | '`') {
+                                current.push('\\');
+                            }
+                            current.push(next);
+                        }
+                        c => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                current.push(chars.next()?);
+            }
+            '|' | '&' | ';' | '<' | '>' | '(' | ')' | '$' | '`' | '*' | '?' | '[' | '{' | '}'
+            | '#' | '~' | '\n' => return None,
+            '=' if words.is_empty() => return None,
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+    (!words.is_empty()).then_some(words)
+}
+
+/// Run a command with root privileges (Ctrl+Shift+R)
+///
+/// GUI commands go through `pkexec`; terminal commands run under `sudo` in the
+/// configured (or detected) terminal emulator.
+pub fn execute_elevated(exec: &str, terminal: bool, merge_login_env: bool) -> Result<()> {
+    let emulator = if terminal {
+        Some(detect_terminal()?)
+    } else {
+        None
+    };
+
+    let command = build_elevated_command(exec, emulator.as_deref());
+    info!("Launching elevated: {}", command);
+
+    direct_command(&command, merge_login_env, None)
+        .spawn()
+        .context("Failed to execute elevated command")?;
+    Ok(())
+}
+
+/// Wrap `exec` to run as root: `sudo` inside `terminal_emulator` when given,
+/// otherwise `pkexec` with the display variables it would strip passed through
+pub fn build_elevated_command(exec: &str, terminal_emulator: Option<&str>) -> String {
+    let exec = clean_exec_string(exec);
+
+    match terminal_emulator {
+        Some(terminal) => terminal_command(terminal, &format!("sudo {}", exec)),
+        None => {
+            let env: Vec<String> = ELEVATED_GUI_ENV
+                .iter()
+                .map(|key| format!("{key}=\"${key}\""))
+                .collect();
+            format!("pkexec env {} sh -c {}", env.join(" "), shell_quote(&exec))
+        }
+    }
+}
+
+/// Detect available terminal emulator, preferring `[terminal] command`
 fn detect_terminal() -> Result<String> {
+    if let Some(configured) = CONFIGURED_TERMINAL
+        .read()
+        .expect("terminal config poisoned")
+        .clone()
+    {
+        debug!("Using configured terminal: {}", configured);
+        return Ok(configured);
+    }
+
     let terminals = [
         "alacritty",
         "kitty",
@@ -313,6 +918,14 @@ fn apply_launch_environment(command: &mut Command, merge_login_env: bool) {
     }
 }
 
+/// Export the startup notification id / activation token to the launched app
+fn apply_startup_id(command: &mut Command, startup_id: Option<&str>) {
+    if let Some(id) = startup_id {
+        command.env("DESKTOP_STARTUP_ID", id);
+        command.env("XDG_ACTIVATION_TOKEN", id);
+    }
+}
+
 /// Load environment variables from the user's login shell and merge with current env
 fn load_shell_environment() -> HashMap<String, String> {
     let mut merged: HashMap<String, String> = std::env::vars().collect();
@@ -413,6 +1026,16 @@ fn open_uri(encoded_target: &str, merge_login_env: bool) -> Result<()> {
         }
     }
 
+    let configured = CONFIGURED_OPEN_COMMAND
+        .read()
+        .expect("open command config poisoned")
+        .clone();
+    if let Some(template) = configured {
+        let command = render_open_command(&template, &target);
+        info!("Opening with configured opener: {}", command);
+        return execute_command(&command, false, merge_login_env);
+    }
+
     if let Err(err) = spawn_file_opener("gio", Some("open"), &target, merge_login_env) {
         debug!(
             "gio open unavailable or failed ({}), falling back to xdg-open",
@@ -619,4 +1242,310 @@ mod tests {
         let _ = fs::remove_file(&path);
         Ok(())
     }
+
+    #[test]
+    fn expand_exec_drops_url_code_without_targets() {
+        assert_eq!(expand_exec("firefox %u", &[]), "firefox");
+        assert_eq!(
+            expand_exec("firefox %u", &["https://example.com"]),
+            "firefox https://example.com"
+        );
+    }
+
+    #[test]
+    fn expand_exec_expands_file_list() {
+        assert_eq!(expand_exec("gimp %F", &[]), "gimp");
+        assert_eq!(
+            expand_exec("gimp-2.10 %F", &["/tmp/a.png", "/tmp/b c.png"]),
+            "gimp-2.10 /tmp/a.png '/tmp/b c.png'"
+        );
+    }
+
+    #[test]
+    fn expand_exec_unescapes_percent_literal() {
+        assert_eq!(expand_exec("printf %%literal", &[]), "printf '%literal'");
+    }
+
+    #[test]
+    fn expand_exec_handles_quoting_and_unsupported_codes() {
+        assert_eq!(
+            expand_exec(r#""/opt/My App/run" --icon %i --name=%c %k"#, &[]),
+            "'/opt/My App/run' --icon --name="
+        );
+        assert_eq!(
+            expand_exec(r#"sh -c "echo \"hi\" \$HOME""#, &[]),
+            r#"sh -c 'echo "hi" $HOME'"#
+        );
+    }
+
+    #[test]
+    fn open_command_template_quotes_target() {
+        assert_eq!(
+            render_open_command("handlr open {}", "/tmp/My Notes.txt"),
+            "handlr open '/tmp/My Notes.txt'"
+        );
+        // Without `{}` the target is appended
+        assert_eq!(
+            render_open_command("firefox --new-tab", "https://x.org/?q=a&b"),
+            "firefox --new-tab 'https://x.org/?q=a&b'"
+        );
+        assert_eq!(
+            render_open_command("opener {} --wait", "it's; rm -rf ~"),
+            "opener 'it'\\''s; rm -rf ~' --wait"
+        );
+
+        // The default is the plain xdg-open call used before it was configurable
+        assert_eq!(
+            open_command_line("/home/me/My Notes.txt"),
+            "xdg-open '/home/me/My Notes.txt'"
+        );
+        assert_eq!(
+            open_command_line("https://example.com/test"),
+            "xdg-open https://example.com/test"
+        );
+    }
+
+    #[test]
+    fn copyable_command_for_each_result_kind() {
+        assert_eq!(copyable_command("firefox %u").as_deref(), Some("firefox"));
+        assert_eq!(
+            copyable_command(&build_open_command("/home/me/My Notes.txt")).as_deref(),
+            Some("xdg-open '/home/me/My Notes.txt'")
+        );
+        assert_eq!(copyable_command("@theme:dracula"), None);
+        assert_eq!(copyable_command("@insert:@calc 2+2"), None);
+
+        // The copied text goes through the clipboard tool verbatim
+        let command = copyable_command("code --new-window %F").unwrap();
+        assert_eq!(
+            crate::utils::clipboard::ClipboardTool::WlCopy.copy_command(&command),
+            "printf '%s' 'code --new-window' | wl-copy"
+        );
+    }
+
+    fn entry_at(path: &str, dbus_activatable: bool) -> DesktopEntry {
+        DesktopEntry {
+            name: "Files".to_string(),
+            generic_name: None,
+            comment: None,
+            exec: "nautilus --new-window".to_string(),
+            icon: None,
+            categories: vec![],
+            keywords: vec![],
+            terminal: false,
+            path: std::path::PathBuf::from(path),
+            no_display: false,
+            hidden: false,
+            dbus_activatable,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
+            actions: vec![],
+        }
+    }
+
+    #[test]
+    fn launch_method_uses_dbus_for_activatable_entries() {
+        let entry = entry_at("/usr/share/applications/org.gnome.Nautilus.desktop", true);
+        assert_eq!(
+            launch_method(&entry),
+            LaunchMethod::DBus("org.gnome.Nautilus".to_string())
+        );
+        assert_eq!(
+            dbus_object_path("org.gnome.Nautilus"),
+            "/org/gnome/Nautilus"
+        );
+        assert_eq!(dbus_object_path("org.kde.dolphin-x"), "/org/kde/dolphin_x");
+        assert_eq!(
+            dbus_activation_command("org.gnome.Nautilus", None, "nautilus --new-window"),
+            "sh -c 'gdbus call --session --dest org.gnome.Nautilus --object-path \
+             /org/gnome/Nautilus --method org.freedesktop.Application.Activate '\\''{}'\\'' \
+             >/dev/null 2>&1 || exec nautilus --new-window'"
+        );
+
+        // Not activatable, terminal apps and non bus-name file names use Exec
+        let plain = entry_at("/usr/share/applications/org.gnome.Nautilus.desktop", false);
+        assert_eq!(launch_method(&plain), LaunchMethod::Exec);
+        let terminal = DesktopEntry {
+            terminal: true,
+            ..entry.clone()
+        };
+        assert_eq!(launch_method(&terminal), LaunchMethod::Exec);
+        let unnamed = entry_at("/usr/share/applications/nautilus.desktop", true);
+        assert_eq!(launch_method(&unnamed), LaunchMethod::Exec);
+        let numeric = entry_at("/usr/share/applications/org.7zip.App.desktop", true);
+        assert_eq!(launch_method(&numeric), LaunchMethod::Exec);
+    }
+
+    #[test]
+    fn startup_id_exported_for_startup_notify_entries() {
+        let notifying = DesktopEntry {
+            startup_notify: true,
+            ..entry_at("/usr/share/applications/org.gnome.gedit.desktop", false)
+        };
+        let startup_id = startup_id_for(&notifying, || Some("launcher-42".to_string()));
+        assert_eq!(startup_id.as_deref(), Some("launcher-42"));
+
+        let command = direct_command("gedit", false, startup_id.as_deref());
+        let envs: HashMap<_, _> = command.get_envs().collect();
+        for key in ["DESKTOP_STARTUP_ID", "XDG_ACTIVATION_TOKEN"] {
+            assert_eq!(
+                envs.get(std::ffi::OsStr::new(key)).copied().flatten(),
+                Some(std::ffi::OsStr::new("launcher-42"))
+            );
+        }
+
+        // Entries without StartupNotify never request an id
+        let silent = entry_at("/usr/share/applications/org.gnome.gedit.desktop", false);
+        assert_eq!(startup_id_for(&silent, || panic!("id requested")), None);
+        assert_eq!(direct_command("gedit", false, None).get_envs().count(), 0);
+
+        assert_eq!(
+            activation_platform_data(Some("it's")),
+            r"{'desktop-startup-id': <'it\'s'>, 'activation-token': <'it\'s'>}"
+        );
+        assert_eq!(activation_platform_data(None), "{}");
+    }
+
+    #[test]
+    fn elevated_command_for_gui_and_terminal() {
+        assert_eq!(
+            build_elevated_command("gparted %f", None),
+            "pkexec env DISPLAY=\"$DISPLAY\" XAUTHORITY=\"$XAUTHORITY\" \
+             WAYLAND_DISPLAY=\"$WAYLAND_DISPLAY\" XDG_RUNTIME_DIR=\"$XDG_RUNTIME_DIR\" \
+             sh -c gparted"
+        );
+        assert_eq!(
+            build_elevated_command("nano /etc/hosts", None),
+            "pkexec env DISPLAY=\"$DISPLAY\" XAUTHORITY=\"$XAUTHORITY\" \
+             WAYLAND_DISPLAY=\"$WAYLAND_DISPLAY\" XDG_RUNTIME_DIR=\"$XDG_RUNTIME_DIR\" \
+             sh -c 'nano /etc/hosts'"
+        );
+
+        assert_eq!(
+            build_elevated_command("htop", Some("kitty")),
+            "kitty sh -c 'sudo htop'"
+        );
+        assert_eq!(
+            build_elevated_command("htop", Some("gnome-terminal")),
+            "gnome-terminal -- sh -c 'sudo htop'"
+        );
+    }
+
+    #[test]
+    fn terminal_apps_get_split_argv() {
+        let exec = expand_exec(r#"app "/path with spaces/file" --name="it's" %f"#, &[]);
+        assert_eq!(
+            command_argv(&exec),
+            vec!["app", "/path with spaces/file", "--name=it's"]
+        );
+        assert_eq!(
+            terminal_argv("alacritty", command_argv(&exec)),
+            vec![
+                "alacritty",
+                "-e",
+                "app",
+                "/path with spaces/file",
+                "--name=it's"
+            ]
+        );
+        assert_eq!(
+            terminal_argv(
+                "/usr/bin/kitty --single-instance",
+                command_argv("htop -d 10")
+            ),
+            vec!["/usr/bin/kitty", "--single-instance", "htop", "-d", "10"]
+        );
+        assert_eq!(
+            terminal_argv("gnome-terminal", command_argv(r"vim my\ notes.txt")),
+            vec!["gnome-terminal", "--", "vim", "my notes.txt"]
+        );
+
+        // Shell syntax still runs through `sh -c`, as one argument
+        for exec in [
+            "ls | less",
+            "EDITOR=vim ranger",
+            "cd ~/src && make",
+            "echo \"$HOME\"",
+        ] {
+            assert_eq!(command_argv(exec), vec!["sh", "-c", exec], "{}", exec);
+        }
+        assert_eq!(
+            command_argv("app 'unterminated"),
+            vec!["sh", "-c", "app 'unterminated"]
+        );
+    }
+
+    #[test]
+    fn hold_open_command_waits_after_exit() {
+        let held = hold_open_command("./build.sh --release");
+        assert_eq!(
+            held,
+            "./build.sh --release; status=$?; echo; \
+             echo \"[exited with status $status] Press Enter to close\"; read -r _"
+        );
+        // The wrapper adds no single quotes, so it nests in the terminal's `sh -c '...'`
+        assert!(!held.contains('\''));
+        assert_eq!(
+            terminal_command("kitty", &held),
+            format!("kitty sh -c '{}'", held)
+        );
+    }
+
+    #[test]
+    fn error_notification_for_failed_command() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // A stand-in notify-send that records its arguments, one per line
+        let dir = std::env::temp_dir().join(format!("nl-notify-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let log = dir.join("args");
+        let stub = dir.join("notify-send");
+        fs::write(
+            &stub,
+            format!("#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\n", log.display()),
+        )
+        .unwrap();
+        fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+        let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap());
+        let run = |wrapped: &str| {
+            let _ = fs::remove_file(&log);
+            let status = Command::new("sh")
+                .arg("-c")
+                .arg(wrapped)
+                .env("PATH", &path)
+                .status()
+                .unwrap();
+            (status.code(), fs::read_to_string(&log).ok())
+        };
+
+        let failing = "for i in 1 2 3 4 5 6; do echo line $i >&2; done; echo; exit 3";
+        let (code, args) = run(&failure_notification_command(failing, true));
+        assert_eq!(code, Some(3));
+        assert_eq!(
+            args.unwrap(),
+            format!(
+                "--app-name=Native Launcher\n--urgency=critical\n--icon=dialog-error\n--\n\
+                 Command failed (exit 3)\n{}\n\nline 2\nline 3\nline 4\nline 5\nline 6\n",
+                failing
+            )
+        );
+
+        // In a terminal the error output isn't captured
+        let (_, args) = run(&failure_notification_command("echo oops >&2; false", false));
+        assert!(args
+            .unwrap()
+            .ends_with("Command failed (exit 1)\necho oops >&2; false\n"));
+
+        // Nothing is sent for a successful command
+        let (code, args) = run(&failure_notification_command("true", true));
+        assert_eq!((code, args), (Some(0), None));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clean_exec_string_keeps_embedded_percent() {
+        assert_eq!(clean_exec_string("date +%d %U"), "date +%d");
+        assert_eq!(clean_exec_string("\"firefox %u\""), "firefox");
+    }
 }
@@ -0,0 +1,97 @@
+//! Data parsed from a file and parsed again once the file changes
+//!
+//! Used for state other code (or the user's shell) writes while the launcher
+//! keeps running in the background, so a daemon never serves a stale copy. The
+//! file is only stat'ed per lookup; it is re-read when its modification time
+//! differs from the one it was parsed at.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Parsed contents of `path`, cached until the file's modification time changes
+pub struct FileCache<T> {
+    path: PathBuf,
+    load: fn(&Path) -> T,
+    /// Last parse and the modification time it saw (`None`: file missing)
+    cached: Mutex<Option<(Option<SystemTime>, Arc<T>)>>,
+}
+
+impl<T> FileCache<T> {
+    /// Cache `path`, parsed with `load` on the first lookup; `load` is also
+    /// called for a missing file, so it decides what "no file" means
+    pub fn new(path: PathBuf, load: fn(&Path) -> T) -> Self {
+        Self {
+            path,
+            load,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Current contents, parsed again if the file changed since the last lookup
+    pub fn get(&self) -> Arc<T> {
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        match &*cached {
+            Some((at, value)) if *at == modified => value.clone(),
+            _ => {
+                let value = Arc::new((self.load)(&self.path));
+                *cached = Some((modified, value.clone()));
+                value
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for FileCache<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileCache")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reparsed_only_when_modified() {
+        let dir =
+            std::env::temp_dir().join(format!("native-launcher-file-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("lines");
+
+        let cache = FileCache::new(path.clone(), |path| {
+            fs::read_to_string(path).unwrap_or_default()
+        });
+        assert_eq!(*cache.get(), "");
+
+        fs::write(&path, "first").unwrap();
+        let first = cache.get();
+        assert_eq!(*first, "first");
+        // Unchanged file: the same parse is handed out again
+        assert!(Arc::ptr_eq(&first, &cache.get()));
+
+        fs::write(&path, "second").unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(120))
+            .unwrap();
+        assert_eq!(*cache.get(), "second");
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(*cache.get(), "");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,8 +1,12 @@
 use crate::desktop::DesktopEntryArena;
+use anyhow::{anyhow, Context, Result};
+use gtk4::gdk_pixbuf::prelude::*;
+use gtk4::gdk_pixbuf::{InterpType, Pixbuf, PixbufLoader};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
-use tracing::debug;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use tracing::{debug, warn};
 
 /// Icon cache to avoid repeated lookups
 static ICON_CACHE: Mutex<Option<HashMap<String, Option<PathBuf>>>> = Mutex::new(None);
@@ -21,17 +25,9 @@ pub fn resolve_icon(icon_name: &str) -> Option<PathBuf> {
 /// Resolve an icon path with a specific size
 pub fn resolve_icon_with_size(icon_name: &str, size: u32) -> Option<PathBuf> {
     // Check cache first
-    {
-        let mut cache = ICON_CACHE.lock().unwrap();
-        if cache.is_none() {
-            *cache = Some(HashMap::new());
-        }
-
-        let cache_key = format!("{}:{}", icon_name, size);
-        if let Some(cached_path) = cache.as_ref().unwrap().get(&cache_key) {
-            debug!("Icon cache hit for: {}", icon_name);
-            return cached_path.clone();
-        }
+    if let Some(cached_path) = cached_icon(icon_name, size) {
+        debug!("Icon cache hit for: {}", icon_name);
+        return cached_path;
     }
 
     // 1. Check if it's an absolute path
@@ -99,8 +95,8 @@ fn lookup_themed_icon(icon_name: &str, size: u32) -> Option<PathBuf> {
     }
 
     // Also try user's local pixmaps
-    if let Some(home) = dirs::home_dir() {
-        let local_pixmaps = home.join(".local/share/pixmaps");
+    if let Some(data_dir) = dirs::data_local_dir() {
+        let local_pixmaps = data_dir.join("pixmaps");
         for ext in &["png", "svg", "xpm"] {
             let pixmaps_path = local_pixmaps.join(format!("{}.{}", icon_name, ext));
             if pixmaps_path.exists() {
@@ -158,9 +154,11 @@ fn lookup_themed_icon(icon_name: &str, size: u32) -> Option<PathBuf> {
 fn get_icon_directories() -> Vec<PathBuf> {
     let mut dirs = Vec::new();
 
-    // User-specific icons
+    // User-specific icons ($XDG_DATA_HOME/icons, then the legacy ~/.icons)
+    if let Some(data_dir) = dirs::data_local_dir() {
+        dirs.push(data_dir.join("icons"));
+    }
     if let Some(home) = dirs::home_dir() {
-        dirs.push(home.join(".local/share/icons"));
         dirs.push(home.join(".icons"));
     }
 
@@ -179,13 +177,116 @@ fn get_icon_directories() -> Vec<PathBuf> {
     dirs.into_iter().filter(|d| d.exists()).collect()
 }
 
+/// Result of an earlier lookup of `icon_name` at `size`; `None` if it wasn't looked up
+fn cached_icon(icon_name: &str, size: u32) -> Option<Option<PathBuf>> {
+    let cache = ICON_CACHE.lock().unwrap();
+    cache
+        .as_ref()?
+        .get(&format!("{}:{}", icon_name, size))
+        .cloned()
+}
+
 /// Cache an icon lookup result
 fn cache_icon(icon_name: &str, size: u32, path: Option<PathBuf>) {
     let mut cache = ICON_CACHE.lock().unwrap();
-    if let Some(cache_map) = cache.as_mut() {
-        let cache_key = format!("{}:{}", icon_name, size);
-        cache_map.insert(cache_key, path);
+    let cache_key = format!("{}:{}", icon_name, size);
+    cache
+        .get_or_insert_with(HashMap::new)
+        .insert(cache_key, path);
+}
+
+/// Icon of the first of `candidates` that has one, else the default icon
+pub fn resolve_first_or_default(candidates: &[String], size: u32) -> PathBuf {
+    candidates
+        .iter()
+        .find_map(|name| resolve_icon_with_size(name, size))
+        .unwrap_or_else(get_default_icon)
+}
+
+/// Called with the icon once a background lookup finishes
+type ApplyIcon = Box<dyn FnOnce(PathBuf) + Send>;
+
+/// Background lookups (candidates and size) queued or running, with the
+/// callbacks waiting for each
+type PendingLookups = HashMap<(Vec<String>, u32), Vec<ApplyIcon>>;
+
+static PENDING_LOOKUPS: Mutex<Option<PendingLookups>> = Mutex::new(None);
+
+/// Queue of the thread that runs all background lookups
+static LOOKUP_QUEUE: OnceLock<Sender<(Vec<String>, u32)>> = OnceLock::new();
+
+/// [`resolve_first_or_default`] without blocking on theme lookups
+///
+/// Returns the icon right away when every lookup it needs is cached. Otherwise
+/// the lookups are queued for the icon worker thread, which hands the icon to
+/// `apply`, and `None` is returned; asking again while the same lookup is still
+/// queued only adds `apply` to its callbacks. `apply` runs on the worker
+/// thread, so widget updates belong in `glib::idle_add_once`.
+pub fn resolve_icon_in_background(
+    candidates: Vec<String>,
+    size: u32,
+    apply: impl FnOnce(PathBuf) + Send + 'static,
+) -> Option<PathBuf> {
+    let mut all_cached = true;
+    for name in &candidates {
+        match cached_icon(name, size) {
+            Some(Some(path)) => return Some(path),
+            Some(None) => continue,
+            None => {
+                all_cached = false;
+                break;
+            }
+        }
+    }
+    if all_cached {
+        return Some(get_default_icon());
     }
+
+    let key = (candidates, size);
+    {
+        let mut pending = PENDING_LOOKUPS.lock().unwrap();
+        let pending = pending.get_or_insert_with(HashMap::new);
+        if let Some(waiting) = pending.get_mut(&key) {
+            waiting.push(Box::new(apply));
+            return None;
+        }
+        pending.insert(key.clone(), vec![Box::new(apply)]);
+    }
+
+    let queue = LOOKUP_QUEUE.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<(Vec<String>, u32)>();
+        std::thread::Builder::new()
+            .name("icon-lookup".to_string())
+            .spawn(move || {
+                for key in receiver {
+                    let path = resolve_first_or_default(&key.0, key.1);
+                    let waiting = PENDING_LOOKUPS
+                        .lock()
+                        .unwrap()
+                        .as_mut()
+                        .and_then(|pending| pending.remove(&key))
+                        .unwrap_or_default();
+                    for apply in waiting {
+                        apply(path.clone());
+                    }
+                }
+            })
+            .expect("failed to spawn icon lookup thread");
+        sender
+    });
+    if queue.send(key).is_err() {
+        warn!("Icon lookup thread is gone");
+    }
+    None
+}
+
+/// Whether `icon_name` has been looked up at `size` (the lookup is cached)
+#[allow(dead_code)]
+pub fn is_icon_cached(icon_name: &str, size: u32) -> bool {
+    let cache = ICON_CACHE.lock().unwrap();
+    cache
+        .as_ref()
+        .is_some_and(|cache_map| cache_map.contains_key(&format!("{}:{}", icon_name, size)))
 }
 
 /// Clear the icon cache (useful when theme changes)
@@ -225,6 +326,30 @@ pub fn preload_icon_cache(entries: &DesktopEntryArena) {
     );
 }
 
+/// Decode an inline PNG icon (returned by a dynamic plugin), scaled to fit `size` pixels
+pub fn pixbuf_from_png(data: &[u8], size: i32) -> Result<Pixbuf> {
+    let loader = PixbufLoader::with_type("png").context("PNG loader unavailable")?;
+    loader.write(data).context("Invalid PNG icon data")?;
+    loader.close().context("Invalid PNG icon data")?;
+    let pixbuf = loader
+        .pixbuf()
+        .ok_or_else(|| anyhow!("PNG icon data decoded to no image"))?;
+
+    // Fit the longer side to `size`, keeping the aspect ratio
+    let longest = pixbuf.width().max(pixbuf.height());
+    if longest == size {
+        return Ok(pixbuf);
+    }
+    let scale = |side: i32| ((side * size) / longest).max(1);
+    pixbuf
+        .scale_simple(
+            scale(pixbuf.width()),
+            scale(pixbuf.height()),
+            InterpType::Bilinear,
+        )
+        .ok_or_else(|| anyhow!("Failed to scale icon to {}px", size))
+}
+
 /// Get default fallback icon path
 pub fn get_default_icon() -> PathBuf {
     // Try common application icon names
@@ -479,6 +604,50 @@ mod tests {
         assert_eq!(icon1, icon2);
     }
 
+    #[test]
+    fn test_background_resolution_uses_cache_first() {
+        // Cached icons come back right away, without a worker
+        cache_icon(
+            "nl-test-cached",
+            32,
+            Some(PathBuf::from("/icons/cached.png")),
+        );
+        cache_icon("nl-test-missing", 32, None);
+        let candidates = vec!["nl-test-missing".to_string(), "nl-test-cached".to_string()];
+        let resolved = resolve_icon_in_background(candidates, 32, |_| {
+            panic!("cached icons are not resolved again")
+        });
+        assert_eq!(resolved, Some(PathBuf::from("/icons/cached.png")));
+
+        // Uncached ones are resolved on a worker thread and cached for next time
+        let icon = std::env::temp_dir().join(format!("nl-test-icon-{}.png", std::process::id()));
+        std::fs::write(&icon, b"").unwrap();
+        let name = icon.to_string_lossy().to_string();
+        let (sender, receiver) = mpsc::channel();
+        let resolved = resolve_icon_in_background(vec![name.clone()], 32, {
+            let sender = sender.clone();
+            move |path| sender.send(path).unwrap()
+        });
+        assert_eq!(resolved, None);
+        // A second row with the same icon waits for the same lookup (or finds
+        // it cached already)
+        let second = sender.clone();
+        if let Some(path) = resolve_icon_in_background(vec![name.clone()], 32, move |path| {
+            second.send(path).unwrap()
+        }) {
+            sender.send(path).unwrap();
+        }
+        for _ in 0..2 {
+            let path = receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap();
+            assert_eq!(path, icon);
+        }
+        assert_eq!(cached_icon(&name, 32), Some(Some(icon.clone())));
+
+        std::fs::remove_file(&icon).unwrap();
+    }
+
     #[test]
     fn test_category_to_icon_development() {
         let categories = vec!["Development".to_string()];
@@ -0,0 +1,104 @@
+//! MIME type detection for files, used to offer "open with" apps
+//!
+//! Common extensions are mapped directly so no process is spawned while typing;
+//! anything else is asked of `xdg-mime query filetype`.
+
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+/// MIME type of a common file extension (lowercase, without the dot)
+pub fn mime_for_extension(extension: &str) -> Option<&'static str> {
+    let mime = match extension {
+        // Images
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "tif" | "tiff" => "image/tiff",
+        "ico" => "image/vnd.microsoft.icon",
+
+        // Video
+        "mp4" => "video/mp4",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "avi" => "video/x-msvideo",
+        "mov" => "video/quicktime",
+
+        // Audio
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "wav" => "audio/x-wav",
+        "ogg" => "audio/ogg",
+        "m4a" => "audio/mp4",
+
+        // Documents
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+        "odp" => "application/vnd.oasis.opendocument.presentation",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+
+        // Archives
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" => "application/gzip",
+        "xz" => "application/x-xz",
+        "7z" => "application/x-7z-compressed",
+
+        // Code
+        "rs" => "text/rust",
+        "py" => "text/x-python",
+        "js" => "application/javascript",
+        "c" => "text/x-csrc",
+        "h" => "text/x-chdr",
+        "cpp" => "text/x-c++src",
+        "sh" => "application/x-shellscript",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// MIME type of `path`: from its extension, falling back to `xdg-mime`
+pub fn mime_for_path(path: &Path) -> Option<String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    if let Some(mime) = extension.as_deref().and_then(mime_for_extension) {
+        return Some(mime.to_string());
+    }
+
+    query_xdg_mime(path)
+}
+
+/// Ask `xdg-mime query filetype` (shared-mime-info) for the type of `path`
+fn query_xdg_mime(path: &Path) -> Option<String> {
+    let output = Command::new("xdg-mime")
+        .args(["query", "filetype"])
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        debug!("xdg-mime could not tell the type of {}", path.display());
+        return None;
+    }
+
+    // Some versions append "; charset=..."
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mime = stdout.split(';').next()?.trim();
+    mime.contains('/').then(|| mime.to_string())
+}
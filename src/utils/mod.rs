@@ -1,7 +1,19 @@
+pub mod atomic;
+pub mod backup;
 pub mod browser;
+pub mod clipboard;
 pub mod exec;
+pub mod file_cache;
 pub mod icons;
+pub mod mime;
+pub mod packages;
 
+pub use atomic::atomic_write;
 #[allow(unused_imports)]
 pub use browser::get_default_browser;
-pub use exec::{build_open_command, execute_command};
+pub use exec::{
+    build_open_command, containing_folder, copyable_command, execute_command, execute_elevated,
+    execute_held_open, execute_shell_command, expand_exec, launch_entry, open_command_line,
+    shell_escape, startup_id_for,
+};
+pub use file_cache::FileCache;
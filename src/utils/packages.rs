@@ -0,0 +1,193 @@
+//! Uninstalling apps through the distribution's package manager
+//!
+//! The package owning an app is found by asking the package manager who owns the
+//! binary named in its `Exec` line (`dpkg -S`, `pacman -Qo`, `rpm -qf`); removal
+//! then runs the matching remove command in a terminal so the user can enter
+//! their password and review what gets removed.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+/// Launchers and interpreters whose owning package is not the app's own
+const WRAPPER_PROGRAMS: &[&str] = &[
+    "env", "flatpak", "snap", "sh", "bash", "zsh", "python", "python3", "java", "wine",
+];
+
+/// Package managers that can report which package owns a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    /// Debian, Ubuntu and derivatives
+    Dpkg,
+    /// Arch and derivatives
+    Pacman,
+    /// Fedora, openSUSE and other RPM distributions
+    Rpm,
+}
+
+impl PackageManager {
+    /// Detect the system package manager
+    pub fn detect() -> Option<Self> {
+        [Self::Dpkg, Self::Pacman, Self::Rpm]
+            .into_iter()
+            .find(|manager| command_exists(manager.query_program()))
+    }
+
+    fn query_program(&self) -> &'static str {
+        match self {
+            PackageManager::Dpkg => "dpkg",
+            PackageManager::Pacman => "pacman",
+            PackageManager::Rpm => "rpm",
+        }
+    }
+
+    /// Arguments asking which package owns `path`
+    fn owner_query_args(&self, path: &Path) -> Vec<String> {
+        let path = path.to_string_lossy().to_string();
+        match self {
+            PackageManager::Dpkg => vec!["-S".to_string(), path],
+            PackageManager::Pacman => vec!["-Qo".to_string(), path],
+            PackageManager::Rpm => vec![
+                "-qf".to_string(),
+                "--queryformat".to_string(),
+                "%{NAME}\\n".to_string(),
+                path,
+            ],
+        }
+    }
+
+    /// Package name from the output of the ownership query
+    ///
+    /// - dpkg: `firefox-esr: /usr/bin/firefox-esr` (several owners are comma separated)
+    /// - pacman: `/usr/bin/firefox is owned by firefox 128.0-1`
+    /// - rpm: `firefox` (thanks to `--queryformat`)
+    pub fn parse_owner(&self, output: &str) -> Option<String> {
+        let line = output
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())?;
+        let package = match self {
+            PackageManager::Dpkg => {
+                let (packages, _) = line.split_once(": ")?;
+                packages.split(',').next()?.trim()
+            }
+            PackageManager::Pacman => line
+                .split_once(" is owned by ")?
+                .1
+                .split_whitespace()
+                .next()?,
+            PackageManager::Rpm => {
+                if line.contains(' ') {
+                    // "file /x is not owned by any package"
+                    return None;
+                }
+                line
+            }
+        };
+
+        (!package.is_empty()).then(|| package.to_string())
+    }
+
+    /// Command that removes `package`
+    pub fn remove_command(&self, package: &str) -> String {
+        match self {
+            PackageManager::Dpkg => format!("sudo apt remove {}", package),
+            PackageManager::Pacman => format!("sudo pacman -R {}", package),
+            PackageManager::Rpm => format!("sudo dnf remove {}", package),
+        }
+    }
+
+    /// Package owning `path`
+    pub fn owner_of(&self, path: &Path) -> Result<String> {
+        let output = Command::new(self.query_program())
+            .args(self.owner_query_args(path))
+            .output()
+            .with_context(|| format!("Failed to run {}", self.query_program()))?;
+        if !output.status.success() {
+            return Err(anyhow!("{} is not owned by any package", path.display()));
+        }
+
+        self.parse_owner(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| anyhow!("{} is not owned by any package", path.display()))
+    }
+}
+
+/// Binary an `Exec` line runs, resolved through `PATH`; `None` for launchers
+/// such as `flatpak run` whose package is not the app's
+pub fn exec_binary(exec: &str) -> Option<PathBuf> {
+    let program = exec.split_whitespace().next()?.trim_matches('"');
+    let name = program.rsplit('/').next()?;
+    if WRAPPER_PROGRAMS.contains(&name) {
+        debug!("Not resolving wrapped Exec line: {}", exec);
+        return None;
+    }
+
+    if program.starts_with('/') {
+        return Some(PathBuf::from(program));
+    }
+
+    let output = Command::new("which").arg(program).output().ok()?;
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Package owning the app started by `exec`, with the command that removes it
+pub fn uninstall_command(manager: PackageManager, exec: &str) -> Result<(String, String)> {
+    let binary =
+        exec_binary(exec).ok_or_else(|| anyhow!("Can't tell which package provides {}", exec))?;
+    let package = manager.owner_of(&binary)?;
+    let command = manager.remove_command(&package);
+    Ok((package, command))
+}
+
+fn command_exists(cmd: &str) -> bool {
+    Command::new("which")
+        .arg(cmd)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_maps_to_remove_command() {
+        let dpkg = PackageManager::Dpkg;
+        let owner = dpkg
+            .parse_owner("firefox-esr: /usr/bin/firefox-esr\n")
+            .unwrap();
+        assert_eq!(dpkg.remove_command(&owner), "sudo apt remove firefox-esr");
+        assert_eq!(
+            dpkg.parse_owner("libc6:amd64, libc6-dev:amd64: /usr/lib/x"),
+            Some("libc6:amd64".to_string())
+        );
+        assert_eq!(dpkg.parse_owner(""), None);
+
+        let pacman = PackageManager::Pacman;
+        let owner = pacman
+            .parse_owner("/usr/bin/firefox is owned by firefox 128.0-1\n")
+            .unwrap();
+        assert_eq!(pacman.remove_command(&owner), "sudo pacman -R firefox");
+
+        let rpm = PackageManager::Rpm;
+        let owner = rpm.parse_owner("gnome-calculator\n").unwrap();
+        assert_eq!(
+            rpm.remove_command(&owner),
+            "sudo dnf remove gnome-calculator"
+        );
+        assert_eq!(
+            rpm.parse_owner("file /opt/x is not owned by any package"),
+            None
+        );
+
+        assert_eq!(
+            exec_binary("/usr/bin/gimp %U"),
+            Some(PathBuf::from("/usr/bin/gimp"))
+        );
+        assert_eq!(exec_binary("flatpak run org.gimp.GIMP"), None);
+        assert_eq!(exec_binary("/usr/bin/env FOO=1 app"), None);
+    }
+}
@@ -0,0 +1,106 @@
+//! Window size from the last session (`window.remember_geometry`)
+//!
+//! Kept in its own state file rather than config.toml, so closing the window
+//! never rewrites the user's config. The configured `window.width` / `height`
+//! apply until a size has been saved.
+
+use crate::utils::atomic_write;
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Smallest size remembered, so a window squashed by accident still opens usable
+pub const MIN_REMEMBERED_SIZE: (i32, i32) = (300, 200);
+
+/// Persisted window size, stored as `WIDTHxHEIGHT`
+#[derive(Debug, Clone)]
+pub struct WindowSizeStore {
+    path: PathBuf,
+}
+
+impl WindowSizeStore {
+    /// Store in the default cache location
+    pub fn new() -> Self {
+        Self::with_path(Self::default_path())
+    }
+
+    /// Store at a specific file
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Remember the size the window was closed at
+    ///
+    /// Only writes the file when the size actually changed.
+    pub fn save(&self, width: i32, height: i32) -> Result<()> {
+        let (min_width, min_height) = MIN_REMEMBERED_SIZE;
+        let size = (width.max(min_width), height.max(min_height));
+        if self.load() == Some(size) {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        atomic_write(&self.path, format!("{}x{}", size.0, size.1))?;
+        debug!("Saved window size {}x{} to {:?}", size.0, size.1, self.path);
+        Ok(())
+    }
+
+    /// Last saved size, if any
+    pub fn load(&self) -> Option<(i32, i32)> {
+        let contents = fs::read_to_string(&self.path).ok()?;
+        let (width, height) = contents.trim().split_once('x')?;
+        Some((width.parse().ok()?, height.parse().ok()?))
+    }
+
+    fn default_path() -> PathBuf {
+        let cache_dir = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+        cache_dir.join("native-launcher").join("window_size")
+    }
+}
+
+impl Default for WindowSizeStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_size_saved_and_restored() {
+        let dir = std::env::temp_dir().join(format!(
+            "native-launcher-window-size-{}",
+            std::process::id()
+        ));
+        let store = WindowSizeStore::with_path(dir.join("window_size"));
+        assert_eq!(store.load(), None);
+
+        store.save(900, 640).unwrap();
+        let modified = fs::metadata(dir.join("window_size"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        // Same size again: nothing to write
+        store.save(900, 640).unwrap();
+        assert_eq!(
+            fs::metadata(dir.join("window_size"))
+                .unwrap()
+                .modified()
+                .unwrap(),
+            modified
+        );
+        let restored = WindowSizeStore::with_path(dir.join("window_size")).load();
+        assert_eq!(restored, Some((900, 640)));
+
+        // A window shrunk to almost nothing comes back at the minimum size
+        store.save(10, 10).unwrap();
+        let restored = store.load();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(restored, Some(MIN_REMEMBERED_SIZE));
+    }
+}
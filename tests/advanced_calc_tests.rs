@@ -1,6 +1,9 @@
+use chrono::NaiveDate;
 use native_launcher::config::Config;
-use native_launcher::plugins::traits::{Plugin, PluginContext};
-use native_launcher::plugins::AdvancedCalculatorPlugin;
+use native_launcher::plugins::advanced_calc::{evaluate_date_expression, DateAnswer};
+use native_launcher::plugins::currency_rates::{CurrencyRates, RateTable};
+use native_launcher::plugins::traits::{Plugin, PluginContext, ResultActivation};
+use native_launcher::plugins::{AdvancedCalculatorPlugin, CalculatorPlugin};
 
 #[test]
 fn test_time_ago_query() {
@@ -135,6 +138,135 @@ fn test_currency_conversion() {
     assert!(results[0].title.contains("JPY"), "Should contain 'JPY'");
 }
 
+#[test]
+fn test_currency_conversion_uses_rate_table() {
+    let rates = RateTable {
+        fetched_at: 1_700_000_000,
+        rates: [("USD", 1.0), ("EUR", 0.5), ("GBP", 0.8)]
+            .into_iter()
+            .map(|(code, rate)| (code.to_string(), rate))
+            .collect(),
+    };
+    let plugin =
+        AdvancedCalculatorPlugin::new().with_currency_rates(CurrencyRates::from_table(rates));
+    let config = Config::default();
+    let context = PluginContext::new(10, &config);
+
+    let results = plugin.search("100 usd to eur", &context).unwrap();
+    assert_eq!(results[0].title, "50.00 EUR");
+    let subtitle = results[0].subtitle.as_deref().unwrap();
+    assert!(subtitle.starts_with("100 USD ≈ 50.00 EUR • rates from "));
+
+    let results = plugin.search("40 GBP to EUR", &context).unwrap();
+    assert_eq!(results[0].title, "25.00 EUR");
+
+    // Currencies missing from the table aren't converted
+    assert!(plugin.search("5 USD to XYZ", &context).unwrap().is_empty());
+}
+
+#[test]
+fn test_date_plus_duration() {
+    // A Monday
+    let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+    let date = |y, m, d| Some(DateAnswer::Date(NaiveDate::from_ymd_opt(y, m, d).unwrap()));
+
+    assert_eq!(
+        evaluate_date_expression("today + 30 days", today),
+        date(2025, 4, 9)
+    );
+    assert_eq!(
+        evaluate_date_expression("today - 2 weeks", today),
+        date(2025, 2, 24)
+    );
+    assert_eq!(
+        evaluate_date_expression("2024-01-31 + 1 month", today),
+        date(2024, 2, 29)
+    );
+    assert_eq!(
+        evaluate_date_expression("2024-02-29+1 year", today),
+        date(2025, 2, 28)
+    );
+    assert_eq!(
+        evaluate_date_expression("Tomorrow + 1 day", today),
+        date(2025, 3, 12)
+    );
+    assert_eq!(
+        evaluate_date_expression("yesterday", today),
+        date(2025, 3, 9)
+    );
+    assert_eq!(
+        evaluate_date_expression("next friday", today),
+        date(2025, 3, 14)
+    );
+    // "next monday" on a Monday is a week away
+    assert_eq!(
+        evaluate_date_expression("next monday", today),
+        date(2025, 3, 17)
+    );
+    assert_eq!(
+        evaluate_date_expression("today + 3 fortnights", today),
+        None
+    );
+    assert_eq!(evaluate_date_expression("2025-13-01", today), None);
+
+    let plugin = AdvancedCalculatorPlugin::new();
+    let config = Config::default();
+    let context = PluginContext::new(10, &config);
+    assert!(plugin.should_handle("today + 30 days"));
+    let results = plugin.search("today + 0 days", &context).unwrap();
+    assert_eq!(results.len(), 1);
+    assert!(results[0]
+        .subtitle
+        .as_deref()
+        .unwrap()
+        .contains("• today •"));
+}
+
+#[test]
+fn test_date_difference() {
+    let today = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+    let days = |answer: Option<DateAnswer>| match answer {
+        Some(DateAnswer::Days { from, to }) => (to - from).num_days(),
+        other => panic!("Expected a day count, got {:?}", other),
+    };
+
+    assert_eq!(
+        days(evaluate_date_expression("days until 2025-12-25", today)),
+        42
+    );
+    assert_eq!(
+        days(evaluate_date_expression("days since 2025-01-01", today)),
+        316
+    );
+    assert_eq!(
+        days(evaluate_date_expression("2024-01-01 to 2024-03-01", today)),
+        60
+    );
+    assert_eq!(
+        days(evaluate_date_expression("2024-03-01 to 2024-01-01", today)),
+        -60
+    );
+    assert!(evaluate_date_expression("days until someday", today).is_none());
+
+    let plugin = AdvancedCalculatorPlugin::new();
+    let config = Config::default();
+    let context = PluginContext::new(10, &config);
+    assert!(plugin.should_handle("2024-01-01 to 2024-03-01"));
+    let results = plugin.search("2024-01-01 to 2024-03-01", &context).unwrap();
+    assert_eq!(results[0].title, "60 days");
+    assert!(results[0].command.is_empty());
+    assert!(
+        matches!(&results[0].activation, Some(ResultActivation::Copy(value)) if value == "60 days"),
+        "Expected the day count to be copied, got {:?}",
+        results[0].activation
+    );
+    assert!(results[0]
+        .subtitle
+        .as_deref()
+        .unwrap()
+        .starts_with("2024-01-01 → 2024-03-01 (8 weeks, 4 days)"));
+}
+
 #[test]
 fn test_timezone_query() {
     let plugin = AdvancedCalculatorPlugin::new();
@@ -195,3 +327,35 @@ fn test_should_handle() {
     assert!(!plugin.should_handle("firefox"));
     assert!(!plugin.should_handle("hello world"));
 }
+
+#[test]
+fn test_calc_recall_lists_recent_calculations() {
+    let advanced = AdvancedCalculatorPlugin::new();
+    let calculator = CalculatorPlugin::new().with_history(advanced.history());
+    let config = Config::default();
+    let context = PluginContext::new(10, &config);
+
+    // Nothing evaluated yet
+    assert!(advanced.search("@calc", &context).unwrap().is_empty());
+
+    // Typing "2+2*3" evaluates each valid prefix; only the final one is kept
+    for query in ["2", "2+2", "2+2*3", "@calc 2.5*3", "7*6"] {
+        calculator.search(query, &context).unwrap();
+    }
+
+    let results = advanced.search("@calc", &context).unwrap();
+    let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+    assert_eq!(titles, vec!["7*6 = 42", "2.5*3 = 7.5", "2+2*3 = 8"]);
+    assert_eq!(results[0].command, "@insert:@calc 7*6");
+    assert!(results[0].score > results[1].score);
+
+    // Recomputing an older expression moves it to the top
+    calculator.search("2.5*3", &context).unwrap();
+    let recent: Vec<_> = advanced
+        .history()
+        .recent()
+        .into_iter()
+        .map(|(expression, _)| expression)
+        .collect();
+    assert_eq!(recent, vec!["2.5*3", "7*6", "2+2*3"]);
+}
@@ -8,6 +8,7 @@ mod tests {
         DesktopEntry {
             name: name.to_string(),
             generic_name: None,
+            comment: None,
             exec: exec.to_string(),
             icon: None,
             categories: vec![],
@@ -15,6 +16,11 @@ mod tests {
             terminal: false,
             path: PathBuf::from("/test"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         }
     }
@@ -49,6 +55,7 @@ mod tests {
         let entry = DesktopEntry {
             name: "Firefox".to_string(),
             generic_name: Some("Web Browser".to_string()),
+            comment: None,
             exec: "firefox".to_string(),
             icon: Some("firefox".to_string()),
             categories: vec!["Network".to_string()],
@@ -56,6 +63,11 @@ mod tests {
             terminal: false,
             path: std::path::PathBuf::from("/test"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         };
 
@@ -87,6 +99,7 @@ mod tests {
         let entry = DesktopEntry {
             name: "Visual Studio Code".to_string(),
             generic_name: Some("Code Editor".to_string()),
+            comment: None,
             exec: "code".to_string(),
             icon: Some("vscode".to_string()),
             categories: vec!["Development".to_string()],
@@ -94,6 +107,11 @@ mod tests {
             terminal: false,
             path: PathBuf::from("/test"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         };
 
@@ -109,6 +127,7 @@ mod tests {
         let entry = DesktopEntry {
             name: "Firefox".to_string(),
             generic_name: Some("Web Browser".to_string()),
+            comment: None,
             exec: "firefox".to_string(),
             icon: Some("firefox".to_string()),
             categories: vec!["Network".to_string()],
@@ -116,6 +135,11 @@ mod tests {
             terminal: false,
             path: std::path::PathBuf::from("/test"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         };
 
@@ -138,6 +162,7 @@ mod tests {
         let entry = DesktopEntry {
             name: "Code".to_string(),
             generic_name: Some("Text Editor".to_string()),
+            comment: None,
             exec: "code".to_string(),
             icon: None,
             categories: vec![],
@@ -145,6 +170,11 @@ mod tests {
             terminal: false,
             path: PathBuf::from("/test"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         };
 
@@ -209,6 +239,7 @@ mod tests {
             DesktopEntry {
                 name: "Firefox Browser".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "firefox".to_string(),
                 icon: None,
                 categories: vec![],
@@ -216,11 +247,17 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/test1"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
             DesktopEntry {
                 name: "Firefox".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "firefox".to_string(),
                 icon: None,
                 categories: vec![],
@@ -228,6 +265,11 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/test2"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
         ];
@@ -246,6 +288,7 @@ mod tests {
             DesktopEntry {
                 name: "Visible App".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "visible".to_string(),
                 icon: None,
                 categories: vec![],
@@ -253,11 +296,17 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/test1"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
             DesktopEntry {
                 name: "Hidden App".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "hidden".to_string(),
                 icon: None,
                 categories: vec![],
@@ -265,6 +314,11 @@ mod tests {
                 terminal: false,
                 path: PathBuf::from("/test2"),
                 no_display: true,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
         ];
@@ -283,6 +337,7 @@ mod tests {
         let terminal_entry = DesktopEntry {
             name: "Htop".to_string(),
             generic_name: None,
+            comment: None,
             exec: "htop".to_string(),
             icon: None,
             categories: vec![],
@@ -290,6 +345,11 @@ mod tests {
             terminal: true,
             path: PathBuf::from("/test"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         };
 
@@ -298,4 +358,266 @@ mod tests {
         let gui_entry = create_test_entry("Firefox", "firefox");
         assert!(!gui_entry.terminal);
     }
+
+    fn write_desktop_file(dir: &std::path::Path, file_name: &str, name: &str, exec: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(
+            dir.join(file_name),
+            format!(
+                "[Desktop Entry]\nType=Application\nName={}\nExec={}\n",
+                name, exec
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_user_entry_shadows_system_entry() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("native-launcher-scan-{}", nanos));
+        let user_dir = root.join("user/applications");
+        let system_dir = root.join("system/applications");
+
+        write_desktop_file(&system_dir, "firefox.desktop", "Firefox", "firefox %u");
+        write_desktop_file(&system_dir, "gimp.desktop", "GIMP", "gimp");
+        write_desktop_file(
+            &user_dir,
+            "firefox.desktop",
+            "Firefox",
+            "firefox --private %u",
+        );
+
+        // A symlink to an already scanned file must not produce a second entry
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(
+            system_dir.join("gimp.desktop"),
+            user_dir.join("gimp-link.desktop"),
+        )
+        .unwrap();
+
+        let scanner = DesktopScanner::with_paths(vec![user_dir.clone(), system_dir.clone()]);
+        let entries = scanner.scan().unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        let firefox: Vec<_> = entries.iter().filter(|e| e.name == "Firefox").collect();
+        assert_eq!(firefox.len(), 1);
+        assert_eq!(firefox[0].exec, "firefox --private %u");
+        assert_eq!(firefox[0].path, user_dir.join("firefox.desktop"));
+
+        assert_eq!(entries.iter().filter(|e| e.name == "GIMP").count(), 1);
+    }
+
+    #[test]
+    fn test_hidden_and_nodisplay_entries() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir()
+            .join(format!("native-launcher-hidden-{}", nanos))
+            .join("applications");
+        write_desktop_file(&dir, "visible.desktop", "Visible", "visible");
+        std::fs::write(
+            dir.join("deleted.desktop"),
+            "[Desktop Entry]\nType=Application\nName=Deleted\nExec=deleted\nHidden=true\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("helper.desktop"),
+            "[Desktop Entry]\nType=Application\nName=Helper\nExec=helper\nNoDisplay=true\n",
+        )
+        .unwrap();
+
+        let names = |show_nodisplay: bool| -> Vec<String> {
+            let mut names: Vec<String> = DesktopScanner::with_paths(vec![dir.clone()])
+                .with_show_nodisplay(show_nodisplay)
+                .scan()
+                .unwrap()
+                .into_iter()
+                .map(|e| e.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        let normal = names(false);
+        let debug = names(true);
+        let deleted = DesktopEntry::from_file(dir.join("deleted.desktop")).unwrap();
+        let helper = DesktopEntry::from_file(dir.join("helper.desktop")).unwrap();
+        let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+
+        assert!(deleted.hidden && !deleted.no_display);
+        assert!(helper.no_display && !helper.hidden);
+
+        // Hidden entries are dropped; NoDisplay entries only appear in debug mode
+        assert_eq!(normal, vec!["Visible"]);
+        assert_eq!(debug, vec!["Helper", "Visible"]);
+    }
+
+    fn write_keyword_entry(file_name: &str, body: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("native-launcher-keywords-{}", nanos));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(file_name);
+        std::fs::write(
+            &path,
+            format!(
+                "[Desktop Entry]\nType=Application\nName=Files\nExec=nautilus\n{}",
+                body
+            ),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_localized_keywords_merged_for_locale() {
+        let path = write_keyword_entry(
+            "files.desktop",
+            "Keywords=folder;manager;\nKeywords[fr]=dossier;gestionnaire;\nKeywords[de]=ordner;\n",
+        );
+
+        let french = DesktopEntry::from_file_with_locale(path.clone(), Some("fr_FR")).unwrap();
+        let default = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        // Localized keywords come first, defaults are kept, other languages ignored
+        assert_eq!(
+            french.keywords,
+            vec!["dossier", "gestionnaire", "folder", "manager"]
+        );
+        assert_eq!(default.keywords, vec!["folder", "manager"]);
+        assert!(french.matches("dossier"));
+        assert!(!french.matches("ordner"));
+    }
+
+    #[test]
+    fn test_localized_comment_for_locale() {
+        let path = write_keyword_entry(
+            "files.desktop",
+            "Comment=Access and organize files\nComment[fr]=Accéder aux fichiers et les organiser\nComment[de]=Dateien verwalten\n",
+        );
+
+        let french = DesktopEntry::from_file_with_locale(path.clone(), Some("fr_FR")).unwrap();
+        let german = DesktopEntry::from_file_with_locale(path.clone(), Some("de")).unwrap();
+        let spanish = DesktopEntry::from_file_with_locale(path.clone(), Some("es_ES")).unwrap();
+        let default = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(
+            french.comment.as_deref(),
+            Some("Accéder aux fichiers et les organiser")
+        );
+        assert_eq!(german.comment.as_deref(), Some("Dateien verwalten"));
+        // Untranslated locales fall back to the default comment
+        assert_eq!(
+            spanish.comment.as_deref(),
+            Some("Access and organize files")
+        );
+        assert_eq!(
+            default.comment.as_deref(),
+            Some("Access and organize files")
+        );
+
+        let path = write_keyword_entry("plain.desktop", "");
+        let plain = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(plain.comment, None);
+    }
+
+    #[test]
+    fn test_escaped_semicolon_in_keyword() {
+        let path = write_keyword_entry("escaped.desktop", "Keywords=rock\\;roll;music;\n");
+
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(entry.keywords, vec!["rock;roll", "music"]);
+    }
+
+    #[test]
+    fn test_startup_notify_parsed() {
+        let path = write_keyword_entry("notify.desktop", "StartupNotify=true\n");
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        assert!(entry.startup_notify);
+
+        let path = write_keyword_entry("silent.desktop", "StartupNotify=false\n");
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        assert!(!entry.startup_notify);
+    }
+
+    #[test]
+    fn test_dbus_activatable_parsed() {
+        use native_launcher::utils::exec::{launch_method, LaunchMethod};
+
+        let path = write_keyword_entry("org.gnome.Nautilus.desktop", "DBusActivatable=true\n");
+        let entry = DesktopEntry::from_file_with_locale(path.clone(), None).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        assert!(entry.dbus_activatable);
+        assert_eq!(
+            launch_method(&entry),
+            LaunchMethod::DBus("org.gnome.Nautilus".to_string())
+        );
+    }
+
+    #[test]
+    fn test_watcher_rescan_makes_new_entry_searchable() {
+        use native_launcher::config::Config;
+        use native_launcher::desktop::watcher::spawn_rescan_watcher;
+        use native_launcher::plugins::PluginManager;
+        use std::time::{Duration, Instant};
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("native-launcher-watch-{}", nanos));
+        let apps_dir = root.join("applications");
+        write_desktop_file(&apps_dir, "gimp.desktop", "GIMP", "gimp");
+
+        let scanner = DesktopScanner::with_paths(vec![apps_dir.clone()]);
+        let arena = DesktopEntryArena::from_vec(scanner.scan().unwrap());
+        let manager = PluginManager::new(arena, None, None, &Config::default());
+        let has_result = |manager: &PluginManager| {
+            manager
+                .search("Zyxwatch", 10)
+                .unwrap()
+                .iter()
+                .any(|r| r.title == "Zyxwatch Editor" && r.plugin_name == "applications")
+        };
+        assert!(!has_result(&manager));
+
+        let entries = manager.shared_entries();
+        spawn_rescan_watcher(
+            scanner.paths().to_vec(),
+            Duration::from_millis(100),
+            move || {
+                let rescanned = scanner.scan().unwrap();
+                entries.store(DesktopEntryArena::from_vec(rescanned));
+            },
+        )
+        .unwrap();
+
+        write_desktop_file(&apps_dir, "zyxwatch.desktop", "Zyxwatch Editor", "zyxwatch");
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !has_result(&manager) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let found = has_result(&manager);
+        let _ = std::fs::remove_dir_all(&root);
+        assert!(
+            found,
+            "new entry should be searchable after the watcher fires"
+        );
+    }
 }
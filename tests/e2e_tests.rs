@@ -10,9 +10,9 @@ use std::sync::OnceLock;
 // Import from main crate
 use native_launcher::config::{Config, ConfigLoader};
 use native_launcher::desktop::{DesktopEntry, DesktopEntryArena, DesktopScanner};
-use native_launcher::plugins::{KeyboardAction, KeyboardEvent, PluginManager};
+use native_launcher::plugins::{KeyboardAction, KeyboardEvent, PluginManager, ResultActivation};
 use native_launcher::search::SearchEngine;
-use native_launcher::ui::{ResultsList, SearchWidget};
+use native_launcher::ui::{Navigation, ResultsList, SearchWidget};
 use native_launcher::usage::UsageTracker;
 
 static GTK_INIT_RESULT: OnceLock<bool> = OnceLock::new();
@@ -251,10 +251,16 @@ fn test_e2e_search_widget_to_results_list() {
             DesktopEntry {
                 name: "Firefox".to_string(),
                 generic_name: Some("Web Browser".to_string()),
+                comment: None,
                 exec: "firefox".to_string(),
                 icon: None,
                 terminal: false,
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 path: std::path::PathBuf::from("/usr/share/applications/firefox.desktop"),
                 keywords: vec!["browser".to_string(), "web".to_string()],
                 categories: vec!["Network".to_string()],
@@ -263,10 +269,16 @@ fn test_e2e_search_widget_to_results_list() {
             DesktopEntry {
                 name: "VS Code".to_string(),
                 generic_name: Some("Code Editor".to_string()),
+                comment: None,
                 exec: "code".to_string(),
                 icon: None,
                 terminal: false,
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 path: std::path::PathBuf::from("/usr/share/applications/code.desktop"),
                 keywords: vec!["editor".to_string(), "development".to_string()],
                 categories: vec!["Development".to_string()],
@@ -332,7 +344,7 @@ fn test_e2e_search_widget_to_results_list() {
         }
 
         // Test 3: Navigation works
-        results_list.select_next();
+        results_list.navigate(Navigation::Next);
         let second_command = results_list.get_selected_command();
         assert_ne!(
             firefox_command, second_command,
@@ -439,6 +451,107 @@ fn test_e2e_multi_plugin_search() {
     });
 }
 
+#[test]
+fn test_e2e_headless_search_in_thread() {
+    // Test: the full plugin search runs on a thread where GTK was never
+    // initialized (any GTK call there would panic)
+    let handle = std::thread::spawn(|| {
+        let entries = vec![DesktopEntry {
+            name: "Firefox".to_string(),
+            generic_name: Some("Web Browser".to_string()),
+            comment: None,
+            exec: "firefox %u".to_string(),
+            icon: Some("firefox".to_string()),
+            categories: vec!["Network".to_string()],
+            keywords: vec!["browser".to_string()],
+            terminal: false,
+            path: std::path::PathBuf::from("/usr/share/applications/firefox.desktop"),
+            no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
+            actions: vec![],
+        }];
+        let config = Config::default();
+        let plugin_manager =
+            PluginManager::new(DesktopEntryArena::from_vec(entries), None, None, &config);
+
+        let results = plugin_manager.search("firefox", 10).expect("Search failed");
+        assert_eq!(results[0].title, "Firefox");
+        let results = plugin_manager.search("2+2", 10).expect("Search failed");
+        assert_eq!(results[0].title, "4");
+        for query in ["", "@emoji smile", "@hash abc", "@uuid"] {
+            plugin_manager.search(query, 10).expect("Search failed");
+        }
+
+        // The QR code is only saved once activated, so searching leaves the
+        // cache untouched (unique text so no earlier save can exist)
+        let query = format!("@qr native-launcher-e2e-{}", std::process::id());
+        let results = plugin_manager.search(&query, 10).expect("Search failed");
+        let saved_to = results.iter().find_map(|r| match &r.activation {
+            Some(ResultActivation::SaveIcon(path)) => Some(path.clone()),
+            _ => None,
+        });
+        let saved_to = saved_to.expect("No QR code result");
+        assert!(!saved_to.exists(), "Search wrote {}", saved_to.display());
+
+        let token = native_launcher::plugins::SearchToken::new(Default::default());
+        let (mut fast, mut slow) = (Vec::new(), Vec::new());
+        plugin_manager
+            .search_incremental("firef", 10, &token, |r| fast = r, |r| slow = r)
+            .expect("Incremental search failed");
+        assert!(fast.iter().chain(&slow).any(|r| r.title == "Firefox"));
+    });
+
+    handle.join().expect("headless search panicked");
+}
+
+#[test]
+fn test_e2e_query_arg_prefills_search() {
+    // Test: `--query firefox` (directly or via the daemon socket) becomes the
+    // query the search entry runs, without needing GTK
+    use native_launcher::daemon::DaemonCommand;
+
+    let args: Vec<String> = ["native-launcher", "--query", "firefox"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    let command = DaemonCommand::from_args(&args);
+    let command = DaemonCommand::parse(&command.to_message()).expect("Unparseable command");
+    let query = command.query().expect("No query to prefill");
+
+    let entries = vec![DesktopEntry {
+        name: "Firefox".to_string(),
+        generic_name: Some("Web Browser".to_string()),
+        comment: None,
+        exec: "firefox %u".to_string(),
+        icon: Some("firefox".to_string()),
+        categories: vec!["Network".to_string()],
+        keywords: vec!["browser".to_string()],
+        terminal: false,
+        path: std::path::PathBuf::from("/usr/share/applications/firefox.desktop"),
+        no_display: false,
+        hidden: false,
+        dbus_activatable: false,
+        startup_notify: false,
+        startup_wm_class: None,
+        mime_types: vec![],
+        actions: vec![],
+    }];
+    let config = Config::default();
+    let plugin_manager =
+        PluginManager::new(DesktopEntryArena::from_vec(entries), None, None, &config);
+
+    let token = native_launcher::plugins::SearchToken::new(Default::default());
+    let (mut fast, mut slow) = (Vec::new(), Vec::new());
+    plugin_manager
+        .search_incremental(query, 10, &token, |r| fast = r, |r| slow = r)
+        .expect("Incremental search failed");
+    assert!(fast.iter().chain(&slow).any(|r| r.title == "Firefox"));
+}
+
 #[test]
 fn test_e2e_fuzzy_search_accuracy() {
     // Test: Fuzzy search finds results even with typos
@@ -6,7 +6,7 @@ use gtk4::glib;
 ///
 /// Run with: cargo test --test ui_tests
 use gtk4::prelude::*;
-use native_launcher::ui::{KeyboardHints, ResultsList, SearchWidget};
+use native_launcher::ui::{KeyboardHints, Navigation, ResultsList, SearchWidget, StatusBar};
 use std::sync::OnceLock;
 
 static GTK_INIT_RESULT: OnceLock<bool> = OnceLock::new();
@@ -120,6 +120,7 @@ fn test_results_list_update_results() {
             DesktopEntry {
                 name: "Test App 1".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "test1".to_string(),
                 icon: None,
                 categories: vec![],
@@ -127,11 +128,17 @@ fn test_results_list_update_results() {
                 terminal: false,
                 path: PathBuf::from("/test1.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
             DesktopEntry {
                 name: "Test App 2".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "test2".to_string(),
                 icon: None,
                 categories: vec![],
@@ -139,6 +146,11 @@ fn test_results_list_update_results() {
                 terminal: false,
                 path: PathBuf::from("/test2.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
         ];
@@ -169,6 +181,7 @@ fn test_results_list_navigation() {
             DesktopEntry {
                 name: "App A".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "app_a".to_string(),
                 icon: None,
                 categories: vec![],
@@ -176,11 +189,17 @@ fn test_results_list_navigation() {
                 terminal: false,
                 path: PathBuf::from("/a.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
             DesktopEntry {
                 name: "App B".to_string(),
                 generic_name: None,
+                comment: None,
                 exec: "app_b".to_string(),
                 icon: None,
                 categories: vec![],
@@ -188,6 +207,11 @@ fn test_results_list_navigation() {
                 terminal: false,
                 path: PathBuf::from("/b.desktop"),
                 no_display: false,
+                hidden: false,
+                dbus_activatable: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                mime_types: vec![],
                 actions: vec![],
             },
         ];
@@ -199,20 +223,173 @@ fn test_results_list_navigation() {
         assert_eq!(exec, "app_a");
 
         // Navigate down
-        results_list.select_next();
+        results_list.navigate(Navigation::Next);
         let (exec, _) = results_list.get_selected_command().unwrap();
         assert_eq!(exec, "app_b");
 
         // Navigate down again (should wrap or stay at bottom)
-        results_list.select_next();
+        results_list.navigate(Navigation::Next);
 
         // Navigate up
-        results_list.select_previous();
+        results_list.navigate(Navigation::Previous);
         let (exec, _) = results_list.get_selected_command().unwrap();
         assert_eq!(exec, "app_a");
     });
 }
 
+#[test]
+fn test_results_list_wrap_and_jump_navigation() {
+    run_gtk_test(|| {
+        use native_launcher::plugins::PluginResult;
+        use native_launcher::ui::Navigation;
+
+        let results_list = ResultsList::new();
+        results_list.set_wrap_navigation(true);
+        results_list.update_plugin_results(
+            ["one", "two", "three"]
+                .iter()
+                .map(|cmd| PluginResult::new(cmd.to_string(), cmd.to_string(), "test".to_string()))
+                .collect(),
+        );
+        let selected = || results_list.get_selected_command().unwrap().0;
+
+        results_list.navigate(Navigation::First);
+        assert_eq!(selected(), "one");
+
+        // Up on the first result wraps to the last, Down wraps back
+        results_list.navigate(Navigation::Previous);
+        assert_eq!(selected(), "three");
+        results_list.navigate(Navigation::Next);
+        assert_eq!(selected(), "one");
+
+        results_list.navigate(Navigation::Last);
+        assert_eq!(selected(), "three");
+        results_list.navigate(Navigation::First);
+        assert_eq!(selected(), "one");
+
+        // Without wrapping the ends are sticky
+        results_list.set_wrap_navigation(false);
+        results_list.navigate(Navigation::Previous);
+        assert_eq!(selected(), "one");
+    });
+}
+
+#[test]
+fn test_results_list_auto_select_first() {
+    run_gtk_test(|| {
+        use native_launcher::plugins::PluginResult;
+
+        let results = |cmds: &[&str]| -> Vec<PluginResult> {
+            cmds.iter()
+                .map(|cmd| PluginResult::new(cmd.to_string(), cmd.to_string(), "test".to_string()))
+                .collect()
+        };
+
+        let results_list = ResultsList::new();
+        results_list.set_auto_select_first(true);
+        results_list.update_plugin_results(results(&["top", "second"]));
+        assert_eq!(results_list.get_selected_command().unwrap().0, "top");
+
+        // A manual selection survives an update with the same results...
+        results_list.navigate(Navigation::Next);
+        results_list.update_plugin_results(results(&["top", "second"]));
+        assert_eq!(results_list.get_selected_command().unwrap().0, "second");
+
+        // ...and new results select their top entry again
+        results_list.update_plugin_results(results(&["new top", "top"]));
+        assert_eq!(results_list.get_selected_command().unwrap().0, "new top");
+
+        // Off: nothing is selected until the user navigates
+        results_list.set_auto_select_first(false);
+        results_list.update_plugin_results(results(&["a", "b"]));
+        assert!(results_list.get_selected_command().is_none());
+        results_list.navigate(Navigation::Next);
+        assert_eq!(results_list.get_selected_command().unwrap().0, "a");
+    });
+}
+
+#[test]
+fn test_results_list_uses_configured_icon_size() {
+    run_gtk_test(|| {
+        use native_launcher::plugins::PluginResult;
+        use native_launcher::utils::icons::is_icon_cached;
+
+        let results_list = ResultsList::new();
+        results_list.set_icon_size(96);
+        results_list.set_row_spacing(8);
+
+        let app = PluginResult::new(
+            "Sized App".to_string(),
+            "sized-app".to_string(),
+            "applications".to_string(),
+        )
+        .with_icon("nl-test-sized-app".to_string());
+        let mut workspace = PluginResult::new(
+            "Sized Workspace".to_string(),
+            "code ~/sized".to_string(),
+            "editors".to_string(),
+        )
+        .with_icon("nl-test-sized-workspace".to_string());
+        workspace.parent_app = Some("code".to_string());
+
+        results_list.update_plugin_results(vec![app, workspace]);
+
+        // Icons are looked up at the configured size, linked entries at two thirds
+        assert!(is_icon_cached("nl-test-sized-app", 96));
+        assert!(!is_icon_cached("nl-test-sized-app", 48));
+        assert!(is_icon_cached("nl-test-sized-workspace", 64));
+    });
+}
+
+/// Whether `widget` or any widget below it has the CSS class
+fn has_css_class_below(widget: &gtk4::Widget, class: &str) -> bool {
+    if widget.has_css_class(class) {
+        return true;
+    }
+    let mut child = widget.first_child();
+    while let Some(current) = child {
+        if has_css_class_below(&current, class) {
+            return true;
+        }
+        child = current.next_sibling();
+    }
+    false
+}
+
+#[test]
+fn test_results_list_renders_badge_classes() {
+    run_gtk_test(|| {
+        use native_launcher::plugins::{PluginResult, ResultBadge};
+
+        let results_list = ResultsList::new();
+        let bookmark = PluginResult::new(
+            "Rust".to_string(),
+            "xdg-open https://www.rust-lang.org".to_string(),
+            "browser_history".to_string(),
+        )
+        .with_badge(ResultBadge::Bookmark);
+        let plain = PluginResult::new("Plain".to_string(), "plain".to_string(), "test".to_string());
+        results_list.update_plugin_results(vec![bookmark, plain]);
+
+        // The themes style badges by these classes
+        let row = results_list
+            .list
+            .row_at_index(0)
+            .unwrap()
+            .upcast::<gtk4::Widget>();
+        assert!(has_css_class_below(&row, "result-source-badge"));
+        assert!(has_css_class_below(&row, "badge-bookmark"));
+        assert!(!has_css_class_below(&row, "badge-running"));
+
+        let row = results_list
+            .list
+            .row_at_index(1)
+            .unwrap()
+            .upcast::<gtk4::Widget>();
+        assert!(!has_css_class_below(&row, "result-source-badge"));
+    });
+}
+
 #[test]
 fn test_results_list_clear() {
     run_gtk_test(|| {
@@ -224,6 +401,7 @@ fn test_results_list_clear() {
         let entry = DesktopEntry {
             name: "Test".to_string(),
             generic_name: None,
+            comment: None,
             exec: "test".to_string(),
             icon: None,
             categories: vec![],
@@ -231,6 +409,11 @@ fn test_results_list_clear() {
             terminal: false,
             path: PathBuf::from("/test.desktop"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         };
 
@@ -264,6 +447,34 @@ fn test_keyboard_hints_default() {
     });
 }
 
+#[test]
+fn test_keyboard_hints_show_copy_command() {
+    run_gtk_test(|| {
+        let hints = KeyboardHints::new();
+        assert!(hints.text().contains("Ctrl+Shift+C Copy Command"));
+    });
+}
+
+#[test]
+fn test_status_bar_shows_and_schedules_clear() {
+    run_gtk_test(|| {
+        let status = StatusBar::new();
+        assert!(status.text().is_empty());
+        assert!(!status.label.is_visible());
+
+        status.show_status("Copied!");
+        assert_eq!(status.text(), "Copied!");
+        assert!(status.label.is_visible());
+        assert!(status.label.has_css_class("visible"));
+        assert!(status.is_clear_scheduled());
+
+        // A newer message replaces the current one
+        status.show_status("Pinned");
+        assert_eq!(status.text(), "Pinned");
+        assert!(status.is_clear_scheduled());
+    });
+}
+
 #[test]
 fn test_results_list_with_actions() {
     run_gtk_test(|| {
@@ -276,6 +487,7 @@ fn test_results_list_with_actions() {
         let entry = DesktopEntry {
             name: "Firefox".to_string(),
             generic_name: Some("Web Browser".to_string()),
+            comment: None,
             exec: "firefox".to_string(),
             icon: Some("firefox".to_string()),
             categories: vec![],
@@ -283,6 +495,11 @@ fn test_results_list_with_actions() {
             terminal: false,
             path: PathBuf::from("/firefox.desktop"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![
                 DesktopAction {
                     id: "new-window".to_string(),
@@ -307,12 +524,12 @@ fn test_results_list_with_actions() {
         assert_eq!(exec, "firefox");
 
         // Navigate to first action
-        results_list.select_next();
+        results_list.navigate(Navigation::Next);
         let (exec, _) = results_list.get_selected_command().unwrap();
         assert_eq!(exec, "firefox --new-window");
 
         // Navigate to second action
-        results_list.select_next();
+        results_list.navigate(Navigation::Next);
         let (exec, _) = results_list.get_selected_command().unwrap();
         assert_eq!(exec, "firefox --private-window");
     });
@@ -329,6 +546,7 @@ fn test_terminal_app_flag() {
         let terminal_entry = DesktopEntry {
             name: "Htop".to_string(),
             generic_name: None,
+            comment: None,
             exec: "htop".to_string(),
             icon: None,
             categories: vec![],
@@ -336,6 +554,11 @@ fn test_terminal_app_flag() {
             terminal: true,
             path: PathBuf::from("/htop.desktop"),
             no_display: false,
+            hidden: false,
+            dbus_activatable: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            mime_types: vec![],
             actions: vec![],
         };
 